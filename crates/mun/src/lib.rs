@@ -6,7 +6,7 @@ use std::ffi::OsString;
 use clap::{App, AppSettings, Arg, SubCommand};
 use mun_project::MANIFEST_FILENAME;
 
-use ops::{build, init, language_server, new, start};
+use ops::{bindgen, build, coverage, init, language_server, new, start};
 
 #[derive(Copy, Debug, Clone, PartialEq, Eq)]
 pub enum ExitStatus {
@@ -51,7 +51,12 @@ where
                         .short("O")
                         .long("opt-level")
                         .takes_value(true)
-                        .help("optimize with possible levels 0-3"),
+                        .help("optimize with possible levels 0-3, overriding the profile"),
+                )
+                .arg(
+                    Arg::with_name("release")
+                        .long("release")
+                        .help("build with the release profile instead of the dev profile"),
                 )
                 .arg(
                     Arg::with_name("target")
@@ -71,6 +76,24 @@ where
                         .long("emit-ir")
                         .help("emits IR instead of a *.munlib"),
                 )
+                .arg(
+                    Arg::with_name("emit-static")
+                        .long("emit-static")
+                        .help("emits a statically linkable object file instead of a *.munlib"),
+                )
+                .arg(
+                    Arg::with_name("instrument-functions")
+                        .long("instrument-functions")
+                        .help("inserts profiler hooks at the entry and exit of every function"),
+                )
+                .arg(
+                    Arg::with_name("coverage")
+                        .long("coverage")
+                        .help("counts the executions of every block and branch for code coverage"),
+                )
+                .arg(Arg::with_name("direct-calls").long("direct-calls").help(
+                    "compiles all modules into a single assembly, calling functions directly",
+                ))
                 .about("Compiles a local Mun file into a module"),
         )
         .subcommand(
@@ -103,12 +126,54 @@ where
                     .index(1),
             ),
         )
+        .subcommand(
+            SubCommand::with_name("coverage")
+                .arg(
+                    Arg::with_name("manifest-path")
+                        .long("manifest-path")
+                        .takes_value(true)
+                        .help(&format!("Path to {}", MANIFEST_FILENAME)),
+                )
+                .arg(
+                    Arg::with_name("COUNTERS")
+                        .help("the coverage counters written by the runtime")
+                        .required(true)
+                        .index(1),
+                )
+                .about("Maps coverage counters back to the source code of a Mun project"),
+        )
+        .subcommand(
+            SubCommand::with_name("bindgen")
+                .arg(
+                    Arg::with_name("LIBRARY")
+                        .help("the compiled library to generate bindings for")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("lang")
+                        .long("lang")
+                        .takes_value(true)
+                        .possible_values(&["rust", "cpp"])
+                        .help("the language of the bindings [default: rust]"),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .short("o")
+                        .long("output")
+                        .takes_value(true)
+                        .help("the file to write the bindings to [default: stdout]"),
+                )
+                .about("Generates typed host bindings for the functions and structs of a library"),
+        )
         .subcommand(SubCommand::with_name("language-server"))
         .get_matches_from_safe(args);
 
     match matches {
         Ok(matches) => match matches.subcommand() {
+            ("bindgen", Some(matches)) => bindgen(matches),
             ("build", Some(matches)) => build(matches),
+            ("coverage", Some(matches)) => coverage(matches),
             ("language-server", Some(matches)) => language_server(matches),
             ("start", Some(matches)) => start(matches).map(|_| ExitStatus::Success),
             ("new", Some(matches)) => new(matches),
@@ -1,10 +1,14 @@
+mod bindgen;
 mod build;
+mod coverage;
 pub mod init;
 mod language_server;
 mod new;
 mod start;
 
+pub use bindgen::bindgen;
 pub use build::build;
+pub use coverage::coverage;
 pub use init::init;
 pub use language_server::language_server;
 pub use new::new;
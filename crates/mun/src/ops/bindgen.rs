@@ -0,0 +1,33 @@
+use std::path::Path;
+
+use anyhow::anyhow;
+use clap::ArgMatches;
+use mun_bindgen::Bindings;
+
+use crate::ExitStatus;
+
+/// This method is invoked when the executable is run with the `bindgen` argument indicating that
+/// a user requested us to generate typed host bindings for a compiled Mun library.
+pub fn bindgen(matches: &ArgMatches) -> Result<ExitStatus, anyhow::Error> {
+    let library_path = Path::new(matches.value_of("LIBRARY").unwrap());
+    let bindings = Bindings::from_library(library_path).map_err(|e| {
+        anyhow!(
+            "could not read bindings from '{}': {}",
+            library_path.display(),
+            e
+        )
+    })?;
+
+    let source = match matches.value_of("lang").unwrap_or("rust") {
+        "rust" => mun_bindgen::rust::generate(&bindings),
+        "cpp" => mun_bindgen::cpp::generate(&bindings),
+        _ => unreachable!(),
+    };
+
+    match matches.value_of("output") {
+        Some(output_path) => std::fs::write(output_path, source)
+            .map_err(|e| anyhow!("could not write bindings to '{}': {}", output_path, e))?,
+        None => print!("{}", source),
+    }
+    Ok(ExitStatus::Success)
+}
@@ -4,19 +4,40 @@ use std::path::{Path, PathBuf};
 use anyhow::anyhow;
 use clap::ArgMatches;
 use mun_compiler::{Config, DisplayColor, Target};
-use mun_project::MANIFEST_FILENAME;
+use mun_project::{ProfileKind, Workspace, MANIFEST_FILENAME};
 
 use crate::ExitStatus;
 
 /// This method is invoked when the executable is run with the `build` argument indicating that a
 /// user requested us to build a project in the current directory or one of its parent directories.
+/// If the project's manifest describes a workspace, all of its members are built.
 pub fn build(matches: &ArgMatches) -> Result<ExitStatus, anyhow::Error> {
     log::trace!("starting build");
 
-    let options = compiler_options(matches)?;
+    let manifest_path = locate_manifest(matches)?;
 
-    // Locate the manifest
-    let manifest_path = match matches.value_of("manifest-path") {
+    log::info!("located build manifest at: {}", manifest_path.display());
+
+    let profile = if matches.is_present("release") {
+        ProfileKind::Release
+    } else {
+        ProfileKind::Dev
+    };
+    let workspace = Workspace::from_manifest_path(&manifest_path)?;
+    let options = compiler_options(matches, &workspace, profile)?;
+
+    if matches.is_present("watch") {
+        mun_compiler_daemon::compile_and_watch_manifest(&manifest_path, options)
+    } else {
+        mun_compiler::compile_manifest(&manifest_path, options)
+    }
+    .map(Into::into)
+}
+
+/// Locates the manifest specified by the `manifest-path` argument, or, if it wasn't specified, in
+/// the current directory or one of its parent directories.
+pub(crate) fn locate_manifest(matches: &ArgMatches) -> Result<PathBuf, anyhow::Error> {
+    match matches.value_of("manifest-path") {
         None => {
             let current_dir =
                 std::env::current_dir().expect("could not determine currrent working directory");
@@ -26,20 +47,11 @@ pub fn build(matches: &ArgMatches) -> Result<ExitStatus, anyhow::Error> {
                     MANIFEST_FILENAME,
                     current_dir.display()
                 )
-            })?
+            })
         }
         Some(path) => std::fs::canonicalize(Path::new(path))
-            .map_err(|_| anyhow::anyhow!("'{}' does not refer to a valid manifest path", path))?,
-    };
-
-    log::info!("located build manifest at: {}", manifest_path.display());
-
-    if matches.is_present("watch") {
-        mun_compiler_daemon::compile_and_watch_manifest(&manifest_path, options)
-    } else {
-        mun_compiler::compile_manifest(&manifest_path, options)
+            .map_err(|_| anyhow::anyhow!("'{}' does not refer to a valid manifest path", path)),
     }
-    .map(Into::into)
 }
 
 /// Find a Mun manifest file in the specified directory or one of its parents.
@@ -55,12 +67,23 @@ fn find_manifest(directory: &Path) -> Option<PathBuf> {
     None
 }
 
-fn compiler_options(matches: &ArgMatches) -> Result<mun_compiler::Config, anyhow::Error> {
-    let optimization_lvl = match matches.value_of("opt-level") {
-        Some("0") => mun_compiler::OptimizationLevel::None,
-        Some("1") => mun_compiler::OptimizationLevel::Less,
-        None | Some("2") => mun_compiler::OptimizationLevel::Default,
-        Some("3") => mun_compiler::OptimizationLevel::Aggressive,
+/// Determines the compiler configuration from the specified profile of the workspace. Arguments
+/// override the settings of the profile.
+fn compiler_options(
+    matches: &ArgMatches,
+    workspace: &Workspace,
+    profile_kind: ProfileKind,
+) -> Result<mun_compiler::Config, anyhow::Error> {
+    let profile = workspace.profile(profile_kind);
+
+    let optimization_lvl = match matches
+        .value_of("opt-level")
+        .map_or(Ok(profile.opt_level()), str::parse)
+    {
+        Ok(0) => mun_compiler::OptimizationLevel::None,
+        Ok(1) => mun_compiler::OptimizationLevel::Less,
+        Ok(2) => mun_compiler::OptimizationLevel::Default,
+        Ok(3) => mun_compiler::OptimizationLevel::Aggressive,
         _ => return Err(anyhow!("Only optimization levels 0-3 are supported")),
     };
 
@@ -76,15 +99,23 @@ fn compiler_options(matches: &ArgMatches) -> Result<mun_compiler::Config, anyhow
         .unwrap_or(DisplayColor::Auto);
 
     let emit_ir = matches.is_present("emit-ir");
+    let emit_static = matches.is_present("emit-static");
+    let instrument_functions = matches.is_present("instrument-functions");
+    let instrument_coverage = matches.is_present("coverage");
+    let direct_calls = matches.is_present("direct-calls");
 
     Ok(Config {
         target: matches
             .value_of("target")
             .map_or_else(Target::host_target, Target::search)?,
         optimization_lvl,
-        out_dir: None,
+        out_dir: Some(workspace.profile_target_dir(profile_kind)),
         display_color,
         emit_ir,
+        emit_static,
+        instrument_functions,
+        instrument_coverage,
+        direct_calls,
     })
 }
 
@@ -0,0 +1,35 @@
+use std::io::stderr;
+
+use clap::ArgMatches;
+use mun_compiler::{Config, DisplayColor, Driver};
+
+use crate::{ops::build::locate_manifest, ExitStatus};
+
+/// This method is invoked when the executable is run with the `coverage` argument indicating that
+/// a user requested us to map coverage counters, recorded by a Mun runtime, back to the source code
+/// of a project.
+pub fn coverage(matches: &ArgMatches) -> Result<ExitStatus, anyhow::Error> {
+    let manifest_path = locate_manifest(matches)?;
+    log::info!("located build manifest at: {}", manifest_path.display());
+
+    let counters_path = matches.value_of("COUNTERS").unwrap();
+    let counters = std::fs::read_to_string(counters_path).map_err(|e| {
+        anyhow::anyhow!(
+            "could not read coverage counters from '{}': {}",
+            counters_path,
+            e
+        )
+    })?;
+
+    let config = Config {
+        display_color: DisplayColor::Disable,
+        ..Config::default()
+    };
+    let (_, driver) = Driver::with_package_path(&manifest_path, config)?;
+    if driver.emit_diagnostics(&mut stderr())? {
+        return Ok(ExitStatus::Error);
+    }
+
+    print!("{}", driver.coverage_report(&counters)?);
+    Ok(ExitStatus::Success)
+}
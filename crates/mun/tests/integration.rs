@@ -53,6 +53,75 @@ fn mun_emit_ir() {
     assert!(ir_path.is_file());
 }
 
+/// Verifies that a newly created project can be built with the release profile, of which the
+/// output directory is specified in the manifest.
+#[test]
+fn mun_build_release() {
+    let project_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+
+    let project_path = project_dir.path().join(PROJECT_NAME);
+
+    let args: Vec<OsString> = vec!["mun".into(), "new".into(), project_path.as_path().into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    let manifest_path = project_path.join("mun.toml");
+    let mut manifest = std::fs::read_to_string(&manifest_path).unwrap();
+    manifest.push_str("\n[profile.release]\nopt-level = 1\nout-dir = \"dist\"\n");
+    std::fs::write(&manifest_path, manifest).unwrap();
+
+    build(&project_path, &["--release"]);
+
+    assert!(project_path.join("dist/mod.munlib").is_file());
+    assert!(!project_path.join("target/mod.munlib").exists());
+}
+
+/// Verifies that bindings can be generated for a newly created project.
+#[test]
+fn mun_bindgen() {
+    let project_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+
+    let project_path = project_dir.path().join(PROJECT_NAME);
+
+    let args: Vec<OsString> = vec!["mun".into(), "new".into(), project_path.as_path().into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    build(&project_path, &[]);
+
+    for (lang, signature) in [
+        (
+            "rust",
+            "pub fn main<'r>(runtime: &'r Runtime) -> Result<f64, String>",
+        ),
+        (
+            "cpp",
+            "inline mun::InvokeResult<double> main(mun::Runtime& runtime)",
+        ),
+    ]
+    .iter()
+    {
+        let bindings_path = project_path.join(format!("bindings.{}", lang));
+        let args: Vec<OsString> = vec![
+            "mun".into(),
+            "bindgen".into(),
+            project_path.join("target/mod.munlib").into(),
+            "--lang".into(),
+            (*lang).into(),
+            "--output".into(),
+            bindings_path.as_path().into(),
+        ];
+        assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+        let bindings = std::fs::read_to_string(&bindings_path).unwrap();
+        assert!(bindings.contains(signature), "{}", bindings);
+    }
+}
+
 fn build(project: &Path, args: &[&str]) {
     let args: Vec<OsString> = vec![
         OsString::from("mun"),
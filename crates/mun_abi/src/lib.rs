@@ -35,19 +35,33 @@ pub mod prelude {
 
 /// Defines the current ABI version
 #[allow(clippy::zero_prefixed_literal)]
-pub const ABI_VERSION: u32 = 00_03_00;
+pub const ABI_VERSION: u32 = 00_05_00;
 /// Defines the name for the `get_info` function
 pub const GET_INFO_FN_NAME: &str = "get_info";
 /// Defines the name for the `get_version` function
 pub const GET_VERSION_FN_NAME: &str = "get_version";
 /// Defines the name for the `set_allocator_handle` function
 pub const SET_ALLOCATOR_HANDLE_FN_NAME: &str = "set_allocator_handle";
+/// Defines the name for the `set_profiler_handle` function. This function is only exported by
+/// assemblies that were compiled with function instrumentation.
+pub const SET_PROFILER_HANDLE_FN_NAME: &str = "set_profiler_handle";
+/// Defines the name for the `set_coverage_handle` function. This function is only exported by
+/// assemblies that were compiled with coverage instrumentation.
+pub const SET_COVERAGE_HANDLE_FN_NAME: &str = "set_coverage_handle";
 
 /// Represents a globally unique identifier (GUID).
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct Guid(pub [u8; 16]);
 
+impl Guid {
+    /// Returns the `Guid` of an `extern struct` with the specified `name`. Both the Mun Compiler
+    /// and the host use this `Guid`, such that the declaration can be linked to the host's type.
+    pub fn from_extern_struct_name(name: &str) -> Guid {
+        Guid(md5::compute(format!("extern struct {}", name)).0)
+    }
+}
+
 /// Represents the privacy level of modules, functions, or variables.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -10,10 +10,14 @@ pub struct ModuleInfo {
     pub(crate) functions: *const FunctionDefinition,
     /// Module types
     pub(crate) types: *const *const TypeInfo,
+    /// Module migration functions
+    pub(crate) migrations: *const FunctionDefinition,
     /// Number of module functions
     pub num_functions: u32,
     /// Number of module types
     pub num_types: u32,
+    /// Number of module migration functions
+    pub num_migrations: u32,
 }
 
 impl ModuleInfo {
@@ -46,6 +50,16 @@ impl ModuleInfo {
         }
     }
 
+    /// Returns the module's migration functions. These are called during hot reloading to convert
+    /// instances of a struct that could not be mapped automatically.
+    pub fn migrations(&self) -> &[FunctionDefinition] {
+        if self.num_migrations == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.migrations, self.num_migrations as usize) }
+        }
+    }
+
     /// Returns the module's types.
     pub fn types(&self) -> &[&TypeInfo] {
         if self.num_types == 0 {
@@ -89,6 +103,31 @@ mod tests {
 
         assert_eq!(module.functions().len(), functions.len());
         assert_eq!(module.types().len(), types.len());
+        assert_eq!(module.migrations().len(), 0);
+    }
+
+    #[test]
+    fn test_module_info_migrations_some() {
+        let fn_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
+        let fn_prototype = fake_fn_prototype(&fn_name, &[], None);
+
+        let migration = FunctionDefinition {
+            prototype: fn_prototype,
+            fn_ptr: ptr::null(),
+        };
+        let migrations = &[migration];
+
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let mut module = fake_module_info(&module_path, &[], &[]);
+        module.migrations = migrations.as_ptr();
+        module.num_migrations = migrations.len() as u32;
+
+        let result_migrations = module.migrations();
+        assert_eq!(result_migrations.len(), migrations.len());
+        for (lhs, rhs) in result_migrations.iter().zip(migrations.iter()) {
+            assert_eq!(lhs.fn_ptr, rhs.fn_ptr);
+            assert_eq!(lhs.prototype.name(), rhs.prototype.name());
+        }
     }
 
     #[test]
@@ -11,6 +11,8 @@ pub struct StructInfo {
     pub(crate) field_types: *const *const TypeInfo,
     /// Struct fields' offsets
     pub(crate) field_offsets: *const u16,
+    /// Struct fields' previous names, or null if none of the fields were renamed
+    pub(crate) field_renamed_from: *const *const c_char,
     // TODO: Field accessibility levels
     // const MunPrivacy_t *field_privacies,
     /// Number of fields
@@ -33,6 +35,10 @@ pub enum StructMemoryKind {
     /// NOTE: When a value struct is used in an external API, a wrapper is created that _pins_ the
     /// value on the heap. The heap-allocated value needs to be *manually deallocated*!
     Value,
+
+    /// An extern struct is an opaque type that is defined by the host. Like a garbage collected
+    /// struct, it is allocated on the heap and uses reference semantics when passed around.
+    Extern,
 }
 
 impl StructInfo {
@@ -72,6 +78,29 @@ impl StructInfo {
         }
     }
 
+    /// Returns the struct's field names prior to being renamed, as specified by the
+    /// `#[renamed_from(name)]` attribute. Fields that were not renamed are `None`.
+    pub fn field_renamed_from(&self) -> impl Iterator<Item = Option<&str>> {
+        let renamed_from = if self.num_fields == 0 || self.field_renamed_from.is_null() {
+            None
+        } else {
+            Some(unsafe {
+                slice::from_raw_parts(self.field_renamed_from, self.num_fields as usize)
+            })
+        };
+
+        (0..self.num_fields()).map(move |idx| {
+            renamed_from.and_then(|names| {
+                let name = names[idx];
+                if name.is_null() {
+                    None
+                } else {
+                    Some(unsafe { str::from_utf8_unchecked(CStr::from_ptr(name).to_bytes()) })
+                }
+            })
+        })
+    }
+
     /// Returns the number of struct fields.
     pub fn num_fields(&self) -> usize {
         self.num_fields.into()
@@ -108,6 +137,7 @@ impl From<StructMemoryKind> for u64 {
         match kind {
             StructMemoryKind::GC => 0,
             StructMemoryKind::Value => 1,
+            StructMemoryKind::Extern => 2,
         }
     }
 }
@@ -151,6 +181,34 @@ mod tests {
         }
         assert_eq!(struct_info.field_types(), field_types);
         assert_eq!(struct_info.field_offsets(), field_offsets);
+        assert_eq!(
+            struct_info.field_renamed_from().collect::<Vec<_>>(),
+            vec![None]
+        );
+    }
+
+    #[test]
+    fn test_struct_info_field_renamed_from() {
+        let field_names = [
+            CString::new("position").expect("Invalid fake field name."),
+            CString::new("velocity").expect("Invalid fake field name."),
+        ];
+        let renamed_from = CString::new("pos").expect("Invalid fake field name.");
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+        let type_info = fake_type_info(&type_name, 1, 1, TypeInfoData::Primitive);
+
+        let field_names = &[field_names[0].as_ptr(), field_names[1].as_ptr()];
+        let field_types = &[&type_info, &type_info];
+        let field_offsets = &[0, 1];
+        let field_renamed_from = &[renamed_from.as_ptr(), std::ptr::null()];
+        let mut struct_info =
+            fake_struct_info(field_names, field_types, field_offsets, Default::default());
+        struct_info.field_renamed_from = field_renamed_from.as_ptr();
+
+        assert_eq!(
+            struct_info.field_renamed_from().collect::<Vec<_>>(),
+            vec![Some("pos"), None]
+        );
     }
 
     #[test]
@@ -168,4 +226,12 @@ mod tests {
 
         assert_eq!(struct_info.memory_kind, struct_memory_kind);
     }
+
+    #[test]
+    fn test_struct_info_memory_kind_extern() {
+        let struct_memory_kind = StructMemoryKind::Extern;
+        let struct_info = fake_struct_info(&[], &[], &[], struct_memory_kind.clone());
+
+        assert_eq!(struct_info.memory_kind, struct_memory_kind);
+    }
 }
@@ -76,6 +76,8 @@ pub(crate) fn fake_module_info(
         num_functions: functions.len() as u32,
         types: types.as_ptr().cast::<*const TypeInfo>(),
         num_types: types.len() as u32,
+        migrations: ptr::null(),
+        num_migrations: 0,
     }
 }
 
@@ -92,6 +94,7 @@ pub(crate) fn fake_struct_info(
         field_names: field_names.as_ptr(),
         field_types: field_types.as_ptr().cast::<*const TypeInfo>(),
         field_offsets: field_offsets.as_ptr(),
+        field_renamed_from: ptr::null(),
         num_fields: field_names.len() as u16,
         memory_kind,
     }
@@ -1,11 +1,11 @@
-use crate::{static_type_map::StaticTypeMap, Guid, StructInfo};
+use crate::{static_type_map::StaticTypeMap, Guid, StructInfo, StructMemoryKind};
 use once_cell::sync::OnceCell;
 use std::{
     convert::TryInto,
     ffi::{CStr, CString},
     fmt::{self, Formatter},
     os::raw::c_char,
-    str,
+    ptr, str,
     sync::Once,
 };
 
@@ -39,6 +39,31 @@ pub enum TypeInfoData {
 }
 
 impl TypeInfo {
+    /// Constructs the type information of a host-defined type, that can be used in Mun code by
+    /// declaring an `extern struct` with the same `name`. The type is opaque to Mun code, so it
+    /// does not have any fields.
+    ///
+    /// The `name` must outlive the returned `TypeInfo`.
+    pub fn new_extern_struct(name: &CStr, size_in_bits: u32, alignment: u8) -> TypeInfo {
+        TypeInfo {
+            guid: Guid::from_extern_struct_name(
+                name.to_str()
+                    .expect("extern struct name must be valid UTF-8"),
+            ),
+            name: name.as_ptr(),
+            size_in_bits,
+            alignment,
+            data: TypeInfoData::Struct(StructInfo {
+                field_names: ptr::null(),
+                field_types: ptr::null(),
+                field_offsets: ptr::null(),
+                field_renamed_from: ptr::null(),
+                num_fields: 0,
+                memory_kind: StructMemoryKind::Extern,
+            }),
+        }
+    }
+
     /// Returns the type's name.
     pub fn name(&self) -> &str {
         unsafe { str::from_utf8_unchecked(CStr::from_ptr(self.name).to_bytes()) }
@@ -287,8 +312,11 @@ impl HasStaticTypeInfo for isize {
 
 #[cfg(test)]
 mod tests {
-    use super::{HasStaticTypeInfoName, TypeInfoData};
-    use crate::test_utils::{fake_struct_info, fake_type_info, FAKE_TYPE_NAME};
+    use super::{HasStaticTypeInfoName, TypeInfo, TypeInfoData};
+    use crate::{
+        test_utils::{fake_struct_info, fake_type_info, FAKE_TYPE_NAME},
+        Guid, StructMemoryKind,
+    };
     use std::ffi::CString;
 
     #[test]
@@ -334,6 +362,26 @@ mod tests {
         assert!(!type_info.data.is_primitive());
     }
 
+    #[test]
+    fn test_type_info_extern_struct() {
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+        let type_info = TypeInfo::new_extern_struct(&type_name, 64, 8);
+
+        assert_eq!(type_info.name(), FAKE_TYPE_NAME);
+        assert_eq!(type_info.size_in_bytes(), 8);
+        assert_eq!(type_info.alignment(), 8);
+        assert_eq!(
+            type_info.guid,
+            Guid::from_extern_struct_name(FAKE_TYPE_NAME)
+        );
+
+        let struct_info = type_info
+            .as_struct()
+            .expect("Extern type must be a struct.");
+        assert_eq!(struct_info.num_fields(), 0);
+        assert_eq!(struct_info.memory_kind, StructMemoryKind::Extern);
+    }
+
     #[test]
     fn test_type_info_eq() {
         let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
@@ -0,0 +1,567 @@
+//! Generates C++ bindings that call through the header-only C++ wrapper of the runtime C API,
+//! `mun/runtime.h`.
+
+use crate::{memory_kind_name, Bindings, Function, Primitive, Struct, Type};
+use std::fmt::Write;
+
+/// Returns the C++ type corresponding to a primitive, if it can be marshalled.
+fn primitive_type(primitive: Primitive) -> Option<&'static str> {
+    let ty = match primitive {
+        Primitive::I8 => "int8_t",
+        Primitive::I16 => "int16_t",
+        Primitive::I32 => "int32_t",
+        Primitive::I64 => "int64_t",
+        Primitive::U8 => "uint8_t",
+        Primitive::U16 => "uint16_t",
+        Primitive::U32 => "uint32_t",
+        Primitive::U64 => "uint64_t",
+        Primitive::F32 => "float",
+        Primitive::F64 => "double",
+        Primitive::Bool => "bool",
+        Primitive::I128 | Primitive::U128 => return None,
+    };
+    Some(ty)
+}
+
+/// Returns whether a primitive can be represented in C++.
+fn supports(primitive: Primitive) -> bool {
+    primitive_type(primitive).is_some()
+}
+
+/// The keywords of C++, which cannot be used as identifiers.
+const KEYWORDS: &[&str] = &[
+    "alignas",
+    "alignof",
+    "and",
+    "and_eq",
+    "asm",
+    "auto",
+    "bitand",
+    "bitor",
+    "bool",
+    "break",
+    "case",
+    "catch",
+    "char",
+    "char16_t",
+    "char32_t",
+    "char8_t",
+    "class",
+    "co_await",
+    "co_return",
+    "co_yield",
+    "compl",
+    "concept",
+    "const",
+    "const_cast",
+    "consteval",
+    "constexpr",
+    "constinit",
+    "continue",
+    "decltype",
+    "default",
+    "delete",
+    "do",
+    "double",
+    "dynamic_cast",
+    "else",
+    "enum",
+    "explicit",
+    "export",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "friend",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "mutable",
+    "namespace",
+    "new",
+    "noexcept",
+    "not",
+    "not_eq",
+    "nullptr",
+    "operator",
+    "or",
+    "or_eq",
+    "private",
+    "protected",
+    "public",
+    "register",
+    "reinterpret_cast",
+    "requires",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "static_assert",
+    "static_cast",
+    "struct",
+    "switch",
+    "template",
+    "this",
+    "thread_local",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "typeid",
+    "typename",
+    "union",
+    "unsigned",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "wchar_t",
+    "while",
+    "xor",
+    "xor_eq",
+];
+
+/// Returns whether `name` is a C++ keyword.
+fn is_keyword(name: &str) -> bool {
+    KEYWORDS.contains(&name)
+}
+
+/// Returns the C++ identifier for `name`, suffixing keywords with an underscore.
+fn ident(name: &str) -> String {
+    if is_keyword(name) {
+        format!("{}_", name)
+    } else {
+        name.to_owned()
+    }
+}
+
+/// Returns the namespace that corresponds to the module at `module_path`.
+fn namespace(module_path: &[&str]) -> String {
+    std::iter::once(String::from("bindings"))
+        .chain(module_path.iter().map(|name| ident(name)))
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Writes `items` - each paired with the path of the module it belongs to - into the namespaces
+/// that correspond to their modules. Items of the same module are grouped in a single namespace
+/// block, in the order in which they are specified.
+fn write_namespaced(out: &mut String, mut items: Vec<(Vec<&str>, String)>) {
+    items.sort_by(|(a, _), (b, _)| a.cmp(b));
+    write_ordered(out, items);
+}
+
+/// Writes `items` - each paired with the path of the module it belongs to - into the namespaces
+/// that correspond to their modules, in the order in which they are specified. Only consecutive
+/// items of the same module are grouped in a single namespace block.
+fn write_ordered(out: &mut String, items: Vec<(Vec<&str>, String)>) {
+    let mut items = items.into_iter().peekable();
+    while let Some((module_path, item)) = items.next() {
+        let namespace = namespace(&module_path);
+        write!(out, "\nnamespace {} {{", namespace).unwrap();
+        out.push_str(&item);
+        while items.peek().map_or(false, |(path, _)| *path == module_path) {
+            out.push_str(&items.next().unwrap().1);
+        }
+        writeln!(out, "}}  // namespace {}", namespace).unwrap();
+    }
+}
+
+/// Generates C++ bindings for the functions and structs in `bindings`. Items that are defined in
+/// a submodule are generated in a corresponding nested namespace of the `bindings` namespace.
+pub fn generate(bindings: &Bindings) -> String {
+    let generator = Generator { bindings };
+    let (mirrors, wrappers): (Vec<&Struct>, Vec<&Struct>) = bindings
+        .structs
+        .iter()
+        .partition(|s| bindings.is_mirrored(s, &supports, &is_keyword));
+
+    let mut out = format!(
+        r#"// Bindings for the Mun assembly `{}`, generated by `mun bindgen`. Do not edit by hand.
+#pragma once
+
+#include <cstdint>
+#include <optional>
+#include <string_view>
+
+#include "mun/runtime.h"
+"#,
+        bindings.assembly_path
+    );
+
+    // Wrappers are declared up front, as they can reference each other
+    let mut declarations: Vec<(Vec<&str>, String)> = Vec::new();
+    for s in wrappers.iter() {
+        let module_path = s.module_path();
+        let declaration = format!("class {};\n", ident(s.ident()));
+        match declarations
+            .iter_mut()
+            .find(|(path, _)| *path == module_path)
+        {
+            Some((_, item)) => item.push_str(&declaration),
+            None => declarations.push((module_path, format!("\n{}", declaration))),
+        }
+    }
+    write_namespaced(&mut out, declarations);
+
+    // Mirrors contain the mirrors they depend on, so they are defined in order
+    let mirror_definitions = mirrors
+        .iter()
+        .map(|s| {
+            let module_path = s.module_path();
+            let mut item = String::from("\n");
+            generator.write_mirror(&mut item, s, &module_path);
+            (module_path, item)
+        })
+        .collect();
+    write_ordered(&mut out, mirror_definitions);
+
+    if !bindings.structs.is_empty() {
+        out.push_str("\nnamespace mun {");
+        for s in bindings.structs.iter() {
+            out.push('\n');
+            generator.write_marshal_declaration(&mut out, s);
+        }
+        out.push_str("}  // namespace mun\n");
+    }
+
+    let classes = wrappers
+        .iter()
+        .map(|s| {
+            let module_path = s.module_path();
+            let mut item = String::from("\n");
+            generator.write_wrapper(&mut item, s, &module_path);
+            (module_path, item)
+        })
+        .collect();
+    write_namespaced(&mut out, classes);
+
+    if !bindings.structs.is_empty() {
+        out.push_str("\nnamespace mun {");
+        for s in mirrors.iter() {
+            out.push('\n');
+            generator.write_mirror_marshal(&mut out, s);
+        }
+        for s in wrappers.iter() {
+            out.push('\n');
+            generator.write_wrapper_marshal(&mut out, s);
+        }
+        out.push_str("}  // namespace mun\n");
+    }
+
+    let mut definitions = Vec::new();
+    for s in wrappers.iter() {
+        let module_path = s.module_path();
+        let mut item = String::new();
+        generator.write_wrapper_accessors(&mut item, s, &module_path);
+        definitions.push((module_path, item));
+    }
+    for function in bindings.functions.iter() {
+        let module_path = function.module_path();
+        let mut item = String::from("\n");
+        generator.write_function(&mut item, function, &module_path);
+        definitions.push((module_path, item));
+    }
+    write_namespaced(&mut out, definitions);
+
+    out
+}
+
+struct Generator<'b> {
+    bindings: &'b Bindings,
+}
+
+impl<'b> Generator<'b> {
+    /// Returns the name through which `s` is referenced from the namespace of the module at
+    /// `scope`, or from outside of the `bindings` namespace if no `scope` is specified.
+    fn struct_name(&self, s: &Struct, scope: Option<&[&str]>) -> String {
+        let path = s
+            .module_path()
+            .into_iter()
+            .chain(std::iter::once(s.ident()))
+            .map(ident)
+            .collect::<Vec<_>>()
+            .join("::");
+        match scope {
+            Some([]) => path,
+            Some(_) => format!("::bindings::{}", path),
+            None => format!("bindings::{}", path),
+        }
+    }
+
+    /// Returns the C++ type corresponding to `ty`, as referenced from the namespace of the module
+    /// at `scope`, or from outside of the `bindings` namespace if no `scope` is specified.
+    fn cpp_type(&self, ty: &Type, scope: Option<&[&str]>) -> String {
+        match ty {
+            Type::Primitive(primitive) => primitive_type(*primitive)
+                .unwrap_or_else(|| unreachable!("unsupported type `{:?}`", primitive))
+                .to_owned(),
+            Type::Struct(name) => match self.bindings.get_struct(name) {
+                Some(s) => self.struct_name(s, scope),
+                None => String::from("mun::StructRef"),
+            },
+            Type::Unsupported(name) => unreachable!("unsupported type `{}`", name),
+        }
+    }
+
+    /// Returns whether `ty` can be represented in C++.
+    fn is_supported(&self, ty: &Type) -> bool {
+        match ty {
+            Type::Primitive(primitive) => supports(*primitive),
+            Type::Struct(_) => true,
+            Type::Unsupported(_) => false,
+        }
+    }
+
+    /// Writes a struct with the same memory layout as the value struct `s` into the namespace of
+    /// the module at `scope`.
+    fn write_mirror(&self, out: &mut String, s: &Struct, scope: &[&str]) {
+        let fields = s
+            .fields
+            .iter()
+            .map(|field| {
+                format!(
+                    "    {} {};\n",
+                    self.cpp_type(&field.ty, Some(scope)),
+                    field.ident()
+                )
+            })
+            .collect::<String>();
+
+        write!(
+            out,
+            r#"/** Mirrors the Mun `value` struct `{name}`. */
+struct {ident} {{
+{fields}}};
+static_assert(sizeof({ident}) == {size}, "The size of `{ident}` does not match the Mun struct");
+"#,
+            name = s.name,
+            ident = ident(s.ident()),
+            fields = fields,
+            size = s.size,
+        )
+        .unwrap();
+    }
+
+    /// Writes the declaration of the `Marshal` specialization of the struct `s`.
+    fn write_marshal_declaration(&self, out: &mut String, s: &Struct) {
+        write!(
+            out,
+            r#"template <>
+struct Marshal<{ty}> {{
+    static constexpr MunValueKind kind = MunValueKind_Struct;
+
+    static MunValue to_value(MunRuntimeHandle runtime, const {ty}& value);
+    static {ty} from_value(MunRuntimeHandle runtime, const MunValue& value);
+}};
+"#,
+            ty = self.struct_name(s, None)
+        )
+        .unwrap();
+    }
+
+    /// Writes the definition of the `Marshal` specialization of the mirrored struct `s`, which
+    /// copies the struct field by field.
+    fn write_mirror_marshal(&self, out: &mut String, s: &Struct) {
+        let set_fields = s
+            .fields
+            .iter()
+            .map(|field| {
+                format!(
+                    "    details::set_field(result, \"{}\", value.{});\n",
+                    field.name,
+                    field.ident()
+                )
+            })
+            .collect::<String>();
+        let get_fields = s
+            .fields
+            .iter()
+            .map(|field| {
+                format!(
+                    "        details::get_field<{}>(ref, \"{}\"),\n",
+                    self.cpp_type(&field.ty, None),
+                    field.name
+                )
+            })
+            .collect::<String>();
+
+        write!(
+            out,
+            r#"inline MunValue Marshal<{ty}>::to_value(MunRuntimeHandle runtime, const {ty}& value) {{
+    StructRef result = details::alloc_struct(runtime, "{name}");
+{set_fields}    return Marshal<StructRef>::to_value(runtime, result);
+}}
+
+inline {ty} Marshal<{ty}>::from_value(MunRuntimeHandle runtime, const MunValue& value) {{
+    const StructRef ref = details::expect_struct(runtime, value, "{name}");
+    return {ty}{{
+{get_fields}    }};
+}}
+"#,
+            name = s.name,
+            ty = self.struct_name(s, None),
+            set_fields = set_fields,
+            get_fields = get_fields,
+        )
+        .unwrap();
+    }
+
+    /// Writes the definition of the `Marshal` specialization of the wrapped struct `s`, which
+    /// marshals the struct by reference.
+    fn write_wrapper_marshal(&self, out: &mut String, s: &Struct) {
+        write!(
+            out,
+            r#"inline MunValue Marshal<{ty}>::to_value(MunRuntimeHandle runtime, const {ty}& value) {{
+    return Marshal<StructRef>::to_value(runtime, value.as_ref());
+}}
+
+inline {ty} Marshal<{ty}>::from_value(MunRuntimeHandle runtime, const MunValue& value) {{
+    return {ty}(details::expect_struct(runtime, value, {ty}::NAME));
+}}
+"#,
+            ty = self.struct_name(s, None),
+        )
+        .unwrap();
+    }
+
+    /// Writes a class that references the struct `s`, declaring typed accessors for its fields,
+    /// into the namespace of the module at `scope`.
+    fn write_wrapper(&self, out: &mut String, s: &Struct, scope: &[&str]) {
+        let mut accessors = String::new();
+        for field in s.fields.iter() {
+            if !self.is_supported(&field.ty) {
+                let ty = match &field.ty {
+                    Type::Primitive(primitive) => format!("core::{:?}", primitive).to_lowercase(),
+                    Type::Struct(name) | Type::Unsupported(name) => name.clone(),
+                };
+                write!(
+                    accessors,
+                    "\n    // `{}` is skipped: unsupported type `{}`.\n",
+                    field.name, ty
+                )
+                .unwrap();
+                continue;
+            }
+
+            write!(
+                accessors,
+                r#"
+    /** Retrieves the value of the field `{name}`. */
+    std::optional<{ty}> {getter}(mun::Error* out_error = nullptr) const;
+
+    /** Sets the value of the field `{name}`. Returns whether the value was set. */
+    bool set_{name}(const {ty}& value, mun::Error* out_error = nullptr);
+"#,
+                name = field.name,
+                getter = ident(&field.ident()),
+                ty = self.cpp_type(&field.ty, Some(scope)),
+            )
+            .unwrap();
+        }
+
+        write!(
+            out,
+            r#"/** A reference to the Mun `{memory_kind}` struct `{name}`. */
+class {ident} {{
+   public:
+    /** The name of the Mun struct */
+    static constexpr std::string_view NAME = "{name}";
+
+    /** Wraps `inner`, which must reference a struct of type `{name}`. */
+    explicit {ident}(mun::StructRef inner) noexcept : m_inner(inner) {{}}
+
+    /** Returns the wrapped struct reference. */
+    mun::StructRef as_ref() const noexcept {{ return m_inner; }}
+{accessors}
+   private:
+    mun::StructRef m_inner;
+}};
+"#,
+            memory_kind = memory_kind_name(&s.memory_kind),
+            name = s.name,
+            ident = ident(s.ident()),
+            accessors = accessors,
+        )
+        .unwrap();
+    }
+
+    /// Writes the definitions of the field accessors of the wrapped struct `s` into the namespace
+    /// of the module at `scope`.
+    fn write_wrapper_accessors(&self, out: &mut String, s: &Struct, scope: &[&str]) {
+        for field in s.fields.iter().filter(|field| self.is_supported(&field.ty)) {
+            write!(
+                out,
+                r#"
+inline std::optional<{ty}> {ident}::{getter}(mun::Error* out_error) const {{
+    return m_inner.get<{ty}>("{name}", out_error);
+}}
+
+inline bool {ident}::set_{name}(const {ty}& value, mun::Error* out_error) {{
+    return m_inner.set("{name}", value, out_error);
+}}
+"#,
+                ident = ident(s.ident()),
+                name = field.name,
+                getter = ident(&field.ident()),
+                ty = self.cpp_type(&field.ty, Some(scope)),
+            )
+            .unwrap();
+        }
+    }
+
+    /// Writes a function that invokes `function` through the runtime into the namespace of the
+    /// module at `scope`.
+    fn write_function(&self, out: &mut String, function: &Function, scope: &[&str]) {
+        if let Some(ty) = function.unsupported_type(supports) {
+            writeln!(
+                out,
+                "// `{}` is skipped: unsupported type `{}`.",
+                function.name, ty
+            )
+            .unwrap();
+            return;
+        }
+
+        let arg_types = function
+            .arg_types
+            .iter()
+            .map(|ty| self.cpp_type(ty, Some(scope)))
+            .collect::<Vec<_>>();
+        let return_type = function
+            .return_type
+            .as_ref()
+            .map_or_else(|| String::from("void"), |ty| self.cpp_type(ty, Some(scope)));
+        let args = arg_types
+            .iter()
+            .enumerate()
+            .map(|(idx, ty)| format!(", {} arg{}", ty, idx))
+            .collect::<String>();
+        let arg_names = (0..arg_types.len())
+            .map(|idx| format!(", arg{}", idx))
+            .collect::<String>();
+        let result_types = std::iter::once(return_type.as_str())
+            .chain(arg_types.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        write!(
+            out,
+            r#"/** Invokes the Mun function `{name}`. */
+inline mun::InvokeResult<{result_types}> {ident}(mun::Runtime& runtime{args}) {{
+    return mun::invoke_fn<{return_type}>(runtime, "{name}"{arg_names});
+}}
+"#,
+            name = function.name,
+            ident = ident(function.ident()),
+            result_types = result_types,
+            return_type = return_type,
+            args = args,
+            arg_names = arg_names,
+        )
+        .unwrap();
+    }
+}
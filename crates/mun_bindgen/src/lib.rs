@@ -0,0 +1,325 @@
+//! Generates typed host bindings for the functions and structs of a Mun assembly.
+//!
+//! [`Bindings`] describe the functions and structs of an assembly, as read from its
+//! `AssemblyInfo`. From these, [`rust::generate`] and [`cpp::generate`] generate wrappers that call
+//! through the Mun Runtime. Regenerating the bindings after changing the assembly turns signature
+//! changes into compile errors in the host. Items that are defined in a submodule are generated in
+//! a nested Rust module or C++ namespace, and names that are keywords of the host language are
+//! escaped.
+//!
+//! Value structs that only contain primitives and other mirrored value structs are mirrored by
+//! `#[repr(C)]` structs that are marshalled by copy. All other structs are wrapped in a reference
+//! with typed field accessors.
+pub mod cpp;
+pub mod rust;
+
+#[cfg(test)]
+mod tests;
+
+use libloader::MunLibrary;
+use std::{collections::HashSet, path::Path};
+
+/// A primitive Mun type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Primitive {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    F32,
+    F64,
+    Bool,
+}
+
+impl Primitive {
+    /// Returns the primitive corresponding to a Mun type name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let primitive = match name {
+            "core::i8" => Primitive::I8,
+            "core::i16" => Primitive::I16,
+            "core::i32" => Primitive::I32,
+            "core::i64" => Primitive::I64,
+            "core::i128" => Primitive::I128,
+            "core::u8" => Primitive::U8,
+            "core::u16" => Primitive::U16,
+            "core::u32" => Primitive::U32,
+            "core::u64" => Primitive::U64,
+            "core::u128" => Primitive::U128,
+            "core::f32" => Primitive::F32,
+            "core::f64" => Primitive::F64,
+            "core::bool" => Primitive::Bool,
+            _ => return None,
+        };
+        Some(primitive)
+    }
+}
+
+/// The type of a field, argument, or return value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Type {
+    /// A primitive type
+    Primitive(Primitive),
+    /// A struct, identified by its name
+    Struct(String),
+    /// A type that cannot be represented in bindings, identified by its name
+    Unsupported(String),
+}
+
+impl Type {
+    fn from_type_info(type_info: &abi::TypeInfo) -> Self {
+        if type_info.data.is_struct() {
+            Type::Struct(type_info.name().to_owned())
+        } else {
+            Primitive::from_name(type_info.name()).map_or_else(
+                || Type::Unsupported(type_info.name().to_owned()),
+                Type::Primitive,
+            )
+        }
+    }
+}
+
+/// A field of a [`Struct`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    /// The name of the field. The fields of tuple structs are named by their index.
+    pub name: String,
+    /// The type of the field
+    pub ty: Type,
+}
+
+impl Field {
+    /// Returns the identifier used for the field in bindings.
+    pub fn ident(&self) -> String {
+        if self.name.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("_{}", self.name)
+        } else {
+            self.name.clone()
+        }
+    }
+}
+
+/// A struct defined in a Mun assembly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Struct {
+    /// The fully qualified name of the struct
+    pub name: String,
+    /// The kind of memory management the struct uses
+    pub memory_kind: abi::StructMemoryKind,
+    /// The size of the struct in bytes
+    pub size: usize,
+    /// The fields of the struct
+    pub fields: Vec<Field>,
+}
+
+impl Struct {
+    /// Returns the path of the module in which the struct is defined; i.e. all but the last
+    /// segment of its name.
+    pub fn module_path(&self) -> Vec<&str> {
+        split_name(&self.name).0
+    }
+
+    /// Returns the name of the struct within its module; i.e. the last segment of its name.
+    pub fn ident(&self) -> &str {
+        split_name(&self.name).1
+    }
+}
+
+/// A function defined in a Mun assembly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Function {
+    /// The name of the function
+    pub name: String,
+    /// The types of the function's arguments
+    pub arg_types: Vec<Type>,
+    /// The return type of the function, if any
+    pub return_type: Option<Type>,
+}
+
+impl Function {
+    /// Returns the path of the module in which the function is defined; i.e. all but the last
+    /// segment of its name.
+    pub fn module_path(&self) -> Vec<&str> {
+        split_name(&self.name).0
+    }
+
+    /// Returns the name of the function within its module; i.e. the last segment of its name.
+    pub fn ident(&self) -> &str {
+        split_name(&self.name).1
+    }
+
+    /// Returns the first unsupported type in the function's signature, if any.
+    pub(crate) fn unsupported_type(&self, supports: impl Fn(Primitive) -> bool) -> Option<String> {
+        self.arg_types
+            .iter()
+            .chain(self.return_type.iter())
+            .find_map(|ty| match ty {
+                Type::Primitive(primitive) if !supports(*primitive) => {
+                    Some(format!("core::{:?}", primitive).to_lowercase())
+                }
+                Type::Unsupported(name) => Some(name.clone()),
+                _ => None,
+            })
+    }
+}
+
+/// The functions and structs of a Mun assembly for which bindings are generated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bindings {
+    /// The path of the assembly
+    pub assembly_path: String,
+    /// The structs of the assembly, ordered such that every value struct succeeds the value
+    /// structs it contains
+    pub structs: Vec<Struct>,
+    /// The functions of the assembly
+    pub functions: Vec<Function>,
+}
+
+impl Bindings {
+    /// Loads the Mun library at `library_path` and reads its bindings.
+    pub fn from_library(library_path: &Path) -> Result<Self, anyhow::Error> {
+        let library = MunLibrary::new(library_path)?;
+
+        let version = library.get_abi_version();
+        if abi::ABI_VERSION != version {
+            return Err(anyhow::anyhow!(
+                "ABI version mismatch. munlib is `{}` but bindgen is `{}`",
+                version,
+                abi::ABI_VERSION
+            ));
+        }
+
+        Ok(Self::from_assembly_info(&library.get_info()))
+    }
+
+    /// Reads the bindings of the functions and structs in `assembly_info`, including all structs
+    /// that are referenced by their signatures and fields.
+    pub fn from_assembly_info(assembly_info: &abi::AssemblyInfo) -> Self {
+        let symbols = &assembly_info.symbols;
+
+        let mut visited = HashSet::new();
+        let mut structs = Vec::new();
+        for type_info in symbols.types() {
+            collect_structs(type_info, &mut visited, &mut structs);
+        }
+
+        let functions = symbols
+            .functions()
+            .iter()
+            .map(|definition| {
+                let signature = &definition.prototype.signature;
+                for type_info in signature
+                    .arg_types()
+                    .iter()
+                    .copied()
+                    .chain(signature.return_type())
+                {
+                    collect_structs(type_info, &mut visited, &mut structs);
+                }
+
+                Function {
+                    name: definition.prototype.name().to_owned(),
+                    arg_types: signature
+                        .arg_types()
+                        .iter()
+                        .map(|type_info| Type::from_type_info(type_info))
+                        .collect(),
+                    return_type: signature.return_type().map(Type::from_type_info),
+                }
+            })
+            .collect();
+
+        Bindings {
+            assembly_path: symbols.path().to_owned(),
+            structs,
+            functions,
+        }
+    }
+
+    /// Returns the struct with the specified name, if it exists.
+    pub fn get_struct(&self, name: &str) -> Option<&Struct> {
+        self.structs.iter().find(|s| s.name == name)
+    }
+
+    /// Returns whether `s` is mirrored by a struct with the same memory layout. This is the case
+    /// for value structs with named fields - that are not keywords of the host language - of which
+    /// the types are primitives that are `supported` by the host language, or other mirrored
+    /// structs.
+    pub(crate) fn is_mirrored(
+        &self,
+        s: &Struct,
+        supports: &impl Fn(Primitive) -> bool,
+        is_keyword: &impl Fn(&str) -> bool,
+    ) -> bool {
+        s.memory_kind == abi::StructMemoryKind::Value
+            && !s.fields.is_empty()
+            && s.fields.iter().all(|field| {
+                field.name == field.ident()
+                    && !is_keyword(&field.name)
+                    && match &field.ty {
+                        Type::Primitive(primitive) => supports(*primitive),
+                        Type::Struct(name) => matches!(
+                            self.get_struct(name),
+                            Some(s) if self.is_mirrored(s, supports, is_keyword)
+                        ),
+                        Type::Unsupported(_) => false,
+                    }
+            })
+    }
+}
+
+/// Returns the name of the memory kind as it is written in Mun.
+pub(crate) fn memory_kind_name(memory_kind: &abi::StructMemoryKind) -> &'static str {
+    match memory_kind {
+        abi::StructMemoryKind::GC => "gc",
+        abi::StructMemoryKind::Value => "value",
+        abi::StructMemoryKind::Extern => "extern",
+    }
+}
+
+/// Splits a fully qualified name into the path of its module and its last segment.
+fn split_name(name: &str) -> (Vec<&str>, &str) {
+    let mut segments = name.split("::").collect::<Vec<_>>();
+    let ident = segments.pop().unwrap();
+    (segments, ident)
+}
+
+/// Collects `type_info` and the structs referenced by its fields in post-order, skipping structs
+/// that were already `visited`.
+fn collect_structs(
+    type_info: &abi::TypeInfo,
+    visited: &mut HashSet<abi::Guid>,
+    structs: &mut Vec<Struct>,
+) {
+    let struct_info = match type_info.as_struct() {
+        Some(struct_info) => struct_info,
+        None => return,
+    };
+
+    if !visited.insert(type_info.guid) {
+        return;
+    }
+
+    for field_type in struct_info.field_types() {
+        collect_structs(field_type, visited, structs);
+    }
+
+    structs.push(Struct {
+        name: type_info.name().to_owned(),
+        memory_kind: struct_info.memory_kind.clone(),
+        size: type_info.size_in_bytes(),
+        fields: struct_info
+            .field_names()
+            .zip(struct_info.field_types())
+            .map(|(name, ty)| Field {
+                name: name.to_owned(),
+                ty: Type::from_type_info(ty),
+            })
+            .collect(),
+    });
+}
@@ -0,0 +1,419 @@
+//! Generates Rust bindings that call through the `mun_runtime` crate.
+
+use crate::{memory_kind_name, Bindings, Function, Primitive, Struct, Type};
+use std::fmt::Write;
+
+/// Returns the Rust type corresponding to a primitive.
+fn primitive_type(primitive: Primitive) -> &'static str {
+    match primitive {
+        Primitive::I8 => "i8",
+        Primitive::I16 => "i16",
+        Primitive::I32 => "i32",
+        Primitive::I64 => "i64",
+        Primitive::I128 => "i128",
+        Primitive::U8 => "u8",
+        Primitive::U16 => "u16",
+        Primitive::U32 => "u32",
+        Primitive::U64 => "u64",
+        Primitive::U128 => "u128",
+        Primitive::F32 => "f32",
+        Primitive::F64 => "f64",
+        Primitive::Bool => "bool",
+    }
+}
+
+/// Returns whether a primitive can be represented in Rust, which is the case for all primitives.
+fn supports(_primitive: Primitive) -> bool {
+    true
+}
+
+/// The keywords of Rust, which cannot be used as identifiers.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in",
+    "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Returns whether `name` is a Rust keyword.
+fn is_keyword(name: &str) -> bool {
+    KEYWORDS.contains(&name)
+}
+
+/// Returns the Rust identifier for `name`, escaping keywords as raw identifiers. Path keywords
+/// cannot be raw identifiers and are suffixed with an underscore instead.
+fn ident(name: &str) -> String {
+    match name {
+        "crate" | "self" | "Self" | "super" => format!("{}_", name),
+        _ if is_keyword(name) => format!("r#{}", name),
+        _ => name.to_owned(),
+    }
+}
+
+/// Returns whether `s` is mirrored by a `#[repr(C)]` struct.
+fn is_mirrored(bindings: &Bindings, s: &Struct) -> bool {
+    bindings.is_mirrored(s, &supports, &is_keyword)
+}
+
+/// Generates Rust bindings for the functions and structs in `bindings`. Items that are defined in
+/// a submodule are generated in a corresponding nested module.
+pub fn generate(bindings: &Bindings) -> String {
+    let mut out = format!(
+        r#"//! Bindings for the Mun assembly `{}`, generated by `mun bindgen`. Do not edit by hand.
+#![allow(dead_code, unused_imports)]
+
+use mun_runtime::{{invoke_fn, MunStruct, Runtime, StructRef}};
+"#,
+        bindings.assembly_path
+    );
+
+    let generator = Generator { bindings };
+    let mut root = Module::default();
+    for s in bindings.structs.iter() {
+        let module_path = s.module_path();
+        let mut item = String::new();
+        if is_mirrored(bindings, s) {
+            generator.write_mirror(&mut item, s, &module_path);
+        } else {
+            generator.write_wrapper(&mut item, s, &module_path);
+        }
+        root.insert(&module_path, item);
+    }
+
+    for function in bindings.functions.iter() {
+        let module_path = function.module_path();
+        let mut item = String::new();
+        generator.write_function(&mut item, function, &module_path);
+        root.insert(&module_path, item);
+    }
+
+    root.write(&mut out, "");
+    out
+}
+
+/// The generated items of a module, and its submodules.
+#[derive(Default)]
+struct Module<'b> {
+    items: Vec<String>,
+    submodules: Vec<(&'b str, Module<'b>)>,
+}
+
+impl<'b> Module<'b> {
+    /// Adds `item` to the module at `path`, relative to this module.
+    fn insert(&mut self, path: &[&'b str], item: String) {
+        match path.split_first() {
+            None => self.items.push(item),
+            Some((name, path)) => {
+                let idx = match self.submodules.iter().position(|(n, _)| n == name) {
+                    Some(idx) => idx,
+                    None => {
+                        self.submodules.push((name, Module::default()));
+                        self.submodules.len() - 1
+                    }
+                };
+                self.submodules[idx].1.insert(path, item);
+            }
+        }
+    }
+
+    /// Writes the items of the module, followed by its submodules, indenting every line with
+    /// `indent`.
+    fn write(&self, out: &mut String, indent: &str) {
+        for item in self.items.iter() {
+            out.push('\n');
+            for line in item.lines() {
+                if !line.is_empty() {
+                    out.push_str(indent);
+                    out.push_str(line);
+                }
+                out.push('\n');
+            }
+        }
+
+        for (name, module) in self.submodules.iter() {
+            writeln!(out, "\n{}pub mod {} {{", indent, ident(name)).unwrap();
+            writeln!(
+                out,
+                "{}    use mun_runtime::{{invoke_fn, MunStruct, Runtime, StructRef}};",
+                indent
+            )
+            .unwrap();
+            module.write(out, &format!("{}    ", indent));
+            writeln!(out, "{}}}", indent).unwrap();
+        }
+    }
+}
+
+struct Generator<'b> {
+    bindings: &'b Bindings,
+}
+
+impl<'b> Generator<'b> {
+    /// Returns the wrapper of the struct type `ty`, if values of type `ty` are wrapped in a
+    /// reference.
+    fn wrapper(&self, ty: &Type) -> Option<&'b Struct> {
+        match ty {
+            Type::Struct(name) => self
+                .bindings
+                .get_struct(name)
+                .filter(|s| !is_mirrored(self.bindings, s)),
+            _ => None,
+        }
+    }
+
+    /// Returns the path through which `s` is referenced from the module at `scope`.
+    fn struct_path(&self, s: &Struct, scope: &[&str]) -> String {
+        let module_path = s.module_path();
+        let common = scope
+            .iter()
+            .zip(module_path.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        std::iter::repeat(String::from("super"))
+            .take(scope.len() - common)
+            .chain(module_path[common..].iter().map(|name| ident(name)))
+            .chain(std::iter::once(ident(s.ident())))
+            .collect::<Vec<_>>()
+            .join("::")
+    }
+
+    /// Returns the Rust type corresponding to `ty` in the module at `scope`, using `lifetime` for
+    /// references.
+    fn rust_type(&self, ty: &Type, lifetime: &str, scope: &[&str]) -> String {
+        match ty {
+            Type::Primitive(primitive) => primitive_type(*primitive).to_owned(),
+            Type::Struct(name) => match self.bindings.get_struct(name) {
+                Some(s) if is_mirrored(self.bindings, s) => self.struct_path(s, scope),
+                Some(s) => format!("{}<{}>", self.struct_path(s, scope), lifetime),
+                None => format!("StructRef<{}>", lifetime),
+            },
+            Type::Unsupported(name) => unreachable!("unsupported type `{}`", name),
+        }
+    }
+
+    /// Returns whether the Rust type corresponding to `ty` has a lifetime.
+    fn has_lifetime(&self, ty: &Type) -> bool {
+        match ty {
+            Type::Struct(name) => !matches!(
+                self.bindings.get_struct(name),
+                Some(s) if is_mirrored(self.bindings, s)
+            ),
+            _ => false,
+        }
+    }
+
+    /// Returns an expression that converts `value` of type `ty` into a value that can be
+    /// marshalled.
+    fn marshal_into(&self, ty: &Type, value: &str) -> String {
+        match self.wrapper(ty) {
+            Some(_) => format!("{}.into_inner()", value),
+            None => value.to_owned(),
+        }
+    }
+
+    /// Returns an expression that converts the `Result` of marshalling a value of type `ty` in the
+    /// module at `scope`.
+    fn marshal_from(&self, ty: Option<&Type>, result: &str, scope: &[&str]) -> String {
+        match ty.and_then(|ty| self.wrapper(ty)) {
+            Some(s) => format!("{}.and_then({}::new)", result, self.struct_path(s, scope)),
+            None => result.to_owned(),
+        }
+    }
+
+    /// Writes a `#[repr(C)]` struct with the same memory layout as the value struct `s` into the
+    /// module at `scope`. The struct of a submodule specifies the fully qualified name of the Mun
+    /// struct, as it differs from the name of the Rust struct.
+    fn write_mirror(&self, out: &mut String, s: &Struct, scope: &[&str]) {
+        let fields = s
+            .fields
+            .iter()
+            .map(|field| {
+                format!(
+                    "    pub {}: {},\n",
+                    field.ident(),
+                    self.rust_type(&field.ty, "'s", scope)
+                )
+            })
+            .collect::<String>();
+        let name_attribute = if scope.is_empty() {
+            String::new()
+        } else {
+            format!("#[mun(name = \"{}\")]\n", s.name)
+        };
+
+        write!(
+            out,
+            r#"/// Mirrors the Mun `value` struct `{name}`.
+#[derive(Clone, Copy, Debug, PartialEq, MunStruct)]
+{name_attribute}#[repr(C)]
+pub struct {ident} {{
+{fields}}}
+"#,
+            name = s.name,
+            name_attribute = name_attribute,
+            ident = ident(s.ident()),
+            fields = fields,
+        )
+        .unwrap();
+    }
+
+    /// Writes a reference to the struct `s`, with typed accessors for its fields, into the module
+    /// at `scope`.
+    fn write_wrapper(&self, out: &mut String, s: &Struct, scope: &[&str]) {
+        let mut accessors = String::new();
+        for field in s.fields.iter() {
+            if let Type::Unsupported(ty) = &field.ty {
+                write!(
+                    accessors,
+                    "\n    // `{}` is skipped: unsupported type `{}`.\n",
+                    field.name, ty
+                )
+                .unwrap();
+                continue;
+            }
+
+            let marshalled = match self.wrapper(&field.ty) {
+                Some(_) => "StructRef<'s>".to_owned(),
+                None => self.rust_type(&field.ty, "'s", scope),
+            };
+            write!(
+                accessors,
+                r#"
+    /// Retrieves the value of the field `{name}`.
+    pub fn {getter}(&self) -> Result<{ty}, String> {{
+        {get}
+    }}
+
+    /// Sets the value of the field `{name}`.
+    pub fn set_{name}(&mut self, value: {ty}) -> Result<(), String> {{
+        self.0.set("{name}", {value})
+    }}
+"#,
+                name = field.name,
+                getter = ident(&field.ident()),
+                ty = self.rust_type(&field.ty, "'s", scope),
+                get = self.marshal_from(
+                    Some(&field.ty),
+                    &format!("self.0.get::<{}>(\"{}\")", marshalled, field.name),
+                    scope
+                ),
+                value = self.marshal_into(&field.ty, "value"),
+            )
+            .unwrap();
+        }
+
+        write!(
+            out,
+            r#"/// A reference to the Mun `{memory_kind}` struct `{name}`.
+#[derive(Clone)]
+pub struct {ident}<'s>(StructRef<'s>);
+
+impl<'s> {ident}<'s> {{
+    /// The name of the Mun struct
+    pub const NAME: &'static str = "{name}";
+
+    /// Wraps `inner`, if it references a struct of type `{name}`.
+    pub fn new(inner: StructRef<'s>) -> Result<Self, String> {{
+        if inner.type_info().name() == Self::NAME {{
+            Ok(Self(inner))
+        }} else {{
+            Err(format!(
+                "Mismatched types. Expected: `{{}}`. Found: `{{}}`.",
+                Self::NAME,
+                inner.type_info().name()
+            ))
+        }}
+    }}
+
+    /// Returns the wrapped struct reference.
+    pub fn into_inner(self) -> StructRef<'s> {{
+        self.0
+    }}
+{accessors}}}
+"#,
+            memory_kind = memory_kind_name(&s.memory_kind),
+            name = s.name,
+            ident = ident(s.ident()),
+            accessors = accessors,
+        )
+        .unwrap();
+    }
+
+    /// Writes a function that invokes `function` through the runtime into the module at `scope`.
+    fn write_function(&self, out: &mut String, function: &Function, scope: &[&str]) {
+        if let Some(ty) = function.unsupported_type(supports) {
+            writeln!(
+                out,
+                "// `{}` is skipped: unsupported type `{}`.",
+                function.name, ty
+            )
+            .unwrap();
+            return;
+        }
+
+        let args = function
+            .arg_types
+            .iter()
+            .enumerate()
+            .map(|(idx, ty)| format!(", arg{}: {}", idx, self.rust_type(ty, "'r", scope)))
+            .collect::<String>();
+        let marshalled_args = function
+            .arg_types
+            .iter()
+            .enumerate()
+            .map(|(idx, ty)| format!(", {}", self.marshal_into(ty, &format!("arg{}", idx))))
+            .collect::<String>();
+        let return_type = function
+            .return_type
+            .as_ref()
+            .map_or_else(|| String::from("()"), |ty| self.rust_type(ty, "'r", scope));
+
+        // Struct references borrow the runtime, all other lifetimes are elided
+        let lifetime = if function
+            .arg_types
+            .iter()
+            .chain(function.return_type.iter())
+            .any(|ty| self.has_lifetime(ty))
+        {
+            "<'r>"
+        } else {
+            ""
+        };
+        let runtime_ref = if lifetime.is_empty() { "&" } else { "&'r " };
+
+        let invocation = format!(
+            "invoke_fn!(runtime, \"{}\"{}).map_err(|e| e.to_string())",
+            function.name, marshalled_args
+        );
+        let body = match function
+            .return_type
+            .as_ref()
+            .and_then(|ty| self.wrapper(ty))
+        {
+            Some(_) => format!(
+                "let result: Result<StructRef<'r>, String> = {};\n    {}",
+                invocation,
+                self.marshal_from(function.return_type.as_ref(), "result", scope)
+            ),
+            None => invocation,
+        };
+
+        write!(
+            out,
+            r#"/// Invokes the Mun function `{name}`.
+pub fn {ident}{lifetime}(runtime: {runtime_ref}Runtime{args}) -> Result<{return_type}, String> {{
+    {body}
+}}
+"#,
+            name = function.name,
+            ident = ident(function.ident()),
+            lifetime = lifetime,
+            runtime_ref = runtime_ref,
+            args = args,
+            return_type = return_type,
+            body = body,
+        )
+        .unwrap();
+    }
+}
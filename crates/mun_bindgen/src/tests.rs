@@ -0,0 +1,247 @@
+use crate::{cpp, rust, Bindings, Field, Function, Primitive, Struct, Type};
+use abi::StructMemoryKind;
+
+fn field(name: &str, ty: Type) -> Field {
+    Field {
+        name: name.to_owned(),
+        ty,
+    }
+}
+
+fn function(name: &str, arg_types: Vec<Type>, return_type: Option<Type>) -> Function {
+    Function {
+        name: name.to_owned(),
+        arg_types,
+        return_type,
+    }
+}
+
+fn bindings() -> Bindings {
+    Bindings {
+        assembly_path: String::from("mod.munlib"),
+        structs: vec![
+            Struct {
+                name: String::from("Vec2"),
+                memory_kind: StructMemoryKind::Value,
+                size: 8,
+                fields: vec![
+                    field("x", Type::Primitive(Primitive::F32)),
+                    field("y", Type::Primitive(Primitive::F32)),
+                ],
+            },
+            Struct {
+                name: String::from("Transform"),
+                memory_kind: StructMemoryKind::Value,
+                size: 16,
+                fields: vec![
+                    field("position", Type::Struct(String::from("Vec2"))),
+                    field("scale", Type::Primitive(Primitive::F32)),
+                    field("visible", Type::Primitive(Primitive::Bool)),
+                ],
+            },
+            Struct {
+                name: String::from("Id"),
+                memory_kind: StructMemoryKind::Value,
+                size: 16,
+                fields: vec![field("0", Type::Primitive(Primitive::U128))],
+            },
+            Struct {
+                name: String::from("Entity"),
+                memory_kind: StructMemoryKind::GC,
+                size: 16,
+                fields: vec![
+                    field("id", Type::Struct(String::from("Id"))),
+                    field("transform", Type::Struct(String::from("Transform"))),
+                    field("parent", Type::Struct(String::from("Entity"))),
+                    field("data", Type::Unsupported(String::from("*const core::u8"))),
+                ],
+            },
+        ],
+        functions: vec![
+            function(
+                "translate",
+                vec![
+                    Type::Struct(String::from("Transform")),
+                    Type::Struct(String::from("Vec2")),
+                ],
+                Some(Type::Struct(String::from("Transform"))),
+            ),
+            function(
+                "spawn",
+                vec![Type::Struct(String::from("Transform"))],
+                Some(Type::Struct(String::from("Entity"))),
+            ),
+            function("despawn", vec![Type::Struct(String::from("Entity"))], None),
+            function(
+                "hash",
+                vec![Type::Primitive(Primitive::I32)],
+                Some(Type::Primitive(Primitive::U128)),
+            ),
+        ],
+    }
+}
+
+#[test]
+fn is_mirrored() {
+    let bindings = bindings();
+    let all = |_| true;
+    let mirrored = bindings
+        .structs
+        .iter()
+        .filter(|s| bindings.is_mirrored(s, &all, &|_| false))
+        .map(|s| s.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(mirrored, vec!["Vec2", "Transform"]);
+}
+
+#[test]
+fn is_mirrored_keyword_field() {
+    let bindings = bindings();
+    let all = |_| true;
+    let is_keyword = |name: &str| name == "y";
+    let mirrored = bindings
+        .structs
+        .iter()
+        .filter(|s| bindings.is_mirrored(s, &all, &is_keyword))
+        .count();
+    assert_eq!(mirrored, 0);
+}
+
+/// Bindings of structs and functions with the same names in different modules, of which some
+/// names are keywords.
+fn module_bindings() -> Bindings {
+    Bindings {
+        assembly_path: String::from("mod.munlib"),
+        structs: vec![
+            Struct {
+                name: String::from("shapes::Point"),
+                memory_kind: StructMemoryKind::Value,
+                size: 8,
+                fields: vec![
+                    field("x", Type::Primitive(Primitive::F32)),
+                    field("y", Type::Primitive(Primitive::F32)),
+                ],
+            },
+            Struct {
+                name: String::from("Bounds"),
+                memory_kind: StructMemoryKind::Value,
+                size: 16,
+                fields: vec![
+                    field("min", Type::Struct(String::from("shapes::Point"))),
+                    field("max", Type::Struct(String::from("shapes::Point"))),
+                ],
+            },
+            Struct {
+                name: String::from("shapes::circle::Shape"),
+                memory_kind: StructMemoryKind::GC,
+                size: 4,
+                fields: vec![field("radius", Type::Primitive(Primitive::F32))],
+            },
+            Struct {
+                name: String::from("shapes::square::Shape"),
+                memory_kind: StructMemoryKind::GC,
+                size: 8,
+                fields: vec![
+                    field("side", Type::Primitive(Primitive::F32)),
+                    field(
+                        "circle",
+                        Type::Struct(String::from("shapes::circle::Shape")),
+                    ),
+                ],
+            },
+            Struct {
+                name: String::from("impl::Default"),
+                memory_kind: StructMemoryKind::Value,
+                size: 4,
+                fields: vec![field("default", Type::Primitive(Primitive::I32))],
+            },
+        ],
+        functions: vec![
+            function(
+                "shapes::circle::new",
+                vec![Type::Primitive(Primitive::F32)],
+                Some(Type::Struct(String::from("shapes::circle::Shape"))),
+            ),
+            function(
+                "shapes::square::new",
+                vec![Type::Primitive(Primitive::F32)],
+                Some(Type::Struct(String::from("shapes::square::Shape"))),
+            ),
+            function(
+                "shapes::circle::bounds",
+                vec![Type::Struct(String::from("shapes::circle::Shape"))],
+                Some(Type::Struct(String::from("Bounds"))),
+            ),
+            function(
+                "shapes::origin",
+                vec![],
+                Some(Type::Struct(String::from("shapes::Point"))),
+            ),
+            function(
+                "shapes::square::inscribed",
+                vec![Type::Struct(String::from("shapes::square::Shape"))],
+                Some(Type::Struct(String::from("shapes::circle::Shape"))),
+            ),
+            function(
+                "impl::match",
+                vec![Type::Struct(String::from("impl::Default"))],
+                Some(Type::Primitive(Primitive::Bool)),
+            ),
+            function("main", vec![], None),
+        ],
+    }
+}
+
+#[test]
+fn module_path() {
+    let bindings = module_bindings();
+    assert_eq!(bindings.structs[2].module_path(), vec!["shapes", "circle"]);
+    assert_eq!(bindings.structs[2].ident(), "Shape");
+    assert_eq!(bindings.functions[5].module_path(), vec!["impl"]);
+    assert_eq!(bindings.functions[5].ident(), "match");
+    assert!(bindings.functions[6].module_path().is_empty());
+}
+
+#[test]
+fn is_mirrored_submodule() {
+    let bindings = module_bindings();
+    let all = |_| true;
+    let mirrored = bindings
+        .structs
+        .iter()
+        .filter(|s| bindings.is_mirrored(s, &all, &|name| name == "default"))
+        .map(|s| s.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(mirrored, vec!["shapes::Point", "Bounds"]);
+}
+
+#[test]
+fn unsupported_type() {
+    let bindings = bindings();
+    let no_u128 = |primitive| primitive != Primitive::U128;
+    assert_eq!(bindings.functions[0].unsupported_type(no_u128), None);
+    assert_eq!(
+        bindings.functions[3].unsupported_type(no_u128),
+        Some(String::from("core::u128"))
+    );
+}
+
+#[test]
+fn generate_rust() {
+    insta::assert_snapshot!(rust::generate(&bindings()));
+}
+
+#[test]
+fn generate_cpp() {
+    insta::assert_snapshot!(cpp::generate(&bindings()));
+}
+
+#[test]
+fn generate_rust_modules() {
+    insta::assert_snapshot!(rust::generate(&module_bindings()));
+}
+
+#[test]
+fn generate_cpp_namespaces() {
+    insta::assert_snapshot!(cpp::generate(&module_bindings()));
+}
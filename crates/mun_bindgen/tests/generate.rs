@@ -0,0 +1,196 @@
+//! Generates bindings for an assembly compiled from a fixture and verifies that they call into it.
+use compiler::Config;
+use mun_bindgen::{rust, Bindings};
+use mun_test::CompileTestDriver;
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// A package with structs and functions with the same names in different modules, of which some
+/// names are keywords in the host languages.
+const FIXTURE: &str = r#"
+//- /mun.toml
+[package]
+name="fixture"
+version="0.1.0"
+
+//- /src/mod.mun
+pub struct(value) Vec2 {
+    x: f32,
+    y: f32,
+}
+
+pub fn add(a: Vec2, b: Vec2) -> Vec2 {
+    Vec2 { x: a.x + b.x, y: a.y + b.y }
+}
+
+//- /src/circle.mun
+pub struct Shape {
+    radius: f32,
+}
+
+pub struct(value) Point {
+    x: f32,
+    y: f32,
+}
+
+pub fn origin() -> Point {
+    Point { x: 0.0, y: 0.0 }
+}
+
+pub fn new(radius: f32) -> Shape {
+    Shape { radius: radius }
+}
+
+pub fn area(shape: Shape) -> f32 {
+    3.0 * shape.radius * shape.radius
+}
+
+//- /src/square.mun
+pub struct Shape {
+    side: f32,
+}
+
+pub fn new(side: f32) -> Shape {
+    Shape { side: side }
+}
+
+pub fn area(shape: Shape) -> f32 {
+    shape.side * shape.side
+}
+
+//- /src/impl.mun
+pub fn match(a: i32, b: i32) -> bool {
+    a == b
+}
+"#;
+
+/// The host program that calls into the fixture through the generated Rust bindings.
+const RUST_MAIN: &str = r#"
+mod bindings;
+
+use mun_runtime::RuntimeBuilder;
+
+fn main() {
+    let library_path = std::env::args().nth(1).expect("expected the path of the assembly");
+    let runtime = RuntimeBuilder::new(library_path).spawn().unwrap();
+    let runtime = runtime.borrow();
+
+    let a = bindings::Vec2 { x: 1.0, y: 2.0 };
+    let b = bindings::Vec2 { x: 3.0, y: 4.0 };
+    assert_eq!(
+        bindings::add(&runtime, a, b).unwrap(),
+        bindings::Vec2 { x: 4.0, y: 6.0 }
+    );
+
+    let circle = bindings::circle::new(&runtime, 2.0).unwrap();
+    assert_eq!(circle.radius().unwrap(), 2.0);
+    assert_eq!(bindings::circle::area(&runtime, circle).unwrap(), 12.0);
+    assert_eq!(
+        bindings::circle::origin(&runtime).unwrap(),
+        bindings::circle::Point { x: 0.0, y: 0.0 }
+    );
+
+    let mut square = bindings::square::new(&runtime, 2.0).unwrap();
+    square.set_side(3.0).unwrap();
+    assert_eq!(bindings::square::area(&runtime, square).unwrap(), 9.0);
+
+    assert!(bindings::r#impl::r#match(&runtime, 1, 1).unwrap());
+}
+"#;
+
+/// Compiles the fixture into a single assembly.
+fn compile_fixture() -> CompileTestDriver {
+    CompileTestDriver::from_fixture_with_config(FIXTURE, |config| Config {
+        direct_calls: true,
+        ..config
+    })
+}
+
+/// Returns the directory that contains the test executable, i.e. `target/<profile>`.
+fn profile_target_dir() -> PathBuf {
+    std::env::current_exe()
+        .unwrap()
+        .parent()
+        .and_then(Path::parent)
+        .unwrap()
+        .to_path_buf()
+}
+
+#[test]
+fn from_library() {
+    let driver = compile_fixture();
+    let bindings = Bindings::from_library(driver.lib_path()).unwrap();
+
+    let mut struct_names = bindings
+        .structs
+        .iter()
+        .map(|s| s.name.as_str())
+        .collect::<Vec<_>>();
+    struct_names.sort_unstable();
+    assert_eq!(
+        struct_names,
+        ["Vec2", "circle::Point", "circle::Shape", "square::Shape"]
+    );
+
+    let mut function_names = bindings
+        .functions
+        .iter()
+        .map(|f| f.name.as_str())
+        .collect::<Vec<_>>();
+    function_names.sort_unstable();
+    assert_eq!(
+        function_names,
+        [
+            "add",
+            "circle::area",
+            "circle::new",
+            "circle::origin",
+            "impl::match",
+            "square::area",
+            "square::new"
+        ]
+    );
+
+    let shape = bindings.get_struct("circle::Shape").unwrap();
+    assert_eq!(shape.module_path(), ["circle"]);
+    assert_eq!(shape.ident(), "Shape");
+}
+
+#[test]
+fn rust_bindings() {
+    let driver = compile_fixture();
+    let bindings = Bindings::from_library(driver.lib_path()).unwrap();
+
+    // Build a host program that depends on `mun_runtime` and includes the bindings
+    let project_dir = tempfile::TempDir::new().unwrap();
+    let runtime_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../mun_runtime");
+    std::fs::write(
+        project_dir.path().join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"host\"\nversion = \"0.1.0\"\nedition = \"2018\"\n\n\
+             [dependencies]\nmun_runtime = {{ path = {:?} }}\n\n[workspace]\n",
+            runtime_dir
+        ),
+    )
+    .unwrap();
+    std::fs::create_dir(project_dir.path().join("src")).unwrap();
+    std::fs::write(project_dir.path().join("src/main.rs"), RUST_MAIN).unwrap();
+    std::fs::write(
+        project_dir.path().join("src/bindings.rs"),
+        rust::generate(&bindings),
+    )
+    .unwrap();
+
+    // Share a target directory between runs, to avoid rebuilding the runtime
+    let status = Command::new(env!("CARGO"))
+        .arg("run")
+        .arg("--")
+        .arg(driver.lib_path())
+        .current_dir(project_dir.path())
+        .env("CARGO_TARGET_DIR", profile_target_dir().join("bindgen"))
+        .status()
+        .unwrap();
+    assert!(status.success(), "Failed to run the Rust bindings");
+}
@@ -4,10 +4,23 @@ use crate::{
     ModuleGroupId,
 };
 use anyhow::anyhow;
-use inkwell::context::Context;
+use inkwell::{
+    context::Context,
+    module::Linkage,
+    values::{AsValueRef, FunctionValue},
+};
 use std::{path::Path, sync::Arc};
 use tempfile::NamedTempFile;
 
+/// The names of the functions through which the runtime accesses an assembly.
+const ASSEMBLY_FN_NAMES: &[&str] = &[
+    abi::GET_INFO_FN_NAME,
+    abi::GET_VERSION_FN_NAME,
+    abi::SET_ALLOCATOR_HANDLE_FN_NAME,
+    abi::SET_PROFILER_HANDLE_FN_NAME,
+    abi::SET_COVERAGE_HANDLE_FN_NAME,
+];
+
 /// An `Assembly` is a successfully linked module of code from one or more files.
 pub struct Assembly<'db, 'ink, 'ctx> {
     code_gen: &'ctx CodeGenContext<'db, 'ink>,
@@ -32,6 +45,53 @@ impl<'db, 'ink, 'ctx> Assembly<'db, 'ink, 'ctx> {
         )
     }
 
+    /// Tries to convert the assembly into an `ObjectFile` that can be statically linked into an
+    /// executable. See [`Assembly::prepare_for_static_linking`].
+    pub fn into_static_object_file(
+        mut self,
+        symbol_prefix: &str,
+    ) -> Result<ObjectFile, anyhow::Error> {
+        self.prepare_for_static_linking(symbol_prefix);
+        self.into_object_file()
+    }
+
+    /// Prepares the assembly to be statically linked together with other assemblies. The functions
+    /// through which the runtime accesses the assembly - e.g. `get_info` - are prefixed with
+    /// `symbol_prefix`, e.g. `{symbol_prefix}_get_info`. All other symbols that are defined by the
+    /// assembly are made internal, so they cannot collide with those of other assemblies.
+    pub(crate) fn prepare_for_static_linking(&mut self, symbol_prefix: &str) {
+        let mut function = self.module.get_first_function();
+        while let Some(current) = function {
+            function = current.get_next_function();
+            if current.as_global_value().is_declaration() {
+                continue;
+            }
+
+            let name = current.get_name().to_string_lossy().into_owned();
+            if ASSEMBLY_FN_NAMES.contains(&name.as_str()) {
+                current.set_linkage(Linkage::External);
+                set_function_name(current, &format!("{}_{}", symbol_prefix, name));
+            } else if is_exported(current.get_linkage()) {
+                current.set_linkage(Linkage::Internal);
+            }
+        }
+
+        let mut global = self.module.get_first_global();
+        while let Some(current) = global {
+            global = current.get_next_global();
+            if !current.is_declaration() && is_exported(current.get_linkage()) {
+                current.set_linkage(Linkage::Internal);
+            }
+        }
+    }
+
+    /// Converts the assembly into LLVM bitcode.
+    pub fn into_bitcode(self) -> AssemblyBitcode {
+        AssemblyBitcode {
+            bitcode: self.module.write_bitcode_to_memory().as_slice().to_vec(),
+        }
+    }
+
     /// Tries to write the `Assembly`'s IR to file.
     pub fn write_ir_to_file(self, output_path: &Path) -> Result<(), anyhow::Error> {
         self.module
@@ -40,6 +100,22 @@ impl<'db, 'ink, 'ctx> Assembly<'db, 'ink, 'ctx> {
     }
 }
 
+/// Returns true if a symbol with the specified `linkage` is visible outside of its object file.
+fn is_exported(linkage: Linkage) -> bool {
+    matches!(linkage, Linkage::External | Linkage::DLLExport)
+}
+
+/// Renames the specified `function`.
+fn set_function_name(function: FunctionValue, name: &str) {
+    unsafe {
+        llvm_sys::core::LLVMSetValueName2(
+            function.as_value_ref(),
+            name.as_ptr() as *const std::os::raw::c_char,
+            name.len(),
+        )
+    }
+}
+
 /// Builds an assembly for the specified file
 fn build_assembly<'db, 'ink, 'ctx>(
     db: &'db dyn CodeGenDatabase,
@@ -109,6 +185,59 @@ pub(crate) fn build_target_assembly(
     Arc::new(TargetAssembly { file })
 }
 
+/// A `StaticAssembly` is a reference to an object file stored on disk, which can be statically
+/// linked into an executable.
+#[derive(Debug)]
+pub struct StaticAssembly {
+    file: NamedTempFile,
+}
+
+impl PartialEq for StaticAssembly {
+    fn eq(&self, other: &Self) -> bool {
+        self.path().eq(other.path())
+    }
+}
+
+impl Eq for StaticAssembly {}
+
+impl StaticAssembly {
+    pub const EXTENSION: &'static str = "o";
+
+    /// Returns the current location of the object file
+    pub fn path(&self) -> &Path {
+        self.file.path()
+    }
+
+    /// Copies the object file to the specified location
+    pub fn copy_to<P: AsRef<Path>>(&self, destination: P) -> Result<(), std::io::Error> {
+        std::fs::copy(self.path(), destination).map(|_| ())
+    }
+}
+
+/// Builds an object file for the specified module, of which the assembly functions are prefixed
+/// with `symbol_prefix`.
+pub(crate) fn build_static_assembly(
+    db: &dyn CodeGenDatabase,
+    module_group: ModuleGroupId,
+    symbol_prefix: String,
+) -> Arc<StaticAssembly> {
+    // Setup the code generation context
+    let inkwell_context = Context::create();
+    let code_gen_context = CodeGenContext::new(&inkwell_context, db);
+
+    // Build an assembly for the module
+    let assembly = build_assembly(db, &code_gen_context, module_group);
+
+    // Convert the assembly into an object file
+    let obj_file = assembly
+        .into_static_object_file(&symbol_prefix)
+        .expect("unable to create object file");
+
+    Arc::new(StaticAssembly {
+        file: obj_file.into_temp_file(),
+    })
+}
+
 /// An `AssemblyIR` is a reference to an IR file stored on disk.
 #[derive(Debug)]
 pub struct AssemblyIR {
@@ -159,3 +288,32 @@ pub(crate) fn build_assembly_ir(
 
     Arc::new(AssemblyIR { file })
 }
+
+/// An `AssemblyBitcode` contains the LLVM bitcode of an assembly, which can be compiled in memory
+/// using a [`JitAssembly`](crate::JitAssembly).
+#[derive(Debug, PartialEq, Eq)]
+pub struct AssemblyBitcode {
+    bitcode: Vec<u8>,
+}
+
+impl AssemblyBitcode {
+    /// Returns the bitcode as a slice of bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bitcode
+    }
+}
+
+/// Builds the bitcode for the specified module.
+pub(crate) fn build_assembly_bitcode(
+    db: &dyn CodeGenDatabase,
+    module_group: ModuleGroupId,
+) -> Arc<AssemblyBitcode> {
+    // Setup the code generation context
+    let inkwell_context = Context::create();
+    let code_gen_context = CodeGenContext::new(&inkwell_context, db);
+
+    // Build an assembly for the module
+    let assembly = build_assembly(db, &code_gen_context, module_group);
+
+    Arc::new(assembly.into_bitcode())
+}
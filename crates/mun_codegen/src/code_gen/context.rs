@@ -18,6 +18,12 @@ pub struct CodeGenContext<'db, 'ink> {
     /// The optimization level
     pub optimization_level: inkwell::OptimizationLevel,
 
+    /// Whether or not to insert profiler hooks in every generated function
+    pub instrument_functions: bool,
+
+    /// Whether or not to count the executions of every block and branch for code coverage
+    pub instrument_coverage: bool,
+
     /// The target to generate code for
     pub target_machine: Arc<TargetMachine>,
 }
@@ -31,6 +37,8 @@ impl<'db, 'ink> CodeGenContext<'db, 'ink> {
             rust_types: RefCell::new(HashMap::default()),
             hir_types: HirTypeCache::new(context, db.upcast(), target_machine.get_target_data()),
             optimization_level: db.optimization_level(),
+            instrument_functions: db.instrument_functions(),
+            instrument_coverage: db.instrument_coverage(),
             target_machine,
             db: db.upcast(),
         }
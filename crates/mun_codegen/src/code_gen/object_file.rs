@@ -33,6 +33,11 @@ impl ObjectFile {
         })
     }
 
+    /// Converts the object file into the temporary file that contains it.
+    pub fn into_temp_file(self) -> NamedTempFile {
+        self.obj_file
+    }
+
     /// Links the object file into a shared object.
     pub fn into_shared_object(self, output_path: &Path) -> Result<(), anyhow::Error> {
         // Construct a linker for the target
@@ -131,13 +131,15 @@ fn gen_signature_return_type_from_type_info<'ink>(
         .unwrap_or_else(|| Value::null(context))
 }
 
-/// Construct a global that holds a reference to all functions. e.g.:
+/// Construct a global with the specified `global_name` that holds a reference to all functions.
+/// e.g.:
 /// MunFunctionDefinition[] definitions = { ... }
 fn get_function_definition_array<'ink, 'a>(
     db: &dyn HirDatabase,
     context: &IrValueContext<'ink, '_, '_>,
     functions: impl Iterator<Item = &'a hir::Function>,
     hir_types: &HirTypeCache,
+    global_name: &str,
 ) -> Global<'ink, [ir::FunctionDefinition<'ink>]> {
     let module = context.module;
     functions
@@ -164,7 +166,7 @@ fn get_function_definition_array<'ink, 'a>(
             }
         })
         .into_value(context)
-        .into_const_private_global("fn.get_info.functions", context)
+        .into_const_private_global(global_name, context)
 }
 
 /// Generate the dispatch table information. e.g.:
@@ -210,6 +212,7 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
     db: &'db dyn HirDatabase,
     context: &IrValueContext<'ink, '_, '_>,
     api: &HashSet<hir::Function>,
+    migrations: &HashSet<hir::Function>,
     dispatch_table: &DispatchTable<'ink>,
     type_table: &TypeTable<'ink>,
     hir_types: &HirTypeCache<'db, 'ink>,
@@ -219,7 +222,23 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
     let module = context.module;
 
     let num_functions = api.len() as u32;
-    let functions = get_function_definition_array(db, context, api.iter(), hir_types);
+    let functions =
+        get_function_definition_array(db, context, api.iter(), hir_types, "fn.get_info.functions");
+
+    // Construct an array of migration functions (or nullptr if there are none)
+    let num_migrations = migrations.len() as u32;
+    let migrations = if migrations.is_empty() {
+        Value::null(context)
+    } else {
+        get_function_definition_array(
+            db,
+            context,
+            migrations.iter(),
+            hir_types,
+            "fn.get_info.migrations",
+        )
+        .as_value(context)
+    };
 
     // Get the TypeTable global
     let types = TypeTable::find_global(module)
@@ -236,6 +255,8 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
         num_functions,
         types,
         num_types: type_table.num_types() as u32,
+        migrations,
+        num_migrations,
     };
 
     // Construct the dispatch table struct
@@ -251,6 +272,8 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
         dependencies,
     );
     gen_set_allocator_handle_fn(context);
+    gen_set_instrumentation_handle_fn(context, abi::SET_PROFILER_HANDLE_FN_NAME, "profilerHandle");
+    gen_set_instrumentation_handle_fn(context, abi::SET_COVERAGE_HANDLE_FN_NAME, "coverageHandle");
     gen_get_version_fn(context);
 }
 
@@ -264,11 +287,12 @@ fn gen_get_info_fn<'ink>(
     dependencies: Vec<String>,
 ) {
     let target = db.target();
+    let returns_by_pointer = target.options.is_like_windows || target.options.is_like_wasm;
 
     // Construct the return type of the `get_info` method. Depending on the C ABI this is either the
-    // `MunAssemblyInfo` struct or void. On windows the return argument is passed back to the caller
-    // through a pointer to the return type as the first argument. e.g.:
-    // On Windows:
+    // `MunAssemblyInfo` struct or void. On windows and WebAssembly the return argument is passed
+    // back to the caller through a pointer to the return type as the first argument. e.g.:
+    // On Windows and WebAssembly:
     // ```c
     // void get_info(MunModuleInfo* result) {...}
     // ```
@@ -276,7 +300,7 @@ fn gen_get_info_fn<'ink>(
     // ```c
     // MunModuleInfo get_info() { ... }
     // ```
-    let get_symbols_type = if target.options.is_like_windows {
+    let get_symbols_type = if returns_by_pointer {
         Value::<'ink, fn(*mut ir::AssemblyInfo<'ink>)>::get_ir_type(context.type_context)
     } else {
         Value::<'ink, fn() -> ir::AssemblyInfo<'ink>>::get_ir_type(context.type_context)
@@ -287,7 +311,7 @@ fn gen_get_info_fn<'ink>(
             .module
             .add_function("get_info", get_symbols_type, Some(Linkage::DLLExport));
 
-    if target.options.is_like_windows {
+    if returns_by_pointer {
         get_symbols_fn.add_attribute(
             inkwell::attributes::AttributeLoc::Param(0),
             context
@@ -302,7 +326,7 @@ fn gen_get_info_fn<'ink>(
 
     // Get a pointer to the IR value that will hold the return value. Again this differs depending
     // on the C ABI.
-    let result_ptr = if target.options.is_like_windows {
+    let result_ptr = if returns_by_pointer {
         get_symbols_fn
             .get_nth_param(0)
             .unwrap()
@@ -354,7 +378,7 @@ fn gen_get_info_fn<'ink>(
     );
 
     // Construct the return statement of the function.
-    if target.options.is_like_windows {
+    if returns_by_pointer {
         builder.build_return(None);
     } else {
         builder.build_return(Some(&builder.build_load(result_ptr, "")));
@@ -390,6 +414,34 @@ fn gen_set_allocator_handle_fn(context: &IrValueContext) {
     builder.build_return(None);
 }
 
+/// Generates a method `void fn_name(void*)` that stores the argument into the global
+/// `global_name`. These globals are used internally to reference the instrumentation handlers used
+/// by this munlib. The method is only generated if the munlib was compiled with the instrumentation
+/// that requires the global.
+fn gen_set_instrumentation_handle_fn(context: &IrValueContext, fn_name: &str, global_name: &str) {
+    let handle_global = match context.module.get_global(global_name) {
+        Some(global) => global,
+        None => return,
+    };
+
+    let set_handle_fn = context.module.add_function(
+        fn_name,
+        Value::<fn(*const u8)>::get_ir_type(context.type_context),
+        Some(Linkage::DLLExport),
+    );
+
+    let builder = context.context.create_builder();
+    let body_ir = context.context.append_basic_block(set_handle_fn, "body");
+    builder.position_at_end(body_ir);
+
+    builder.build_store(
+        handle_global.as_pointer_value(),
+        set_handle_fn.get_nth_param(0).unwrap(),
+    );
+
+    builder.build_return(None);
+}
+
 /// Generates a `get_version` method that returns the current abi version.
 /// Specifically, it returns the abi version the function was generated in.
 fn gen_get_version_fn(context: &IrValueContext) {
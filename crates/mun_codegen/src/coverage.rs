@@ -0,0 +1,41 @@
+use hir::{Body, Expr, ExprId, HirDatabase};
+
+/// Returns the expressions of the `function` whose executions are counted when the function is
+/// compiled with coverage instrumentation. The position of an expression in the returned `Vec` is
+/// the index of its counter.
+///
+/// The function body, every block and every branch arm is counted. Branch arms are the `then` and
+/// `else` branches of `if` expressions (an `else if` is counted as the `else` branch), the bodies
+/// of loops and the values of `break` expressions; they are counted even if they are not blocks.
+/// Regions are ordered by a pre-order traversal of the function body, so the first counter always
+/// belongs to the function body.
+pub fn coverage_regions(db: &dyn HirDatabase, function: hir::Function) -> Vec<ExprId> {
+    let body = function.body(db);
+    let mut regions = Vec::new();
+    collect_regions(&body, body.body_expr(), true, &mut regions);
+    regions
+}
+
+fn collect_regions(body: &Body, expr: ExprId, is_arm: bool, regions: &mut Vec<ExprId>) {
+    if is_arm || matches!(&body[expr], Expr::Block { .. }) {
+        regions.push(expr);
+    }
+
+    let arms = branch_arms(&body[expr]);
+    body[expr]
+        .walk_child_exprs(|child| collect_regions(body, child, arms.contains(&child), regions));
+}
+
+/// Returns the child expressions of `expr` that are only executed when their branch is taken.
+fn branch_arms(expr: &Expr) -> Vec<ExprId> {
+    match expr {
+        Expr::If {
+            then_branch,
+            else_branch,
+            ..
+        } => std::iter::once(*then_branch).chain(*else_branch).collect(),
+        Expr::Loop { body } | Expr::While { body, .. } => vec![*body],
+        Expr::Break { expr: Some(value) } => vec![*value],
+        _ => Vec::new(),
+    }
+}
@@ -1,4 +1,6 @@
-use crate::{AssemblyIR, ModuleGroupId, ModulePartition, TargetAssembly};
+use crate::{
+    AssemblyBitcode, AssemblyIR, ModuleGroupId, ModulePartition, StaticAssembly, TargetAssembly,
+};
 use by_address::ByAddress;
 use inkwell::targets::{CodeModel, InitializationConfig, RelocMode, Target, TargetTriple};
 use std::sync::Arc;
@@ -15,6 +17,19 @@ pub trait CodeGenDatabase: hir::HirDatabase + hir::Upcast<dyn hir::HirDatabase>
     #[salsa::input]
     fn optimization_level(&self) -> inkwell::OptimizationLevel;
 
+    /// Set whether profiler hooks are inserted at the entry and exit of every generated function
+    #[salsa::input]
+    fn instrument_functions(&self) -> bool;
+
+    /// Set whether the executions of every block and branch are counted for code coverage
+    #[salsa::input]
+    fn instrument_coverage(&self) -> bool;
+
+    /// Set whether all modules of a package are compiled into a single assembly, such that calls
+    /// between them are direct calls instead of going through the dispatch table
+    #[salsa::input]
+    fn direct_calls(&self) -> bool;
+
     /// Returns the current module partition
     #[salsa::invoke(crate::module_partition::build_partition)]
     fn module_partition(&self) -> Arc<ModulePartition>;
@@ -27,9 +42,22 @@ pub trait CodeGenDatabase: hir::HirDatabase + hir::Upcast<dyn hir::HirDatabase>
     #[salsa::invoke(crate::assembly::build_assembly_ir)]
     fn assembly_ir(&self, module_group: ModuleGroupId) -> Arc<AssemblyIR>;
 
+    /// Returns the LLVM bitcode for the specified module, which can be compiled in memory.
+    #[salsa::invoke(crate::assembly::build_assembly_bitcode)]
+    fn assembly_bitcode(&self, module_group: ModuleGroupId) -> Arc<AssemblyBitcode>;
+
     /// Returns a fully linked shared object for the specified module.
     #[salsa::invoke(crate::assembly::build_target_assembly)]
     fn target_assembly(&self, module_group: ModuleGroupId) -> Arc<TargetAssembly>;
+
+    /// Returns an object file for the specified module that can be statically linked into an
+    /// executable. Its assembly functions are prefixed with `symbol_prefix`.
+    #[salsa::invoke(crate::assembly::build_static_assembly)]
+    fn static_assembly(
+        &self,
+        module_group: ModuleGroupId,
+        symbol_prefix: String,
+    ) -> Arc<StaticAssembly>;
 }
 
 /// Constructs the primary interface to the complete machine description for the target machine. All
@@ -38,14 +66,26 @@ fn target_machine(db: &dyn CodeGenDatabase) -> ByAddress<Arc<inkwell::targets::T
     // Get the HIR target
     let target = db.target();
 
-    // Initialize the x86 target
-    Target::initialize_x86(&InitializationConfig::default());
+    // Initialize the LLVM target for the architecture we are compiling for
+    match target.arch.as_str() {
+        "aarch64" => Target::initialize_aarch64(&InitializationConfig::default()),
+        "wasm32" => Target::initialize_webassembly(&InitializationConfig::default()),
+        _ => Target::initialize_x86(&InitializationConfig::default()),
+    }
 
     // Retrieve the LLVM target using the specified target.
     let target_triple = TargetTriple::create(&db.target().llvm_target);
     let llvm_target = Target::from_triple(&target_triple)
         .expect("could not find llvm target tripple for Mun target");
 
+    // WebAssembly modules are linked into a single static module, position independent code would
+    // require an Emscripten-style dynamic linker on the host.
+    let reloc_mode = if target.options.is_like_wasm {
+        RelocMode::Static
+    } else {
+        RelocMode::PIC
+    };
+
     // Construct target machine for machine code generation
     let target_machine = llvm_target
         .create_target_machine(
@@ -53,7 +93,7 @@ fn target_machine(db: &dyn CodeGenDatabase) -> ByAddress<Arc<inkwell::targets::T
             &target.options.cpu,
             &target.options.features,
             db.optimization_level(),
-            RelocMode::PIC,
+            reloc_mode,
             CodeModel::Default,
         )
         .expect("could not create llvm target machine");
@@ -21,4 +21,13 @@ pub trait Intrinsic: Sync {
 intrinsics! {
     /// Allocates memory for the specified `type` in the allocator referred to by `alloc_handle`.
     pub fn new(type: *const TypeInfo, alloc_handle: *mut ffi::c_void) -> *const *mut ffi::c_void;
+    /// Notifies the profiler referred to by `profiler_handle` that the function with the name
+    /// `fn_name` was entered.
+    pub fn profiler_enter(fn_name: *const u8, profiler_handle: *mut ffi::c_void) -> ();
+    /// Notifies the profiler referred to by `profiler_handle` that the function with the name
+    /// `fn_name` is about to return.
+    pub fn profiler_exit(fn_name: *const u8, profiler_handle: *mut ffi::c_void) -> ();
+    /// Increments the coverage counter with index `counter` of the function with the name
+    /// `fn_name`, in the coverage map referred to by `coverage_handle`.
+    pub fn coverage_hit(fn_name: *const u8, counter: u32, coverage_handle: *mut ffi::c_void) -> ();
 }
@@ -1,6 +1,7 @@
 use crate::module_group::ModuleGroup;
 use crate::{
-    intrinsics,
+    coverage::coverage_regions,
+    intrinsics::{self, Intrinsic},
     ir::ty::HirTypeCache,
     ir::types as ir,
     ir::{dispatch_table::DispatchTable, type_table::TypeTable},
@@ -29,6 +30,8 @@ struct LoopInfo<'ink> {
 #[derive(Clone)]
 pub(crate) struct ExternalGlobals<'ink> {
     pub alloc_handle: Option<GlobalValue<'ink>>,
+    pub profiler_handle: Option<GlobalValue<'ink>>,
+    pub coverage_handle: Option<GlobalValue<'ink>>,
     pub dispatch_table: Option<GlobalValue<'ink>>,
     pub type_table: Option<Global<'ink, [*const ir::TypeInfo<'ink>]>>,
 }
@@ -51,6 +54,8 @@ pub(crate) struct BodyIrGenerator<'db, 'ink, 't> {
     hir_function: hir::Function,
     external_globals: ExternalGlobals<'ink>,
     module_group: &'t ModuleGroup,
+    instrumented_fn_name: Option<PointerValue<'ink>>,
+    coverage_counters: HashMap<ExprId, u32>,
 }
 
 impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
@@ -95,6 +100,8 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             external_globals,
             hir_types,
             module_group,
+            instrumented_fn_name: None,
+            coverage_counters: HashMap::default(),
         }
     }
 
@@ -127,6 +134,29 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             }
         }
 
+        // Instrumentation hooks identify the function by its name
+        if self.external_globals.profiler_handle.is_some()
+            || self.external_globals.coverage_handle.is_some()
+        {
+            let fn_name = self.hir_function.full_name(self.db);
+            self.instrumented_fn_name = Some(
+                self.builder
+                    .build_global_string_ptr(&fn_name, "fn_name")
+                    .as_pointer_value(),
+            );
+        }
+
+        if self.external_globals.coverage_handle.is_some() {
+            self.coverage_counters = coverage_regions(self.db, self.hir_function)
+                .into_iter()
+                .enumerate()
+                .map(|(idx, expr)| (expr, idx as u32))
+                .collect();
+        }
+
+        // Notify the profiler that the function was entered, if the function is instrumented
+        self.gen_profiler_hook(&intrinsics::profiler_enter);
+
         // Generate code for the body of the function
         let ret_value = self.gen_expr(self.body.body_expr());
 
@@ -143,13 +173,67 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             .clone();
         if !block_ret_type.is_never() {
             if fn_ret_type.is_empty() {
+                self.gen_profiler_hook(&intrinsics::profiler_exit);
                 self.builder.build_return(None);
             } else if let Some(value) = ret_value {
+                self.gen_profiler_hook(&intrinsics::profiler_exit);
                 self.builder.build_return(Some(&value));
             }
         }
     }
 
+    /// Generates a call to the specified profiler hook, if the function is instrumented.
+    fn gen_profiler_hook(&self, hook: &impl Intrinsic) {
+        let (profiler_handle, fn_name) = match (
+            self.external_globals.profiler_handle,
+            self.instrumented_fn_name,
+        ) {
+            (Some(profiler_handle), Some(fn_name)) => (profiler_handle, fn_name),
+            _ => return,
+        };
+
+        let hook_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+            self.external_globals.dispatch_table,
+            &self.builder,
+            hook,
+        );
+        let profiler_handle = self
+            .builder
+            .build_load(profiler_handle.as_pointer_value(), "profiler_handle");
+        self.builder
+            .build_call(hook_fn_ptr, &[fn_name.into(), profiler_handle], "");
+    }
+
+    /// Generates a call to the coverage hook that increments the counter of the specified
+    /// expression, if the expression is instrumented.
+    fn gen_coverage_hit(&self, expr: ExprId) {
+        let (coverage_handle, fn_name, counter) = match (
+            self.external_globals.coverage_handle,
+            self.instrumented_fn_name,
+            self.coverage_counters.get(&expr),
+        ) {
+            (Some(coverage_handle), Some(fn_name), Some(counter)) => {
+                (coverage_handle, fn_name, *counter)
+            }
+            _ => return,
+        };
+
+        let hook_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+            self.external_globals.dispatch_table,
+            &self.builder,
+            &intrinsics::coverage_hit,
+        );
+        let counter = self.context.i32_type().const_int(counter.into(), false);
+        let coverage_handle = self
+            .builder
+            .build_load(coverage_handle.as_pointer_value(), "coverage_handle");
+        self.builder.build_call(
+            hook_fn_ptr,
+            &[fn_name.into(), counter.into(), coverage_handle],
+            "",
+        );
+    }
+
     pub fn gen_fn_wrapper(&mut self) {
         let fn_sig = self.hir_function.ty(self.db).callable_sig(self.db).unwrap();
         let args: Vec<BasicValueEnum> = fn_sig
@@ -206,6 +290,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
     /// Generates IR for the specified expression. Dependending on the type of expression an IR
     /// value is returned.
     fn gen_expr(&mut self, expr: ExprId) -> Option<inkwell::values::BasicValueEnum<'ink>> {
+        self.gen_coverage_hit(expr);
         let body = self.body.clone();
         match &body[expr] {
             Expr::Block {
@@ -361,6 +446,9 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                 // TODO: Root memory in GC
                 self.gen_struct_alloc_on_heap(hir_struct, struct_lit)
             }
+            hir::StructMemoryKind::Extern => unreachable!(
+                "extern struct literals are rejected during type inference, so no code is generated"
+            ),
         }
     }
 
@@ -1182,6 +1270,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         let ret_value = ret_expr.and_then(|expr| self.gen_expr(expr));
 
         // Construct a return statement from the returned value of the body
+        self.gen_profiler_hook(&intrinsics::profiler_exit);
         if let Some(value) = ret_value {
             self.builder.build_return(Some(&value));
         } else {
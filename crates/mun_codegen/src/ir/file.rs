@@ -18,6 +18,8 @@ pub struct FileIR<'ink> {
     pub llvm_module: Module<'ink>,
     /// The `hir::Function`s that constitute the file's API.
     pub api: HashSet<hir::Function>,
+    /// The `hir::Function`s that are used to migrate struct instances during hot reloading.
+    pub migrations: HashSet<hir::Function>,
 }
 
 /// Generates IR for the specified file.
@@ -63,6 +65,12 @@ pub(crate) fn gen_file_ir<'db, 'ink>(
         let alloc_handle = group_ir
             .allocator_handle_type
             .map(|ty| llvm_module.add_global(ty, None, "allocatorHandle"));
+        let profiler_handle = group_ir
+            .profiler_handle_type
+            .map(|ty| llvm_module.add_global(ty, None, "profilerHandle"));
+        let coverage_handle = group_ir
+            .coverage_handle_type
+            .map(|ty| llvm_module.add_global(ty, None, "coverageHandle"));
         let dispatch_table = group_ir
             .dispatch_table
             .ty()
@@ -74,6 +82,8 @@ pub(crate) fn gen_file_ir<'db, 'ink>(
         };
         ExternalGlobals {
             alloc_handle,
+            profiler_handle,
+            coverage_handle,
             dispatch_table,
             type_table: type_table.map(|g| unsafe { Global::from_raw(g) }),
         }
@@ -124,5 +134,15 @@ pub(crate) fn gen_file_ir<'db, 'ink>(
         .filter(|&f| module_group.should_export_fn(code_gen.db, f))
         .collect();
 
-    FileIR { llvm_module, api }
+    let migrations: HashSet<hir::Function> = functions
+        .keys()
+        .copied()
+        .filter(|&f| f.is_migration(code_gen.db))
+        .collect();
+
+    FileIR {
+        llvm_module,
+        api,
+        migrations,
+    }
 }
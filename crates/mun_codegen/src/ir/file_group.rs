@@ -25,6 +25,10 @@ pub struct FileGroupIR<'ink> {
     pub(crate) type_table: TypeTable<'ink>,
     /// The allocator handle, if it exists
     pub(crate) allocator_handle_type: Option<PointerType<'ink>>,
+    /// The profiler handle, if function instrumentation is enabled
+    pub(crate) profiler_handle_type: Option<PointerType<'ink>>,
+    /// The coverage handle, if coverage instrumentation is enabled
+    pub(crate) coverage_handle_type: Option<PointerType<'ink>>,
     /// The modules that contain code that was referenced from this group of modules
     pub(crate) referenced_modules: FxHashSet<hir::Module>,
 }
@@ -39,6 +43,8 @@ pub(crate) fn gen_file_group_ir<'db, 'ink>(
     // Use a `BTreeMap` to guarantee deterministically ordered output.
     let mut intrinsics_map = BTreeMap::new();
     let mut needs_alloc = false;
+    let mut needs_profiler = false;
+    let mut needs_coverage = false;
 
     // Collect all intrinsic functions, wrapper function, and generate struct declarations.
     for def in module_group
@@ -57,6 +63,24 @@ pub(crate) fn gen_file_group_ir<'db, 'ink>(
                     &f.infer(code_gen.db),
                 );
 
+                if code_gen.instrument_functions {
+                    intrinsics::collect_profiler_hooks(
+                        &code_gen.context,
+                        code_gen.target_machine.get_target_data(),
+                        &mut intrinsics_map,
+                        &mut needs_profiler,
+                    );
+                }
+
+                if code_gen.instrument_coverage {
+                    intrinsics::collect_coverage_hooks(
+                        &code_gen.context,
+                        code_gen.target_machine.get_target_data(),
+                        &mut intrinsics_map,
+                        &mut needs_coverage,
+                    );
+                }
+
                 let fn_sig = f.ty(code_gen.db).callable_sig(code_gen.db).unwrap();
                 if f.visibility(code_gen.db).is_externally_visible()
                     && !fn_sig.marshallable(code_gen.db)
@@ -153,11 +177,35 @@ pub(crate) fn gen_file_group_ir<'db, 'ink>(
         None
     };
 
+    // Create the profiler handle global value
+    let profiler_handle_type = if needs_profiler {
+        let profiler_handle_type = code_gen.context.i8_type().ptr_type(AddressSpace::Generic);
+        let global = llvm_module.add_global(profiler_handle_type, None, "profilerHandle");
+        global.set_initializer(&profiler_handle_type.const_null());
+        global.set_unnamed_address(UnnamedAddress::Global);
+        Some(profiler_handle_type)
+    } else {
+        None
+    };
+
+    // Create the coverage handle global value
+    let coverage_handle_type = if needs_coverage {
+        let coverage_handle_type = code_gen.context.i8_type().ptr_type(AddressSpace::Generic);
+        let global = llvm_module.add_global(coverage_handle_type, None, "coverageHandle");
+        global.set_initializer(&coverage_handle_type.const_null());
+        global.set_unnamed_address(UnnamedAddress::Global);
+        Some(coverage_handle_type)
+    } else {
+        None
+    };
+
     FileGroupIR {
         llvm_module,
         dispatch_table,
         type_table,
         allocator_handle_type,
+        profiler_handle_type,
+        coverage_handle_type,
         referenced_modules,
     }
 }
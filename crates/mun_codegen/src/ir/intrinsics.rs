@@ -114,3 +114,26 @@ pub fn collect_wrapper_body<'ink>(
     // self.collect_intrinsic(entries, &intrinsics::drop, module);
     *needs_alloc = true;
 }
+
+/// Collects the profiler hooks that are called from instrumented function bodies.
+pub fn collect_profiler_hooks<'ink>(
+    context: &'ink Context,
+    target: TargetData,
+    intrinsics: &mut IntrinsicsMap<'ink>,
+    needs_profiler: &mut bool,
+) {
+    collect_intrinsic(context, &target, &intrinsics::profiler_enter, intrinsics);
+    collect_intrinsic(context, &target, &intrinsics::profiler_exit, intrinsics);
+    *needs_profiler = true;
+}
+
+/// Collects the coverage hook that is called from instrumented function bodies.
+pub fn collect_coverage_hooks<'ink>(
+    context: &'ink Context,
+    target: TargetData,
+    intrinsics: &mut IntrinsicsMap<'ink>,
+    needs_coverage: &mut bool,
+) {
+    collect_intrinsic(context, &target, &intrinsics::coverage_hit, intrinsics);
+    *needs_coverage = true;
+}
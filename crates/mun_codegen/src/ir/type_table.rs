@@ -333,10 +333,37 @@ impl<'db, 'ink, 't> TypeTableBuilder<'db, 'ink, 't> {
                 self.value_context,
             );
 
+        // Construct an array of the fields' previous names (or null if no field was renamed)
+        let field_renamed_from = if fields
+            .iter()
+            .any(|field| field.renamed_from(self.db).is_some())
+        {
+            fields
+                .iter()
+                .enumerate()
+                .map(|(idx, field)| match field.renamed_from(self.db) {
+                    Some(renamed_from) => CString::new(renamed_from.to_string())
+                        .expect("field name is not a valid CString")
+                        .intern(
+                            format!("struct_info::<{}>::field_renamed_from.{}", name, idx),
+                            self.value_context,
+                        )
+                        .as_value(self.value_context),
+                    None => Value::null(self.value_context),
+                })
+                .into_const_private_pointer_or_null(
+                    format!("struct_info::<{}>::field_renamed_from", name),
+                    self.value_context,
+                )
+        } else {
+            Value::null(self.value_context)
+        };
+
         ir::StructInfo {
             field_names,
             field_types,
             field_offsets,
+            field_renamed_from,
             num_fields: fields
                 .len()
                 .try_into()
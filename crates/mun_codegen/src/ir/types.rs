@@ -80,6 +80,7 @@ pub struct StructInfo<'ink> {
     pub field_names: Value<'ink, *const *const u8>,
     pub field_types: Value<'ink, *const *const TypeInfo<'ink>>,
     pub field_offsets: Value<'ink, *const u16>,
+    pub field_renamed_from: Value<'ink, *const *const u8>,
     pub num_fields: u16,
     pub memory_kind: abi::StructMemoryKind,
 }
@@ -89,8 +90,10 @@ pub struct ModuleInfo<'ink> {
     pub path: Value<'ink, *const u8>,
     pub functions: Value<'ink, *const FunctionDefinition<'ink>>,
     pub types: Value<'ink, *const *const TypeInfo<'ink>>,
+    pub migrations: Value<'ink, *const FunctionDefinition<'ink>>,
     pub num_functions: u32,
     pub num_types: u32,
+    pub num_migrations: u32,
 }
 
 #[derive(AsValue)]
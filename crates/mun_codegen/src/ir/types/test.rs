@@ -138,6 +138,7 @@ fn test_struct_info_abi_compatible() {
         field_names: std::ptr::null(),
         field_types: std::ptr::null(),
         field_offsets: std::ptr::null(),
+        field_renamed_from: std::ptr::null(),
         num_fields: 0,
         memory_kind: abi::StructMemoryKind::Value,
     };
@@ -154,6 +155,8 @@ fn test_module_info_abi_compatible() {
         num_functions: 0,
         types: std::ptr::null(),
         num_types: 0,
+        migrations: std::ptr::null(),
+        num_migrations: 0,
     };
 
     super::ModuleInfo::test(&abi_type);
@@ -181,6 +184,8 @@ fn test_assembly_info_abi_compatible() {
             num_functions: 0,
             types: std::ptr::null(),
             num_types: 0,
+            migrations: std::ptr::null(),
+            num_migrations: 0,
         },
         dispatch_table: abi::DispatchTable {
             prototypes: std::ptr::null(),
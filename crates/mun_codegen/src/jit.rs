@@ -0,0 +1,173 @@
+use crate::AssemblyBitcode;
+use anyhow::anyhow;
+use inkwell::{
+    context::Context,
+    memory_buffer::MemoryBuffer,
+    module::Module,
+    targets::{InitializationConfig, Target},
+    OptimizationLevel,
+};
+use llvm_sys::{
+    error::{LLVMConsumeError, LLVMDisposeErrorMessage, LLVMErrorRef, LLVMGetErrorMessage},
+    orc::{
+        LLVMOrcAddEagerlyCompiledIR, LLVMOrcCreateInstance, LLVMOrcDisposeInstance,
+        LLVMOrcDisposeMangledSymbol, LLVMOrcGetMangledSymbol, LLVMOrcGetSymbolAddressIn,
+        LLVMOrcJITStackRef, LLVMOrcModuleHandle, LLVMOrcRemoveModule,
+    },
+    support::{LLVMLoadLibraryPermanently, LLVMSearchForAddressOfSymbol},
+    target_machine::{
+        LLVMCodeGenOptLevel, LLVMCodeModel, LLVMCreateTargetMachine, LLVMDisposeMessage,
+        LLVMGetTargetFromTriple, LLVMRelocMode,
+    },
+};
+use mun_target::spec;
+use std::{
+    ffi::{c_void, CStr, CString},
+    os::raw::c_char,
+    ptr::{self, NonNull},
+    sync::Once,
+};
+
+/// A `JitAssembly` is an assembly that was compiled in memory using LLVM's ORC JIT, instead of being
+/// linked into a shared object. Its code remains valid for as long as the `JitAssembly` exists.
+pub struct JitAssembly {
+    jit: LLVMOrcJITStackRef,
+    handle: LLVMOrcModuleHandle,
+    // The context of the compiled module must outlive the JIT
+    _context: Context,
+}
+
+impl JitAssembly {
+    /// Compiles the `bitcode` of an assembly in memory. The `target` must be the host target.
+    pub fn new(
+        target: &spec::Target,
+        optimization_level: OptimizationLevel,
+        bitcode: &AssemblyBitcode,
+    ) -> Result<Self, anyhow::Error> {
+        // Symbols that are not defined by an assembly - e.g. `memcpy` - are resolved in the host
+        // process.
+        static LOAD_PROCESS_SYMBOLS: Once = Once::new();
+        LOAD_PROCESS_SYMBOLS.call_once(|| unsafe {
+            LLVMLoadLibraryPermanently(ptr::null());
+        });
+
+        Target::initialize_native(&InitializationConfig::default()).map_err(|e| anyhow!(e))?;
+
+        let context = Context::create();
+        let buffer = MemoryBuffer::create_from_memory_range(bitcode.as_bytes(), "assembly");
+        let module = Module::parse_bitcode_from_buffer(&buffer, &context)
+            .map_err(|e| anyhow!("could not parse assembly bitcode: {}", e))?;
+
+        let triple = CString::new(target.llvm_target.as_str())?;
+        let cpu = CString::new(target.options.cpu.as_str())?;
+        let features = CString::new(target.options.features.as_str())?;
+
+        unsafe {
+            let mut llvm_target = ptr::null_mut();
+            let mut error_message = ptr::null_mut();
+            if LLVMGetTargetFromTriple(triple.as_ptr(), &mut llvm_target, &mut error_message) != 0 {
+                let message = CStr::from_ptr(error_message).to_string_lossy().into_owned();
+                LLVMDisposeMessage(error_message);
+                return Err(anyhow!("could not find llvm target: {}", message));
+            }
+
+            // The JIT takes ownership of the target machine
+            let target_machine = LLVMCreateTargetMachine(
+                llvm_target,
+                triple.as_ptr(),
+                cpu.as_ptr(),
+                features.as_ptr(),
+                codegen_opt_level(optimization_level),
+                LLVMRelocMode::LLVMRelocDefault,
+                LLVMCodeModel::LLVMCodeModelJITDefault,
+            );
+            let jit = LLVMOrcCreateInstance(target_machine);
+
+            // The JIT takes ownership of the module
+            let mut handle = 0;
+            let result = into_result(LLVMOrcAddEagerlyCompiledIR(
+                jit,
+                &mut handle,
+                module.as_mut_ptr(),
+                Some(resolve_symbol),
+                ptr::null_mut(),
+            ));
+            std::mem::forget(module);
+
+            if let Err(e) = result {
+                LLVMConsumeError(LLVMOrcDisposeInstance(jit));
+                return Err(anyhow!("could not compile assembly: {}", e));
+            }
+
+            Ok(Self {
+                jit,
+                handle,
+                _context: context,
+            })
+        }
+    }
+
+    /// Retrieves the address of the symbol with the specified `name`, if it exists.
+    pub fn get_symbol_address(&self, name: &str) -> Option<NonNull<c_void>> {
+        let name = CString::new(name).ok()?;
+        unsafe {
+            let mut mangled_name = ptr::null_mut();
+            LLVMOrcGetMangledSymbol(self.jit, &mut mangled_name, name.as_ptr());
+
+            let mut address = 0;
+            let result = into_result(LLVMOrcGetSymbolAddressIn(
+                self.jit,
+                &mut address,
+                self.handle,
+                mangled_name,
+            ));
+            LLVMOrcDisposeMangledSymbol(mangled_name);
+
+            result.ok()?;
+            NonNull::new(address as *mut c_void)
+        }
+    }
+}
+
+impl Drop for JitAssembly {
+    fn drop(&mut self) {
+        unsafe {
+            LLVMConsumeError(LLVMOrcRemoveModule(self.jit, self.handle));
+            LLVMConsumeError(LLVMOrcDisposeInstance(self.jit));
+        }
+    }
+}
+
+/// Resolves a symbol that is not defined by the assembly in the host process.
+extern "C" fn resolve_symbol(name: *const c_char, _ctx: *mut c_void) -> u64 {
+    // Symbol names are mangled, so strip the global prefix on platforms that have one
+    let name = if cfg!(target_os = "macos") {
+        unsafe { name.add(1) }
+    } else {
+        name
+    };
+
+    unsafe { LLVMSearchForAddressOfSymbol(name) as u64 }
+}
+
+/// Converts an `LLVMErrorRef` into a `Result`, disposing of the error in the process.
+unsafe fn into_result(error: LLVMErrorRef) -> Result<(), String> {
+    if error.is_null() {
+        return Ok(());
+    }
+
+    let message = LLVMGetErrorMessage(error);
+    let result = CStr::from_ptr(message).to_string_lossy().into_owned();
+    LLVMDisposeErrorMessage(message);
+    Err(result)
+}
+
+/// Converts an `OptimizationLevel` into its LLVM equivalent.
+fn codegen_opt_level(optimization_level: OptimizationLevel) -> LLVMCodeGenOptLevel {
+    match optimization_level {
+        OptimizationLevel::None => LLVMCodeGenOptLevel::LLVMCodeGenLevelNone,
+        OptimizationLevel::Less => LLVMCodeGenOptLevel::LLVMCodeGenLevelLess,
+        OptimizationLevel::Default => LLVMCodeGenOptLevel::LLVMCodeGenLevelDefault,
+        OptimizationLevel::Aggressive => LLVMCodeGenOptLevel::LLVMCodeGenLevelAggressive,
+    }
+}
@@ -1,9 +1,11 @@
 pub use inkwell::{builder::Builder, context::Context, module::Module, OptimizationLevel};
 
 pub use crate::{
-    assembly::{AssemblyIR, TargetAssembly},
+    assembly::{AssemblyBitcode, AssemblyIR, StaticAssembly, TargetAssembly},
     code_gen::AssemblyBuilder,
+    coverage::coverage_regions,
     db::{CodeGenDatabase, CodeGenDatabaseStorage},
+    jit::JitAssembly,
     module_group::ModuleGroup,
     module_partition::{ModuleGroupId, ModulePartition},
 };
@@ -14,6 +16,8 @@ mod db;
 #[macro_use]
 mod ir;
 mod assembly;
+mod coverage;
+mod jit;
 
 #[cfg(test)]
 mod mock;
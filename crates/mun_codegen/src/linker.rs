@@ -31,6 +31,7 @@ pub fn create_with_target(target: &spec::Target) -> Box<dyn Linker> {
         LinkerFlavor::Ld => Box::new(LdLinker::new(target)),
         LinkerFlavor::Ld64 => Box::new(Ld64Linker::new(target)),
         LinkerFlavor::Msvc => Box::new(MsvcLinker::new(target)),
+        LinkerFlavor::Wasm => Box::new(WasmLinker::new(target)),
     }
 }
 
@@ -90,8 +91,14 @@ struct Ld64Linker {
 
 impl Ld64Linker {
     fn new(target: &spec::Target) -> Self {
+        // ld64 uses Apple's naming for architectures
+        let arch = match target.arch.as_str() {
+            "aarch64" => "arm64",
+            arch => arch,
+        };
+
         Ld64Linker {
-            args: vec![format!("-arch {}", &target.arch)],
+            args: vec![format!("-arch {}", arch)],
         }
     }
 }
@@ -189,3 +196,66 @@ impl Linker for MsvcLinker {
             .map_err(LinkerError::LinkError)
     }
 }
+
+struct WasmLinker {
+    args: Vec<String>,
+}
+
+impl WasmLinker {
+    fn new(_target: &spec::Target) -> Self {
+        WasmLinker {
+            args: Vec::default(),
+        }
+    }
+}
+
+impl Linker for WasmLinker {
+    fn add_object(&mut self, path: &Path) -> Result<(), LinkerError> {
+        let path_str = path
+            .to_str()
+            .ok_or_else(|| LinkerError::PathError(path.to_owned()))?
+            .to_owned();
+        self.args.push(path_str);
+        Ok(())
+    }
+
+    fn build_shared_object(&mut self, path: &Path) -> Result<(), LinkerError> {
+        let path_str = path
+            .to_str()
+            .ok_or_else(|| LinkerError::PathError(path.to_owned()))?;
+
+        // A munlib is a library, it has no `_start` entry point
+        self.args.push("--no-entry".to_owned());
+
+        // Symbols that cannot be resolved (e.g. libc intrinsics) are imported from the host
+        self.args.push("--allow-undefined".to_owned());
+
+        // Export the function table so the host can add its own functions to it and resolve
+        // dispatch table entries
+        self.args.push("--export-table".to_owned());
+        self.args.push("--growable-table".to_owned());
+
+        self.args
+            .push(format!("--export={}", abi::GET_INFO_FN_NAME));
+        self.args
+            .push(format!("--export={}", abi::GET_VERSION_FN_NAME));
+        self.args
+            .push(format!("--export={}", abi::SET_ALLOCATOR_HANDLE_FN_NAME));
+        self.args
+            .push(format!("--export={}", abi::SET_PROFILER_HANDLE_FN_NAME));
+        self.args
+            .push(format!("--export={}", abi::SET_COVERAGE_HANDLE_FN_NAME));
+
+        // Specify output path
+        self.args.push("-o".to_owned());
+        self.args.push(path_str.to_owned());
+
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<(), LinkerError> {
+        mun_lld::link(mun_lld::LldFlavor::Wasm, &self.args)
+            .ok()
+            .map_err(LinkerError::LinkError)
+    }
+}
@@ -68,6 +68,9 @@ impl Default for MockDatabase {
             events: Default::default(),
         };
         db.set_optimization_level(OptimizationLevel::Default);
+        db.set_instrument_functions(false);
+        db.set_instrument_coverage(false);
+        db.set_direct_calls(false);
         db.set_target(Target::host_target().unwrap());
         db
     }
@@ -62,17 +62,35 @@ impl Index<ModuleGroupId> for ModulePartition {
     }
 }
 
-/// Builds a module partition from the contents of the database
+/// Builds a module partition from the contents of the database. Every module is compiled into a
+/// separate assembly, unless direct calls are enabled, in which case all modules of a package are
+/// grouped into a single assembly. The assemblies of dependencies are named after their package,
+/// e.g. `math` and `math::ops`. They do not collide with those of the root package, because modules
+/// in the root of a package cannot have the same name as one of its dependencies.
 pub(crate) fn build_partition(db: &dyn CodeGenDatabase) -> Arc<ModulePartition> {
     let mut partition = ModulePartition::default();
+    if db.direct_calls() {
+        for package in hir::Package::all(db.upcast()) {
+            let name = package
+                .name(db.upcast())
+                .map_or_else(|| String::from("mod"), |name| name.to_string());
+            partition.add_group(
+                db.upcast(),
+                ModuleGroup::new(db.upcast(), name, package.modules(db.upcast())),
+            );
+        }
+        return Arc::new(partition);
+    }
+
     for module in hir::Package::all(db.upcast())
         .into_iter()
         .flat_map(|package| package.modules(db.upcast()))
     {
-        let name = if module.name(db.upcast()).is_some() {
-            module.full_name(db.upcast())
-        } else {
+        let name = module.full_name(db.upcast());
+        let name = if name.is_empty() {
             String::from("mod")
+        } else {
+            name
         };
 
         partition.add_group(
@@ -968,6 +968,107 @@ fn nested_private_recursive_fn_with_args() {
     )
 }
 
+#[test]
+fn static_linking_symbols() {
+    let db = MockDatabase::with_files(
+        r#"
+    //- /mod.mun
+    pub fn main() -> i32 {
+        5
+    }
+    "#,
+    );
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let module_partition = db.module_partition();
+    let (module_group_id, _) = module_partition.iter().next().unwrap();
+    let mut assembly = AssemblyBuilder::new(&code_gen, &module_partition, module_group_id)
+        .build()
+        .expect("Failed to build assembly");
+    assembly.prepare_for_static_linking("foo");
+
+    let ir_file = tempfile::NamedTempFile::new().unwrap();
+    assembly.write_ir_to_file(ir_file.path()).unwrap();
+    let ir = std::fs::read_to_string(ir_file.path()).unwrap();
+
+    // The assembly functions are prefixed, and all other symbols are internal
+    assert!(ir.contains("define void @foo_set_allocator_handle("));
+    assert!(ir.contains("@foo_get_info("));
+    assert!(ir.contains("@foo_get_version("));
+    assert!(!ir.contains("@get_info("));
+    assert!(ir.contains("define internal i32 @main()"));
+}
+
+#[test]
+fn cross_compile_aarch64_linux() {
+    let object = cross_compile_object("aarch64-unknown-linux-gnu");
+
+    // ELF magic followed by `e_machine` = EM_AARCH64
+    assert_eq!(&object[0..4], b"\x7fELF");
+    assert_eq!(u16::from_le_bytes([object[18], object[19]]), 183);
+}
+
+#[test]
+fn cross_compile_aarch64_darwin() {
+    let object = cross_compile_object("aarch64-apple-darwin");
+
+    // 64-bit Mach-O magic followed by `cputype` = CPU_TYPE_ARM64
+    assert_eq!(
+        u32::from_le_bytes([object[0], object[1], object[2], object[3]]),
+        0xfeed_facf
+    );
+    assert_eq!(
+        u32::from_le_bytes([object[4], object[5], object[6], object[7]]),
+        0x0100_000c
+    );
+}
+
+#[test]
+fn cross_compile_wasm32() {
+    let object = cross_compile_object("wasm32-unknown-unknown");
+
+    // WebAssembly magic followed by the binary format version
+    assert_eq!(&object[0..4], b"\0asm");
+    assert_eq!(u32::from_le_bytes([object[4], object[5], object[6], object[7]]), 1);
+}
+
+/// Compiles a small fixture for the specified target triple and returns the bytes of the resulting
+/// object file.
+fn cross_compile_object(triple: &str) -> Vec<u8> {
+    let mut db = MockDatabase::with_files(
+        r#"
+    //- /mod.mun
+    pub struct Vec2 {
+        x: f32,
+        y: i64,
+    }
+
+    pub fn main() -> i32 {
+        5
+    }
+    "#,
+    );
+    let target = Target::search(triple).unwrap();
+    db.set_target(target.clone());
+
+    // The data layout that LLVM reports must match the one the ABI is computed from
+    assert_eq!(
+        db.target_machine()
+            .get_target_data()
+            .get_data_layout()
+            .as_str()
+            .to_str()
+            .unwrap(),
+        target.data_layout
+    );
+
+    let module_partition = db.module_partition();
+    let (module_group_id, _) = module_partition.iter().next().unwrap();
+    let assembly = db.static_assembly(module_group_id, String::from("mod"));
+    std::fs::read(assembly.path()).unwrap()
+}
+
 fn test_snapshot(text: &str) {
     test_snapshot_with_optimization(text, OptimizationLevel::Default);
 }
@@ -82,6 +82,16 @@ impl TypeInfo {
 
     pub fn new_struct(db: &dyn HirDatabase, s: hir::Struct, type_size: TypeSize) -> TypeInfo {
         let name = s.full_name(db);
+        if s.is_extern(db) {
+            // The guid of an extern struct must match that of the host-defined type
+            return Self {
+                guid: Guid::from_extern_struct_name(&name),
+                name,
+                size: type_size,
+                data: TypeInfoData::Struct(s),
+            };
+        }
+
         let guid_string = {
             let fields: Vec<String> = s
                 .fields(db)
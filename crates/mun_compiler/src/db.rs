@@ -62,6 +62,9 @@ impl CompilerDatabase {
     pub fn set_config(&mut self, config: &Config) {
         self.set_target(config.target.clone());
         self.set_optimization_level(config.optimization_lvl);
+        self.set_instrument_functions(config.instrument_functions);
+        self.set_instrument_coverage(config.instrument_coverage);
+        self.set_direct_calls(config.direct_calls);
     }
 }
 
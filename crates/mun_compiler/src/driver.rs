@@ -6,22 +6,27 @@ use crate::{
     PathOrInline, RelativePath,
 };
 use hir::{
-    AstDatabase, DiagnosticSink, FileId, Module, PackageSet, SourceDatabase, SourceRoot,
-    SourceRootId, Upcast,
+    AstDatabase, DiagnosticSink, FileId, HirDatabase, Module, PackageSet, SourceDatabase,
+    SourceRoot, SourceRootId, Upcast,
+};
+use mun_codegen::{
+    AssemblyBitcode, AssemblyIR, CodeGenDatabase, JitAssembly, ModuleGroup, StaticAssembly,
+    TargetAssembly,
 };
-use mun_codegen::{AssemblyIR, CodeGenDatabase, ModuleGroup, TargetAssembly};
 use paths::RelativePathBuf;
 
 use std::{path::PathBuf, sync::Arc};
 
 mod config;
+mod coverage;
 mod display_color;
 
 pub use self::config::Config;
+pub use self::coverage::{CoverageReport, FileCoverage, RegionCoverage};
 pub use self::display_color::DisplayColor;
 
 use crate::diagnostics_snippets::{emit_hir_diagnostic, emit_syntax_error};
-use mun_project::{Package, LOCKFILE_NAME};
+use mun_project::{Package, PackageGraph, LOCKFILE_NAME};
 use std::{collections::HashMap, convert::TryInto, path::Path, time::Duration};
 use walkdir::WalkDir;
 
@@ -31,8 +36,10 @@ pub struct Driver {
     db: CompilerDatabase,
     out_dir: PathBuf,
 
-    source_root: SourceRoot,
-    path_to_file_id: HashMap<RelativePathBuf, FileId>,
+    /// The packages that are compiled, if the driver was constructed from a package manifest
+    package_graph: Option<PackageGraph>,
+    source_roots: HashMap<SourceRootId, SourceRoot>,
+    path_to_file_id: HashMap<(SourceRootId, RelativePathBuf), FileId>,
     file_id_to_path: HashMap<FileId, RelativePathBuf>,
     next_file_id: usize,
 
@@ -40,6 +47,8 @@ pub struct Driver {
 
     display_color: DisplayColor,
     emit_ir: bool,
+    emit_static: bool,
+    package_name: Option<String>,
 }
 
 impl Driver {
@@ -48,13 +57,16 @@ impl Driver {
         Ok(Self {
             db: CompilerDatabase::new(&config),
             out_dir,
-            source_root: Default::default(),
+            package_graph: None,
+            source_roots: Default::default(),
             path_to_file_id: Default::default(),
             file_id_to_path: Default::default(),
             next_file_id: 0,
             module_to_temp_assembly_path: Default::default(),
             display_color: config.display_color,
             emit_ir: config.emit_ir,
+            emit_static: config.emit_static,
+            package_name: None,
         })
     }
 
@@ -91,10 +103,9 @@ impl Driver {
         driver.next_file_id += 1;
         driver.db.set_file_text(file_id, Arc::from(text));
         driver.db.set_file_source_root(file_id, WORKSPACE);
-        driver.source_root.insert_file(file_id, rel_path);
-        driver
-            .db
-            .set_source_root(WORKSPACE, Arc::new(driver.source_root.clone()));
+        let mut source_root = SourceRoot::default();
+        source_root.insert_file(file_id, rel_path);
+        driver.set_source_root(WORKSPACE, source_root);
 
         let mut package_set = PackageSet::default();
         package_set.add_package(WORKSPACE);
@@ -102,18 +113,20 @@ impl Driver {
 
         driver
             .path_to_file_id
-            .insert(RelativePathBuf::from("mod.mun"), file_id);
+            .insert((WORKSPACE, RelativePathBuf::from("mod.mun")), file_id);
 
         Ok((driver, file_id))
     }
 
-    /// Constructs a driver with a package manifest directory
+    /// Constructs a driver with a package manifest directory. The path dependencies of the package
+    /// are resolved and compiled together with the package; each into its own assemblies.
     pub fn with_package_path<P: AsRef<Path>>(
         package_path: P,
         config: Config,
     ) -> Result<(Package, Driver), anyhow::Error> {
-        // Load the manifest file as a package
-        let package = Package::from_file(package_path)?;
+        // Load the manifest file as a package, together with its dependencies
+        let package_graph = PackageGraph::from_manifest_path(package_path)?;
+        let package = package_graph.root().clone();
 
         // Determine output directory
         let output_dir = ensure_package_output_dir(&package, &config)
@@ -121,42 +134,39 @@ impl Driver {
 
         // Construct the driver
         let mut driver = Driver::with_config(config, output_dir)?;
+        driver.package_name = Some(package.name().to_owned());
 
-        // Iterate over all files in the source directory of the package and store their information in
-        // the database
-        let source_directory = package.source_directory();
-        if !source_directory.is_dir() {
-            anyhow::bail!("the source directory does not exist")
-        }
-
-        for source_file_path in iter_source_files(&source_directory) {
-            let relative_path = compute_source_relative_path(&source_directory, &source_file_path)?;
-
-            // Load the contents of the file
-            let file_contents = std::fs::read_to_string(&source_file_path).map_err(|e| {
-                anyhow::anyhow!(
-                    "could not read contents of '{}': {}",
-                    source_file_path.display(),
-                    e
-                )
-            })?;
-
-            let file_id = driver.alloc_file_id(&relative_path)?;
-            driver.db.set_file_text(file_id, Arc::from(file_contents));
-            driver.db.set_file_source_root(file_id, WORKSPACE);
-            driver
-                .source_root
-                .insert_file(file_id, relative_path.clone());
-        }
-
-        // Store the source root in the database
-        driver
-            .db
-            .set_source_root(WORKSPACE, Arc::new(driver.source_root.clone()));
-
+        // Store the information of all source files in the database. The files of the package are
+        // stored in the `WORKSPACE` source root, the files of each dependency in a separate one.
         let mut package_set = PackageSet::default();
-        package_set.add_package(WORKSPACE);
+        let mut package_ids = Vec::with_capacity(package_graph.packages().len());
+        for (idx, member) in package_graph.packages().iter().enumerate() {
+            let is_root = idx == package_graph.root_index();
+            let source_root_id = source_root_of_package(&package_graph, idx);
+
+            let mut source_root = SourceRoot::default();
+            for (relative_path, file_contents) in read_source_files(member)? {
+                let file_id = driver.alloc_package_file_id(source_root_id, &relative_path)?;
+                driver.db.set_file_text(file_id, Arc::from(file_contents));
+                driver.db.set_file_source_root(file_id, source_root_id);
+                source_root.insert_file(file_id, relative_path);
+            }
+            driver.set_source_root(source_root_id, source_root);
+
+            // Dependencies are referred to by their name, in which dashes are replaced by
+            // underscores. The manifest guarantees that the result is a valid identifier.
+            let package_id = if is_root {
+                package_set.add_package(source_root_id)
+            } else {
+                package_set.add_named_package(source_root_id, member.name().replace('-', "_"))
+            };
+            for &dependency in package_graph.dependencies(idx) {
+                package_set.add_dependency(package_id, package_ids[dependency]);
+            }
+            package_ids.push(package_id);
+        }
         driver.db.set_packages(Arc::new(package_set));
+        driver.package_graph = Some(package_graph);
 
         Ok((package, driver))
     }
@@ -171,12 +181,39 @@ impl Driver {
         &mut self,
         relative_path: P,
     ) -> Result<FileId, anyhow::Error> {
+        self.alloc_package_file_id(WORKSPACE, relative_path)
+    }
+
+    /// Returns a file id for the file with the given `relative_path` in the specified source root.
+    fn alloc_package_file_id<P: AsRef<RelativePath>>(
+        &mut self,
+        source_root_id: SourceRootId,
+        relative_path: P,
+    ) -> Result<FileId, anyhow::Error> {
+        let key = (
+            source_root_id,
+            relative_path.as_ref().to_relative_path_buf(),
+        );
+
         // Re-use existing id to get better caching performance
-        if let Some(id) = self.path_to_file_id.get(relative_path.as_ref()) {
+        if let Some(id) = self.path_to_file_id.get(&key) {
             return Ok(*id);
         }
 
         // Allocate a new id
+        let id = self.next_file_id()?;
+
+        // Update bookkeeping
+        self.file_id_to_path.insert(id, key.1.clone());
+        self.path_to_file_id.insert(key, id);
+
+        Ok(id)
+    }
+
+    /// Allocates a new file id, without associating it with a path.
+    ///
+    /// The allocation of an id might fail if more file IDs exist than can be allocated.
+    fn next_file_id(&mut self) -> Result<FileId, anyhow::Error> {
         // TODO: See if we can figure out if the compiler cleared the cache of a certain file, at
         //  which point we can sort of reset the `next_file_id`
         let id = FileId(
@@ -185,13 +222,6 @@ impl Driver {
                 .map_err(|_e| anyhow::anyhow!("too many active source files"))?,
         );
         self.next_file_id += 1;
-
-        // Update bookkeeping
-        self.path_to_file_id
-            .insert(relative_path.as_ref().to_relative_path_buf(), id);
-        self.file_id_to_path
-            .insert(id, relative_path.as_ref().to_relative_path_buf());
-
         Ok(id)
     }
 }
@@ -204,11 +234,10 @@ impl Driver {
         text: impl AsRef<str>,
     ) -> anyhow::Result<()> {
         let file_id = self
-            .path_to_file_id
-            .get(path.as_ref())
+            .get_file_id_for_path(path.as_ref())
             .ok_or_else(|| anyhow::anyhow!("the path '{}' is unknown", path.as_ref()))?;
         self.db
-            .set_file_text(*file_id, Arc::from(text.as_ref().to_owned()));
+            .set_file_text(file_id, Arc::from(text.as_ref().to_owned()));
         Ok(())
     }
 }
@@ -323,6 +352,8 @@ impl Driver {
             for module in package.modules(self.db.upcast()) {
                 if self.emit_ir {
                     self.write_assembly_ir(module)?;
+                } else if self.emit_static {
+                    self.write_static_assembly(module)?;
                 } else {
                     self.write_target_assembly(module, force)?;
                 }
@@ -402,6 +433,52 @@ impl Driver {
         Ok(true)
     }
 
+    /// Generates a statically linkable object file for the specified module and stores it in the
+    /// output location.
+    fn write_static_assembly(&mut self, module: hir::Module) -> Result<(), anyhow::Error> {
+        log::trace!("writing static assembly for {:?}", module);
+
+        // Find the module group to which the module belongs
+        let module_partition = self.db.module_partition();
+        let module_group_id = module_partition
+            .group_for_module(module)
+            .expect("could not find the module in the module partition");
+        let module_group = &module_partition[module_group_id];
+
+        // Get the compiled object file
+        let static_assembly = self
+            .db
+            .static_assembly(module_group_id, self.static_symbol_prefix(module_group));
+
+        // Determine the filename of the group
+        let assembly_path = self
+            .path_for_module_group(module_group)
+            .with_extension(StaticAssembly::EXTENSION);
+
+        // Write to disk
+        static_assembly.copy_to(&assembly_path)?;
+
+        Ok(())
+    }
+
+    /// Returns the prefix of the assembly functions of the specified module group, when it is
+    /// statically linked. It consists of the package name - or `mun` if there is no package -
+    /// followed by the group's name, unless it is the root module. Characters that are invalid in
+    /// a C identifier are replaced by underscores.
+    fn static_symbol_prefix(&self, module_group: &ModuleGroup) -> String {
+        let package_name = self.package_name.as_deref().unwrap_or("mun");
+        let prefix = if module_group.name == "mod" {
+            package_name.to_owned()
+        } else {
+            format!("{}_{}", package_name, module_group.name)
+        };
+
+        prefix
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect()
+    }
+
     /// Generates IR for the specified module and stores it in the output location.
     fn write_assembly_ir(&mut self, module: hir::Module) -> Result<(), anyhow::Error> {
         log::trace!("writing assembly IR for {:?}", module);
@@ -429,83 +506,275 @@ impl Driver {
 }
 
 impl Driver {
+    /// Returns the bitcode of all assemblies, together with the relative path of their module
+    /// group. The bitcode is cached, so it is only regenerated if the assembly changed.
+    pub fn assembly_bitcodes(&self) -> Vec<(RelativePathBuf, Arc<AssemblyBitcode>)> {
+        let module_partition = self.db.module_partition();
+        module_partition
+            .iter()
+            .map(|(module_group_id, module_group)| {
+                (
+                    module_group.relative_file_path(),
+                    self.db.assembly_bitcode(module_group_id),
+                )
+            })
+            .collect()
+    }
+
+    /// Compiles the `bitcode` of an assembly in memory, using LLVM's ORC JIT.
+    pub fn jit_compile(&self, bitcode: &AssemblyBitcode) -> Result<JitAssembly, anyhow::Error> {
+        JitAssembly::new(&self.db.target(), self.db.optimization_level(), bitcode)
+    }
+}
+
+impl Driver {
+    /// Returns the packages that are compiled, if the driver was constructed from a package
+    /// manifest.
+    pub fn package_graph(&self) -> Option<&PackageGraph> {
+        self.package_graph.as_ref()
+    }
+
     /// Returns the `FileId` of the file with the given relative path
     pub fn get_file_id_for_path<P: AsRef<RelativePath>>(&self, path: P) -> Option<FileId> {
-        self.path_to_file_id.get(path.as_ref()).copied()
+        self.get_file_id_in(WORKSPACE, path)
+    }
+
+    /// Returns the `FileId` of the file with the given path, relative to the source directory of
+    /// the package at `package_idx` in the [`PackageGraph`].
+    pub fn get_package_file_id_for_path<P: AsRef<RelativePath>>(
+        &self,
+        package_idx: usize,
+        path: P,
+    ) -> Option<FileId> {
+        self.get_file_id_in(self.package_source_root(package_idx), path)
     }
 
     /// Tells the driver that the file at the specified `path` has changed its contents. Returns the
     /// `FileId` of the modified file.
     pub fn update_file<P: AsRef<RelativePath>>(&mut self, path: P, contents: String) -> FileId {
-        let file_id = *self
-            .path_to_file_id
-            .get(path.as_ref())
+        self.update_file_in(WORKSPACE, path, contents)
+    }
+
+    /// Tells the driver that the file at the specified `path` - relative to the source directory of
+    /// the package at `package_idx` in the [`PackageGraph`] - has changed its contents. Returns the
+    /// `FileId` of the modified file.
+    pub fn update_package_file<P: AsRef<RelativePath>>(
+        &mut self,
+        package_idx: usize,
+        path: P,
+        contents: String,
+    ) -> FileId {
+        self.update_file_in(self.package_source_root(package_idx), path, contents)
+    }
+
+    /// Adds a new file to the driver. Returns the `FileId` of the new file.
+    pub fn add_file<P: AsRef<RelativePath>>(&mut self, path: P, contents: String) -> FileId {
+        self.add_file_in(WORKSPACE, path, contents)
+    }
+
+    /// Adds a new file to the package at `package_idx` in the [`PackageGraph`]. Returns the
+    /// `FileId` of the new file.
+    pub fn add_package_file<P: AsRef<RelativePath>>(
+        &mut self,
+        package_idx: usize,
+        path: P,
+        contents: String,
+    ) -> FileId {
+        self.add_file_in(self.package_source_root(package_idx), path, contents)
+    }
+
+    /// Removes the specified file from the driver.
+    pub fn remove_file<P: AsRef<RelativePath>>(&mut self, path: P) -> FileId {
+        self.remove_file_in(WORKSPACE, path)
+    }
+
+    /// Removes the specified file from the package at `package_idx` in the [`PackageGraph`].
+    pub fn remove_package_file<P: AsRef<RelativePath>>(
+        &mut self,
+        package_idx: usize,
+        path: P,
+    ) -> FileId {
+        self.remove_file_in(self.package_source_root(package_idx), path)
+    }
+
+    /// Renames the specified file to the specified path
+    pub fn rename<P1: AsRef<RelativePath>, P2: AsRef<RelativePath>>(
+        &mut self,
+        from: P1,
+        to: P2,
+    ) -> FileId {
+        self.rename_in(WORKSPACE, from, to)
+    }
+
+    /// Renames the specified file of the package at `package_idx` in the [`PackageGraph`] to the
+    /// specified path
+    pub fn rename_package_file<P1: AsRef<RelativePath>, P2: AsRef<RelativePath>>(
+        &mut self,
+        package_idx: usize,
+        from: P1,
+        to: P2,
+    ) -> FileId {
+        self.rename_in(self.package_source_root(package_idx), from, to)
+    }
+
+    /// Returns the source root of the package at `package_idx` in the [`PackageGraph`].
+    fn package_source_root(&self, package_idx: usize) -> SourceRootId {
+        let package_graph = self
+            .package_graph
+            .as_ref()
+            .expect("the driver was not constructed from a package manifest");
+        assert!(
+            package_idx < package_graph.packages().len(),
+            "the package index is out of bounds"
+        );
+        source_root_of_package(package_graph, package_idx)
+    }
+
+    /// Stores the `source_root` in the database
+    fn set_source_root(&mut self, source_root_id: SourceRootId, source_root: SourceRoot) {
+        self.db
+            .set_source_root(source_root_id, Arc::new(source_root.clone()));
+        self.source_roots.insert(source_root_id, source_root);
+    }
+
+    fn get_file_id_in<P: AsRef<RelativePath>>(
+        &self,
+        source_root_id: SourceRootId,
+        path: P,
+    ) -> Option<FileId> {
+        self.path_to_file_id
+            .get(&(source_root_id, path.as_ref().to_relative_path_buf()))
+            .copied()
+    }
+
+    fn update_file_in<P: AsRef<RelativePath>>(
+        &mut self,
+        source_root_id: SourceRootId,
+        path: P,
+        contents: String,
+    ) -> FileId {
+        let file_id = self
+            .get_file_id_in(source_root_id, path)
             .expect("writing to a file that is not part of the source root should never happen");
         self.db.set_file_text(file_id, Arc::from(contents));
         file_id
     }
 
-    /// Adds a new file to the driver. Returns the `FileId` of the new file.
-    pub fn add_file<P: AsRef<RelativePath>>(&mut self, path: P, contents: String) -> FileId {
-        let file_id = self.alloc_file_id(path.as_ref()).unwrap();
+    fn add_file_in<P: AsRef<RelativePath>>(
+        &mut self,
+        source_root_id: SourceRootId,
+        path: P,
+        contents: String,
+    ) -> FileId {
+        let file_id = self
+            .alloc_package_file_id(source_root_id, path.as_ref())
+            .unwrap();
 
         // Insert the new file
         self.db.set_file_text(file_id, Arc::from(contents));
-        self.db.set_file_source_root(file_id, WORKSPACE);
+        self.db.set_file_source_root(file_id, source_root_id);
 
         // Update the source root
-        self.source_root
-            .insert_file(file_id, path.as_ref().to_relative_path_buf());
-        self.db
-            .set_source_root(WORKSPACE, Arc::new(self.source_root.clone()));
+        let mut source_root = self
+            .source_roots
+            .remove(&source_root_id)
+            .unwrap_or_default();
+        source_root.insert_file(file_id, path.as_ref().to_relative_path_buf());
+        self.set_source_root(source_root_id, source_root);
 
         file_id
     }
 
-    /// Removes the specified file from the driver.
-    pub fn remove_file<P: AsRef<RelativePath>>(&mut self, path: P) -> FileId {
-        let file_id = *self
-            .path_to_file_id
-            .get(path.as_ref())
+    fn remove_file_in<P: AsRef<RelativePath>>(
+        &mut self,
+        source_root_id: SourceRootId,
+        path: P,
+    ) -> FileId {
+        let file_id = self
+            .get_file_id_in(source_root_id, path)
             .expect("removing to a file that is not part of the source root should never happen");
 
         // Update the source root
-        self.source_root.remove_file(file_id);
-        self.db
-            .set_source_root(WORKSPACE, Arc::new(self.source_root.clone()));
+        let mut source_root = self
+            .source_roots
+            .remove(&source_root_id)
+            .unwrap_or_default();
+        source_root.remove_file(file_id);
+        self.set_source_root(source_root_id, source_root);
 
         file_id
     }
 
-    /// Renames the specified file to the specified path
-    pub fn rename<P1: AsRef<RelativePath>, P2: AsRef<RelativePath>>(
+    fn rename_in<P1: AsRef<RelativePath>, P2: AsRef<RelativePath>>(
         &mut self,
+        source_root_id: SourceRootId,
         from: P1,
         to: P2,
     ) -> FileId {
+        let from = (source_root_id, from.as_ref().to_relative_path_buf());
+        let to = (source_root_id, to.as_ref().to_relative_path_buf());
         let file_id = *self
             .path_to_file_id
-            .get(from.as_ref())
+            .get(&from)
             .expect("renaming from a file that is not part of the source root should never happen");
-        if let Some(previous) = self.path_to_file_id.get(to.as_ref()) {
+        if let Some(previous) = self.path_to_file_id.get(&to) {
             // If there was some other file with this path in the database, forget about it.
             self.file_id_to_path.remove(previous);
         }
 
-        self.file_id_to_path
-            .insert(file_id, to.as_ref().to_relative_path_buf());
-        self.path_to_file_id.remove(from.as_ref()); // FileId now belongs to to
+        self.file_id_to_path.insert(file_id, to.1.clone());
+        self.path_to_file_id.remove(&from); // FileId now belongs to to
+        self.path_to_file_id.insert(to.clone(), file_id);
 
-        self.source_root.remove_file(file_id);
-        self.source_root
-            .insert_file(file_id, to.as_ref().to_relative_path_buf());
-        self.db
-            .set_source_root(WORKSPACE, Arc::new(self.source_root.clone()));
+        let mut source_root = self
+            .source_roots
+            .remove(&source_root_id)
+            .unwrap_or_default();
+        source_root.remove_file(file_id);
+        source_root.insert_file(file_id, to.1);
+        self.set_source_root(source_root_id, source_root);
 
         file_id
     }
 }
 
+/// Returns the source root that contains the files of the package at `package_idx` in the
+/// `package_graph`. The files of the root package are stored in the `WORKSPACE` source root, the
+/// files of each dependency in a separate one.
+fn source_root_of_package(package_graph: &PackageGraph, package_idx: usize) -> SourceRootId {
+    if package_idx == package_graph.root_index() {
+        WORKSPACE
+    } else {
+        SourceRootId(package_idx as u32 + 1)
+    }
+}
+
+/// Reads the contents of all source files of `package`, together with their paths relative to the
+/// package's source directory.
+fn read_source_files(package: &Package) -> Result<Vec<(RelativePathBuf, String)>, anyhow::Error> {
+    let source_directory = package.source_directory();
+    if !source_directory.is_dir() {
+        anyhow::bail!("the source directory of `{}` does not exist", package)
+    }
+
+    iter_source_files(&source_directory)
+        .map(|source_file_path| {
+            let relative_path = compute_source_relative_path(&source_directory, &source_file_path)?;
+
+            // Load the contents of the file
+            let file_contents = std::fs::read_to_string(&source_file_path).map_err(|e| {
+                anyhow::anyhow!(
+                    "could not read contents of '{}': {}",
+                    source_file_path.display(),
+                    e
+                )
+            })?;
+
+            Ok((relative_path, file_contents))
+        })
+        .collect()
+}
+
 pub fn iter_source_files(source_dir: &Path) -> impl Iterator<Item = PathBuf> {
     WalkDir::new(source_dir)
         .into_iter()
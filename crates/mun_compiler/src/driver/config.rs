@@ -1,5 +1,6 @@
 use crate::DisplayColor;
 pub use mun_codegen::OptimizationLevel;
+use mun_project::Profile;
 use mun_target::spec::Target;
 use std::path::PathBuf;
 
@@ -21,6 +22,22 @@ pub struct Config {
 
     /// Whether or not to emit an IR file instead of a munlib.
     pub emit_ir: bool,
+
+    /// Whether or not to emit an object file that can be statically linked into an executable
+    /// instead of a munlib. The functions through which the runtime accesses its assembly - e.g.
+    /// `get_info` - are prefixed with the name of the package, e.g. `{package}_get_info`.
+    pub emit_static: bool,
+
+    /// Whether or not to insert profiler hooks at the entry and exit of every function.
+    pub instrument_functions: bool,
+
+    /// Whether or not to count the executions of every block and branch for code coverage.
+    pub instrument_coverage: bool,
+
+    /// Whether or not to compile all modules of a package into a single assembly, such that calls
+    /// between modules are direct calls instead of going through the dispatch table. Individual
+    /// modules can then no longer be hot reloaded.
+    pub direct_calls: bool,
 }
 
 impl Default for Config {
@@ -34,6 +51,26 @@ impl Default for Config {
             out_dir: None,
             display_color: DisplayColor::Auto,
             emit_ir: false,
+            emit_static: false,
+            instrument_functions: false,
+            instrument_coverage: false,
+            direct_calls: false,
+        }
+    }
+}
+
+impl Config {
+    /// Returns this configuration with the settings of the `profile` applied.
+    pub fn with_profile(self, profile: &Profile) -> Self {
+        let optimization_lvl = match profile.opt_level() {
+            0 => OptimizationLevel::None,
+            1 => OptimizationLevel::Less,
+            2 => OptimizationLevel::Default,
+            _ => OptimizationLevel::Aggressive,
+        };
+        Config {
+            optimization_lvl,
+            ..self
         }
     }
 }
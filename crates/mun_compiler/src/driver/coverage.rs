@@ -0,0 +1,251 @@
+use super::Driver;
+use hir::{ModuleDef, SourceDatabase, Upcast};
+use paths::RelativePathBuf;
+use std::{collections::HashMap, fmt};
+
+/// The number of executions of a single block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegionCoverage {
+    /// The full path of the function that contains the block
+    pub function: String,
+    /// The line at which the block starts (one-based)
+    pub line: u32,
+    /// The column at which the block starts (one-based)
+    pub column: u32,
+    /// The number of times the block was executed
+    pub count: u64,
+}
+
+/// The coverage of all blocks in a single source file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileCoverage {
+    /// The path of the file relative to the source directory
+    pub path: RelativePathBuf,
+    /// All blocks in the file, ordered by their position in the file
+    pub regions: Vec<RegionCoverage>,
+}
+
+impl FileCoverage {
+    /// Returns the number of blocks that were executed at least once.
+    pub fn num_covered(&self) -> usize {
+        self.regions
+            .iter()
+            .filter(|region| region.count > 0)
+            .count()
+    }
+}
+
+/// Maps the coverage counters recorded by a Mun runtime back to source code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CoverageReport {
+    /// The coverage of every source file, ordered by path
+    pub files: Vec<FileCoverage>,
+}
+
+impl CoverageReport {
+    /// Returns the number of blocks that were executed at least once.
+    pub fn num_covered(&self) -> usize {
+        self.files.iter().map(FileCoverage::num_covered).sum()
+    }
+
+    /// Returns the total number of blocks.
+    pub fn num_regions(&self) -> usize {
+        self.files.iter().map(|file| file.regions.len()).sum()
+    }
+}
+
+fn percentage(covered: usize, total: usize) -> f64 {
+    if total == 0 {
+        100.0
+    } else {
+        covered as f64 / total as f64 * 100.0
+    }
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for file in self.files.iter() {
+            let covered = file.num_covered();
+            writeln!(
+                f,
+                "{}: {}/{} blocks covered ({:.2}%)",
+                file.path,
+                covered,
+                file.regions.len(),
+                percentage(covered, file.regions.len())
+            )?;
+
+            for region in file.regions.iter() {
+                writeln!(
+                    f,
+                    "  {}:{}:{} {} {}",
+                    file.path, region.line, region.column, region.function, region.count
+                )?;
+            }
+        }
+
+        let covered = self.num_covered();
+        let total = self.num_regions();
+        writeln!(
+            f,
+            "total: {}/{} blocks covered ({:.2}%)",
+            covered,
+            total,
+            percentage(covered, total)
+        )
+    }
+}
+
+/// Parses coverage counters in the format written by `mun_runtime::Coverage::write_counters`.
+fn parse_counters(counters: &str) -> Result<HashMap<(String, usize), u64>, anyhow::Error> {
+    let mut result = HashMap::new();
+    for (line_idx, line) in counters.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let parse_line = || -> Option<(String, usize, u64)> {
+            let mut parts = line.split_whitespace();
+            let function = parts.next()?.to_owned();
+            let counter = parts.next()?.parse().ok()?;
+            let count = parts.next()?.parse().ok()?;
+            if parts.next().is_some() {
+                return None;
+            }
+            Some((function, counter, count))
+        };
+
+        let (function, counter, count) = parse_line().ok_or_else(|| {
+            anyhow::anyhow!(
+                "invalid coverage counter at line {}: '{}'",
+                line_idx + 1,
+                line
+            )
+        })?;
+        *result.entry((function, counter)).or_insert(0) += count;
+    }
+    Ok(result)
+}
+
+impl Driver {
+    /// Maps the coverage `counters`, written by `mun_runtime::Coverage::write_counters`, back to
+    /// the source code currently in the database.
+    ///
+    /// The counters are only valid if they were recorded with assemblies compiled from the same
+    /// source code.
+    pub fn coverage_report(&self, counters: &str) -> Result<CoverageReport, anyhow::Error> {
+        let counters = parse_counters(counters)?;
+
+        let mut files: HashMap<RelativePathBuf, Vec<(u32, RegionCoverage)>> = HashMap::new();
+        for package in hir::Package::all(self.db.upcast()) {
+            for module in package.modules(self.db.upcast()) {
+                for def in module.declarations(self.db.upcast()) {
+                    let function = match def {
+                        ModuleDef::Function(f) if !f.is_extern(self.db.upcast()) => f,
+                        _ => continue,
+                    };
+
+                    let full_name = function.full_name(self.db.upcast());
+                    let source_map = function.body_source_map(self.db.upcast());
+                    let regions = mun_codegen::coverage_regions(self.db.upcast(), function);
+                    for (counter, expr) in regions.into_iter().enumerate() {
+                        let range = match source_map.expr_range(expr) {
+                            Some(range) => range,
+                            None => continue,
+                        };
+
+                        let line_index = self.db.line_index(range.file_id);
+                        let line_col = line_index.line_col(range.value.start());
+                        let region = RegionCoverage {
+                            function: full_name.clone(),
+                            line: line_col.line + 1,
+                            column: line_col.col_utf16 + 1,
+                            count: counters
+                                .get(&(full_name.clone(), counter))
+                                .copied()
+                                .unwrap_or(0),
+                        };
+
+                        files
+                            .entry(self.db.file_relative_path(range.file_id))
+                            .or_default()
+                            .push((range.value.start().into(), region));
+                    }
+                }
+            }
+        }
+
+        let mut files: Vec<FileCoverage> = files
+            .into_iter()
+            .map(|(path, mut regions)| {
+                regions.sort_by_key(|(offset, _)| *offset);
+                FileCoverage {
+                    path,
+                    regions: regions.into_iter().map(|(_, region)| region).collect(),
+                }
+            })
+            .collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(CoverageReport { files })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Config, Driver, PathOrInline, RelativePathBuf};
+
+    fn coverage_report(source_code: &str, counters: &str) -> super::CoverageReport {
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: source_code.to_owned(),
+        };
+        let (driver, _) = Driver::with_file(Config::default(), input).unwrap();
+        driver.coverage_report(counters).unwrap()
+    }
+
+    #[test]
+    fn test_coverage_report() {
+        let report = coverage_report(
+            "pub fn main(a: bool) -> i32 {\n    if a {\n        1\n    } else {\n        2\n    }\n}\n",
+            "main 0 2\nmain 1 2\n",
+        );
+
+        assert_eq!(report.files.len(), 1);
+        let regions: Vec<(u32, u64)> = report.files[0]
+            .regions
+            .iter()
+            .map(|region| (region.line, region.count))
+            .collect();
+        assert_eq!(regions, vec![(1, 2), (2, 2), (4, 0)]);
+        assert_eq!(report.num_covered(), 2);
+        assert_eq!(report.num_regions(), 3);
+    }
+
+    #[test]
+    fn test_coverage_report_else_if() {
+        let report = coverage_report(
+            "pub fn main(a: bool, b: bool) -> i32 {\n    if a {\n        1\n    } else if b {\n        2\n    } else {\n        3\n    }\n}\n",
+            "main 0 1\nmain 2 1\nmain 4 1\n",
+        );
+
+        let regions: Vec<(u32, u64)> = report.files[0]
+            .regions
+            .iter()
+            .map(|region| (region.line, region.count))
+            .collect();
+        assert_eq!(regions, vec![(1, 1), (2, 0), (4, 1), (4, 0), (6, 1)]);
+        assert_eq!(report.num_covered(), 3);
+        assert_eq!(report.num_regions(), 5);
+    }
+
+    #[test]
+    fn test_invalid_counters() {
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: "pub fn main() {}".to_owned(),
+        };
+        let (driver, _) = Driver::with_file(Config::default(), input).unwrap();
+        assert!(driver.coverage_report("main zero 1").is_err());
+    }
+}
@@ -12,12 +12,12 @@ pub use paths::{RelativePath, RelativePathBuf};
 use std::path::{Path, PathBuf};
 
 pub use crate::driver::DisplayColor;
-pub use crate::driver::{Config, Driver};
-pub use mun_codegen::OptimizationLevel;
+pub use crate::driver::{Config, CoverageReport, Driver, FileCoverage, RegionCoverage};
+pub use mun_codegen::{AssemblyBitcode, JitAssembly, OptimizationLevel};
 
 pub use crate::db::CompilerDatabase;
 pub use annotate_snippets::snippet::AnnotationType;
-use mun_project::Package;
+use mun_project::{Package, Workspace};
 use std::ffi::OsStr;
 use std::io::stderr;
 
@@ -79,18 +79,47 @@ pub fn ensure_package_output_dir(
     Ok(out_dir)
 }
 
+/// Returns the configuration with which to compile the specified workspace member, such that its
+/// output is stored in the workspace's target directory. If an output directory is specified, it
+/// replaces the workspace's target directory.
+pub fn workspace_member_config(workspace: &Workspace, member: &Package, config: &Config) -> Config {
+    let output_dir = workspace.output_dir(member);
+    let out_dir = match &config.out_dir {
+        Some(out_dir) => out_dir.join(
+            output_dir
+                .strip_prefix(workspace.target_dir())
+                .expect("the output directory of a member is in the target directory"),
+        ),
+        None => output_dir,
+    };
+
+    Config {
+        out_dir: Some(out_dir),
+        ..config.clone()
+    }
+}
+
+/// Compiles all members of the workspace - or the single package - described by the manifest at
+/// `manifest_path`. Returns false if errors were emitted for any of the members.
 pub fn compile_manifest(manifest_path: &Path, config: Config) -> Result<bool, anyhow::Error> {
-    let (_package, mut driver) = Driver::with_package_path(manifest_path, config)?;
+    let workspace = Workspace::from_manifest_path(manifest_path)?;
 
-    // Emit diagnostics. If one of the snippets is an error, abort gracefully.
-    if driver.emit_diagnostics(&mut stderr())? {
-        return Ok(false);
-    };
+    let mut succeeded = true;
+    for member in workspace.members() {
+        let config = workspace_member_config(&workspace, member, &config);
+        let (_package, mut driver) = Driver::with_package_path(member.manifest_path(), config)?;
 
-    // Write out all assemblies
-    driver.write_all_assemblies(false)?;
+        // Emit diagnostics. If one of the snippets is an error, continue with the next member.
+        if driver.emit_diagnostics(&mut stderr())? {
+            succeeded = false;
+            continue;
+        };
+
+        // Write out all assemblies
+        driver.write_all_assemblies(false)?;
+    }
 
-    Ok(true)
+    Ok(succeeded)
 }
 
 /// Determines the relative path of a file to the source directory.
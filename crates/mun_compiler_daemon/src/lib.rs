@@ -1,32 +1,55 @@
 use std::sync::mpsc::channel;
 use std::time::Duration;
 
-use mun_compiler::{compute_source_relative_path, is_source_file, Config, Driver};
-use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use mun_compiler::{
+    compute_source_relative_path, is_source_file, workspace_member_config, Config, Driver,
+    RelativePathBuf,
+};
+use mun_project::{Package, Workspace};
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
 
 use std::io::stderr;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-/// Compiles and watches the package at the specified path. Recompiles changes that occur.
+/// A member of the workspace together with the driver that compiles it
+struct WatchedMember {
+    driver: Driver,
+    /// The canonical source directories of the member and the packages that it transitively
+    /// depends on, indexed like the package graph of the driver
+    package_directories: Vec<PathBuf>,
+}
+
+/// Compiles and watches all members of the workspace - or the single package - at the specified
+/// path. Recompiles changes that occur.
 pub fn compile_and_watch_manifest(
     manifest_path: &Path,
     config: Config,
 ) -> Result<bool, anyhow::Error> {
-    // Create the compiler driver
-    let (package, mut driver) = Driver::with_package_path(manifest_path, config)?;
+    let workspace = Workspace::from_manifest_path(manifest_path)?;
 
-    // Start watching the source directory
+    // Create the filesystem watcher
     let (watcher_tx, watcher_rx) = channel();
     let mut watcher: RecommendedWatcher = Watcher::new(watcher_tx, Duration::from_millis(10))?;
-    let source_directory = package.source_directory();
 
-    watcher.watch(&source_directory, RecursiveMode::Recursive)?;
-    println!("Watching: {}", source_directory.display());
+    let mut members = Vec::with_capacity(workspace.members().len());
+    let mut watched_directories: Vec<PathBuf> = Vec::new();
+    for member in workspace.members() {
+        let mut member = WatchedMember::new(&workspace, member, &config)?;
 
-    // Emit all current errors, and write the assemblies if no errors occured
-    if !driver.emit_diagnostics(&mut stderr())? {
-        driver.write_all_assemblies(false)?
+        // Start watching the source directories of the package and its dependencies. A dependency
+        // that is shared by multiple members is only watched once.
+        for directory in member.package_directories.iter() {
+            if !watched_directories.contains(directory) {
+                watcher.watch(directory, RecursiveMode::Recursive)?;
+                println!("Watching: {}", directory.display());
+                watched_directories.push(directory.clone());
+            }
+        }
+
+        // Emit all current errors, and write the assemblies if no errors occured
+        member.compile()?;
+        members.push(member);
     }
 
     // Insert Ctrl+C handler so we can gracefully quit
@@ -40,55 +63,252 @@ pub fn compile_and_watch_manifest(
     // Start watching filesystem events.
     while !should_quit.load(std::sync::atomic::Ordering::SeqCst) {
         if let Ok(event) = watcher_rx.recv_timeout(Duration::from_millis(1)) {
-            use notify::DebouncedEvent::*;
-            match event {
-                Write(ref path) if is_source_file(path) => {
-                    let relative_path = compute_source_relative_path(&source_directory, path)?;
+            // Every member that contains the changed source file - either directly or through a
+            // dependency - is updated. Errors are reported, but do not stop the daemon.
+            for member in members.iter_mut() {
+                let result = member.process_event(&event).and_then(|has_changes| {
+                    if has_changes {
+                        member.compile()
+                    } else {
+                        Ok(())
+                    }
+                });
+                if let Err(err) = result {
+                    log::error!("{:#}", err);
+                }
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+impl WatchedMember {
+    /// Constructs the driver that compiles the `member` of the `workspace`
+    fn new(
+        workspace: &Workspace,
+        member: &Package,
+        config: &Config,
+    ) -> Result<Self, anyhow::Error> {
+        let config = workspace_member_config(workspace, member, config);
+        let (_, driver) = Driver::with_package_path(member.manifest_path(), config)?;
+
+        // The directories are canonicalized, such that the paths of events can be matched against
+        // them
+        let package_directories = driver
+            .package_graph()
+            .expect("a driver that compiles a package has a package graph")
+            .packages()
+            .iter()
+            .map(|package| {
+                let source_directory = package.source_directory();
+                source_directory.canonicalize().unwrap_or(source_directory)
+            })
+            .collect();
+
+        Ok(Self {
+            driver,
+            package_directories,
+        })
+    }
+
+    /// Emits all current errors, and writes the assemblies if no errors occured
+    fn compile(&mut self) -> Result<(), anyhow::Error> {
+        if !self.driver.emit_diagnostics(&mut stderr())? {
+            self.driver.write_all_assemblies(false)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the index of the package that contains the source file at `path`, together with
+    /// the path of the file relative to the package's source directory. Returns `None` if `path` is
+    /// not a source file of any of the packages of the member.
+    fn source_file(&self, path: &Path) -> Result<Option<(usize, RelativePathBuf)>, anyhow::Error> {
+        if !is_source_file(path) {
+            return Ok(None);
+        }
+
+        // The innermost directory is used, in case a package is nested in another
+        let package = self
+            .package_directories
+            .iter()
+            .enumerate()
+            .filter(|(_, directory)| path.starts_with(directory))
+            .max_by_key(|(_, directory)| directory.as_os_str().len());
+        match package {
+            Some((package_idx, directory)) => Ok(Some((
+                package_idx,
+                compute_source_relative_path(directory, path)?,
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    /// Applies a filesystem `event` to the driver. Returns whether a source file of the member
+    /// changed.
+    fn process_event(&mut self, event: &DebouncedEvent) -> Result<bool, anyhow::Error> {
+        match event {
+            DebouncedEvent::Write(path) => {
+                if let Some((package_idx, relative_path)) = self.source_file(path)? {
                     let file_contents = std::fs::read_to_string(path)?;
                     log::info!("Modifying {}", relative_path);
-                    driver.update_file(relative_path, file_contents);
-                    if !driver.emit_diagnostics(&mut stderr())? {
-                        driver.write_all_assemblies(false)?;
-                    }
+                    self.driver
+                        .update_package_file(package_idx, relative_path, file_contents);
+                    return Ok(true);
                 }
-                Create(ref path) if is_source_file(path) => {
-                    let relative_path = compute_source_relative_path(&source_directory, path)?;
+            }
+            DebouncedEvent::Create(path) => {
+                if let Some((package_idx, relative_path)) = self.source_file(path)? {
                     let file_contents = std::fs::read_to_string(path)?;
                     log::info!("Creating {}", relative_path);
-                    driver.add_file(relative_path, file_contents);
-                    if !driver.emit_diagnostics(&mut stderr())? {
-                        driver.write_all_assemblies(false)?;
-                    }
+                    self.driver
+                        .add_package_file(package_idx, relative_path, file_contents);
+                    return Ok(true);
                 }
-                Remove(ref path) if is_source_file(path) => {
-                    // Simply remove the source file from the source root
-                    let relative_path = compute_source_relative_path(&source_directory, path)?;
+            }
+            DebouncedEvent::Remove(path) => {
+                if let Some((package_idx, relative_path)) = self.source_file(path)? {
                     log::info!("Removing {}", relative_path);
                     // TODO: Remove assembly files if there are no files referencing it.
-                    // let assembly_path = driver.assembly_output_path(driver.get_file_id_for_path(&relative_path).expect("cannot remove a file that was not part of the compilation in the first place"));
-                    // if assembly_path.is_file() {
-                    //     std::fs::remove_file(assembly_path)?;
-                    // }
-                    driver.remove_file(relative_path);
-                    driver.emit_diagnostics(&mut stderr())?;
+                    self.driver.remove_package_file(package_idx, relative_path);
+                    return Ok(true);
                 }
-                Rename(ref from, ref to) => {
-                    // Renaming is done by changing the relative path of the original source file but
-                    // not modifying any text. This ensures that most of the cache for the renamed file
-                    // stays alive. This is effectively a rename of the file_id in the database.
-                    let from_relative_path = compute_source_relative_path(&source_directory, from)?;
-                    let to_relative_path = compute_source_relative_path(&source_directory, to)?;
-
-                    log::info!("Renaming {} to {}", from_relative_path, to_relative_path,);
-                    driver.rename(from_relative_path, to_relative_path);
-                    if !driver.emit_diagnostics(&mut stderr())? {
-                        driver.write_all_assemblies(false)?;
+            }
+            DebouncedEvent::Rename(from, to) => {
+                match (self.source_file(from)?, self.source_file(to)?) {
+                    (Some((from_idx, from_relative_path)), Some((to_idx, to_relative_path)))
+                        if from_idx == to_idx =>
+                    {
+                        // Renaming is done by changing the relative path of the original source
+                        // file but not modifying any text. This ensures that most of the cache for
+                        // the renamed file stays alive. This is effectively a rename of the file_id
+                        // in the database.
+                        log::info!("Renaming {} to {}", from_relative_path, to_relative_path);
+                        self.driver.rename_package_file(
+                            from_idx,
+                            from_relative_path,
+                            to_relative_path,
+                        );
+                        return Ok(true);
+                    }
+                    // A file that moves between packages is removed from one package and added to
+                    // the other
+                    (from_file, to_file) => {
+                        let has_changes = from_file.is_some() || to_file.is_some();
+                        if let Some((package_idx, relative_path)) = from_file {
+                            log::info!("Removing {}", relative_path);
+                            self.driver.remove_package_file(package_idx, relative_path);
+                        }
+                        if let Some((package_idx, relative_path)) = to_file {
+                            let file_contents = std::fs::read_to_string(to)?;
+                            log::info!("Creating {}", relative_path);
+                            self.driver
+                                .add_package_file(package_idx, relative_path, file_contents);
+                        }
+                        return Ok(has_changes);
                     }
                 }
-                _ => {}
             }
+            _ => {}
         }
+        Ok(false)
     }
+}
 
-    Ok(true)
+#[cfg(test)]
+mod tests {
+    use super::WatchedMember;
+    use mun_compiler::Config;
+    use mun_hir::fixture::Fixture;
+    use mun_project::Workspace;
+    use notify::DebouncedEvent;
+
+    #[test]
+    fn shared_dependency() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        for entry in Fixture::parse(
+            r#"
+        //- /mun.toml
+        [workspace]
+        members = ["a", "b"]
+
+        //- /a/mun.toml
+        [package]
+        name="a"
+        version="0.0.0"
+
+        [dependencies]
+        shared = { path = "../shared" }
+
+        //- /a/src/mod.mun
+        pub fn main() -> i32 { shared::value() }
+
+        //- /b/mun.toml
+        [package]
+        name="b"
+        version="0.0.0"
+
+        [dependencies]
+        shared = { path = "../shared" }
+
+        //- /b/src/mod.mun
+        pub fn main() -> i32 { shared::value() + 1 }
+
+        //- /shared/mun.toml
+        [package]
+        name="shared"
+        version="0.0.0"
+
+        //- /shared/src/mod.mun
+        pub fn value() -> i32 { 5 }
+        "#,
+        ) {
+            let path = entry.relative_path.to_path(temp_dir.path());
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, entry.text).unwrap();
+        }
+
+        let workspace = Workspace::from_manifest_path(temp_dir.path().join("mun.toml")).unwrap();
+        let config = Config {
+            out_dir: Some(temp_dir.path().join("target")),
+            ..Config::default()
+        };
+        let mut members = workspace
+            .members()
+            .iter()
+            .map(|member| WatchedMember::new(&workspace, member, &config).unwrap())
+            .collect::<Vec<_>>();
+        for member in members.iter() {
+            assert!(!member.driver.emit_diagnostics(&mut Vec::new()).unwrap());
+        }
+
+        // Changing the shared dependency affects both members
+        let path = temp_dir
+            .path()
+            .canonicalize()
+            .unwrap()
+            .join("shared/src/mod.mun");
+        std::fs::write(&path, "pub fn value() -> f32 { 5.0 }").unwrap();
+        for member in members.iter_mut() {
+            assert!(member
+                .process_event(&DebouncedEvent::Write(path.clone()))
+                .unwrap());
+            assert!(member.driver.emit_diagnostics(&mut Vec::new()).unwrap());
+        }
+
+        // A source file that is not part of the member is ignored
+        let path = path.with_file_name("mod.txt");
+        std::fs::write(&path, "").unwrap();
+        for member in members.iter_mut() {
+            assert!(!member
+                .process_event(&DebouncedEvent::Write(path.clone()))
+                .unwrap());
+        }
+
+        // A change that cannot be applied is reported as an error
+        let path = path.with_file_name("removed.mun");
+        assert!(members[0]
+            .process_event(&DebouncedEvent::Write(path))
+            .is_err());
+    }
 }
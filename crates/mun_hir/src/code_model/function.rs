@@ -11,7 +11,7 @@ use crate::{
     Ty, Visibility,
 };
 use itertools::Itertools;
-use mun_syntax::ast::TypeAscriptionOwner;
+use mun_syntax::ast::{AttrsOwner, TypeAscriptionOwner};
 use std::iter::once;
 use std::sync::Arc;
 
@@ -35,6 +35,7 @@ pub struct FunctionData {
     type_ref_map: TypeRefMap,
     type_ref_source_map: TypeRefSourceMap,
     is_extern: bool,
+    is_migration: bool,
 }
 
 impl FunctionData {
@@ -60,6 +61,10 @@ impl FunctionData {
             type_ref_builder.unit()
         };
 
+        let is_migration = src
+            .attrs()
+            .any(|attr| attr.simple_name().as_deref() == Some("migrate"));
+
         let (type_ref_map, type_ref_source_map) = type_ref_builder.finish();
 
         Arc::new(FunctionData {
@@ -69,6 +74,7 @@ impl FunctionData {
             type_ref_map,
             type_ref_source_map,
             is_extern: func.is_extern,
+            is_migration,
             visibility: item_tree[func.visibility].clone(),
         })
     }
@@ -107,10 +113,17 @@ impl Function {
 
     /// Returns the full name of the function including all module specifiers (e.g: `foo::bar`).
     pub fn full_name(self, db: &dyn HirDatabase) -> String {
-        self.module(db)
-            .path_to_root(db)
+        let module = self.module(db);
+        module
+            .package()
+            .name(db)
             .into_iter()
-            .filter_map(|module| module.name(db))
+            .chain(
+                module
+                    .path_to_root(db)
+                    .into_iter()
+                    .filter_map(|module| module.name(db)),
+            )
             .chain(once(self.name(db)))
             .map(|name| name.to_string())
             .intersperse(String::from("::"))
@@ -146,7 +159,13 @@ impl Function {
         db.fn_data(self.id).is_extern
     }
 
-    pub(crate) fn body_source_map(self, db: &dyn HirDatabase) -> Arc<BodySourceMap> {
+    /// Returns true if this function is annotated with `#[migrate]`, which means that it is used
+    /// to migrate instances of a struct during hot reloading.
+    pub fn is_migration(self, db: &dyn HirDatabase) -> bool {
+        db.fn_data(self.id).is_migration
+    }
+
+    pub fn body_source_map(self, db: &dyn HirDatabase) -> Arc<BodySourceMap> {
         db.body_with_source_map(self.id.into()).1
     }
 
@@ -1,8 +1,10 @@
 use super::{Function, Package, Struct, TypeAlias};
+use crate::diagnostics::ModuleShadowsDependency;
 use crate::ids::{ItemDefinitionId, ModuleId};
 use crate::primitive_type::PrimitiveType;
-use crate::{DiagnosticSink, FileId, HirDatabase, Name};
+use crate::{DiagnosticSink, FileId, HirDatabase, InFile, Name};
 use itertools::Itertools;
+use mun_syntax::SyntaxNodePtr;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub struct Module {
@@ -24,6 +26,13 @@ impl Module {
             .find(|m| m.file_id(db) == Some(file))
     }
 
+    /// Returns the package that contains this module.
+    pub fn package(self) -> Package {
+        Package {
+            id: self.id.package,
+        }
+    }
+
     /// Returns the parent module of this module.
     pub fn parent(self, db: &dyn HirDatabase) -> Option<Module> {
         let module_tree = db.module_tree(self.id.package);
@@ -72,6 +81,27 @@ impl Module {
         let package_defs = db.package_defs(self.id.package);
         package_defs.add_diagnostics(db.upcast(), self.id.local_id, sink);
 
+        // Modules in the root of a package cannot have the same name as a dependency, as they
+        // would shadow it
+        if let (Some(name), Some(file_id)) = (self.name(db), self.file_id(db)) {
+            let is_top_level = self
+                .parent(db)
+                .map_or(false, |parent| parent.parent(db).is_none());
+            if is_top_level
+                && self
+                    .package()
+                    .dependencies(db)
+                    .into_iter()
+                    .any(|dependency| dependency.name(db).as_ref() == Some(&name))
+            {
+                let source_file = db.parse(file_id).syntax_node();
+                sink.push(ModuleShadowsDependency {
+                    module: InFile::new(file_id, SyntaxNodePtr::new(&source_file)),
+                    name,
+                });
+            }
+        }
+
         // Add diagnostics from the item tree
         if let Some(file_id) = self.file_id(db) {
             let item_tree = db.item_tree(file_id);
@@ -119,9 +149,14 @@ impl Module {
 
     /// Returns the name of this module including all parent modules
     pub fn full_name(self, db: &dyn HirDatabase) -> String {
-        self.path_to_root(db)
-            .iter()
-            .filter_map(|&module| module.name(db))
+        self.package()
+            .name(db)
+            .into_iter()
+            .chain(
+                self.path_to_root(db)
+                    .iter()
+                    .filter_map(|&module| module.name(db)),
+            )
             .map(|name| name.to_string())
             .intersperse(String::from("::"))
             .collect()
@@ -1,5 +1,5 @@
 use super::Module;
-use crate::{HirDatabase, ModuleId, PackageId};
+use crate::{HirDatabase, ModuleId, Name, PackageId};
 
 /// A `Package` describes a single package.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -13,6 +13,21 @@ impl Package {
         db.packages().iter().map(|id| Package { id }).collect()
     }
 
+    /// Returns the name by which other packages depend on this package, or `None` if this is the
+    /// root package of the compilation
+    pub fn name(self, db: &dyn HirDatabase) -> Option<Name> {
+        db.packages()[self.id].name.as_ref().map(Name::new)
+    }
+
+    /// Returns the packages this package depends on
+    pub fn dependencies(self, db: &dyn HirDatabase) -> Vec<Package> {
+        db.packages()[self.id]
+            .dependencies
+            .iter()
+            .map(|&id| Package { id })
+            .collect()
+    }
+
     /// Returns the root module of the package (represented by the `mod.rs` in the source root)
     pub fn root_module(self, db: &dyn HirDatabase) -> Module {
         let module_tree = db.module_tree(self.id);
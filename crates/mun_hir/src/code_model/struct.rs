@@ -10,10 +10,13 @@ use crate::{
 };
 use mun_syntax::{
     ast,
-    ast::{NameOwner, TypeAscriptionOwner},
+    ast::{AttrsOwner, ExternOwner, NameOwner, TypeAscriptionOwner},
+    AstNode, SyntaxNodePtr,
 };
 use std::{fmt, sync::Arc};
 
+use crate::code_model::src::HasSource;
+use crate::diagnostics::ExternStructWithFields;
 use crate::resolve::HasResolver;
 use crate::visibility::RawVisibility;
 pub use ast::StructMemoryKind;
@@ -49,6 +52,14 @@ impl StructField {
         self.parent.data(db.upcast()).fields[self.id].name.clone()
     }
 
+    /// Returns the previous name of the field, as specified by a `#[renamed_from(name)]`
+    /// attribute.
+    pub fn renamed_from(self, db: &dyn HirDatabase) -> Option<Name> {
+        self.parent.data(db.upcast()).fields[self.id]
+            .renamed_from
+            .clone()
+    }
+
     pub fn id(self) -> LocalStructFieldId {
         self.id
     }
@@ -76,10 +87,17 @@ impl Struct {
 
     /// Returns the full name of the struct including all module specifiers (e.g: `foo::Bar`).
     pub fn full_name(self, db: &dyn HirDatabase) -> String {
-        self.module(db)
-            .path_to_root(db)
+        let module = self.module(db);
+        module
+            .package()
+            .name(db)
             .into_iter()
-            .filter_map(|module| module.name(db))
+            .chain(
+                module
+                    .path_to_root(db)
+                    .into_iter()
+                    .filter_map(|module| module.name(db)),
+            )
             .chain(once(self.name(db)))
             .map(|name| name.to_string())
             .intersperse(String::from("::"))
@@ -102,6 +120,12 @@ impl Struct {
             .map(|(id, _)| StructField { parent: self, id })
     }
 
+    /// Returns whether the struct is an opaque type that is defined by the host (e.g:
+    /// `extern struct Foo;`).
+    pub fn is_extern(self, db: &dyn HirDatabase) -> bool {
+        self.data(db.upcast()).memory_kind == StructMemoryKind::Extern
+    }
+
     pub fn ty(self, db: &dyn HirDatabase) -> Ty {
         // TODO: Add detection of cyclick types
         db.type_for_def(self.into(), Namespace::Types).0
@@ -115,6 +139,14 @@ impl Struct {
         let data = self.data(db.upcast());
         let lower = self.lower(db);
         lower.add_diagnostics(db, self.file_id(db), data.type_ref_source_map(), sink);
+
+        if self.is_extern(db) && !data.fields.is_empty() {
+            sink.push(ExternStructWithFields {
+                strukt: self
+                    .source(db.upcast())
+                    .map(|s| SyntaxNodePtr::new(s.syntax())),
+            });
+        }
     }
 }
 
@@ -134,6 +166,8 @@ impl Struct {
 pub struct StructFieldData {
     pub name: Name,
     pub type_ref: LocalTypeRefId,
+    /// The previous name of the field, used to migrate memory during hot reloading
+    pub renamed_from: Option<Name>,
 }
 
 /// A struct's fields' data (record, tuple, or unit struct)
@@ -175,10 +209,13 @@ impl StructData {
         let strukt = &item_tree[loc.id.value];
         let src = item_tree.source(db, loc.id.value);
 
-        let memory_kind = src
-            .memory_type_specifier()
-            .map(|s| s.kind())
-            .unwrap_or_default();
+        let memory_kind = if src.is_extern() {
+            StructMemoryKind::Extern
+        } else {
+            src.memory_type_specifier()
+                .map(|s| s.kind())
+                .unwrap_or_default()
+        };
 
         let mut type_ref_builder = TypeRefBuilder::default();
         let (fields, kind) = match src.kind() {
@@ -188,6 +225,7 @@ impl StructData {
                     .map(|fd| StructFieldData {
                         name: fd.name().map(|n| n.as_name()).unwrap_or_else(Name::missing),
                         type_ref: type_ref_builder.alloc_from_node_opt(fd.ascribed_type().as_ref()),
+                        renamed_from: renamed_from(&fd),
                     })
                     .collect();
                 (fields, StructKind::Record)
@@ -199,6 +237,7 @@ impl StructData {
                     .map(|(index, fd)| StructFieldData {
                         name: Name::new_tuple_field(index),
                         type_ref: type_ref_builder.alloc_from_node_opt(fd.type_ref().as_ref()),
+                        renamed_from: None,
                     })
                     .collect();
                 (fields, StructKind::Tuple)
@@ -229,6 +268,15 @@ impl StructData {
     }
 }
 
+/// Returns the name specified by the `#[renamed_from(name)]` attribute of a record field, if any.
+fn renamed_from(field: &ast::RecordFieldDef) -> Option<Name> {
+    field
+        .attrs()
+        .filter(|attr| attr.simple_name().as_deref() == Some("renamed_from"))
+        .find_map(|attr| attr.token_tree()?.single_ident())
+        .map(Name::new)
+}
+
 impl HasVisibility for Struct {
     fn visibility(&self, db: &dyn HirDatabase) -> Visibility {
         self.data(db.upcast())
@@ -607,7 +607,8 @@ pub struct ExternNonPrimitiveParam {
 
 impl Diagnostic for ExternNonPrimitiveParam {
     fn message(&self) -> String {
-        "extern functions can only have primitives as parameter- and return types".to_string()
+        "extern functions can only have primitives or extern structs as parameter- and return types"
+            .to_string()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
@@ -619,6 +620,65 @@ impl Diagnostic for ExternNonPrimitiveParam {
     }
 }
 
+#[derive(Debug)]
+pub struct ExternStructWithFields {
+    pub strukt: InFile<SyntaxNodePtr>,
+}
+
+impl Diagnostic for ExternStructWithFields {
+    fn message(&self) -> String {
+        "extern structs cannot have fields".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.strukt
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct ExternStructLit {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+}
+
+impl Diagnostic for ExternStructLit {
+    fn message(&self) -> String {
+        "extern structs cannot be constructed in Mun".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidMigrationFunction {
+    pub func: InFile<SyntaxNodePtr>,
+}
+
+impl Diagnostic for InvalidMigrationFunction {
+    fn message(&self) -> String {
+        "migration functions must have a single gc struct parameter and return a gc struct"
+            .to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.func
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
 /// An error that is emitted if a literal is too large to even parse
 #[derive(Debug)]
 pub struct IntLiteralTooLarge {
@@ -784,3 +844,26 @@ impl Diagnostic for ImportDuplicateDefinition {
         self
     }
 }
+
+#[derive(Debug)]
+pub struct ModuleShadowsDependency {
+    pub module: InFile<SyntaxNodePtr>,
+    pub name: Name,
+}
+
+impl Diagnostic for ModuleShadowsDependency {
+    fn message(&self) -> String {
+        format!(
+            "module `{}` has the same name as a dependency of its package",
+            self.name
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.module
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
@@ -12,7 +12,7 @@ use crate::type_ref::{LocalTypeRefId, TypeRef, TypeRefBuilder, TypeRefMap, TypeR
 use either::Either;
 pub use mun_syntax::ast::PrefixOp as UnaryOp;
 use mun_syntax::ast::{ArgListOwner, BinOp, LoopBodyOwner, NameOwner, TypeAscriptionOwner};
-use mun_syntax::{ast, AstNode, AstPtr, SmolStr, T};
+use mun_syntax::{ast, AstNode, AstPtr, SmolStr, TextRange, T};
 use rustc_hash::FxHashMap;
 use std::ops::Index;
 use std::sync::Arc;
@@ -173,6 +173,19 @@ impl BodySourceMap {
         self.expr_map_back.get(expr).cloned()
     }
 
+    /// Returns the range in the source file of the specified expression, if it originates from
+    /// source code.
+    pub fn expr_range(&self, expr: ExprId) -> Option<InFile<TextRange>> {
+        self.expr_syntax(expr).map(|src| {
+            src.map(|ptr| {
+                ptr.either(
+                    |ptr| ptr.syntax_node_ptr().range(),
+                    |ptr| ptr.syntax_node_ptr().range(),
+                )
+            })
+        })
+    }
+
     pub fn type_ref_syntax(&self, type_ref: LocalTypeRefId) -> Option<AstPtr<ast::TypeRef>> {
         self.type_refs.type_ref_syntax(type_ref)
     }
@@ -1,11 +1,13 @@
 use crate::code_model::src::HasSource;
 use crate::diagnostics::{
     ExternCannotHaveBody, ExternNonPrimitiveParam, FreeTypeAliasWithoutTypeRef,
+    InvalidMigrationFunction,
 };
 use crate::expr::BodySourceMap;
 use crate::in_file::InFile;
 use crate::{
-    diagnostics::DiagnosticSink, Body, Expr, Function, HirDatabase, InferenceResult, TypeAlias,
+    diagnostics::DiagnosticSink, Body, Expr, Function, HirDatabase, InferenceResult,
+    StructMemoryKind, Ty, TypeAlias,
 };
 use mun_syntax::{AstNode, SyntaxNodePtr};
 use std::sync::Arc;
@@ -40,6 +42,7 @@ impl<'a> ExprValidator<'a> {
         self.validate_literal_ranges(sink);
         self.validate_uninitialized_access(sink);
         self.validate_extern(sink);
+        self.validate_migration(sink);
     }
 
     pub fn validate_extern(&self, sink: &mut DiagnosticSink) {
@@ -61,7 +64,7 @@ impl<'a> ExprValidator<'a> {
         if let Some(sig) = self.func.ty(self.db).callable_sig(self.db) {
             let fn_data = self.func.data(self.db.upcast());
             for (arg_ty, ty_ref) in sig.params().iter().zip(fn_data.params()) {
-                if arg_ty.as_struct().is_some() {
+                if arg_ty.as_struct().map_or(false, |s| !s.is_extern(self.db)) {
                     let arg_ptr = fn_data
                         .type_ref_source_map()
                         .type_ref_syntax(*ty_ref)
@@ -74,7 +77,10 @@ impl<'a> ExprValidator<'a> {
             }
 
             let return_ty = sig.ret();
-            if return_ty.as_struct().is_some() {
+            if return_ty
+                .as_struct()
+                .map_or(false, |s| !s.is_extern(self.db))
+            {
                 let arg_ptr = fn_data
                     .type_ref_source_map()
                     .type_ref_syntax(*fn_data.ret_type())
@@ -86,6 +92,40 @@ impl<'a> ExprValidator<'a> {
             }
         }
     }
+
+    /// Validates that a migration function takes a single garbage collected struct and returns a
+    /// garbage collected struct.
+    pub fn validate_migration(&self, sink: &mut DiagnosticSink) {
+        if !self.func.is_migration(self.db) {
+            return;
+        }
+
+        let is_gc_struct = |ty: &Ty| {
+            ty.as_struct().map_or(false, |s| {
+                s.data(self.db.upcast()).memory_kind == StructMemoryKind::GC
+            })
+        };
+
+        let is_valid = !self.func.is_extern(self.db)
+            && self
+                .func
+                .ty(self.db)
+                .callable_sig(self.db)
+                .map_or(false, |sig| {
+                    sig.params().len() == 1
+                        && is_gc_struct(&sig.params()[0])
+                        && is_gc_struct(sig.ret())
+                });
+
+        if !is_valid {
+            sink.push(InvalidMigrationFunction {
+                func: self
+                    .func
+                    .source(self.db.upcast())
+                    .map(|f| SyntaxNodePtr::new(f.syntax())),
+            });
+        }
+    }
 }
 
 pub struct TypeAliasValidator<'a> {
@@ -78,6 +78,25 @@ fn test_free_type_alias_without_type_ref() {
     )
 }
 
+#[test]
+fn test_invalid_migration_function() {
+    diagnostics_snapshot(
+        r#"
+    struct(gc) Foo { a: f32 }
+    struct(value) Bar { a: f32 }
+
+    #[migrate]
+    fn valid(old: Foo) -> Foo { old }
+
+    #[migrate]
+    fn invalid_param(old: Bar) -> Foo { Foo { a: old.a } }
+
+    #[migrate]
+    fn invalid_arity() -> Foo { Foo { a: 0.0 } }
+    "#,
+    )
+}
+
 fn diagnostics(content: &str) -> String {
     let (db, _file_id) = MockDatabase::with_single_file(content);
 
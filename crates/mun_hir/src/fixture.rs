@@ -14,6 +14,14 @@ pub struct Fixture {
 
     /// The text of the file
     pub text: String,
+
+    /// The name of the package that starts at this file, if specified with `package:name`. All
+    /// succeeding files belong to the same package, until another package is specified.
+    pub package: Option<String>,
+
+    /// The names of the packages that the package of this file depends on, if specified with
+    /// `deps:name,other_name`
+    pub dependencies: Vec<String>,
 }
 
 impl Fixture {
@@ -72,7 +80,7 @@ impl Fixture {
 
     /// Parses a fixture meta line like:
     /// ```
-    /// //- /main.mun
+    /// //- /main.mun package:game deps:math
     /// ```
     fn parse_meta_line(line: impl AsRef<str>) -> Fixture {
         let line = line.as_ref();
@@ -85,9 +93,23 @@ impl Fixture {
         assert!(path.starts_with('/'));
         let relative_path = RelativePathBuf::from(&path[1..]);
 
+        let mut package = None;
+        let mut dependencies = Vec::new();
+        for component in components[1..].iter() {
+            if let Some(name) = component.strip_prefix("package:") {
+                package = Some(name.to_owned());
+            } else if let Some(names) = component.strip_prefix("deps:") {
+                dependencies = names.split(',').map(ToOwned::to_owned).collect();
+            } else {
+                panic!("invalid meta component: {:?}", component);
+            }
+        }
+
         Fixture {
             relative_path,
             text: String::new(),
+            package,
+            dependencies,
         }
     }
 }
@@ -163,7 +185,9 @@ mod test {
             Fixture::parse(""),
             vec![Fixture {
                 relative_path: RelativePathBuf::from(DEFAULT_FILE_NAME),
-                text: "".to_owned()
+                text: "".to_owned(),
+                package: None,
+                dependencies: Vec::new(),
             }]
         );
     }
@@ -174,7 +198,9 @@ mod test {
             Fixture::parse(format!("{} /foo.mun\nfn hello_world() {{}}", META_LINE)),
             vec![Fixture {
                 relative_path: RelativePathBuf::from("foo.mun"),
-                text: "fn hello_world() {}\n".to_owned()
+                text: "fn hello_world() {}\n".to_owned(),
+                package: None,
+                dependencies: Vec::new(),
             }]
         );
     }
@@ -196,11 +222,44 @@ mod test {
             vec![
                 Fixture {
                     relative_path: RelativePathBuf::from("foo.mun"),
-                    text: "fn hello_world() {\n}\n\n".to_owned()
+                    text: "fn hello_world() {\n}\n\n".to_owned(),
+                    package: None,
+                    dependencies: Vec::new(),
                 },
                 Fixture {
                     relative_path: RelativePathBuf::from("bar.mun"),
-                    text: "fn baz() {\n}\n".to_owned()
+                    text: "fn baz() {\n}\n".to_owned(),
+                    package: None,
+                    dependencies: Vec::new(),
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn package_fixtures() {
+        assert_eq!(
+            Fixture::parse(
+                r#"
+                //- /mod.mun deps:math
+                fn main() {}
+
+                //- /mod.mun package:math
+                pub fn add() {}
+            "#
+            ),
+            vec![
+                Fixture {
+                    relative_path: RelativePathBuf::from("mod.mun"),
+                    text: "fn main() {}\n\n".to_owned(),
+                    package: None,
+                    dependencies: vec![String::from("math")],
+                },
+                Fixture {
+                    relative_path: RelativePathBuf::from("mod.mun"),
+                    text: "pub fn add() {}\n".to_owned(),
+                    package: Some(String::from("math")),
+                    dependencies: Vec::new(),
                 }
             ]
         );
@@ -16,8 +16,8 @@ pub use crate::{
     diagnostics::{Diagnostic, DiagnosticSink},
     display::HirDisplay,
     expr::{
-        ArithOp, BinaryOp, Body, CmpOp, Expr, ExprId, ExprScopes, Literal, LogicOp, Ordering, Pat,
-        PatId, RecordLitField, Statement, UnaryOp,
+        ArithOp, BinaryOp, Body, BodySourceMap, CmpOp, Expr, ExprId, ExprScopes, Literal, LogicOp,
+        Ordering, Pat, PatId, RecordLitField, Statement, UnaryOp,
     },
     ids::{ItemLoc, ModuleId},
     in_file::InFile,
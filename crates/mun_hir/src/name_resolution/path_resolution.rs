@@ -65,13 +65,42 @@ impl PackageDefs {
     /// Resolves the specified `name` from within the specified `module`
     fn resolve_name_in_module(
         &self,
-        _db: &dyn DefDatabase,
+        db: &dyn DefDatabase,
         module: LocalModuleId,
         name: &Name,
     ) -> PerNs<(ItemDefinitionId, Visibility)> {
         self[module]
             .get(name)
             .or(BUILTIN_SCOPE.get(name).copied().unwrap_or_else(PerNs::none))
+            .or(self.resolve_name_in_dependencies(db, name))
+    }
+
+    /// Resolves the specified `name` as the root module of one of the package's dependencies
+    fn resolve_name_in_dependencies(
+        &self,
+        db: &dyn DefDatabase,
+        name: &Name,
+    ) -> PerNs<(ItemDefinitionId, Visibility)> {
+        let packages = db.packages();
+        let dependency = packages[self.module_tree.package]
+            .dependencies
+            .iter()
+            .copied()
+            .find(|&dependency| {
+                packages[dependency].name.as_ref().map(Name::new).as_ref() == Some(name)
+            });
+
+        match dependency {
+            Some(package) => PerNs::types((
+                ModuleId {
+                    package,
+                    local_id: db.module_tree(package).root,
+                }
+                .into(),
+                Visibility::Public,
+            )),
+            None => PerNs::none(),
+        }
     }
 
     /// Resolves the specified `path` from within the specified `module`. Also returns whether or
@@ -118,6 +147,7 @@ impl PackageDefs {
             )),
         };
 
+        let mut package = self.module_tree.package;
         for (i, segment) in segments {
             let (curr, vis) = match curr_per_ns.take_types() {
                 Some(r) => r,
@@ -127,24 +157,34 @@ impl PackageDefs {
             };
 
             curr_per_ns = match curr {
-                ItemDefinitionId::ModuleId(module) => self[module.local_id].get(&segment),
+                ItemDefinitionId::ModuleId(module) if module.package == package => {
+                    self[module.local_id].get(&segment)
+                }
+                ItemDefinitionId::ModuleId(module) => {
+                    // Only the public items of other packages can be accessed
+                    package = module.package;
+                    db.package_defs(module.package)[module.local_id]
+                        .get(&segment)
+                        .and_then(|(item, vis)| {
+                            if vis.is_externally_visible() {
+                                Some((item, vis))
+                            } else {
+                                None
+                            }
+                        })
+                }
                 // TODO: Enum variants
                 s => {
                     return ResolvePathResult::with(
                         PerNs::types((s, vis)),
                         ReachedFixedPoint::Yes,
                         Some(i),
-                        Some(self.module_tree.package),
+                        Some(package),
                     );
                 }
             };
         }
 
-        ResolvePathResult::with(
-            curr_per_ns,
-            ReachedFixedPoint::Yes,
-            None,
-            Some(self.module_tree.package),
-        )
+        ResolvePathResult::with(curr_per_ns, ReachedFixedPoint::Yes, None, Some(package))
     }
 }
@@ -280,6 +280,33 @@ impl<'db> DefCollector<'db> {
 
         if import.is_glob {
             match resolution.take_types() {
+                Some((ItemDefinitionId::ModuleId(m), _)) if m.package != self.package_id => {
+                    // The items of another package are all resolved, so all its public items can
+                    // be imported at once
+                    let package_defs = self.db.package_defs(m.package);
+                    let resolutions = package_defs[m.local_id]
+                        .entries()
+                        .map(|(n, res)| ImportResolution {
+                            name: Some(n.clone()),
+                            resolution: res.and_then(|(item, vis)| {
+                                if vis.is_externally_visible() {
+                                    Some((item, vis))
+                                } else {
+                                    None
+                                }
+                            }),
+                        })
+                        .filter(|res| !res.resolution.is_none())
+                        .collect::<Vec<_>>();
+
+                    self.update(
+                        import_module_id,
+                        import_visibility,
+                        ImportType::Glob,
+                        import.source,
+                        &resolutions,
+                    );
+                }
                 Some((ItemDefinitionId::ModuleId(m), _)) => {
                     let scope = &self.package_defs[m.local_id];
 
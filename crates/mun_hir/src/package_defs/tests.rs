@@ -145,6 +145,42 @@ fn use_() {
     )
 }
 
+#[test]
+fn use_dependency() {
+    resolve_snapshot(
+        r#"
+    //- /mod.mun deps:math
+    use math::ops::Vec2;
+    use math::Hidden; // Only public items of other packages are accessible
+
+    //- /foo.mun
+    use math::ops::*;
+
+    //- /mod.mun package:math
+    pub(package) struct Hidden;
+
+    //- /ops.mun
+    pub struct Vec2;
+    "#,
+    )
+}
+
+#[test]
+fn module_shadows_dependency() {
+    resolve_snapshot(
+        r#"
+    //- /mod.mun deps:math
+    pub fn main() {}
+
+    //- /math.mun
+    pub fn add() {}
+
+    //- /mod.mun package:math
+    pub fn add() {}
+    "#,
+    )
+}
+
 fn resolve_snapshot(text: &str) {
     let text = text.trim().replace("\n    ", "\n");
     let resolved = resolve(&text);
@@ -156,9 +192,13 @@ fn resolve(content: &str) -> String {
 
     Package::all(&db)
         .iter()
+        .sorted_by_key(|package| package.id)
         .map(|package| {
             let package_defs = db.package_defs(package.id);
-            tree_for_module(&db, &package_defs, package.root_module(&db)).to_string()
+            tree_for_module(&db, &package_defs, package.root_module(&db))
+                .to_string()
+                .trim_end()
+                .to_owned()
         })
         .intersperse("\n".to_owned())
         .collect()
@@ -272,13 +312,15 @@ fn tree_for_module(
     node
 }
 
-/// Returns a fully qualified path of a module e.g. `package::foo::bar::baz`
+/// Returns a fully qualified path of a module e.g. `package::foo::bar::baz`, or `math::foo` if the
+/// module is part of the dependency `math`
 fn fully_qualified_module_path(db: &dyn HirDatabase, module: Module) -> String {
     module
         .path_to_root(db)
         .into_iter()
         .map(|m| {
             m.name(db)
+                .or_else(|| m.package().name(db))
                 .map(|name| name.to_string())
                 .unwrap_or_else(|| "package".to_owned())
         })
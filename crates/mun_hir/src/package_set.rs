@@ -7,6 +7,13 @@ use std::ops::Index;
 pub struct PackageData {
     /// The source root that holds the source files
     pub source_root: SourceRootId,
+
+    /// The name by which other packages depend on this package. The items of a named package are
+    /// qualified with its name, e.g. `math::Vec2`. The root package of a compilation is unnamed.
+    pub name: Option<String>,
+
+    /// The packages this package depends on
+    pub dependencies: Vec<PackageId>,
 }
 
 /// Represents the id of a single package, all packages have a unique id, the main package and all
@@ -23,16 +30,46 @@ pub struct PackageSet {
 impl PackageSet {
     /// Adds a new package to the package set
     pub fn add_package(&mut self, source_root: SourceRootId) -> PackageId {
-        let data = PackageData { source_root };
-        let package_id = PackageId(self.arena.len() as u32);
-        self.arena.insert(package_id, data);
-        package_id
+        self.alloc_package(source_root, None)
+    }
+
+    /// Adds a new package to the package set that other packages can depend on by `name`
+    pub fn add_named_package(
+        &mut self,
+        source_root: SourceRootId,
+        name: impl Into<String>,
+    ) -> PackageId {
+        self.alloc_package(source_root, Some(name.into()))
+    }
+
+    /// Adds a dependency from the package `from` on the named package `to`
+    pub fn add_dependency(&mut self, from: PackageId, to: PackageId) {
+        assert!(
+            self.arena[&to].name.is_some(),
+            "a package can only depend on a named package"
+        );
+        self.arena
+            .get_mut(&from)
+            .expect("unknown package")
+            .dependencies
+            .push(to);
     }
 
     /// Iterates over all packages
     pub fn iter(&self) -> impl Iterator<Item = PackageId> + '_ {
         self.arena.keys().copied()
     }
+
+    fn alloc_package(&mut self, source_root: SourceRootId, name: Option<String>) -> PackageId {
+        let data = PackageData {
+            source_root,
+            name,
+            dependencies: Vec::new(),
+        };
+        let package_id = PackageId(self.arena.len() as u32);
+        self.arena.insert(package_id, data);
+        package_id
+    }
 }
 
 impl Index<PackageId> for PackageSet {
@@ -151,26 +151,28 @@ impl Ty {
             TypeCtor::Struct(s) => {
                 let name = s.name(db).to_string();
 
-                Some(if s.data(db.upcast()).memory_kind == StructMemoryKind::GC {
-                    format!("struct {}", name)
-                } else {
-                    let fields: Vec<String> = s
-                        .fields(db)
-                        .into_iter()
-                        .map(|f| {
-                            let ty_string = f
-                                .ty(db)
-                                .guid_string(db)
-                                .expect("type should be convertible to a string");
-                            format!("{}: {}", f.name(db).to_string(), ty_string)
-                        })
-                        .collect();
-
-                    format!(
-                        "struct {name}{{{fields}}}",
-                        name = name,
-                        fields = fields.join(",")
-                    )
+                Some(match s.data(db.upcast()).memory_kind {
+                    StructMemoryKind::GC => format!("struct {}", name),
+                    StructMemoryKind::Extern => format!("extern struct {}", name),
+                    StructMemoryKind::Value => {
+                        let fields: Vec<String> = s
+                            .fields(db)
+                            .into_iter()
+                            .map(|f| {
+                                let ty_string = f
+                                    .ty(db)
+                                    .guid_string(db)
+                                    .expect("type should be convertible to a string");
+                                format!("{}: {}", f.name(db).to_string(), ty_string)
+                            })
+                            .collect();
+
+                        format!(
+                            "struct {name}{{{fields}}}",
+                            name = name,
+                            fields = fields.join(",")
+                        )
+                    }
                 })
             }
             TypeCtor::Bool => Some("core::bool".to_string()),
@@ -403,7 +403,12 @@ impl<'a> InferenceResultBuilder<'a> {
                     self.infer_expr(*expr, &Expectation::has_type(ty.clone()));
                 }
                 if let Some(s) = ty.as_struct() {
-                    self.check_record_lit(tgt_expr, &ty, s, &fields);
+                    if s.is_extern(self.db) {
+                        self.diagnostics
+                            .push(InferenceDiagnostic::ExternStructLit { id: tgt_expr });
+                    } else {
+                        self.check_record_lit(tgt_expr, &ty, s, &fields);
+                    }
                 }
                 ty
             }
@@ -707,7 +712,10 @@ impl<'a> InferenceResultBuilder<'a> {
                         Some(ty)
                     }
                     ValueNs::StructId(s) => {
-                        if check_params.is_unit_struct {
+                        if Struct::from(s).is_extern(self.db) {
+                            self.diagnostics
+                                .push(diagnostics::InferenceDiagnostic::ExternStructLit { id });
+                        } else if check_params.is_unit_struct {
                             self.check_unit_struct_lit(id, s.into())
                         }
                         let (ty, _) = self
@@ -1016,9 +1024,10 @@ mod diagnostics {
         code_model::{src::HasSource, StructKind},
         diagnostics::{
             AccessUnknownField, BreakOutsideLoop, BreakWithValueOutsideLoop, CannotApplyBinaryOp,
-            CannotApplyUnaryOp, ExpectedFunction, FieldCountMismatch, IncompatibleBranch,
-            InvalidLHS, LiteralOutOfRange, MismatchedStructLit, MismatchedType, MissingElseBranch,
-            MissingFields, NoFields, NoSuchField, ParameterCountMismatch, ReturnMissingExpression,
+            CannotApplyUnaryOp, ExpectedFunction, ExternStructLit, FieldCountMismatch,
+            IncompatibleBranch, InvalidLHS, LiteralOutOfRange, MismatchedStructLit, MismatchedType,
+            MissingElseBranch, MissingFields, NoFields, NoSuchField, ParameterCountMismatch,
+            ReturnMissingExpression,
         },
         diagnostics::{CyclicType, DiagnosticSink, UnresolvedType, UnresolvedValue},
         ty::infer::ExprOrPatId,
@@ -1120,6 +1129,9 @@ mod diagnostics {
         PathIsPrivate {
             id: ExprId,
         },
+        ExternStructLit {
+            id: ExprId,
+        },
     }
 
     impl InferenceDiagnostic {
@@ -1161,6 +1173,19 @@ mod diagnostics {
                         expr: type_ref.syntax_node_ptr(),
                     });
                 }
+                InferenceDiagnostic::ExternStructLit { id } => {
+                    let expr_syntax = body
+                        .expr_syntax(*id)
+                        .map(|ptr| {
+                            ptr.value
+                                .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr())
+                        })
+                        .expect("could not resolve expression to syntax node");
+                    sink.push(ExternStructLit {
+                        file,
+                        expr: expr_syntax,
+                    });
+                }
                 InferenceDiagnostic::PathIsPrivate { id } => {
                     let expr_syntax = body
                         .expr_syntax(*id)
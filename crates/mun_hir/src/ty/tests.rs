@@ -557,6 +557,25 @@ fn extern_fn() {
     )
 }
 
+#[test]
+fn extern_struct() {
+    infer_snapshot(
+        r#"
+    extern struct Foo;
+    extern struct Bar { a: i32 }    // extern structs cannot have fields
+    extern fn new_foo() -> Foo;
+    extern fn use_foo(foo: Foo);
+
+    fn main() {
+        let foo = new_foo();
+        use_foo(foo);
+        let bar = Foo;  // extern structs cannot be constructed in Mun
+        let baz = Bar { a: 1 };  // extern structs cannot be constructed in Mun
+    }
+    "#,
+    )
+}
+
 #[test]
 fn infer_type_alias() {
     infer_snapshot(
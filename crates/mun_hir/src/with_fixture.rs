@@ -22,27 +22,54 @@ pub trait WithFixture: Default + SourceDatabase + 'static {
     }
 }
 
-/// Fills the specified database with all the files from the specified `fixture`
+/// Fills the specified database with all the files from the specified `fixture`. Files belong to
+/// the unnamed root package, unless a named package is specified in the fixture.
 fn with_files(db: &mut dyn SourceDatabase, fixture: &str) -> Vec<FileId> {
     let fixture = Fixture::parse(fixture);
 
-    let mut source_root = SourceRoot::default();
-    let source_root_id = SourceRootId(0);
+    // The source root, name, and dependencies of each package
+    let mut packages: Vec<(SourceRoot, Option<String>, Vec<String>)> = Vec::new();
     let mut files = Vec::new();
 
     for (idx, entry) in fixture.into_iter().enumerate() {
+        if packages.is_empty() || entry.package.is_some() {
+            packages.push((SourceRoot::default(), entry.package, Vec::new()));
+        }
+        let source_root_id = SourceRootId((packages.len() - 1) as u32);
+        let (source_root, _, dependencies) = packages.last_mut().unwrap();
+
         let file_id = FileId(idx.try_into().expect("too many files"));
         db.set_file_text(file_id, Arc::from(entry.text));
         db.set_file_source_root(file_id, source_root_id);
         source_root.insert_file(file_id, entry.relative_path);
+        dependencies.extend(entry.dependencies);
         files.push(file_id);
     }
 
-    db.set_source_root(source_root_id, Arc::new(source_root));
+    let mut package_set = PackageSet::default();
+    let package_ids = packages
+        .iter()
+        .enumerate()
+        .map(|(idx, (source_root, name, _))| {
+            let source_root_id = SourceRootId(idx as u32);
+            db.set_source_root(source_root_id, Arc::new(source_root.clone()));
+            match name {
+                Some(name) => package_set.add_named_package(source_root_id, name.clone()),
+                None => package_set.add_package(source_root_id),
+            }
+        })
+        .collect::<Vec<_>>();
 
-    let mut packages = PackageSet::default();
-    packages.add_package(source_root_id);
-    db.set_packages(Arc::new(packages));
+    for ((_, _, dependencies), &package_id) in packages.iter().zip(package_ids.iter()) {
+        for dependency in dependencies {
+            let dependency_idx = packages
+                .iter()
+                .position(|(_, name, _)| name.as_ref() == Some(dependency))
+                .unwrap_or_else(|| panic!("unknown package `{}`", dependency));
+            package_set.add_dependency(package_id, package_ids[dependency_idx]);
+        }
+    }
+    db.set_packages(Arc::new(package_set));
 
     files
 }
@@ -9,22 +9,62 @@ use std::{
 impl LanguageServerState {
     /// Called to update all workspaces from the files
     pub(crate) fn fetch_workspaces(&mut self) {
-        // Load all the manifests as packages
-        let packages = self
-            .config
-            .discovered_projects
-            .clone()
-            .into_iter()
-            .flatten()
-            .filter_map(|project| match project::Package::from_file(&project.path) {
-                Ok(package) => Some(package),
+        // Load all the manifests as workspaces and resolve the dependencies of their member
+        // packages. A package can be discovered both by itself and as a member of a workspace.
+        let discovered_projects = self.config.discovered_projects.clone().unwrap_or_default();
+        let mut package_graphs: Vec<project::PackageGraph> = Vec::new();
+        for project in discovered_projects {
+            let members = match project::Workspace::from_manifest_path(&project.path) {
+                Ok(workspace) => workspace.members().to_vec(),
                 Err(err) => {
                     self.show_message(
                         lsp_types::MessageType::Error,
                         format!("mun failed to load package: {:#}", err),
                     );
-                    None
+                    continue;
                 }
+            };
+
+            for member in members {
+                if package_graphs
+                    .iter()
+                    .any(|graph| graph.root().manifest_path() == member.manifest_path())
+                {
+                    continue;
+                }
+
+                match project::PackageGraph::from_manifest_path(member.manifest_path()) {
+                    Ok(package_graph) => package_graphs.push(package_graph),
+                    Err(err) => {
+                        self.show_message(
+                            lsp_types::MessageType::Error,
+                            format!("mun failed to load package: {:#}", err),
+                        );
+                    }
+                }
+            }
+        }
+
+        // Collect the packages of all graphs. A package can be a dependency of multiple packages,
+        // so for every graph the indices of its packages in `packages` are stored.
+        let mut packages: Vec<project::Package> = Vec::new();
+        let graph_package_indices = package_graphs
+            .iter()
+            .map(|package_graph| {
+                package_graph
+                    .packages()
+                    .iter()
+                    .map(|package| {
+                        let package = canonicalize(package);
+                        match packages.iter().position(|p| *p == package) {
+                            Some(idx) => idx,
+                            None => {
+                                packages.push(package);
+                                packages.len() - 1
+                            }
+                        }
+                    })
+                    .collect::<Vec<_>>()
             })
             .collect::<Vec<_>>();
 
@@ -87,10 +127,36 @@ impl LanguageServerState {
 
         self.vfs_monitor.set_config(monitor_config);
 
-        // Create the set of packages
+        // Create the set of packages. Packages that are depended upon are named, such that their
+        // items can be referred to.
+        let mut dependencies: Vec<(usize, usize)> = Vec::new();
+        for (package_graph, indices) in package_graphs.iter().zip(graph_package_indices.iter()) {
+            for (graph_idx, &idx) in indices.iter().enumerate() {
+                for &dependency in package_graph.dependencies(graph_idx) {
+                    let dependency = (idx, indices[dependency]);
+                    if !dependencies.contains(&dependency) {
+                        dependencies.push(dependency);
+                    }
+                }
+            }
+        }
+
         let mut package_set = hir::PackageSet::default();
-        for (idx, _package) in packages.iter().enumerate() {
-            package_set.add_package(hir::SourceRootId(idx as u32));
+        let package_ids = packages
+            .iter()
+            .enumerate()
+            .map(|(idx, package)| {
+                let source_root = hir::SourceRootId(idx as u32);
+                if dependencies.iter().any(|&(_, to)| to == idx) {
+                    // Dependencies are referred to by their name, in which dashes are not allowed
+                    package_set.add_named_package(source_root, package.name().replace('-', "_"))
+                } else {
+                    package_set.add_package(source_root)
+                }
+            })
+            .collect::<Vec<_>>();
+        for (from, to) in dependencies {
+            package_set.add_dependency(package_ids[from], package_ids[to]);
         }
         change.set_packages(package_set);
 
@@ -139,3 +205,12 @@ impl LanguageServerState {
         source_roots
     }
 }
+
+/// Returns the `package` with a canonical manifest path, such that a package that is referred to by
+/// different paths - e.g. as a workspace member and as a dependency - is only loaded once.
+fn canonicalize(package: &project::Package) -> project::Package {
+    match package.manifest_path().canonicalize() {
+        Ok(manifest_path) => project::Package::new(package.manifest().clone(), &manifest_path),
+        Err(_) => package.clone(),
+    }
+}
@@ -8,8 +8,16 @@ pub struct MunLibrary(TempLibrary);
 
 impl MunLibrary {
     pub fn new(library_path: &Path) -> Result<Self, anyhow::Error> {
-        let library = TempLibrary::new(library_path)?;
+        Self::from_temp_library(TempLibrary::new(library_path)?)
+    }
+
+    /// Loads a Mun library from the contents of a `*.munlib` file.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, anyhow::Error> {
+        Self::from_temp_library(TempLibrary::from_bytes(bytes)?)
+    }
 
+    /// Verifies that the loaded `library` is a Mun library.
+    fn from_temp_library(library: TempLibrary) -> Result<Self, anyhow::Error> {
         // Verify that the `*.munlib` contains all required functions
         let _get_abi_version_fn: libloading::Symbol<'_, extern "C" fn() -> u32> =
             unsafe { library.library().get(abi::GET_VERSION_FN_NAME.as_bytes()) }?;
@@ -62,4 +70,31 @@ impl MunLibrary {
 
         set_allocator_handle_fn(allocator_ptr);
     }
+
+    /// Sets the profiler handle of the library, if the library was compiled with function
+    /// instrumentation. Returns whether or not the library is instrumented.
+    pub fn set_profiler_handle(&mut self, profiler_ptr: *mut c_void) -> bool {
+        self.set_instrumentation_handle(abi::SET_PROFILER_HANDLE_FN_NAME, profiler_ptr)
+    }
+
+    /// Sets the coverage handle of the library, if the library was compiled with coverage
+    /// instrumentation. Returns whether or not the library is instrumented.
+    pub fn set_coverage_handle(&mut self, coverage_ptr: *mut c_void) -> bool {
+        self.set_instrumentation_handle(abi::SET_COVERAGE_HANDLE_FN_NAME, coverage_ptr)
+    }
+
+    fn set_instrumentation_handle(&mut self, fn_name: &str, handle: *mut c_void) -> bool {
+        let set_handle_fn = unsafe {
+            self.0
+                .library()
+                .get::<extern "C" fn(*mut c_void)>(fn_name.as_bytes())
+        };
+
+        if let Ok(set_handle_fn) = set_handle_fn {
+            set_handle_fn(handle);
+            true
+        } else {
+            false
+        }
+    }
 }
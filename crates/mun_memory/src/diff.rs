@@ -180,12 +180,12 @@ fn append_struct_mapping<T>(
         .iter()
         .flat_map(|new_idx| {
             let new_ty = unsafe { new.get_unchecked(*new_idx) };
-            let new_fields = new_ty.fields();
             deletions
                 .iter()
                 .map(|old_idx| {
                     let old_ty = unsafe { old.get_unchecked(*old_idx) };
                     let old_fields = old_ty.fields();
+                    let new_fields = renamed_fields(&old_fields, new_ty);
                     let length = myers::diff_length(&old_fields, &new_fields);
 
                     // Given N old fields and M new fields, the smallest set capable of
@@ -242,7 +242,8 @@ fn append_struct_mapping<T>(
             // ASSUMPTION: Don't use recursion, because all types are individually checked for
             // differences.
             // TODO: Support value struct vs heap struct?
-            let diff = field_diff(&old_ty.fields(), &new_ty.fields());
+            let old_fields = old_ty.fields();
+            let diff = field_diff(&old_fields, &renamed_fields(&old_fields, new_ty));
 
             // Edit the struct, potentially moving it in the process.
             Diff::Edit {
@@ -285,6 +286,42 @@ fn append_struct_mapping<T>(
     }
 }
 
+/// Returns the fields of `new_ty`, where every field that was renamed from a field in
+/// `old_fields` carries its previous name. This allows renamed fields to be matched with their old
+/// counterparts.
+fn renamed_fields<'t, T>(old_fields: &[(&str, T)], new_ty: &'t T) -> Vec<(&'t str, T)>
+where
+    T: TypeFields<T>,
+{
+    let new_fields = new_ty.fields();
+    let renamed_from = new_ty.fields_renamed_from();
+
+    let is_old_field = |name: &str| old_fields.iter().any(|(old_name, _)| *old_name == name);
+    let is_new_field = |name: &str| new_fields.iter().any(|(new_name, _)| *new_name == name);
+
+    let aliases: Vec<Option<&'t str>> = new_fields
+        .iter()
+        .enumerate()
+        .map(|(idx, (name, _))| {
+            renamed_from
+                .get(idx)
+                .copied()
+                .flatten()
+                // Only use the previous name if the field did not exist before, and its previous
+                // name is not used by another field.
+                .filter(|old_name| {
+                    !is_old_field(name) && is_old_field(old_name) && !is_new_field(old_name)
+                })
+        })
+        .collect();
+
+    new_fields
+        .into_iter()
+        .zip(aliases)
+        .map(|((name, ty), alias)| (alias.unwrap_or(name), ty))
+        .collect()
+}
+
 /// Given an `old` and a `new` set of fields, calculates the difference.
 fn field_diff<T>(old: &[(&str, T)], new: &[(&str, T)]) -> Vec<FieldDiff>
 where
@@ -21,6 +21,10 @@ pub trait TypeTrace: Send + Sync {
 
     /// Returns an iterator to iterate over all GC objects that are referenced by the given object.
     fn trace(&self, obj: GcPtr) -> Self::Trace;
+
+    /// Finalizes the given object, right before its memory is deallocated by a collection. By
+    /// default, this does nothing.
+    fn finalize(&self, _obj: GcPtr) {}
 }
 
 /// An object that can be used to allocate and collect memory.
@@ -188,6 +188,7 @@ where
                 }
                 true
             } else {
+                obj.ty.finalize(*h);
                 unsafe { std::alloc::dealloc(obj.ptr, obj.ty.layout()) };
                 self.observer.event(Event::Deallocation(*h));
                 {
@@ -205,6 +206,27 @@ where
     }
 }
 
+impl<T, O> MarkSweep<T, O>
+where
+    T: TypeMemory + TypeTrace + Clone + Eq,
+    O: Observer<Event = Event>,
+{
+    /// Returns the handles of all objects of type `ty`.
+    pub fn objects_of_type(&self, ty: &T) -> Vec<GcPtr> {
+        self.objects
+            .read()
+            .iter()
+            .filter_map(|(handle, object_info)| {
+                if object_info.ty == *ty {
+                    Some(*handle)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
 impl<T, O> MemoryMapper<T> for MarkSweep<T, O>
 where
     T: TypeDesc + TypeMemory + TypeTrace + Clone + Eq + Hash,
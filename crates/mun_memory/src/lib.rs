@@ -49,4 +49,9 @@ pub trait TypeFields<T>: Send + Sync {
     fn fields(&self) -> Vec<(&str, T)>;
     /// Returns the type's fields' offsets.
     fn offsets(&self) -> &[u16];
+    /// Returns the names of the type's fields prior to being renamed. Fields that were not renamed
+    /// are `None`.
+    fn fields_renamed_from(&self) -> Vec<Option<&str>> {
+        self.fields().iter().map(|_| None).collect()
+    }
 }
@@ -508,3 +508,96 @@ fn rename_field2() {
     );
     assert_eq_struct(&apply_diff(old, new, diff), &vec![struct2.clone()]);
 }
+
+#[test]
+fn renamed_from_field() {
+    let int = TypeInfo::new_fundamental::<i64>();
+    let float = TypeInfo::new_fundamental::<f64>();
+
+    let struct1 = TypeInfo::new_struct(
+        STRUCT1_NAME,
+        STRUCT1_GUID,
+        StructInfo::new(&[("a", &int), ("pos", &float)]),
+    );
+    let struct2 = TypeInfo::new_struct(
+        STRUCT1_NAME,
+        STRUCT2_GUID,
+        StructInfo::new(&[("a", &int), ("position", &float)]).with_renamed_from("position", "pos"),
+    );
+
+    let old = &[&struct1];
+    let new = &[&struct2];
+
+    let diff = diff(old, new);
+    assert_eq!(
+        diff,
+        vec![Diff::Move {
+            old_index: 0,
+            new_index: 0,
+        }]
+    );
+}
+
+#[test]
+fn renamed_from_cast_field() {
+    let int = TypeInfo::new_fundamental::<i64>();
+    let float = TypeInfo::new_fundamental::<f64>();
+
+    let struct1 = TypeInfo::new_struct(
+        STRUCT1_NAME,
+        STRUCT1_GUID,
+        StructInfo::new(&[("pos", &float), ("a", &int)]),
+    );
+    let struct2 = TypeInfo::new_struct(
+        STRUCT1_NAME,
+        STRUCT2_GUID,
+        StructInfo::new(&[("a", &int), ("position", &int)]).with_renamed_from("position", "pos"),
+    );
+
+    let old = &[&struct1];
+    let new = &[&struct2];
+
+    let diff = diff(old, new);
+    assert_eq!(
+        diff,
+        vec![Diff::Edit {
+            diff: vec![FieldDiff::Move {
+                old_index: 0,
+                new_index: 1,
+                edit: Some(FieldEditKind::ConvertType),
+            }],
+            old_index: 0,
+            new_index: 0,
+        }]
+    );
+}
+
+#[test]
+fn renamed_from_existing_field() {
+    let float = TypeInfo::new_fundamental::<f64>();
+
+    let struct1 = TypeInfo::new_struct(
+        STRUCT1_NAME,
+        STRUCT1_GUID,
+        StructInfo::new(&[("pos", &float), ("position", &float)]),
+    );
+    let struct2 = TypeInfo::new_struct(
+        STRUCT1_NAME,
+        STRUCT2_GUID,
+        StructInfo::new(&[("position", &float)]).with_renamed_from("position", "pos"),
+    );
+
+    let old = &[&struct1];
+    let new = &[&struct2];
+
+    // The rename hint is ignored, because `position` already existed
+    let diff = diff(old, new);
+    assert_eq!(
+        diff,
+        vec![Diff::Edit {
+            diff: vec![FieldDiff::Delete { index: 0 }],
+            old_index: 0,
+            new_index: 0,
+        }]
+    );
+}
@@ -17,6 +17,8 @@ pub const STRUCT2_GUID: abi::Guid = abi::Guid([
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct StructInfo {
     fields: Vec<(String, TypeInfo)>,
+    /// Pairs of field names and their previous names
+    renamed_from: Vec<(String, String)>,
 }
 
 impl StructInfo {
@@ -26,9 +28,17 @@ impl StructInfo {
                 .iter()
                 .map(|(name, ty)| (name.to_string(), (*ty).clone()))
                 .collect(),
+            renamed_from: Vec::new(),
         }
     }
 
+    /// Marks the field with the specified `name` as renamed from `renamed_from`.
+    pub fn with_renamed_from(mut self, name: &str, renamed_from: &str) -> Self {
+        self.renamed_from
+            .push((name.to_string(), renamed_from.to_string()));
+        self
+    }
+
     pub fn layout(&self) -> Layout {
         // NOTE: This implementation is naive, but it is merely a test
         let size = self.fields.iter().map(|ty| ty.1.layout.size()).sum();
@@ -129,6 +139,22 @@ impl<'t> TypeFields<&'t TypeInfo> for &'t TypeInfo {
         // This is a stub, as we don't do any actual memory mapping
         &[]
     }
+
+    fn fields_renamed_from(&self) -> Vec<Option<&str>> {
+        match &self.data {
+            TypeInfoData::Primitive => Vec::new(),
+            TypeInfoData::Struct(s) => s
+                .fields
+                .iter()
+                .map(|(name, _)| {
+                    s.renamed_from
+                        .iter()
+                        .find(|(field_name, _)| field_name == name)
+                        .map(|(_, renamed_from)| renamed_from.as_str())
+                })
+                .collect(),
+        }
+    }
 }
 
 pub fn apply_myers_diff<'t, T: Copy + Eq>(old: &[T], new: &[T], diff: Vec<myers::Diff>) -> Vec<T> {
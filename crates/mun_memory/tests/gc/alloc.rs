@@ -1,6 +1,9 @@
 use super::util::{EventAggregator, HasTypeInfo, TypeInfo};
-use mun_memory::gc::{Event, GcRootPtr, GcRuntime, MarkSweep};
-use std::sync::Arc;
+use mun_memory::gc::{Event, GcPtr, GcRootPtr, GcRuntime, MarkSweep};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 
 #[test]
 fn alloc() {
@@ -63,3 +66,30 @@ fn collect_rooted() {
     assert_eq!(events.next(), Some(Event::End));
     assert_eq!(events.next(), None);
 }
+
+#[test]
+fn collect_finalize() {
+    static FINALIZED: AtomicUsize = AtomicUsize::new(0);
+    static FINALIZED_TYPE: TypeInfo = TypeInfo {
+        size: std::mem::size_of::<i64>(),
+        alignment: std::mem::align_of::<i64>(),
+        tracer: None,
+        finalizer: Some(&(finalize as fn(handle: GcPtr))),
+    };
+
+    fn finalize(_handle: GcPtr) {
+        FINALIZED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    let runtime = Arc::new(MarkSweep::<&'static TypeInfo, EventAggregator<Event>>::default());
+    runtime.alloc(&FINALIZED_TYPE);
+    let rooted = GcRootPtr::new(&runtime, runtime.alloc(&FINALIZED_TYPE));
+
+    // Only the unreachable object is finalized
+    runtime.collect();
+    assert_eq!(FINALIZED.load(Ordering::SeqCst), 1);
+
+    rooted.unroot();
+    runtime.collect();
+    assert_eq!(FINALIZED.load(Ordering::SeqCst), 2);
+}
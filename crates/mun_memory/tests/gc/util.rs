@@ -8,6 +8,7 @@ pub struct TypeInfo {
     pub size: usize,
     pub alignment: usize,
     pub tracer: Option<&'static fn(handle: GcPtr) -> Vec<GcPtr>>,
+    pub finalizer: Option<&'static fn(handle: GcPtr)>,
 }
 
 pub trait Trace {
@@ -29,7 +30,8 @@ macro_rules! impl_primitive_types {
                 static [<TYPE_ $ty>]: TypeInfo = TypeInfo {
                     size: std::mem::size_of::<$ty>(),
                     alignment: std::mem::align_of::<$ty>(),
-                    tracer: None
+                    tracer: None,
+                    finalizer: None,
                 };
 
                 impl HasTypeInfo for $ty {
@@ -58,7 +60,8 @@ macro_rules! impl_struct_ty {
             static [<TYPE_ $ty>]: TypeInfo = TypeInfo {
                 size: std::mem::size_of::<$ty>(),
                 alignment: std::mem::align_of::<$ty>(),
-                tracer: Some(&([<trace_ $ty>] as fn(handle: GcPtr) -> Vec<GcPtr>))
+                tracer: Some(&([<trace_ $ty>] as fn(handle: GcPtr) -> Vec<GcPtr>)),
+                finalizer: None,
             };
 
             impl HasTypeInfo for $ty {
@@ -95,6 +98,12 @@ impl gc::TypeTrace for &'static TypeInfo {
         };
         handles.into_iter()
     }
+
+    fn finalize(&self, obj: GcPtr) {
+        if let Some(finalizer) = self.finalizer {
+            finalizer(obj)
+        }
+    }
 }
 
 pub struct EventAggregator<T: Sync + Send + Sized> {
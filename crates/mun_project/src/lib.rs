@@ -1,10 +1,16 @@
-pub use manifest::{Manifest, ManifestMetadata, PackageId};
+pub use manifest::{Dependency, Manifest, ManifestMetadata, PackageId, WorkspaceConfig};
 pub use package::Package;
+pub use package_graph::PackageGraph;
+pub use profile::{Profile, ProfileKind};
 pub use project_manifest::ProjectManifest;
+pub use workspace::Workspace;
 
 mod manifest;
 mod package;
+mod package_graph;
+mod profile;
 mod project_manifest;
+mod workspace;
 
 pub const MANIFEST_FILENAME: &str = "mun.toml";
 pub const LOCKFILE_NAME: &str = ".munlock";
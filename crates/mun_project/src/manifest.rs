@@ -1,5 +1,6 @@
+use crate::profile::{Profile, ProfileKind, Profiles};
 use std::fmt;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 mod toml;
@@ -9,6 +10,20 @@ mod toml;
 pub struct Manifest {
     package_id: PackageId,
     metadata: ManifestMetadata,
+    dependencies: Vec<Dependency>,
+    workspace: Option<WorkspaceConfig>,
+    profiles: Profiles,
+}
+
+/// The contents of a manifest file: either the manifest of a package, or a virtual manifest that
+/// only describes a workspace.
+#[derive(PartialEq, Clone, Debug)]
+pub(crate) enum EitherManifest {
+    Real(Manifest),
+    Virtual {
+        workspace: WorkspaceConfig,
+        profiles: Profiles,
+    },
 }
 
 /// General metadata for a package.
@@ -17,6 +32,21 @@ pub struct ManifestMetadata {
     pub authors: Vec<String>,
 }
 
+/// A dependency on another package, as specified in the `dependencies` section of a manifest.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Dependency {
+    name: String,
+    path: PathBuf,
+}
+
+/// The `workspace` section of a manifest, which groups multiple packages that are built together.
+#[derive(PartialEq, Clone, Debug)]
+pub struct WorkspaceConfig {
+    members: Vec<String>,
+    exclude: Vec<String>,
+    target_dir: Option<PathBuf>,
+}
+
 /// Unique identifier of a package and version
 #[derive(PartialEq, Clone, Debug)]
 pub struct PackageId {
@@ -52,6 +82,65 @@ impl Manifest {
     pub fn metadata(&self) -> &ManifestMetadata {
         &self.metadata
     }
+
+    /// Returns the dependencies of the package, ordered by name
+    pub fn dependencies(&self) -> &[Dependency] {
+        &self.dependencies
+    }
+
+    /// Returns the workspace configuration, if the package is the root of a workspace
+    pub fn workspace(&self) -> Option<&WorkspaceConfig> {
+        self.workspace.as_ref()
+    }
+
+    /// Returns the build settings of the specified profile
+    pub fn profile(&self, kind: ProfileKind) -> &Profile {
+        self.profiles.get(kind)
+    }
+
+    /// Returns the build settings of all profiles
+    pub(crate) fn profiles(&self) -> &Profiles {
+        &self.profiles
+    }
+}
+
+impl EitherManifest {
+    /// Try to read a manifest from a file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<EitherManifest, anyhow::Error> {
+        let file_contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("could not read manifest file: {}", e))?;
+        Self::from_str(&file_contents)
+    }
+}
+
+impl Dependency {
+    /// Returns the name of the package that is depended upon
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the path of the directory that contains the package, relative to the manifest
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl WorkspaceConfig {
+    /// Returns the glob patterns of the member packages' directories, relative to the manifest
+    pub fn members(&self) -> &[String] {
+        &self.members
+    }
+
+    /// Returns the paths of directories that are excluded from the members, relative to the
+    /// manifest
+    pub fn exclude(&self) -> &[String] {
+        &self.exclude
+    }
+
+    /// Returns the directory that all members write their output to, relative to the manifest
+    pub fn target_dir(&self) -> Option<&Path> {
+        self.target_dir.as_deref()
+    }
 }
 
 impl PackageId {
@@ -83,9 +172,21 @@ impl std::str::FromStr for Manifest {
     }
 }
 
+impl std::str::FromStr for EitherManifest {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let manifest = ::toml::from_str::<toml::TomlManifest>(s)
+            .map_err(|e| anyhow::anyhow!("could not parse manifest: {}", e))?;
+        manifest.into_either_manifest()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::Manifest;
+    use super::EitherManifest;
+    use crate::{Manifest, ProfileKind};
+    use std::path::Path;
     use std::str::FromStr;
 
     #[test]
@@ -107,5 +208,214 @@ mod tests {
         );
         assert_eq!(manifest.metadata().authors, vec!["Mun Team"]);
         assert_eq!(format!("{}", manifest.package_id()), "test v0.2.0");
+        assert!(manifest.dependencies().is_empty());
+        assert!(manifest.workspace().is_none());
+    }
+
+    #[test]
+    fn parse_dependencies() {
+        let manifest = Manifest::from_str(
+            r#"
+        [package]
+        name="test"
+        version="0.2.0"
+
+        [dependencies]
+        math = { path = "../math" }
+        core_utils = { path = "libs/core_utils" }
+        "#,
+        )
+        .unwrap();
+
+        let dependencies = manifest
+            .dependencies()
+            .iter()
+            .map(|dependency| (dependency.name(), dependency.path()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            dependencies,
+            vec![
+                ("core_utils", Path::new("libs/core_utils")),
+                ("math", Path::new("../math"))
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_version_dependency() {
+        let error = Manifest::from_str(
+            r#"
+        [package]
+        name="test"
+        version="0.2.0"
+
+        [dependencies]
+        math = "0.1"
+        "#,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "only path dependencies are supported, but `math` does not specify a path"
+        );
+    }
+
+    #[test]
+    fn parse_invalid_dependency_name() {
+        let parse = |name: &str| {
+            Manifest::from_str(&format!(
+                r#"
+        [package]
+        name="test"
+        version="0.2.0"
+
+        [dependencies]
+        "{}" = {{ path = "../dep" }}
+        "#,
+                name
+            ))
+        };
+
+        assert!(parse("core-utils").is_ok());
+        assert_eq!(
+            parse("2d").unwrap_err().to_string(),
+            "invalid dependency name `2d`: the name must consist of ASCII letters, digits, `-` or `_`, and cannot start with a digit"
+        );
+        assert_eq!(
+            parse("math.utils").unwrap_err().to_string(),
+            "invalid dependency name `math.utils`: the name must consist of ASCII letters, digits, `-` or `_`, and cannot start with a digit"
+        );
+        assert_eq!(
+            parse("struct").unwrap_err().to_string(),
+            "invalid dependency name `struct`: `struct` is a Mun keyword"
+        );
+    }
+
+    #[test]
+    fn parse_workspace() {
+        let manifest = Manifest::from_str(
+            r#"
+        [package]
+        name="test"
+        version="0.2.0"
+
+        [workspace]
+        members = ["scripts/*"]
+        exclude = ["scripts/old"]
+        target-dir = "build"
+        "#,
+        )
+        .unwrap();
+
+        let workspace = manifest.workspace().unwrap();
+        assert_eq!(workspace.members(), &["scripts/*"]);
+        assert_eq!(workspace.exclude(), &["scripts/old"]);
+        assert_eq!(workspace.target_dir(), Some(Path::new("build")));
+    }
+
+    #[test]
+    fn parse_virtual_manifest() {
+        let manifest = r#"
+        [workspace]
+        members = ["scripts/*"]
+        "#;
+
+        match EitherManifest::from_str(manifest).unwrap() {
+            EitherManifest::Virtual { workspace, .. } => {
+                assert_eq!(workspace.members(), &["scripts/*"]);
+                assert!(workspace.exclude().is_empty());
+                assert_eq!(workspace.target_dir(), None);
+            }
+            EitherManifest::Real(_) => panic!("expected a virtual manifest"),
+        }
+
+        assert_eq!(
+            Manifest::from_str(manifest).unwrap_err().to_string(),
+            "manifest does not contain a `package` section"
+        );
+    }
+
+    #[test]
+    fn parse_profiles() {
+        let manifest = Manifest::from_str(
+            r#"
+        [package]
+        name="test"
+        version="0.2.0"
+
+        [profile.dev]
+        opt-level = 0
+        overflow-checks = false
+
+        [profile.release]
+        debug = false
+        out-dir = "dist"
+        "#,
+        )
+        .unwrap();
+
+        let dev = manifest.profile(ProfileKind::Dev);
+        assert_eq!(dev.opt_level(), 0);
+        assert_eq!(dev.out_dir(), None);
+
+        let release = manifest.profile(ProfileKind::Release);
+        assert_eq!(release.opt_level(), 3);
+        assert_eq!(release.out_dir(), Some(Path::new("dist")));
+    }
+
+    #[test]
+    fn parse_unsupported_profile_settings() {
+        let error = Manifest::from_str(
+            r#"
+        [package]
+        name="test"
+        version="0.2.0"
+
+        [profile.release]
+        debug = true
+        "#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "profile `release` enables `debug`, but debug information is not supported yet"
+        );
+
+        let error = Manifest::from_str(
+            r#"
+        [package]
+        name="test"
+        version="0.2.0"
+
+        [profile.dev]
+        overflow-checks = true
+        "#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "profile `dev` enables `overflow-checks`, but overflow checks are not supported yet"
+        );
+    }
+
+    #[test]
+    fn parse_invalid_opt_level() {
+        let error = Manifest::from_str(
+            r#"
+        [package]
+        name="test"
+        version="0.2.0"
+
+        [profile.release]
+        opt-level = 4
+        "#,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "opt-level of profile `release` must be between 0 and 3, but is 4"
+        );
     }
 }
@@ -1,11 +1,24 @@
-use super::{Manifest, ManifestMetadata, PackageId};
+use super::{Dependency, EitherManifest, Manifest, ManifestMetadata, PackageId, WorkspaceConfig};
+use crate::profile::{Profile, ProfileKind, Profiles};
 use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// The keywords of the Mun language, which cannot be used as the name of a dependency.
+const KEYWORDS: &[&str] = &[
+    "break", "do", "else", "false", "for", "fn", "if", "in", "as", "use", "nil", "return", "true",
+    "while", "loop", "let", "mut", "class", "struct", "never", "pub", "type", "package", "super",
+    "self", "extern",
+];
 
 /// A manifest as specified in a mun.toml file.
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct TomlManifest {
-    package: TomlProject,
+    package: Option<TomlProject>,
+    dependencies: Option<BTreeMap<String, TomlDependency>>,
+    workspace: Option<TomlWorkspace>,
+    profile: Option<TomlProfiles>,
 }
 
 /// Represents the `package` section of a mun.toml file.
@@ -16,22 +29,206 @@ pub struct TomlProject {
     authors: Option<Vec<String>>,
 }
 
+/// Represents the `workspace` section of a mun.toml file.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct TomlWorkspace {
+    members: Vec<String>,
+    exclude: Option<Vec<String>>,
+    target_dir: Option<PathBuf>,
+}
+
+/// Represents the `profile` sections of a mun.toml file.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TomlProfiles {
+    dev: Option<TomlProfile>,
+    release: Option<TomlProfile>,
+}
+
+/// Represents a single `profile` section of a mun.toml file, e.g. `profile.release`.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct TomlProfile {
+    opt_level: Option<u8>,
+    debug: Option<bool>,
+    overflow_checks: Option<bool>,
+    out_dir: Option<PathBuf>,
+}
+
+/// Represents a dependency in the `dependencies` section of a mun.toml file.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum TomlDependency {
+    /// A version requirement, e.g. `foo = "0.1"`
+    Simple(String),
+    /// A table, e.g. `foo = { path = "../foo" }`
+    Detailed(DetailedTomlDependency),
+}
+
+/// Represents a dependency that is specified as a table.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct DetailedTomlDependency {
+    path: Option<PathBuf>,
+}
+
 impl TomlManifest {
+    /// Convert this toml manifest into either a "real" manifest or, if it does not contain a
+    /// `package` section, a virtual manifest.
+    pub fn into_either_manifest(self) -> Result<EitherManifest, anyhow::Error> {
+        if self.package.is_some() {
+            return self.into_real_manifest().map(EitherManifest::Real);
+        }
+        if self.dependencies.is_some() {
+            anyhow::bail!("a virtual manifest cannot specify dependencies");
+        }
+        match self.workspace {
+            Some(workspace) => Ok(EitherManifest::Virtual {
+                workspace: workspace.into_workspace_config(),
+                profiles: TomlProfiles::into_profiles(self.profile)?,
+            }),
+            None => {
+                anyhow::bail!("manifest contains neither a `package` nor a `workspace` section")
+            }
+        }
+    }
+
     /// Convert this toml manifest into a "real" manifest.
     pub fn into_real_manifest(self) -> Result<Manifest, anyhow::Error> {
-        let name = self.package.name.trim();
+        let package = match self.package {
+            Some(package) => package,
+            None => anyhow::bail!("manifest does not contain a `package` section"),
+        };
+
+        let name = package.name.trim();
         if name.is_empty() {
             anyhow::bail!("package name cannot be an empty string");
         }
 
+        let dependencies = self
+            .dependencies
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, dependency)| {
+                validate_dependency_name(&name)?;
+                let path = match dependency {
+                    TomlDependency::Detailed(DetailedTomlDependency { path: Some(path) }) => path,
+                    _ => anyhow::bail!(
+                        "only path dependencies are supported, but `{}` does not specify a path",
+                        name
+                    ),
+                };
+                Ok(Dependency { name, path })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
         Ok(Manifest {
             package_id: PackageId {
                 name: name.to_owned(),
-                version: self.package.version,
+                version: package.version,
             },
             metadata: ManifestMetadata {
-                authors: self.package.authors.unwrap_or_default(),
+                authors: package.authors.unwrap_or_default(),
             },
+            dependencies,
+            workspace: self.workspace.map(TomlWorkspace::into_workspace_config),
+            profiles: TomlProfiles::into_profiles(self.profile)?,
         })
     }
 }
+
+/// Ensures that a dependency can be referred to from Mun code by its `name`, in which dashes are
+/// replaced by underscores.
+fn validate_dependency_name(name: &str) -> Result<(), anyhow::Error> {
+    let identifier = name.replace('-', "_");
+    let mut chars = identifier.chars();
+    let is_identifier = match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    };
+    if !is_identifier || identifier == "_" {
+        anyhow::bail!(
+            "invalid dependency name `{}`: the name must consist of ASCII letters, digits, `-` or `_`, and cannot start with a digit",
+            name
+        );
+    }
+    if KEYWORDS.contains(&identifier.as_str()) {
+        anyhow::bail!(
+            "invalid dependency name `{}`: `{}` is a Mun keyword",
+            name,
+            identifier
+        );
+    }
+    Ok(())
+}
+
+impl TomlProfiles {
+    /// Convert the toml profile sections - if any - into "real" profiles, using the defaults for
+    /// all settings that are not specified.
+    fn into_profiles(profiles: Option<TomlProfiles>) -> Result<Profiles, anyhow::Error> {
+        let (dev, release) = match profiles {
+            Some(profiles) => (profiles.dev, profiles.release),
+            None => (None, None),
+        };
+
+        Ok(Profiles {
+            dev: TomlProfile::into_profile(dev, ProfileKind::Dev)?,
+            release: TomlProfile::into_profile(release, ProfileKind::Release)?,
+        })
+    }
+}
+
+impl TomlProfile {
+    /// Convert this toml profile section - if any - into the "real" profile of the specified kind.
+    fn into_profile(
+        profile: Option<TomlProfile>,
+        kind: ProfileKind,
+    ) -> Result<Profile, anyhow::Error> {
+        let default = Profile::default_for(kind);
+        let profile = match profile {
+            Some(profile) => profile,
+            None => return Ok(default),
+        };
+
+        let opt_level = profile.opt_level.unwrap_or(default.opt_level);
+        if opt_level > 3 {
+            anyhow::bail!(
+                "opt-level of profile `{}` must be between 0 and 3, but is {}",
+                kind,
+                opt_level
+            );
+        }
+
+        // Debug information and overflow checks are not yet supported by the compiler
+        if profile.debug == Some(true) {
+            anyhow::bail!(
+                "profile `{}` enables `debug`, but debug information is not supported yet",
+                kind
+            );
+        }
+        if profile.overflow_checks == Some(true) {
+            anyhow::bail!(
+                "profile `{}` enables `overflow-checks`, but overflow checks are not supported yet",
+                kind
+            );
+        }
+
+        Ok(Profile {
+            opt_level,
+            out_dir: profile.out_dir.or(default.out_dir),
+        })
+    }
+}
+
+impl TomlWorkspace {
+    /// Convert this toml workspace section into its "real" configuration.
+    fn into_workspace_config(self) -> WorkspaceConfig {
+        WorkspaceConfig {
+            members: self.members,
+            exclude: self.exclude.unwrap_or_default(),
+            target_dir: self.target_dir,
+        }
+    }
+}
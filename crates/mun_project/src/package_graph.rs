@@ -0,0 +1,206 @@
+use crate::{Package, MANIFEST_FILENAME};
+use anyhow::Context;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// A package together with all the packages it transitively depends on, resolved from the path
+/// dependencies in their manifests.
+#[derive(Clone, Debug)]
+pub struct PackageGraph {
+    // All packages, ordered such that every package succeeds its dependencies. The root package is
+    // the last package.
+    packages: Vec<Package>,
+    // For every package, the indices of its direct dependencies in `packages`
+    dependencies: Vec<Vec<usize>>,
+}
+
+impl PackageGraph {
+    /// Loads the package at `manifest_path` and resolves all of its dependencies.
+    pub fn from_manifest_path<P: AsRef<Path>>(manifest_path: P) -> anyhow::Result<Self> {
+        let mut resolver = Resolver::default();
+        resolver.resolve(manifest_path.as_ref(), None)?;
+
+        Ok(PackageGraph {
+            packages: resolver.packages,
+            dependencies: resolver.dependencies,
+        })
+    }
+
+    /// Returns the root package; i.e. the package from which the dependencies were resolved
+    pub fn root(&self) -> &Package {
+        self.packages
+            .last()
+            .expect("a package graph contains a root package")
+    }
+
+    /// Returns the index of the root package
+    pub fn root_index(&self) -> usize {
+        self.packages.len() - 1
+    }
+
+    /// Returns all packages, ordered such that every package succeeds its dependencies
+    pub fn packages(&self) -> &[Package] {
+        &self.packages
+    }
+
+    /// Returns the indices of the direct dependencies of the package at `index`
+    pub fn dependencies(&self, index: usize) -> &[usize] {
+        &self.dependencies[index]
+    }
+}
+
+/// Resolves the packages of a [`PackageGraph`] in post-order.
+#[derive(Default)]
+struct Resolver {
+    packages: Vec<Package>,
+    dependencies: Vec<Vec<usize>>,
+    // The indices of the resolved packages, by their canonical manifest path
+    resolved: HashMap<PathBuf, usize>,
+    // The canonical manifest paths of the packages that are being resolved
+    stack: Vec<PathBuf>,
+}
+
+impl Resolver {
+    /// Resolves the package at `manifest_path` and its dependencies, returning the index of the
+    /// package. If the package is a dependency, `expected_name` is the name by which it is depended
+    /// upon.
+    fn resolve(
+        &mut self,
+        manifest_path: &Path,
+        expected_name: Option<&str>,
+    ) -> anyhow::Result<usize> {
+        let canonical_path = manifest_path
+            .canonicalize()
+            .with_context(|| format!("could not find manifest '{}'", manifest_path.display()))?;
+
+        if let Some(&index) = self.resolved.get(&canonical_path) {
+            return Ok(index);
+        }
+        if self.stack.contains(&canonical_path) {
+            anyhow::bail!(
+                "cyclic dependency on the package at '{}'",
+                manifest_path.display()
+            );
+        }
+
+        let package = Package::from_file(manifest_path)?;
+        if let Some(expected_name) = expected_name {
+            if package.name() != expected_name {
+                anyhow::bail!(
+                    "dependency `{}` refers to package `{}` at '{}'",
+                    expected_name,
+                    package.name(),
+                    manifest_path.display()
+                );
+            }
+        }
+
+        self.stack.push(canonical_path.clone());
+        let dependencies = package
+            .manifest()
+            .dependencies()
+            .iter()
+            .map(|dependency| {
+                let manifest_path = package
+                    .root()
+                    .join(dependency.path())
+                    .join(MANIFEST_FILENAME);
+                self.resolve(&manifest_path, Some(dependency.name()))
+                    .with_context(|| {
+                        format!(
+                            "could not resolve dependency `{}` of `{}`",
+                            dependency.name(),
+                            package
+                        )
+                    })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        self.stack.pop();
+
+        // Items of dependencies are qualified with the name of their package, so it has to be
+        // unique
+        if let Some(other) = self
+            .packages
+            .iter()
+            .find(|other| other.name() == package.name())
+        {
+            anyhow::bail!(
+                "multiple packages are named `{}`: '{}' and '{}'",
+                package.name(),
+                other.manifest_path().display(),
+                package.manifest_path().display()
+            );
+        }
+
+        let index = self.packages.len();
+        self.packages.push(package);
+        self.dependencies.push(dependencies);
+        self.resolved.insert(canonical_path, index);
+        Ok(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PackageGraph;
+    use std::path::Path;
+
+    fn write_package(root: &Path, name: &str, dependencies: &[(&str, &str)]) {
+        let dependencies = dependencies
+            .iter()
+            .map(|(name, path)| format!("{} = {{ path = \"{}\" }}\n", name, path))
+            .collect::<String>();
+
+        std::fs::create_dir_all(root.join(name)).unwrap();
+        std::fs::write(
+            root.join(name).join("mun.toml"),
+            format!(
+                "[package]\nname = \"{}\"\nversion = \"0.1.0\"\n\n[dependencies]\n{}",
+                name, dependencies
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn resolve() {
+        let root = tempfile::tempdir().unwrap();
+        write_package(root.path(), "game", &[("math", "../math"), ("ai", "../ai")]);
+        write_package(root.path(), "ai", &[("math", "../math")]);
+        write_package(root.path(), "math", &[]);
+
+        let graph = PackageGraph::from_manifest_path(root.path().join("game/mun.toml")).unwrap();
+        let names = graph
+            .packages()
+            .iter()
+            .map(|package| package.name())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["math", "ai", "game"]);
+        assert_eq!(graph.root().name(), "game");
+        assert_eq!(graph.dependencies(0), &[] as &[usize]);
+        assert_eq!(graph.dependencies(1), &[0]);
+        assert_eq!(graph.dependencies(graph.root_index()), &[1, 0]);
+    }
+
+    #[test]
+    fn resolve_cycle() {
+        let root = tempfile::tempdir().unwrap();
+        write_package(root.path(), "game", &[("math", "../math")]);
+        write_package(root.path(), "math", &[("game", "../game")]);
+
+        let error =
+            PackageGraph::from_manifest_path(root.path().join("game/mun.toml")).unwrap_err();
+        assert!(format!("{:#}", error).contains("cyclic dependency"));
+    }
+
+    #[test]
+    fn resolve_mismatched_name() {
+        let root = tempfile::tempdir().unwrap();
+        write_package(root.path(), "game", &[("maths", "../math")]);
+        write_package(root.path(), "math", &[]);
+
+        let error =
+            PackageGraph::from_manifest_path(root.path().join("game/mun.toml")).unwrap_err();
+        assert!(format!("{:#}", error).contains("dependency `maths` refers to package `math`"));
+    }
+}
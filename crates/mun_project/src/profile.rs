@@ -0,0 +1,87 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Identifies one of the profiles with which packages can be built.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProfileKind {
+    /// The profile used during development, specified by the `profile.dev` section of a manifest
+    Dev,
+    /// The profile used for distribution, specified by the `profile.release` section of a manifest
+    Release,
+}
+
+/// The settings with which packages are built, as specified in a `profile` section of a manifest.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Profile {
+    pub(crate) opt_level: u8,
+    pub(crate) out_dir: Option<PathBuf>,
+}
+
+/// The profiles specified by a manifest, or their defaults if a manifest does not specify them.
+#[derive(PartialEq, Clone, Debug)]
+pub(crate) struct Profiles {
+    pub dev: Profile,
+    pub release: Profile,
+}
+
+impl ProfileKind {
+    /// Returns the name of the profile, as used in manifests
+    pub fn name(self) -> &'static str {
+        match self {
+            ProfileKind::Dev => "dev",
+            ProfileKind::Release => "release",
+        }
+    }
+}
+
+impl fmt::Display for ProfileKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl Profile {
+    /// Returns the default settings of the specified profile
+    pub fn default_for(kind: ProfileKind) -> Self {
+        match kind {
+            ProfileKind::Dev => Profile {
+                opt_level: 2,
+                out_dir: None,
+            },
+            ProfileKind::Release => Profile {
+                opt_level: 3,
+                out_dir: None,
+            },
+        }
+    }
+
+    /// Returns the optimization level, ranging from 0 (no optimizations) to 3
+    pub fn opt_level(&self) -> u8 {
+        self.opt_level
+    }
+
+    /// Returns the directory to write all output to - in place of the target directory - relative
+    /// to the manifest
+    pub fn out_dir(&self) -> Option<&Path> {
+        self.out_dir.as_deref()
+    }
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Profiles {
+            dev: Profile::default_for(ProfileKind::Dev),
+            release: Profile::default_for(ProfileKind::Release),
+        }
+    }
+}
+
+impl Profiles {
+    /// Returns the profile of the specified kind
+    pub fn get(&self, kind: ProfileKind) -> &Profile {
+        match kind {
+            ProfileKind::Dev => &self.dev,
+            ProfileKind::Release => &self.release,
+        }
+    }
+}
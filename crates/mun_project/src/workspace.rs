@@ -0,0 +1,297 @@
+use crate::{
+    manifest::EitherManifest,
+    profile::{Profile, ProfileKind, Profiles},
+    Package, WorkspaceConfig, MANIFEST_FILENAME,
+};
+use anyhow::Context;
+use std::path::{Path, PathBuf};
+
+/// A set of packages that are built together and share a target directory. A workspace is either
+/// described by the `workspace` section of a manifest, or consists of a single package.
+#[derive(Clone, Debug)]
+pub struct Workspace {
+    // The location of the manifest which marks the root of the workspace
+    manifest_path: PathBuf,
+    // The member packages, ordered by their location
+    members: Vec<Package>,
+    // The directory that all members write their output to
+    target_dir: PathBuf,
+    // Whether the members share the target directory, i.e. whether the manifest contains a
+    // `workspace` section
+    is_shared: bool,
+    // The profiles of the root manifest, which apply to all members
+    profiles: Profiles,
+}
+
+impl Workspace {
+    /// Loads the workspace - or single package - described by the manifest at `manifest_path`,
+    /// together with all of its members.
+    pub fn from_manifest_path<P: AsRef<Path>>(manifest_path: P) -> anyhow::Result<Self> {
+        let manifest_path = manifest_path.as_ref();
+        let root = manifest_path.parent().unwrap();
+
+        let (root_package, config, profiles) = match EitherManifest::from_file(manifest_path)? {
+            EitherManifest::Real(manifest) => {
+                let config = manifest.workspace().cloned();
+                let profiles = manifest.profiles().clone();
+                (
+                    Some(Package::new(manifest, manifest_path)),
+                    config,
+                    profiles,
+                )
+            }
+            EitherManifest::Virtual {
+                workspace,
+                profiles,
+            } => (None, Some(workspace), profiles),
+        };
+
+        let config = match config {
+            Some(config) => config,
+            None => {
+                return Ok(Workspace {
+                    manifest_path: manifest_path.to_path_buf(),
+                    members: root_package.into_iter().collect(),
+                    target_dir: root.join("target"),
+                    is_shared: false,
+                    profiles,
+                })
+            }
+        };
+
+        let mut members = root_package.into_iter().collect::<Vec<_>>();
+        for member_root in find_members(root, &config)? {
+            let member_manifest_path = member_root.join(MANIFEST_FILENAME);
+            if members
+                .iter()
+                .any(|member| member.manifest_path() == member_manifest_path)
+            {
+                continue;
+            }
+
+            let member = Package::from_file(&member_manifest_path).with_context(|| {
+                format!(
+                    "could not load workspace member '{}'",
+                    member_root.display()
+                )
+            })?;
+
+            // Members write their output to a directory named after them, so names have to be
+            // unique
+            if let Some(other) = members.iter().find(|other| other.name() == member.name()) {
+                anyhow::bail!(
+                    "multiple workspace members are named `{}`: '{}' and '{}'",
+                    member.name(),
+                    other.manifest_path().display(),
+                    member.manifest_path().display()
+                );
+            }
+            members.push(member);
+        }
+
+        Ok(Workspace {
+            manifest_path: manifest_path.to_path_buf(),
+            members,
+            target_dir: root.join(config.target_dir().unwrap_or_else(|| Path::new("target"))),
+            is_shared: true,
+            profiles,
+        })
+    }
+
+    /// Returns the path of the manifest that describes the workspace
+    pub fn manifest_path(&self) -> &Path {
+        &self.manifest_path
+    }
+
+    /// Returns the root folder of the workspace
+    pub fn root(&self) -> &Path {
+        self.manifest_path().parent().unwrap()
+    }
+
+    /// Returns all member packages, ordered by their location
+    pub fn members(&self) -> &[Package] {
+        &self.members
+    }
+
+    /// Returns the directory that all members write their output to
+    pub fn target_dir(&self) -> &Path {
+        &self.target_dir
+    }
+
+    /// Returns the build settings of the specified profile. The profiles of the workspace's
+    /// manifest apply to all members; those of the members themselves are ignored.
+    pub fn profile(&self, kind: ProfileKind) -> &Profile {
+        self.profiles.get(kind)
+    }
+
+    /// Returns the directory that all members write their output to when built with the specified
+    /// profile. Unless the profile specifies an output directory, the output of release builds is
+    /// stored in the `release` directory of the target directory.
+    pub fn profile_target_dir(&self, kind: ProfileKind) -> PathBuf {
+        match (self.profile(kind).out_dir(), kind) {
+            (Some(out_dir), _) => self.root().join(out_dir),
+            (None, ProfileKind::Dev) => self.target_dir.clone(),
+            (None, ProfileKind::Release) => self.target_dir.join("release"),
+        }
+    }
+
+    /// Returns the directory that `member` writes its output to. The members of a workspace each
+    /// write to a directory named after them, in the shared target directory.
+    pub fn output_dir(&self, member: &Package) -> PathBuf {
+        if self.is_shared {
+            self.target_dir.join(member.name())
+        } else {
+            self.target_dir.clone()
+        }
+    }
+}
+
+/// Finds the root directories of all members specified by `config`, ordered by their location.
+fn find_members(root: &Path, config: &WorkspaceConfig) -> anyhow::Result<Vec<PathBuf>> {
+    // Glob characters in the root directory itself should be matched literally
+    let escaped_root = PathBuf::from(glob::Pattern::escape(&root.to_string_lossy()));
+    let exclude = config
+        .exclude()
+        .iter()
+        .map(|path| root.join(path))
+        .collect::<Vec<_>>();
+
+    let mut members = Vec::new();
+    for pattern in config.members() {
+        let paths = glob::glob(&escaped_root.join(pattern).to_string_lossy())
+            .with_context(|| format!("invalid workspace member pattern `{}`", pattern))?;
+        for path in paths {
+            let path = path.with_context(|| {
+                format!("could not expand workspace member pattern `{}`", pattern)
+            })?;
+            if path.is_dir() && !exclude.iter().any(|exclude| path.starts_with(exclude)) {
+                members.push(path);
+            }
+        }
+    }
+
+    members.sort();
+    members.dedup();
+    Ok(members)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Workspace;
+    use crate::ProfileKind;
+    use std::path::Path;
+
+    fn write_package(root: &Path, name: &str) {
+        std::fs::create_dir_all(root.join(name)).unwrap();
+        std::fs::write(
+            root.join(name).join("mun.toml"),
+            format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\n", name),
+        )
+        .unwrap();
+    }
+
+    fn member_names(workspace: &Workspace) -> Vec<&str> {
+        workspace
+            .members()
+            .iter()
+            .map(|member| member.name())
+            .collect()
+    }
+
+    #[test]
+    fn single_package() {
+        let root = tempfile::tempdir().unwrap();
+        write_package(root.path(), "game");
+
+        let workspace = Workspace::from_manifest_path(root.path().join("game/mun.toml")).unwrap();
+        assert_eq!(member_names(&workspace), vec!["game"]);
+        assert_eq!(workspace.target_dir(), root.path().join("game/target"));
+        assert_eq!(
+            workspace.output_dir(&workspace.members()[0]),
+            root.path().join("game/target")
+        );
+    }
+
+    #[test]
+    fn virtual_workspace() {
+        let root = tempfile::tempdir().unwrap();
+        write_package(&root.path().join("scripts"), "ai");
+        write_package(&root.path().join("scripts"), "old");
+        write_package(&root.path().join("scripts"), "ui");
+        write_package(root.path(), "tools");
+        std::fs::write(root.path().join("scripts/README.md"), "").unwrap();
+        std::fs::write(
+            root.path().join("mun.toml"),
+            "[workspace]\nmembers = [\"scripts/*\", \"tools\"]\nexclude = [\"scripts/old\"]\n",
+        )
+        .unwrap();
+
+        let workspace = Workspace::from_manifest_path(root.path().join("mun.toml")).unwrap();
+        assert_eq!(member_names(&workspace), vec!["ai", "ui", "tools"]);
+        assert_eq!(workspace.target_dir(), root.path().join("target"));
+        assert_eq!(
+            workspace.output_dir(&workspace.members()[0]),
+            root.path().join("target/ai")
+        );
+    }
+
+    #[test]
+    fn root_package_workspace() {
+        let root = tempfile::tempdir().unwrap();
+        write_package(root.path(), "ai");
+        std::fs::write(
+            root.path().join("mun.toml"),
+            "[package]\nname = \"game\"\nversion = \"0.1.0\"\n\n\
+             [workspace]\nmembers = [\"ai\"]\ntarget-dir = \"build\"\n",
+        )
+        .unwrap();
+
+        let workspace = Workspace::from_manifest_path(root.path().join("mun.toml")).unwrap();
+        assert_eq!(member_names(&workspace), vec!["game", "ai"]);
+        assert_eq!(
+            workspace.output_dir(&workspace.members()[0]),
+            root.path().join("build/game")
+        );
+    }
+
+    #[test]
+    fn member_without_manifest() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(root.path().join("scripts/ai")).unwrap();
+        std::fs::write(
+            root.path().join("mun.toml"),
+            "[workspace]\nmembers = [\"scripts/*\"]\n",
+        )
+        .unwrap();
+
+        let error = Workspace::from_manifest_path(root.path().join("mun.toml")).unwrap_err();
+        assert!(format!("{:#}", error).contains("could not load workspace member"));
+    }
+
+    #[test]
+    fn profile_target_dirs() {
+        let root = tempfile::tempdir().unwrap();
+        write_package(root.path(), "ai");
+        std::fs::write(
+            root.path().join("mun.toml"),
+            "[workspace]\nmembers = [\"ai\"]\n\n[profile.dev]\nout-dir = \"dev\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.path().join("ai/mun.toml"),
+            "[package]\nname = \"ai\"\nversion = \"0.1.0\"\n\n[profile.release]\nopt-level = 0\n",
+        )
+        .unwrap();
+
+        let workspace = Workspace::from_manifest_path(root.path().join("mun.toml")).unwrap();
+        assert_eq!(
+            workspace.profile_target_dir(ProfileKind::Dev),
+            root.path().join("dev")
+        );
+        assert_eq!(
+            workspace.profile_target_dir(ProfileKind::Release),
+            root.path().join("target/release")
+        );
+        assert_eq!(workspace.profile(ProfileKind::Release).opt_level(), 3);
+    }
+}
@@ -101,6 +101,35 @@ pub fn empty_benchmark(c: &mut Criterion) {
     }
 }
 
+/// A benchmark method to measure the overhead of calling functions in other modules through the
+/// dispatch table, compared to calling them directly.
+pub fn cross_module_benchmark(c: &mut Criterion) {
+    // Perform setup (not part of the benchmark)
+    let runtime = util::runtime_from_package("cross_module", false);
+    let direct_runtime = util::runtime_from_package("cross_module", true);
+
+    let mut group = c.benchmark_group("cross_module");
+
+    // Iterate over a number of samples
+    for i in [100i64, 1000i64, 8000i64].iter() {
+        group.bench_with_input(BenchmarkId::new("mun dispatch table", i), i, |b, i| {
+            let runtime_ref = runtime.borrow();
+            b.iter(|| {
+                let _: i64 = invoke_fn!(runtime_ref, "main", *i).unwrap();
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("mun direct calls", i), i, |b, i| {
+            let runtime_ref = direct_runtime.borrow();
+            b.iter(|| {
+                let _: i64 = invoke_fn!(runtime_ref, "main", *i).unwrap();
+            })
+        });
+    }
+
+    group.finish();
+}
+
 #[derive(Clone, Default)]
 struct RustChild(f32, f32, f32, f32);
 
@@ -296,6 +325,7 @@ criterion_group!(
     benches,
     fibonacci_benchmark,
     empty_benchmark,
+    cross_module_benchmark,
     get_struct_field_benchmark,
     set_struct_field_benchmark
 );
@@ -36,6 +36,38 @@ pub fn runtime_from_file<P: AsRef<Path>>(p: P) -> Rc<RefCell<mun_runtime::Runtim
     RuntimeBuilder::new(out_path).spawn().unwrap()
 }
 
+/// Compiles the package at `p` and spawns a runtime without hot reloading. If `direct_calls` is
+/// enabled, calls between the package's modules do not go through the dispatch table.
+pub fn runtime_from_package<P: AsRef<Path>>(
+    p: P,
+    direct_calls: bool,
+) -> Rc<RefCell<mun_runtime::Runtime>> {
+    let out_dir = tempfile::TempDir::new().unwrap();
+    let (_, mut driver) = Driver::with_package_path(
+        compute_resource_path(p).join("mun.toml"),
+        Config {
+            optimization_lvl: OptimizationLevel::Aggressive,
+            out_dir: Some(out_dir.path().to_path_buf()),
+            direct_calls,
+            ..Config::default()
+        },
+    )
+    .unwrap();
+    let mut cursor = NoColor::new(Cursor::new(Vec::new()));
+    if driver.emit_diagnostics(&mut cursor).unwrap() {
+        let errors = String::from_utf8(cursor.into_inner().into_inner())
+            .unwrap_or_else(|e| format!("<could not utf8 decode error string: {}>", e));
+        panic!("compiler errors..\n{}", errors);
+    }
+
+    // The runtime loads copies of the libraries, so the output directory can be removed afterwards
+    driver.write_all_assemblies(false).unwrap();
+    RuntimeBuilder::new(out_dir.path().join("mod.munlib"))
+        .disable_hot_reloading()
+        .spawn()
+        .unwrap()
+}
+
 pub fn lua_from_file<P: AsRef<Path>>(p: P) -> Lua {
     let lua = Lua::new();
     lua.load(&std::fs::read_to_string(compute_resource_path(p)).unwrap())
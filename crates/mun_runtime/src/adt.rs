@@ -4,6 +4,7 @@ use crate::{
     reflection::{
         equals_argument_type, equals_return_type, ArgumentReflection, ReturnTypeReflection,
     },
+    value::Value,
     Runtime,
 };
 use memory::gc::{GcRuntime, HasIndirectionPtr};
@@ -28,6 +29,17 @@ impl RawStruct {
     pub unsafe fn get_ptr(&self) -> *const u8 {
         self.0.deref()
     }
+
+    /// Returns the garbage collector handle of the struct.
+    pub fn handle(&self) -> GcPtr {
+        self.0
+    }
+}
+
+impl From<GcPtr> for RawStruct {
+    fn from(handle: GcPtr) -> Self {
+        RawStruct(handle)
+    }
 }
 
 /// Type-agnostic wrapper for interoperability with a Mun struct. This is merely a reference to the
@@ -183,6 +195,68 @@ impl<'s> StructRef<'s> {
         Marshal::marshal_to_ptr(value, field_ptr, Some(field_type));
         Ok(())
     }
+
+    /// Returns the names of the struct's fields.
+    pub fn field_names(&self) -> impl Iterator<Item = &str> {
+        // Safety: `as_struct` is guaranteed to return `Some` for `StructRef`s.
+        self.type_info().as_struct().unwrap().field_names()
+    }
+
+    /// Retrieves the value of the field corresponding to the specified `field_name` as a
+    /// dynamically typed [`Value`].
+    pub fn get_value(&self, field_name: &str) -> Result<Value<'s>, String> {
+        let type_info = self.type_info();
+
+        // Safety: `as_struct` is guaranteed to return `Some` for `StructRef`s.
+        let struct_info = type_info.as_struct().unwrap();
+        let field_idx =
+            abi::StructInfo::find_field_index(type_info.name(), struct_info, field_name)?;
+
+        // Safety: If we found the `field_idx`, we are guaranteed to also have the `field_type` and
+        // `field_offset`.
+        let field_type = unsafe { struct_info.field_types().get_unchecked(field_idx) };
+        unsafe {
+            let field_ptr = self.field_offset_unchecked::<u8>(struct_info, field_idx);
+            Value::read(field_ptr, field_type, self.runtime)
+        }
+    }
+
+    /// Retrieves the names and dynamically typed values of all of the struct's fields.
+    pub fn values(&self) -> Result<Vec<(&str, Value<'s>)>, String> {
+        self.field_names()
+            .map(|field_name| Ok((field_name, self.get_value(field_name)?)))
+            .collect()
+    }
+
+    /// Sets the value of the field corresponding to the specified `field_name` to a dynamically
+    /// typed [`Value`].
+    pub fn set_value(&mut self, field_name: &str, value: Value<'s>) -> Result<(), String> {
+        let type_info = self.type_info();
+
+        // Safety: `as_struct` is guaranteed to return `Some` for `StructRef`s.
+        let struct_info = type_info.as_struct().unwrap();
+        let field_idx =
+            abi::StructInfo::find_field_index(type_info.name(), struct_info, field_name)?;
+
+        // Safety: If we found the `field_idx`, we are guaranteed to also have the `field_type` and
+        // `field_offset`.
+        let field_type = unsafe { struct_info.field_types().get_unchecked(field_idx) };
+        equals_argument_type(self.runtime, field_type, &value).map_err(|(expected, found)| {
+            format!(
+                "Mismatched types for `{}::{}`. Expected: `{}`. Found: `{}`.",
+                type_info.name(),
+                field_name,
+                expected,
+                found,
+            )
+        })?;
+
+        unsafe {
+            let field_ptr = self.field_offset_unchecked::<u8>(struct_info, field_idx);
+            value.write(field_ptr, field_type);
+        }
+        Ok(())
+    }
 }
 
 impl<'r> ArgumentReflection for StructRef<'r> {
@@ -222,7 +296,7 @@ impl<'s> Marshal<'s> for StructRef<'s> {
         StructRef::new(value, runtime)
     }
 
-    fn marshal_into<'r>(self) -> Self::MunType {
+    fn marshal_into(self) -> Self::MunType {
         self.into_raw()
     }
 
@@ -245,7 +319,7 @@ impl<'s> Marshal<'s> for StructRef<'s> {
 
             // Create a new object using the runtime's intrinsic
             let mut gc_handle = {
-                runtime.gc().alloc(
+                runtime.gc.alloc(
                     // Safety: `ty` is a shared reference, so is guaranteed to not be `ptr::null()`.
                     UnsafeTypeInfo::new(unsafe {
                         NonNull::new_unchecked(type_info as *const abi::TypeInfo as *mut _)
@@ -1,35 +1,227 @@
 use crate::{
-    garbage_collector::{GarbageCollector, UnsafeTypeInfo},
-    DispatchTable,
+    garbage_collector::{GarbageCollector, GcPtr, GcRootPtr, UnsafeTypeInfo},
+    Coverage, DispatchTable, ExternTypeInfo, Profiler, StaticLibrary,
 };
 use abi::{AssemblyInfo, FunctionPrototype};
-use anyhow::anyhow;
 use libloader::{MunLibrary, TempLibrary};
 use log::error;
-use memory::mapping::{Mapping, MemoryMapper};
+use memory::{
+    gc::{GcRuntime, HasIndirectionPtr},
+    mapping::{Mapping, MemoryMapper},
+    TypeDesc, TypeMemory,
+};
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     ffi::c_void,
     path::{Path, PathBuf},
     ptr::NonNull,
     sync::Arc,
 };
+use thiserror::Error;
+
+/// An error that occurs when linking assemblies.
+#[derive(Debug, Error)]
+pub enum LinkError {
+    /// One or more functions could not be found in the dispatch table.
+    #[error("Failed to link due to missing dependencies.")]
+    MissingFunctions(Vec<String>),
+    /// One or more extern structs are not defined by the host.
+    #[error("Failed to link due to missing extern types.")]
+    MissingTypes(Vec<String>),
+    /// A function exists in the dispatch table, but its signature differs from the expected one.
+    #[error("Failed to link: function '{name}' is missing. A function with the same name does exist, but the signatures do not match (expected: {expected}, found: {found}).")]
+    SignatureMismatch {
+        /// The name of the function
+        name: String,
+        /// The expected signature
+        expected: String,
+        /// The signature found in the dispatch table
+        found: String,
+    },
+}
+
+/// An error that occurs when hot reloading assemblies. If a reload fails, the previously linked
+/// assemblies, the dispatch table, and the heap are left untouched.
+#[derive(Debug, Error)]
+pub enum ReloadError {
+    /// An assembly could not be loaded.
+    #[error("failed to load assembly '{}'", .path.display())]
+    Load {
+        /// The path of the assembly
+        path: PathBuf,
+        /// The underlying error
+        #[source]
+        source: anyhow::Error,
+    },
+    /// The reloaded assemblies could not be linked.
+    #[error(transparent)]
+    Link(#[from] LinkError),
+    /// A migration function does not have a single gc struct argument and a gc struct return type.
+    #[error(
+        "migration function '{name}' must have a single gc struct argument and return a gc struct"
+    )]
+    InvalidMigration {
+        /// The name of the migration function
+        name: String,
+    },
+    /// The argument type of a migration function does not match the memory layout of the old type.
+    #[error("the argument of migration function '{name}' does not match the memory layout of the old '{ty}' type")]
+    IncompatibleMigration {
+        /// The name of the migration function
+        name: String,
+        /// The name of the old type
+        ty: String,
+    },
+    /// The source code of a package that is executed by the JIT backend could not be compiled.
+    #[cfg(feature = "jit")]
+    #[error("failed to compile package:\n{0}")]
+    Compile(String),
+}
+
+/// Describes the changes made by a successful reload, such that hosts can invalidate what they
+/// cached of the reloaded assemblies.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReloadEvent {
+    /// The library paths of the reloaded assemblies
+    pub assemblies: Vec<PathBuf>,
+    /// The sorted names of all functions that were added, removed, or replaced. Previously
+    /// obtained definitions of these functions are no longer valid.
+    pub changed_functions: Vec<String>,
+    /// The sorted names of all struct types of which the memory layout changed. Existing objects
+    /// of these types were mapped - or migrated - to their new layout.
+    pub migrated_types: Vec<String>,
+}
+
+/// The compiled code of an assembly.
+pub enum AssemblyLibrary {
+    /// A shared library that was loaded from disk
+    Shared(TempLibrary),
+    /// An assembly that was compiled in memory by the JIT backend
+    #[cfg(feature = "jit")]
+    Jit(compiler::JitAssembly),
+    /// An assembly that is statically linked into the executable
+    Static(StaticLibrary),
+}
+
+impl From<MunLibrary> for AssemblyLibrary {
+    fn from(library: MunLibrary) -> Self {
+        AssemblyLibrary::Shared(library.into_inner())
+    }
+}
+
+/// The functions that the compiled code of an assembly exposes to the runtime.
+pub(crate) trait LibrarySymbols: Into<AssemblyLibrary> {
+    /// Returns the ABI version that the assembly was compiled with.
+    fn get_abi_version(&self) -> u32;
+
+    /// Returns the assembly's information.
+    fn get_info(&self) -> AssemblyInfo;
+
+    /// Sets the allocator handle of the assembly.
+    fn set_allocator_handle(&mut self, allocator_ptr: *mut c_void);
+
+    /// Sets the profiler handle of the assembly, if it was compiled with function
+    /// instrumentation. Returns whether or not the assembly is instrumented.
+    fn set_profiler_handle(&mut self, profiler_ptr: *mut c_void) -> bool;
+
+    /// Sets the coverage handle of the assembly, if it was compiled with coverage
+    /// instrumentation. Returns whether or not the assembly is instrumented.
+    fn set_coverage_handle(&mut self, coverage_ptr: *mut c_void) -> bool;
+}
+
+impl LibrarySymbols for MunLibrary {
+    fn get_abi_version(&self) -> u32 {
+        MunLibrary::get_abi_version(self)
+    }
+
+    fn get_info(&self) -> AssemblyInfo {
+        MunLibrary::get_info(self)
+    }
+
+    fn set_allocator_handle(&mut self, allocator_ptr: *mut c_void) {
+        MunLibrary::set_allocator_handle(self, allocator_ptr)
+    }
+
+    fn set_profiler_handle(&mut self, profiler_ptr: *mut c_void) -> bool {
+        MunLibrary::set_profiler_handle(self, profiler_ptr)
+    }
+
+    fn set_coverage_handle(&mut self, coverage_ptr: *mut c_void) -> bool {
+        MunLibrary::set_coverage_handle(self, coverage_ptr)
+    }
+}
 
 /// An assembly is a hot reloadable compilation unit, consisting of one or more Mun modules.
 pub struct Assembly {
     library_path: PathBuf,
-    library: TempLibrary,
-    legacy_libs: Vec<TempLibrary>,
+    library: AssemblyLibrary,
+    // Only used for relinking, which requires hot reloading
+    #[cfg_attr(not(feature = "hot_reloading"), allow(dead_code))]
+    legacy_libs: Vec<AssemblyLibrary>,
     info: AssemblyInfo,
+    #[cfg_attr(not(feature = "hot_reloading"), allow(dead_code))]
     allocator: Arc<GarbageCollector>,
+    _profiler: Option<Arc<Profiler>>,
+    _coverage: Option<Arc<Coverage>>,
 }
 
 impl Assembly {
     /// Loads an assembly and its information for the shared library at `library_path`. The
     /// resulting `Assembly` is ensured to be linkable.
-    pub fn load(library_path: &Path, gc: Arc<GarbageCollector>) -> Result<Self, anyhow::Error> {
-        let mut library = MunLibrary::new(library_path)?;
+    ///
+    /// If a `profiler` is specified and the library was compiled with function instrumentation,
+    /// calls to the library's functions are recorded by the `profiler`. Similarly, if `coverage`
+    /// is specified and the library was compiled with coverage instrumentation, block executions
+    /// are counted by `coverage`.
+    pub fn load(
+        library_path: &Path,
+        gc: Arc<GarbageCollector>,
+        profiler: Option<Arc<Profiler>>,
+        coverage: Option<Arc<Coverage>>,
+    ) -> Result<Self, anyhow::Error> {
+        let library = MunLibrary::new(library_path)?;
+        Assembly::from_library(library_path.to_path_buf(), library, gc, profiler, coverage)
+    }
 
+    /// Loads an assembly and its information from the contents of a shared library, which is
+    /// identified by `library_path`. The resulting `Assembly` is ensured to be linkable.
+    ///
+    /// See [`Assembly::load`] for the use of the `profiler` and `coverage`.
+    pub fn load_from_bytes(
+        library_path: &Path,
+        bytes: &[u8],
+        gc: Arc<GarbageCollector>,
+        profiler: Option<Arc<Profiler>>,
+        coverage: Option<Arc<Coverage>>,
+    ) -> Result<Self, anyhow::Error> {
+        let library = MunLibrary::from_bytes(bytes)?;
+        Assembly::from_library(library_path.to_path_buf(), library, gc, profiler, coverage)
+    }
+
+    /// Loads an assembly and its information from a library that is statically linked into the
+    /// executable, which is identified by `library_path`. The resulting `Assembly` is ensured to
+    /// be linkable.
+    ///
+    /// See [`Assembly::load`] for the use of the `profiler` and `coverage`.
+    pub fn load_static(
+        library_path: &Path,
+        library: StaticLibrary,
+        gc: Arc<GarbageCollector>,
+        profiler: Option<Arc<Profiler>>,
+        coverage: Option<Arc<Coverage>>,
+    ) -> Result<Self, anyhow::Error> {
+        Assembly::from_library(library_path.to_path_buf(), library, gc, profiler, coverage)
+    }
+
+    /// Constructs an assembly from the compiled code in `library`, which is identified by
+    /// `library_path`.
+    pub(crate) fn from_library<L: LibrarySymbols>(
+        library_path: PathBuf,
+        mut library: L,
+        gc: Arc<GarbageCollector>,
+        profiler: Option<Arc<Profiler>>,
+        coverage: Option<Arc<Coverage>>,
+    ) -> Result<Self, anyhow::Error> {
         let version = library.get_abi_version();
         if abi::ABI_VERSION != version {
             return Err(anyhow::anyhow!(
@@ -42,13 +234,23 @@ impl Assembly {
         let allocator_ptr = Arc::into_raw(gc.clone()) as *mut std::ffi::c_void;
         library.set_allocator_handle(allocator_ptr);
 
+        if let Some(profiler) = profiler.as_ref() {
+            library.set_profiler_handle(Arc::as_ptr(profiler) as *mut std::ffi::c_void);
+        }
+
+        if let Some(coverage) = coverage.as_ref() {
+            library.set_coverage_handle(Arc::as_ptr(coverage) as *mut std::ffi::c_void);
+        }
+
         let info = library.get_info();
         let assembly = Assembly {
-            library_path: library_path.to_path_buf(),
-            library: library.into_inner(),
+            library_path,
+            library: library.into(),
             legacy_libs: Vec::new(),
             info,
             allocator: gc,
+            _profiler: profiler,
+            _coverage: coverage,
         };
 
         Ok(assembly)
@@ -58,7 +260,7 @@ impl Assembly {
     fn link_all_impl<'a>(
         dispatch_table: &mut DispatchTable,
         to_link: impl Iterator<Item = (&'a mut *const c_void, &'a FunctionPrototype)>,
-    ) -> anyhow::Result<()> {
+    ) -> Result<(), LinkError> {
         let mut to_link: Vec<_> = to_link.collect();
 
         let mut retry = true;
@@ -72,7 +274,11 @@ impl Assembly {
                 if let Some(fn_def) = dispatch_table.get_fn(fn_prototype.name()) {
                     // Ensure that the function's signature is the same.
                     if fn_prototype.signature != fn_def.prototype.signature {
-                        return Err(anyhow!("Failed to link: function '{}' is missing. A function with the same name does exist, but the signatures do not match (expected: {}, found: {}).", fn_prototype.name(), fn_prototype, fn_def.prototype));
+                        return Err(LinkError::SignatureMismatch {
+                            name: fn_prototype.name().to_string(),
+                            expected: fn_prototype.to_string(),
+                            found: fn_def.prototype.to_string(),
+                        });
                     }
 
                     *dispatch_ptr = fn_def.fn_ptr;
@@ -87,14 +293,43 @@ impl Assembly {
         }
 
         if !to_link.is_empty() {
-            for (_, fn_prototype) in to_link {
-                error!(
-                    "Failed to link: function `{}` is missing.",
-                    fn_prototype.name()
-                );
+            let missing = to_link
+                .into_iter()
+                .map(|(_, fn_prototype)| {
+                    error!(
+                        "Failed to link: function `{}` is missing.",
+                        fn_prototype.name()
+                    );
+                    fn_prototype.name().to_string()
+                })
+                .collect();
+
+            return Err(LinkError::MissingFunctions(missing));
+        }
+
+        Ok(())
+    }
+
+    /// Ensures that all extern structs used by the `assemblies` are defined by the host.
+    fn link_types<'a>(
+        assemblies: impl Iterator<Item = &'a Assembly>,
+        extern_types: &[ExternTypeInfo],
+    ) -> Result<(), LinkError> {
+        let mut missing = Vec::new();
+        for type_info in assemblies.flat_map(|asm| asm.info.symbols.types().iter()) {
+            if is_extern(type_info)
+                && !extern_types
+                    .iter()
+                    .any(|extern_type| extern_type.type_info().guid == type_info.guid)
+                && !missing.iter().any(|name| name == type_info.name())
+            {
+                error!("Failed to link: type `{}` is missing.", type_info.name());
+                missing.push(type_info.name().to_string());
             }
+        }
 
-            return Err(anyhow!("Failed to link due to missing dependencies."));
+        if !missing.is_empty() {
+            return Err(LinkError::MissingTypes(missing));
         }
 
         Ok(())
@@ -105,9 +340,12 @@ impl Assembly {
     pub(super) fn link_all<'a>(
         assemblies: impl Iterator<Item = &'a mut Assembly>,
         dispatch_table: &DispatchTable,
+        extern_types: &[ExternTypeInfo],
     ) -> anyhow::Result<DispatchTable> {
         let assemblies: Vec<&'a mut _> = assemblies.collect();
 
+        Assembly::link_types(assemblies.iter().map(|asm| &**asm), extern_types)?;
+
         // Clone the dispatch table, such that we can roll back if linking fails
         let mut dispatch_table = dispatch_table.clone();
 
@@ -131,13 +369,21 @@ impl Assembly {
         Ok(dispatch_table)
     }
 
-    /// Tries to link the `assemblies`, resulting in a new [`DispatchTable`] on success. This leaves
-    /// the original `dispatch_table` intact, in case of linking errors.
+    /// Tries to link the `unlinked_assemblies`, resulting in a new [`DispatchTable`] and a
+    /// description of the changes on success.
+    ///
+    /// Relinking is atomic: all fallible steps - linking, and validating migrations - are performed
+    /// before any memory is mapped or any linked assembly is replaced. On failure, the
+    /// `linked_assemblies`, the original `dispatch_table`, and the heap are left intact.
+    #[cfg_attr(not(feature = "hot_reloading"), allow(dead_code))]
     pub(super) fn relink_all(
         unlinked_assemblies: &mut HashMap<PathBuf, Assembly>,
         linked_assemblies: &mut HashMap<PathBuf, Assembly>,
         dispatch_table: &DispatchTable,
-    ) -> anyhow::Result<DispatchTable> {
+        extern_types: &[ExternTypeInfo],
+    ) -> Result<(DispatchTable, ReloadEvent), ReloadError> {
+        Assembly::link_types(unlinked_assemblies.values(), extern_types)?;
+
         let mut assemblies = unlinked_assemblies
             .iter_mut()
             .map(|(old_path, asm)| {
@@ -150,11 +396,14 @@ impl Assembly {
         // Clone the dispatch table, such that we can roll back if linking fails
         let mut dispatch_table = dispatch_table.clone();
 
+        let mut changed_functions = BTreeSet::new();
+
         // Remove the old assemblies' functions from the dispatch table
         for (_, old_assembly) in assemblies.iter() {
             if let Some(assembly) = old_assembly {
                 for function in assembly.info.symbols.functions() {
                     dispatch_table.remove_fn(function.prototype.name());
+                    changed_functions.insert(function.prototype.name().to_owned());
                 }
             }
         }
@@ -163,6 +412,7 @@ impl Assembly {
         for (new_assembly, _) in assemblies.iter() {
             for function in new_assembly.info().symbols.functions() {
                 dispatch_table.insert_fn(function.prototype.name(), function.clone());
+                changed_functions.insert(function.prototype.name().to_owned());
             }
         }
 
@@ -180,13 +430,25 @@ impl Assembly {
             .filter_map(|(new_asm, old_asm)| old_asm.map(|old_asm| (old_asm, new_asm)))
             .collect();
 
-        let mut assemblies_to_keep = HashMap::new();
+        // Determine the memory mapping and migrations of all assemblies before touching any memory,
+        // such that an invalid migration leaves the linked assemblies intact.
+        let mut mappings = Vec::with_capacity(assemblies_to_map.len());
         for (old_assembly, new_assembly) in assemblies_to_map.iter() {
+            let migrations = new_assembly
+                .info
+                .symbols
+                .migrations()
+                .iter()
+                .map(Migration::new)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // Objects of extern types are never mapped, as the host's definitions do not change
             let old_types: Vec<UnsafeTypeInfo> = old_assembly
                 .info
                 .symbols
                 .types()
                 .iter()
+                .filter(|ty| !is_extern(ty))
                 .map(|ty| {
                     // Safety: `ty` is a shared reference, so is guaranteed to not be `ptr::null()`.
                     UnsafeTypeInfo::new(unsafe {
@@ -200,16 +462,86 @@ impl Assembly {
                 .symbols
                 .types()
                 .iter()
+                .filter(|ty| !is_extern(ty))
                 .map(|ty| {
                     // Safety: `ty` is a shared reference, so is guaranteed to not be `ptr::null()`.
                     UnsafeTypeInfo::new(unsafe {
                         NonNull::new_unchecked(*ty as *const abi::TypeInfo as *mut _)
                     })
                 })
+                // The argument type of a migration mirrors an old type, so it should not be
+                // considered as a candidate for mapping the old type.
+                .filter(|ty| !migrations.iter().any(|migration| migration.old_ty == *ty))
                 .collect();
 
             let mapping = Mapping::new(&old_types, &new_types);
-            let deleted_objects = old_assembly.allocator.map_memory(mapping);
+
+            // Find the migrations for types that are converted
+            let mut type_migrations = Vec::new();
+            for (old_ty, conversion) in mapping.conversions.iter() {
+                if let Some(migration) = migrations
+                    .iter()
+                    .find(|migration| migration.new_ty == conversion.new_ty)
+                {
+                    if !migration.is_compatible_with(*old_ty) {
+                        return Err(ReloadError::IncompatibleMigration {
+                            name: migration.name.clone(),
+                            ty: old_ty.name().to_string(),
+                        });
+                    }
+                    type_migrations.push((*old_ty, migration.clone()));
+                }
+            }
+
+            mappings.push((old_assembly, new_assembly, mapping, type_migrations));
+        }
+
+        let migrated_types: BTreeSet<String> = mappings
+            .iter()
+            .flat_map(|(_, _, mapping, _)| mapping.conversions.keys())
+            .map(|ty| ty.name().to_owned())
+            .collect();
+
+        // From here on nothing can fail, so the reload is committed
+        let mut assemblies_to_keep = HashMap::new();
+        // The results of migrations are rooted until the reload has completed
+        let mut migration_results = Vec::new();
+        for (old_assembly, new_assembly, mapping, type_migrations) in mappings {
+            let allocator = &old_assembly.allocator;
+
+            // Migrate all objects before their memory is mapped, such that the objects that their
+            // fields refer to are still valid. A migration receives a copy of the object, as its
+            // argument type is allocated by the new assembly.
+            let mut migrated_objects = Vec::new();
+            for (old_ty, migration) in type_migrations.iter() {
+                for handle in allocator.objects_of_type(old_ty) {
+                    let mut copy = allocator.alloc(migration.old_ty);
+                    unsafe {
+                        std::ptr::copy_nonoverlapping(
+                            handle.deref::<u8>(),
+                            copy.deref_mut::<u8>(),
+                            old_ty.layout().size(),
+                        )
+                    };
+                    let copy = GcRootPtr::new(allocator, copy);
+                    let migrated = GcRootPtr::new(allocator, (migration.fn_ptr)(copy.handle()));
+                    migrated_objects.push((handle, migrated.handle(), migration));
+                    migration_results.push((copy, migrated));
+                }
+            }
+
+            let deleted_objects = allocator.map_memory(mapping);
+
+            // Overwrite the mapped objects with the result of their migration
+            for (mut handle, migrated, migration) in migrated_objects {
+                unsafe {
+                    std::ptr::copy_nonoverlapping(
+                        migrated.deref::<u8>(),
+                        handle.deref_mut::<u8>(),
+                        migration.new_ty.layout().size(),
+                    )
+                };
+            }
 
             if !deleted_objects.is_empty() {
                 // Retain the previous assembly
@@ -223,10 +555,28 @@ impl Assembly {
         let mut newly_linked = HashMap::new();
         std::mem::swap(unlinked_assemblies, &mut newly_linked);
 
+        let mut reloaded_assemblies: Vec<PathBuf> = newly_linked
+            .values()
+            .map(|assembly| assembly.library_path().to_path_buf())
+            .collect();
+        reloaded_assemblies.sort();
+
+        let event = ReloadEvent {
+            assemblies: reloaded_assemblies,
+            changed_functions: changed_functions.into_iter().collect(),
+            migrated_types: migrated_types.into_iter().collect(),
+        };
+
         for (old_path, mut new_assembly) in newly_linked.into_iter() {
-            let mut old_assembly = linked_assemblies
-                .remove(&old_path)
-                .expect("Assembly must exist.");
+            // Assemblies that were added to a JIT-compiled package have no previous version
+            let mut old_assembly = match linked_assemblies.remove(&old_path) {
+                Some(old_assembly) => old_assembly,
+                None => {
+                    linked_assemblies
+                        .insert(new_assembly.library_path().to_path_buf(), new_assembly);
+                    continue;
+                }
+            };
 
             let new_path = if let Some(new_path) = assemblies_to_keep.remove(&old_path) {
                 // Retain all existing legacy libs
@@ -244,7 +594,10 @@ impl Assembly {
             linked_assemblies.insert(new_path, new_assembly);
         }
 
-        Ok(dispatch_table)
+        // The migrated objects have been copied into the mapped objects, so they can be collected
+        drop(migration_results);
+
+        Ok((dispatch_table, event))
     }
 
     /// Returns the assembly's information.
@@ -257,8 +610,77 @@ impl Assembly {
         self.library_path.as_path()
     }
 
-    /// Converts the `Assembly` into a `TempLibrary`, consuming the input in the process.
-    pub fn into_library(self) -> TempLibrary {
+    /// Converts the `Assembly` into its compiled code, consuming the input in the process.
+    pub fn into_library(self) -> AssemblyLibrary {
         self.library
     }
 }
+
+/// A user-defined function - annotated with `#[migrate]` - that converts an instance of an old
+/// struct type to an instance of the new struct type.
+#[cfg_attr(not(feature = "hot_reloading"), allow(dead_code))]
+#[derive(Clone)]
+struct Migration {
+    name: String,
+    fn_ptr: extern "C" fn(GcPtr) -> GcPtr,
+    /// The argument type, which mirrors the memory layout of the old struct type
+    old_ty: UnsafeTypeInfo,
+    /// The return type, i.e. the new struct type
+    new_ty: UnsafeTypeInfo,
+}
+
+#[cfg_attr(not(feature = "hot_reloading"), allow(dead_code))]
+impl Migration {
+    /// Constructs a `Migration` from its function definition, validating its signature.
+    fn new(definition: &abi::FunctionDefinition) -> Result<Self, ReloadError> {
+        let is_gc_struct = |ty: &abi::TypeInfo| {
+            ty.as_struct()
+                .map_or(false, |s| s.memory_kind == abi::StructMemoryKind::GC)
+        };
+
+        let signature = &definition.prototype.signature;
+        match (signature.arg_types(), signature.return_type()) {
+            ([old_ty], Some(new_ty)) if is_gc_struct(old_ty) && is_gc_struct(new_ty) => Ok(Self {
+                name: definition.prototype.name().to_string(),
+                fn_ptr: unsafe { std::mem::transmute(definition.fn_ptr) },
+                old_ty: UnsafeTypeInfo::new(NonNull::from(*old_ty)),
+                new_ty: UnsafeTypeInfo::new(NonNull::from(new_ty)),
+            }),
+            _ => Err(ReloadError::InvalidMigration {
+                name: definition.prototype.name().to_string(),
+            }),
+        }
+    }
+
+    /// Returns whether the memory layout of the migration's argument type is identical to that of
+    /// `old_ty`.
+    fn is_compatible_with(&self, old_ty: UnsafeTypeInfo) -> bool {
+        let (lhs, rhs) = unsafe {
+            (
+                self.old_ty.into_inner().as_ref(),
+                old_ty.into_inner().as_ref(),
+            )
+        };
+        match (lhs.as_struct(), rhs.as_struct()) {
+            (Some(lhs_struct), Some(rhs_struct)) => {
+                lhs.size_in_bytes() == rhs.size_in_bytes()
+                    && lhs.alignment() == rhs.alignment()
+                    && lhs_struct.field_names().eq(rhs_struct.field_names())
+                    && lhs_struct.field_offsets() == rhs_struct.field_offsets()
+                    && lhs_struct
+                        .field_types()
+                        .iter()
+                        .map(|ty| ty.name())
+                        .eq(rhs_struct.field_types().iter().map(|ty| ty.name()))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Returns whether the type is an `extern struct`, which is defined by the host.
+fn is_extern(type_info: &abi::TypeInfo) -> bool {
+    type_info
+        .as_struct()
+        .map_or(false, |s| s.memory_kind == abi::StructMemoryKind::Extern)
+}
@@ -0,0 +1,128 @@
+use parking_lot::Mutex;
+use rustc_hash::FxHashMap;
+use std::{
+    ffi::{self, CStr},
+    io::{self, Write},
+    os::raw::c_char,
+};
+
+/// The execution counters of a single function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionCoverage {
+    /// The full path of the function
+    pub path: String,
+    /// The number of executions of every block and branch in the function, indexed by counter. Counters that
+    /// were never hit might be missing from the end.
+    pub counters: Vec<u64>,
+}
+
+#[derive(Default)]
+struct CoverageState {
+    /// Maps the path of a function to the index of its counters. The path is used, because a
+    /// reloaded munlib can be loaded at the address of the one it replaced.
+    path_to_idx: FxHashMap<String, usize>,
+    functions: Vec<FunctionCoverage>,
+}
+
+impl CoverageState {
+    /// Returns the index of the function with the null-terminated name, `fn_name`.
+    ///
+    /// # Safety
+    ///
+    /// `fn_name` must point to a valid null-terminated string.
+    unsafe fn function_idx(&mut self, fn_name: *const u8) -> usize {
+        let path = CStr::from_ptr(fn_name as *const c_char).to_string_lossy();
+        if let Some(idx) = self.path_to_idx.get(path.as_ref()) {
+            return *idx;
+        }
+
+        let path = path.into_owned();
+        let idx = self.functions.len();
+        self.functions.push(FunctionCoverage {
+            path: path.clone(),
+            counters: Vec::new(),
+        });
+        self.path_to_idx.insert(path, idx);
+        idx
+    }
+}
+
+/// Counts the executions of blocks in Mun functions.
+///
+/// Mun functions are only counted if their assembly was compiled with coverage instrumentation
+/// enabled. The counters can be mapped back to source code by the `mun coverage` command.
+#[derive(Default)]
+pub struct Coverage {
+    state: Mutex<CoverageState>,
+}
+
+impl Coverage {
+    /// Returns the counters of all functions that were executed since the coverage was created or
+    /// last reset, ordered by path.
+    pub fn counters(&self) -> Vec<FunctionCoverage> {
+        let state = self.state.lock();
+        let mut functions: Vec<FunctionCoverage> = state
+            .functions
+            .iter()
+            .filter(|function| function.counters.iter().any(|count| *count > 0))
+            .cloned()
+            .collect();
+
+        functions.sort_by(|a, b| a.path.cmp(&b.path));
+        functions
+    }
+
+    /// Resets all counters to zero.
+    pub fn reset(&self) {
+        let mut state = self.state.lock();
+        for function in state.functions.iter_mut() {
+            function.counters.clear();
+        }
+    }
+
+    /// Writes all non-zero counters. Every line contains the path of a function, the index of a
+    /// counter, and its count; separated by spaces.
+    pub fn write_counters<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        for function in self.counters() {
+            for (idx, count) in function.counters.iter().enumerate() {
+                if *count > 0 {
+                    writeln!(writer, "{} {} {}", function.path, idx, count)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Increments the counter with index `counter` of the function with the specified name.
+    ///
+    /// # Safety
+    ///
+    /// `fn_name` must point to a valid null-terminated string.
+    unsafe fn hit(&self, fn_name: *const u8, counter: u32) {
+        let mut state = self.state.lock();
+        let idx = state.function_idx(fn_name);
+
+        let counters = &mut state.functions[idx].counters;
+        let counter = counter as usize;
+        if counters.len() <= counter {
+            counters.resize(counter + 1, 0);
+        }
+        counters[counter] += 1;
+    }
+}
+
+/// Called by instrumented Mun functions when a block is executed.
+pub(crate) extern "C" fn coverage_hit(
+    fn_name: *const u8,
+    counter: u32,
+    coverage_handle: *mut ffi::c_void,
+) {
+    // Safety: the coverage handle is either null or set by the `Runtime` using
+    // `set_coverage_handle`, in which case the `Runtime` keeps the coverage alive for as long as the
+    // munlib is loaded.
+    if let Some(coverage) = unsafe { (coverage_handle as *const Coverage).as_ref() } {
+        // Safety: the Mun Compiler guarantees that `fn_name` is a null-terminated string, embedded
+        // in the munlib.
+        unsafe { coverage.hit(fn_name, counter) };
+    }
+}
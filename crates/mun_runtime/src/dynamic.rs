@@ -0,0 +1,173 @@
+use crate::{
+    adt::RawStruct, marshal::Marshal, reflection::equals_argument_type, Runtime, StructRef, Value,
+};
+use abi::HasStaticTypeInfo;
+use libffi::{
+    low::CodePtr,
+    middle::{Cif, Type},
+    raw::{ffi_arg, ffi_call},
+};
+use std::{ffi::c_void, mem::size_of, ptr::NonNull};
+
+/// Storage for a single argument or return value of a dynamic invocation. This is large enough to
+/// hold any of the supported types; i.e. all primitives except for 128-bit integers, and struct
+/// handles. It is also large enough to hold an `ffi_arg`, to which libffi widens small integral
+/// return values.
+type RawValue = u64;
+
+macro_rules! impl_ffi_type {
+    ($($ty:ty => $ffi_ty:ident),+) => {
+        /// Returns the libffi type used to pass a value of type `type_info` to a Mun function.
+        /// Structs are always passed by handle.
+        fn ffi_type(type_info: &abi::TypeInfo) -> Result<Type, String> {
+            if type_info.data.is_struct() {
+                return Ok(Type::pointer());
+            }
+
+            if type_info.guid == i128::type_info().guid || type_info.guid == u128::type_info().guid {
+                return Err(format!(
+                    "Unsupported type `{}` for dynamic invocation: 128-bit integers cannot be passed by libffi.",
+                    type_info.name()
+                ));
+            }
+
+            $(
+                if type_info.guid == <$ty>::type_info().guid {
+                    return Ok(Type::$ffi_ty());
+                }
+            )+
+
+            Err(format!(
+                "Unsupported type `{}` for dynamic invocation.",
+                type_info.name()
+            ))
+        }
+    };
+}
+
+impl_ffi_type!(
+    i8 => i8,
+    i16 => i16,
+    i32 => i32,
+    i64 => i64,
+    u8 => u8,
+    u16 => u16,
+    u32 => u32,
+    u64 => u64,
+    f32 => f32,
+    f64 => f64,
+    bool => u8
+);
+
+impl Runtime {
+    /// Invokes the function `function_name` with the dynamically typed `arguments`. Contrary to
+    /// [`invoke_fn!`], the argument and return types do not need to be known at compile time.
+    ///
+    /// The arguments are validated against the function's signature. If successful, the return
+    /// value is returned, or `None` if the function does not return a value.
+    pub fn invoke_dynamic<'r>(
+        &'r self,
+        function_name: &str,
+        arguments: &[Value<'r>],
+    ) -> Result<Option<Value<'r>>, String> {
+        let function_info = self
+            .get_function_definition(function_name)
+            .ok_or_else(|| format!("Failed to obtain function '{}'", function_name))?;
+
+        // Validate function signature
+        let arg_types = function_info.prototype.signature.arg_types();
+        if arg_types.len() != arguments.len() {
+            return Err(format!(
+                "Invalid number of arguments. Expected: {}. Found: {}.",
+                arg_types.len(),
+                arguments.len(),
+            ));
+        }
+
+        for (idx, (arg_type, argument)) in arg_types.iter().zip(arguments.iter()).enumerate() {
+            equals_argument_type(self, arg_type, argument).map_err(|(expected, found)| {
+                format!(
+                    "Invalid argument type at index {}. Expected: {}. Found: {}.",
+                    idx, expected, found,
+                )
+            })?;
+        }
+
+        let return_type = function_info.prototype.signature.return_type();
+        let cif = Cif::new(
+            arg_types
+                .iter()
+                .map(|arg_type| ffi_type(arg_type))
+                .collect::<Result<Vec<_>, _>>()?,
+            return_type.map_or_else(|| Ok(Type::void()), ffi_type)?,
+        );
+
+        // Marshal the arguments. Structs are always passed by handle.
+        let mut raw_arguments: Vec<RawValue> = vec![0; arguments.len()];
+        for ((raw_argument, argument), arg_type) in raw_arguments
+            .iter_mut()
+            .zip(arguments.iter())
+            .zip(arg_types.iter())
+        {
+            let ptr = NonNull::from(raw_argument).cast::<u8>();
+            match argument.clone() {
+                // Safety: the argument storage is large enough to contain any supported type
+                Value::Struct(s) => unsafe { ptr.cast::<RawStruct>().as_ptr().write(s.into_raw()) },
+                argument => unsafe { argument.write(ptr, arg_type) },
+            }
+        }
+        let mut argument_ptrs: Vec<*mut c_void> = raw_arguments
+            .iter_mut()
+            .map(|raw_argument| raw_argument as *mut RawValue as *mut c_void)
+            .collect();
+
+        let mut raw_result: RawValue = 0;
+
+        // Safety: the call interface was constructed from the function's signature, which was
+        // validated against the arguments.
+        unsafe {
+            ffi_call(
+                cif.as_raw_ptr(),
+                Some(*CodePtr::from_ptr(function_info.fn_ptr).as_safe_fun()),
+                &mut raw_result as *mut RawValue as *mut c_void,
+                argument_ptrs.as_mut_ptr(),
+            );
+        }
+
+        // Marshal the result
+        return_type
+            .map(|return_type| {
+                let ptr = return_value_ptr(&mut raw_result, return_type);
+                if return_type.data.is_struct() {
+                    // Safety: a struct is always returned by handle
+                    let raw = unsafe { ptr.cast::<RawStruct>().as_ptr().read() };
+                    Ok(Value::Struct(StructRef::marshal_from(raw, self)))
+                } else {
+                    // Safety: the result storage contains a value of type `return_type`
+                    unsafe { Value::read(ptr, return_type, self) }
+                }
+            })
+            .transpose()
+    }
+}
+
+/// Returns a pointer to the return value of type `type_info` in `raw_result`.
+///
+/// libffi widens integral return values that are smaller than an `ffi_arg` to a full `ffi_arg`. On
+/// big-endian targets, the value is therefore stored at the end of the `ffi_arg` instead of at its
+/// start.
+fn return_value_ptr(raw_result: &mut RawValue, type_info: &abi::TypeInfo) -> NonNull<u8> {
+    let ptr = NonNull::from(raw_result).cast::<u8>();
+    let size = type_info.size_in_bytes();
+    let is_widened = !type_info.data.is_struct()
+        && type_info.guid != f32::type_info().guid
+        && type_info.guid != f64::type_info().guid
+        && size < size_of::<ffi_arg>();
+
+    if cfg!(target_endian = "big") && is_widened {
+        // Safety: the offset lies within the `ffi_arg` stored in `raw_result`
+        unsafe { NonNull::new_unchecked(ptr.as_ptr().add(size_of::<ffi_arg>() - size)) }
+    } else {
+        ptr
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{adt::RawStruct, garbage_collector::UnsafeTypeInfo, Runtime};
+use memory::gc::HasIndirectionPtr;
+use std::{alloc::Layout, any::TypeId, convert::TryInto, ffi::CString, ptr::NonNull};
+
+/// A function that traces the Mun structs that are referenced by an object of a host-defined type,
+/// by calling `visit` for each of them. This prevents the referenced structs from being garbage
+/// collected.
+pub type TraceFn = fn(object: NonNull<u8>, visit: &mut dyn FnMut(RawStruct));
+
+/// A function that drops an object of a host-defined type in place, right before its memory is
+/// reclaimed by the garbage collector. It is called while the garbage collector is collecting, so
+/// it must not access the garbage collector; e.g. by dropping a
+/// [`RootedStruct`](crate::RootedStruct).
+pub type DropFn = fn(object: NonNull<u8>);
+
+/// The type information of a host-defined type, that can be used in Mun code by declaring an
+/// `extern struct` with the same name. Objects of the type are allocated by the host - using
+/// [`Runtime::new_extern`] - and are opaque to Mun code, which can only hold them and pass them
+/// to `extern fn`s.
+#[repr(C)]
+pub struct ExternTypeInfo {
+    // The `TypeInfo` is the first field, such that a pointer to it is also a pointer to the
+    // `ExternTypeInfo`.
+    type_info: abi::TypeInfo,
+    trace: Option<TraceFn>,
+    drop: Option<DropFn>,
+    /// The Rust type of the objects, if the type information was constructed from one
+    type_id: Option<TypeId>,
+    _name: CString,
+}
+
+impl ExternTypeInfo {
+    /// Constructs the type information of a host-defined type with the specified `name` and
+    /// memory `layout`.
+    pub fn new<S: AsRef<str>>(name: S, layout: Layout) -> Self {
+        let name = CString::new(name.as_ref()).expect("Invalid type name.");
+        let type_info = abi::TypeInfo::new_extern_struct(
+            &name,
+            (layout.size() * 8)
+                .try_into()
+                .expect("size of the type is larger than the maximum allowed ABI size"),
+            layout
+                .align()
+                .try_into()
+                .expect("alignment of the type is larger than the maximum allowed ABI size"),
+        );
+
+        Self {
+            type_info,
+            trace: None,
+            drop: None,
+            type_id: None,
+            _name: name,
+        }
+    }
+
+    /// Constructs the type information of the Rust type `T`, which is exposed to Mun with the
+    /// specified `name`. Objects of the type are dropped when they are garbage collected.
+    pub fn of<T: 'static>(name: &str) -> Self {
+        fn drop_in_place<T>(object: NonNull<u8>) {
+            // Safety: objects of the type are only constructed by `Runtime::new_extern::<T>`
+            unsafe { std::ptr::drop_in_place(object.cast::<T>().as_ptr()) }
+        }
+
+        let mut extern_type = Self::new(name, Layout::new::<T>());
+        if std::mem::needs_drop::<T>() {
+            extern_type.drop = Some(drop_in_place::<T>);
+        }
+        extern_type.type_id = Some(TypeId::of::<T>());
+        extern_type
+    }
+
+    /// Sets the function that traces the Mun structs referenced by objects of this type. Without
+    /// it, objects of this type are assumed not to reference any Mun structs.
+    pub fn with_trace(mut self, trace: TraceFn) -> Self {
+        self.trace = Some(trace);
+        self
+    }
+
+    /// Sets the function that drops objects of this type when they are garbage collected. Without
+    /// it, objects of this type are never dropped.
+    pub fn with_drop(mut self, drop: DropFn) -> Self {
+        self.drop = Some(drop);
+        self
+    }
+
+    /// Returns the type information of the host-defined type.
+    pub fn type_info(&self) -> &abi::TypeInfo {
+        &self.type_info
+    }
+
+    /// Returns the function that traces the Mun structs referenced by objects of this type, if
+    /// any.
+    pub(crate) fn trace(&self) -> Option<TraceFn> {
+        self.trace
+    }
+
+    /// Returns the function that drops objects of this type, if any.
+    pub(crate) fn drop_fn(&self) -> Option<DropFn> {
+        self.drop
+    }
+
+    /// Retrieves the `ExternTypeInfo` that contains the specified `type_info`.
+    ///
+    /// # Safety
+    ///
+    /// `type_info` must have been obtained using [`ExternTypeInfo::type_info`].
+    pub(crate) unsafe fn from_type_info(type_info: &abi::TypeInfo) -> &Self {
+        &*(type_info as *const abi::TypeInfo).cast::<Self>()
+    }
+}
+
+impl Runtime {
+    /// Allocates an object of the host-defined type `type_name` - that was registered using
+    /// [`RuntimeBuilder::insert_type`](crate::RuntimeBuilder::insert_type) - and moves the `value`
+    /// into it.
+    ///
+    /// The `value` is dropped by the type's drop function when the object is garbage collected. If
+    /// `T` needs to be dropped, the type must therefore have been constructed using
+    /// [`ExternTypeInfo::of`] or have a drop function. Objects that are still alive when the
+    /// runtime is dropped are leaked.
+    ///
+    /// The object is not rooted, and will be garbage collected unless it is referenced by a
+    /// rooted Mun struct.
+    pub fn new_extern<T: 'static>(&self, type_name: &str, value: T) -> Result<RawStruct, String> {
+        let extern_type = self
+            .extern_types
+            .iter()
+            .find(|ty| ty.type_info().name() == type_name)
+            .ok_or_else(|| format!("Failed to obtain extern type `{}`.", type_name))?;
+
+        let type_info = extern_type.type_info();
+        if type_info.size_in_bytes() != std::mem::size_of::<T>()
+            || type_info.alignment() != std::mem::align_of::<T>()
+        {
+            return Err(format!(
+                "Mismatched layout for `{}`. Expected: {} bytes, aligned to {}. Found: {} bytes, aligned to {}.",
+                type_name,
+                type_info.size_in_bytes(),
+                type_info.alignment(),
+                std::mem::size_of::<T>(),
+                std::mem::align_of::<T>(),
+            ));
+        }
+
+        if extern_type
+            .type_id
+            .map_or(false, |type_id| type_id != TypeId::of::<T>())
+        {
+            return Err(format!(
+                "Mismatched type for `{}`. Found: `{}`.",
+                type_name,
+                std::any::type_name::<T>(),
+            ));
+        }
+
+        if std::mem::needs_drop::<T>() && extern_type.drop.is_none() {
+            return Err(format!(
+                "`{}` needs to be dropped, but `{}` has no drop function.",
+                std::any::type_name::<T>(),
+                type_name,
+            ));
+        }
+
+        let mut handle = self
+            .gc()
+            .alloc(UnsafeTypeInfo::new(NonNull::from(type_info)))?;
+
+        // Safety: the object was allocated with the memory layout of `T`
+        unsafe { handle.deref_mut::<T>().write(value) };
+        Ok(RawStruct::from(handle))
+    }
+}
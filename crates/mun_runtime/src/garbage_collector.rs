@@ -1,8 +1,9 @@
+use crate::ExternTypeInfo;
 use memory::{
-    gc::{self, HasIndirectionPtr},
+    gc::{self, GcRuntime, HasIndirectionPtr},
     TypeGroup,
 };
-use std::{alloc::Layout, hash::Hash, ptr::NonNull};
+use std::{alloc::Layout, hash::Hash, ptr::NonNull, sync::Arc};
 
 /// `UnsafeTypeInfo` is a type that wraps a `NonNull<TypeInfo>` and indicates unsafe interior
 /// operations on the wrapped `TypeInfo`. The unsafety originates from uncertainty about the
@@ -81,6 +82,14 @@ impl memory::TypeFields<UnsafeTypeInfo> for UnsafeTypeInfo {
             &[]
         }
     }
+
+    fn fields_renamed_from(&self) -> Vec<Option<&str>> {
+        if let Some(s) = unsafe { self.0.as_ref().as_struct() } {
+            s.field_renamed_from().collect()
+        } else {
+            Vec::new()
+        }
+    }
 }
 
 unsafe impl Send for UnsafeTypeInfo {}
@@ -90,12 +99,18 @@ pub struct Trace {
     obj: GcPtr,
     ty: UnsafeTypeInfo,
     index: usize,
+    /// The objects referenced by an object of an extern type, as reported by its trace function
+    extern_refs: std::vec::IntoIter<GcPtr>,
 }
 
 impl Iterator for Trace {
     type Item = GcPtr;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(obj) = self.extern_refs.next() {
+            return Some(obj);
+        }
+
         let struct_ty = unsafe { self.ty.0.as_ref() }.as_struct()?;
         let field_count = struct_ty.field_types().len();
         while self.index < field_count {
@@ -104,7 +119,10 @@ impl Iterator for Trace {
 
             let field_ty = struct_ty.field_types()[index];
             if let Some(field_struct_ty) = field_ty.as_struct() {
-                if field_struct_ty.memory_kind == abi::StructMemoryKind::GC {
+                if matches!(
+                    field_struct_ty.memory_kind,
+                    abi::StructMemoryKind::GC | abi::StructMemoryKind::Extern
+                ) {
                     let offset = struct_ty.field_offsets()[index];
                     return Some(unsafe {
                         *self.obj.deref::<u8>().add(offset as usize).cast::<GcPtr>()
@@ -137,10 +155,41 @@ impl gc::TypeTrace for UnsafeTypeInfo {
     type Trace = Trace;
 
     fn trace(&self, obj: GcPtr) -> Self::Trace {
+        let ty = unsafe { self.0.as_ref() };
+        let mut extern_refs = Vec::new();
+        if ty
+            .as_struct()
+            .map_or(false, |s| s.memory_kind == abi::StructMemoryKind::Extern)
+        {
+            // Safety: objects of extern types are only allocated through `HostGc`, which resolves
+            // their type information to that of an `ExternTypeInfo`. Mun code cannot construct
+            // extern structs, and objects of extern types are never mapped.
+            let extern_ty = unsafe { ExternTypeInfo::from_type_info(ty) };
+            if let Some(trace) = extern_ty.trace() {
+                let ptr = unsafe { NonNull::new_unchecked(obj.deref::<u8>() as *mut u8) };
+                trace(ptr, &mut |raw| extern_refs.push(raw.handle()));
+            }
+        }
+
         Trace {
             ty: *self,
             obj,
             index: 0,
+            extern_refs: extern_refs.into_iter(),
+        }
+    }
+
+    fn finalize(&self, obj: GcPtr) {
+        let ty = unsafe { self.0.as_ref() };
+        if ty
+            .as_struct()
+            .map_or(false, |s| s.memory_kind == abi::StructMemoryKind::Extern)
+        {
+            // Safety: see `trace`
+            let extern_ty = unsafe { ExternTypeInfo::from_type_info(ty) };
+            if let Some(drop_fn) = extern_ty.drop_fn() {
+                drop_fn(unsafe { NonNull::new_unchecked(obj.deref::<u8>() as *mut u8) });
+            }
         }
     }
 }
@@ -149,4 +198,80 @@ impl gc::TypeTrace for UnsafeTypeInfo {
 pub type GarbageCollector = gc::MarkSweep<UnsafeTypeInfo, gc::NoopObserver<gc::Event>>;
 
 pub use gc::GcPtr;
+
+/// The garbage collector of a [`Runtime`](crate::Runtime), as exposed to the host.
+///
+/// Tracing and dropping an object of an extern type relies on its type information being contained
+/// in an [`ExternTypeInfo`]. Assemblies contain their own type information for the extern structs
+/// that they declare, so extern types are resolved - by `Guid` - to the types registered with the
+/// runtime upon allocation.
+pub struct HostGc {
+    gc: Arc<GarbageCollector>,
+    extern_types: Vec<UnsafeTypeInfo>,
+}
+
+impl HostGc {
+    /// Constructs a `HostGc` that allocates objects in `gc`, resolving extern types to the
+    /// `extern_types` registered with the runtime.
+    ///
+    /// # Safety
+    ///
+    /// The `extern_types` must outlive the `HostGc`.
+    pub(crate) unsafe fn new(gc: Arc<GarbageCollector>, extern_types: &[ExternTypeInfo]) -> Self {
+        Self {
+            gc,
+            extern_types: extern_types
+                .iter()
+                .map(|ty| UnsafeTypeInfo::new(NonNull::from(ty.type_info())))
+                .collect(),
+        }
+    }
+
+    /// Allocates an object of the given type returning a GcPtr. Returns an error if `ty` is an
+    /// extern type that was not registered with the runtime.
+    pub fn alloc(&self, ty: UnsafeTypeInfo) -> Result<GcPtr, String> {
+        let type_info = unsafe { ty.0.as_ref() };
+        let ty = if type_info
+            .as_struct()
+            .map_or(false, |s| s.memory_kind == abi::StructMemoryKind::Extern)
+        {
+            *self
+                .extern_types
+                .iter()
+                .find(|extern_ty| unsafe { extern_ty.0.as_ref() }.guid == type_info.guid)
+                .ok_or_else(|| {
+                    format!(
+                        "Extern type `{}` is not registered with the runtime.",
+                        type_info.name()
+                    )
+                })?
+        } else {
+            ty
+        };
+        Ok(self.gc.alloc(ty))
+    }
+
+    /// Returns the type of the specified `obj`.
+    pub fn ptr_type(&self, obj: GcPtr) -> UnsafeTypeInfo {
+        self.gc.ptr_type(obj)
+    }
+
+    /// Roots the specified `obj`, which keeps it and objects it references alive. See
+    /// [`GcRuntime::root`].
+    pub fn root(&self, obj: GcPtr) {
+        self.gc.root(obj)
+    }
+
+    /// Unroots the specified `obj`, potentially allowing it and objects it references to be
+    /// collected. See [`GcRuntime::unroot`].
+    pub fn unroot(&self, obj: GcPtr) {
+        self.gc.unroot(obj)
+    }
+
+    /// Returns stats about the current state of the garbage collector.
+    pub fn stats(&self) -> gc::Stats {
+        self.gc.stats()
+    }
+}
+
 pub type GcRootPtr = gc::GcRootPtr<UnsafeTypeInfo, GarbageCollector>;
@@ -0,0 +1,494 @@
+use crate::{
+    assembly::{AssemblyLibrary, LibrarySymbols},
+    Assembly, DiagnosticsFn, ReloadError, ReloadEvent, Runtime,
+};
+use abi::AssemblyInfo;
+use compiler::{
+    compute_source_relative_path, is_source_file, AssemblyBitcode, Config, DisplayColor, Driver,
+    JitAssembly, RelativePathBuf,
+};
+use log::{error, info, warn};
+use mun_project::{Manifest, ProfileKind};
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    collections::HashMap,
+    ffi::c_void,
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{channel, Receiver},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// Compiles the assemblies of a Mun package - and its dependencies - in memory, and keeps track of
+/// changes to their source files.
+pub(crate) struct JitCompiler {
+    driver: Driver,
+    source_directory: PathBuf,
+    /// The canonical source directories of all packages, indexed like the package graph
+    package_directories: Vec<PathBuf>,
+    /// Watches the source directories of all packages, unless hot reloading is disabled
+    _watcher: Option<RecommendedWatcher>,
+    watcher_rx: Receiver<DebouncedEvent>,
+    /// The bitcode of the assemblies that are currently linked
+    linked_bitcode: HashMap<PathBuf, Arc<AssemblyBitcode>>,
+    /// The compiled code of assemblies that were unlinked because their source files were removed.
+    /// Objects of their types can outlive them, so their type information is retained.
+    unlinked_libraries: Vec<AssemblyLibrary>,
+    diagnostics_fn: Option<DiagnosticsFn>,
+}
+
+impl JitCompiler {
+    /// Constructs a `JitCompiler` for the package at `manifest_path`, that compiles with the settings
+    /// of the package's `profile`. If `hot_reloading` is enabled, it starts watching the source
+    /// directories of the package and its dependencies. Compiler diagnostics are passed to
+    /// `diagnostics_fn`, if specified.
+    fn new(
+        manifest_path: &Path,
+        profile: ProfileKind,
+        instrument_functions: bool,
+        instrument_coverage: bool,
+        hot_reloading: bool,
+        diagnostics_fn: Option<DiagnosticsFn>,
+    ) -> anyhow::Result<Self> {
+        let config = Config {
+            display_color: DisplayColor::Disable,
+            instrument_functions,
+            instrument_coverage,
+            ..Config::default()
+        };
+        let manifest = Manifest::from_file(manifest_path)?;
+        let config = config.with_profile(manifest.profile(profile));
+        let (package, driver) = Driver::with_package_path(manifest_path, config)?;
+
+        // Directories are canonicalized, such that the paths of events can be matched against them
+        let package_directories: Vec<PathBuf> = driver
+            .package_graph()
+            .expect("a driver that compiles a package has a package graph")
+            .packages()
+            .iter()
+            .map(|package| {
+                let source_directory = package.source_directory();
+                source_directory.canonicalize().unwrap_or(source_directory)
+            })
+            .collect();
+
+        // Without a watcher, the sender is dropped and no events are ever received
+        let (tx, rx) = channel();
+        let watcher = if hot_reloading {
+            let mut watcher: RecommendedWatcher = Watcher::new(tx, Duration::from_millis(10))?;
+            for directory in package_directories.iter() {
+                watcher.watch(directory, RecursiveMode::Recursive)?;
+            }
+            Some(watcher)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            driver,
+            source_directory: package.source_directory(),
+            package_directories,
+            _watcher: watcher,
+            watcher_rx: rx,
+            linked_bitcode: HashMap::new(),
+            unlinked_libraries: Vec::new(),
+            diagnostics_fn,
+        })
+    }
+
+    /// Returns the index of the package that contains the source file at `path`, together with
+    /// the path of the file relative to the package's source directory. Returns `None` if `path` is
+    /// not a source file of any package.
+    fn source_file(&self, path: &Path) -> anyhow::Result<Option<(usize, RelativePathBuf)>> {
+        if !is_source_file(path) {
+            return Ok(None);
+        }
+
+        // The innermost directory is used, in case a package is nested in another
+        let package = self
+            .package_directories
+            .iter()
+            .enumerate()
+            .filter(|(_, directory)| path.starts_with(directory))
+            .max_by_key(|(_, directory)| directory.as_os_str().len());
+        match package {
+            Some((package_idx, directory)) => Ok(Some((
+                package_idx,
+                compute_source_relative_path(directory, path)?,
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    /// Applies all changes to the source files of the packages to the compiler. Returns whether any
+    /// source file changed.
+    fn process_events(&mut self) -> anyhow::Result<bool> {
+        let mut has_changes = false;
+        while let Ok(event) = self.watcher_rx.try_recv() {
+            use notify::DebouncedEvent::*;
+            match event {
+                Write(ref path) => {
+                    if let Some((package_idx, relative_path)) = self.source_file(path)? {
+                        let file_contents = std::fs::read_to_string(path)?;
+                        self.driver
+                            .update_package_file(package_idx, relative_path, file_contents);
+                        has_changes = true;
+                    }
+                }
+                Create(ref path) => {
+                    if let Some((package_idx, relative_path)) = self.source_file(path)? {
+                        let file_contents = std::fs::read_to_string(path)?;
+                        self.driver
+                            .add_package_file(package_idx, relative_path, file_contents);
+                        has_changes = true;
+                    }
+                }
+                Remove(ref path) => {
+                    if let Some((package_idx, relative_path)) = self.source_file(path)? {
+                        self.driver.remove_package_file(package_idx, relative_path);
+                        has_changes = true;
+                    }
+                }
+                Rename(ref from, ref to) => {
+                    match (self.source_file(from)?, self.source_file(to)?) {
+                        (
+                            Some((from_idx, from_relative_path)),
+                            Some((to_idx, to_relative_path)),
+                        ) if from_idx == to_idx => {
+                            self.driver.rename_package_file(
+                                from_idx,
+                                from_relative_path,
+                                to_relative_path,
+                            );
+                            has_changes = true;
+                        }
+                        // A file that is renamed to, or from, something other than a source file of the
+                        // same package is treated as a removal, and a creation respectively
+                        (from_file, to_file) => {
+                            if let Some((package_idx, relative_path)) = from_file {
+                                self.driver.remove_package_file(package_idx, relative_path);
+                                has_changes = true;
+                            }
+                            if let Some((package_idx, relative_path)) = to_file {
+                                let file_contents = std::fs::read_to_string(to)?;
+                                self.driver.add_package_file(
+                                    package_idx,
+                                    relative_path,
+                                    file_contents,
+                                );
+                                has_changes = true;
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(has_changes)
+    }
+
+    /// Compiles all assemblies of which the code changed since they were last linked. Returns the
+    /// compiled assemblies, together with their path and bitcode.
+    fn compile(
+        &mut self,
+    ) -> Result<Vec<(PathBuf, Arc<AssemblyBitcode>, JitAssembly)>, ReloadError> {
+        let mut diagnostics = Vec::new();
+        let has_errors = self
+            .driver
+            .emit_diagnostics(&mut diagnostics)
+            .map_err(|e| ReloadError::Compile(e.to_string()))?;
+        let diagnostics = String::from_utf8_lossy(&diagnostics).into_owned();
+        if !diagnostics.is_empty() {
+            if let Some(diagnostics_fn) = self.diagnostics_fn.as_mut() {
+                diagnostics_fn(&diagnostics);
+            } else if has_errors {
+                error!("{}", diagnostics);
+            } else {
+                warn!("{}", diagnostics);
+            }
+        }
+        if has_errors {
+            return Err(ReloadError::Compile(diagnostics));
+        }
+
+        self.driver
+            .assembly_bitcodes()
+            .into_iter()
+            .map(|(relative_path, bitcode)| {
+                (relative_path.to_path(&self.source_directory), bitcode)
+            })
+            .filter(|(path, bitcode)| self.linked_bitcode.get(path) != Some(bitcode))
+            .map(|(path, bitcode)| {
+                let assembly = self
+                    .driver
+                    .jit_compile(&bitcode)
+                    .and_then(|assembly| {
+                        validate_symbols(&assembly)?;
+                        Ok(assembly)
+                    })
+                    .map_err(|source| ReloadError::Load {
+                        path: path.clone(),
+                        source,
+                    })?;
+                Ok((path, bitcode, assembly))
+            })
+            .collect()
+    }
+
+    /// Returns the paths of the linked assemblies of which all source files were removed.
+    fn removed_assemblies(&self) -> Vec<PathBuf> {
+        let paths: Vec<PathBuf> = self
+            .driver
+            .assembly_bitcodes()
+            .into_iter()
+            .map(|(relative_path, _)| relative_path.to_path(&self.source_directory))
+            .collect();
+
+        let mut removed: Vec<PathBuf> = self
+            .linked_bitcode
+            .keys()
+            .filter(|path| !paths.contains(path))
+            .cloned()
+            .collect();
+        removed.sort();
+        removed
+    }
+
+    /// Marks the `bitcode` as linked, such that it is not recompiled until its source files change.
+    fn set_linked(&mut self, bitcode: impl IntoIterator<Item = (PathBuf, Arc<AssemblyBitcode>)>) {
+        self.linked_bitcode.extend(bitcode);
+    }
+
+    /// Marks the `assembly` as unlinked, retaining its compiled code.
+    fn set_unlinked(&mut self, assembly: Assembly) {
+        self.linked_bitcode.remove(assembly.library_path());
+        self.unlinked_libraries.push(assembly.into_library());
+    }
+}
+
+impl Runtime {
+    /// Compiles the assemblies of the package at `manifest_path` in memory - with the settings of
+    /// its `profile` - and links them. If `hot_reloading` is enabled, they are recompiled when the
+    /// package's source files change. Compiler diagnostics are passed to `diagnostics_fn`, if
+    /// specified.
+    pub(crate) fn add_jit_package(
+        &mut self,
+        manifest_path: &Path,
+        profile: ProfileKind,
+        hot_reloading: bool,
+        diagnostics_fn: Option<DiagnosticsFn>,
+    ) -> anyhow::Result<()> {
+        let mut compiler = JitCompiler::new(
+            manifest_path,
+            profile,
+            self.profiler.is_some(),
+            self.coverage.is_some(),
+            hot_reloading,
+            diagnostics_fn,
+        )?;
+
+        let mut loaded = HashMap::new();
+        let mut linked_bitcode = Vec::new();
+        for (path, bitcode, jit_assembly) in compiler.compile()? {
+            let assembly = Assembly::from_library(
+                path.clone(),
+                jit_assembly,
+                self.gc.clone(),
+                self.profiler.clone(),
+                self.coverage.clone(),
+            )?;
+            loaded.insert(path.clone(), assembly);
+            linked_bitcode.push((path, bitcode));
+        }
+
+        self.dispatch_table = Assembly::link_all(
+            loaded.values_mut(),
+            &self.dispatch_table,
+            &self.extern_types,
+        )?;
+        self.assemblies.extend(loaded);
+
+        compiler.set_linked(linked_bitcode);
+        self.jit = Some(compiler);
+
+        Ok(())
+    }
+
+    /// Recompiles and relinks the assemblies of the package that is executed by the JIT backend, if
+    /// its source files changed. The function pointers of recompiled functions are swapped in the
+    /// dispatch table. If reloading fails, it is retried when the source files change again.
+    pub(crate) fn jit_update(&mut self) -> Result<bool, ReloadError> {
+        let compiler = self
+            .jit
+            .as_mut()
+            .expect("the runtime does not use the JIT backend");
+
+        let has_changes = compiler
+            .process_events()
+            .map_err(|e| ReloadError::Compile(e.to_string()))?;
+        if !has_changes {
+            return Ok(false);
+        }
+
+        let mut loaded = HashMap::new();
+        let mut linked_bitcode = Vec::new();
+        for (path, bitcode, jit_assembly) in compiler.compile()? {
+            let assembly = Assembly::from_library(
+                path.clone(),
+                jit_assembly,
+                self.gc.clone(),
+                self.profiler.clone(),
+                self.coverage.clone(),
+            )
+            .map_err(|source| ReloadError::Load {
+                path: path.clone(),
+                source,
+            })?;
+            loaded.insert(path.clone(), assembly);
+            linked_bitcode.push((path, bitcode));
+        }
+
+        let removed = compiler.removed_assemblies();
+        if loaded.is_empty() && removed.is_empty() {
+            return Ok(false);
+        }
+
+        info!("Relinking recompiled assemblies:");
+        for path in loaded.keys() {
+            info!("{}", path.to_string_lossy());
+        }
+
+        // Remove the functions of assemblies of which the source files were removed. Any remaining
+        // code that called them would have failed to compile.
+        let mut dispatch_table = self.dispatch_table.clone();
+        let mut removed_functions = Vec::new();
+        for path in removed.iter() {
+            info!("Unlinking removed assembly: {}", path.to_string_lossy());
+            for function in self.assemblies[path].info().symbols.functions() {
+                dispatch_table.remove_fn(function.prototype.name());
+                removed_functions.push(function.prototype.name().to_owned());
+            }
+        }
+
+        let (dispatch_table, event) = Assembly::relink_all(
+            &mut loaded,
+            &mut self.assemblies,
+            &dispatch_table,
+            &self.extern_types,
+        )?;
+        self.dispatch_table = dispatch_table;
+        compiler.set_linked(linked_bitcode);
+        for path in removed.iter() {
+            let assembly = self
+                .assemblies
+                .remove(path)
+                .expect("a removed assembly must be linked");
+            compiler.set_unlinked(assembly);
+        }
+
+        let event = if removed.is_empty() {
+            event
+        } else {
+            let mut assemblies = event.assemblies;
+            assemblies.extend(removed);
+            assemblies.sort();
+
+            let mut changed_functions = event.changed_functions;
+            changed_functions.extend(removed_functions);
+            changed_functions.sort();
+            changed_functions.dedup();
+
+            ReloadEvent {
+                assemblies,
+                changed_functions,
+                ..event
+            }
+        };
+        self.on_reloaded(event);
+
+        Ok(true)
+    }
+}
+
+impl From<JitAssembly> for AssemblyLibrary {
+    fn from(assembly: JitAssembly) -> Self {
+        AssemblyLibrary::Jit(assembly)
+    }
+}
+
+// The required symbols are checked by `validate_symbols` upon compilation
+impl LibrarySymbols for JitAssembly {
+    fn get_abi_version(&self) -> u32 {
+        let get_abi_version_fn: extern "C" fn() -> u32 =
+            unsafe { get_fn(self, abi::GET_VERSION_FN_NAME) }.unwrap();
+
+        get_abi_version_fn()
+    }
+
+    fn get_info(&self) -> AssemblyInfo {
+        let get_info_fn: extern "C" fn() -> AssemblyInfo =
+            unsafe { get_fn(self, abi::GET_INFO_FN_NAME) }.unwrap();
+
+        get_info_fn()
+    }
+
+    fn set_allocator_handle(&mut self, allocator_ptr: *mut c_void) {
+        let set_allocator_handle_fn: extern "C" fn(*mut c_void) =
+            unsafe { get_fn(self, abi::SET_ALLOCATOR_HANDLE_FN_NAME) }.unwrap();
+
+        set_allocator_handle_fn(allocator_ptr);
+    }
+
+    fn set_profiler_handle(&mut self, profiler_ptr: *mut c_void) -> bool {
+        set_instrumentation_handle(self, abi::SET_PROFILER_HANDLE_FN_NAME, profiler_ptr)
+    }
+
+    fn set_coverage_handle(&mut self, coverage_ptr: *mut c_void) -> bool {
+        set_instrumentation_handle(self, abi::SET_COVERAGE_HANDLE_FN_NAME, coverage_ptr)
+    }
+}
+
+/// Ensures that the `assembly` contains the functions that the runtime requires to load it.
+fn validate_symbols(assembly: &JitAssembly) -> anyhow::Result<()> {
+    for fn_name in [
+        abi::GET_VERSION_FN_NAME,
+        abi::GET_INFO_FN_NAME,
+        abi::SET_ALLOCATOR_HANDLE_FN_NAME,
+    ]
+    .iter()
+    {
+        if assembly.get_symbol_address(fn_name).is_none() {
+            return Err(anyhow::anyhow!(
+                "the compiled assembly does not contain the `{}` function",
+                fn_name
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Retrieves the function called `fn_name` from the `assembly`, if it exists.
+///
+/// # Safety
+///
+/// `T` must be a function pointer with the same signature as the function.
+unsafe fn get_fn<T: Copy>(assembly: &JitAssembly, fn_name: &str) -> Option<T> {
+    assembly
+        .get_symbol_address(fn_name)
+        .map(|address| std::mem::transmute_copy(&address.as_ptr()))
+}
+
+/// Sets an instrumentation handle of the `assembly`, if it exists. Returns whether or not the
+/// assembly is instrumented.
+fn set_instrumentation_handle(assembly: &JitAssembly, fn_name: &str, handle: *mut c_void) -> bool {
+    let set_handle_fn: Option<extern "C" fn(*mut c_void)> = unsafe { get_fn(assembly, fn_name) };
+    if let Some(set_handle_fn) = set_handle_fn {
+        set_handle_fn(handle);
+        true
+    } else {
+        false
+    }
+}
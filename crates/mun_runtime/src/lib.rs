@@ -10,46 +10,128 @@ mod macros;
 #[macro_use]
 mod garbage_collector;
 mod adt;
+mod coverage;
+mod dynamic;
+mod extern_type;
+#[cfg(feature = "jit")]
+mod jit;
 mod marshal;
+mod mun_struct;
+mod profiler;
 mod reflection;
+#[cfg(feature = "serde")]
+mod serialization;
+mod static_library;
+mod value;
 
 use anyhow::Result;
+use coverage::coverage_hit;
+#[cfg(feature = "hot_reloading")]
 use ffi::OsString;
 use garbage_collector::GarbageCollector;
-use log::{debug, error, info};
+use log::error;
+#[cfg(feature = "hot_reloading")]
+use log::{debug, info};
 use memory::gc::{self, GcRuntime};
+#[cfg(feature = "hot_reloading")]
 use mun_project::LOCKFILE_NAME;
+#[cfg(feature = "hot_reloading")]
 use notify::{RawEvent, RecommendedWatcher, RecursiveMode, Watcher};
+use profiler::{profiler_enter, profiler_exit};
 use rustc_hash::FxHashMap;
+#[cfg(feature = "hot_reloading")]
+use std::sync::mpsc::{channel, Receiver};
 use std::{
     cell::RefCell,
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     ffi, io, mem,
     path::{Path, PathBuf},
     ptr::NonNull,
     rc::Rc,
     string::ToString,
-    sync::{
-        mpsc::{channel, Receiver},
-        Arc,
-    },
+    sync::Arc,
 };
 
+#[cfg(feature = "serde")]
+pub use crate::serialization::StructSeed;
 pub use crate::{
-    adt::{RootedStruct, StructRef},
-    assembly::Assembly,
-    garbage_collector::UnsafeTypeInfo,
+    adt::{RawStruct, RootedStruct, StructRef},
+    assembly::{Assembly, AssemblyLibrary, LinkError, ReloadError, ReloadEvent},
+    coverage::{Coverage, FunctionCoverage},
+    extern_type::{DropFn, ExternTypeInfo, TraceFn},
+    garbage_collector::{HostGc, UnsafeTypeInfo},
     marshal::Marshal,
+    mun_struct::MunStruct,
+    profiler::{FunctionProfile, Profiler, MAX_TRACE_EVENTS},
     reflection::{ArgumentReflection, ReturnTypeReflection},
+    static_library::StaticLibrary,
+    value::Value,
 };
-pub use abi::IntoFunctionDefinition;
+pub use abi::{self, IntoFunctionDefinition};
+#[cfg(feature = "jit")]
+pub use mun_project::ProfileKind;
+pub use mun_runtime_macros::MunStruct;
+
+/// The backend that a [`Runtime`] uses to execute Mun code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    /// Loads `*.munlib` shared libraries that were compiled by the Mun compiler, and reloads them
+    /// when they are rewritten.
+    SharedLibrary,
+    /// Compiles the source code of a Mun package in memory, using LLVM's ORC JIT, and recompiles
+    /// it when its source files change. The library path of the [`Runtime`] is interpreted as the
+    /// path of the package's manifest, whose profile of the specified kind is used for compilation.
+    #[cfg(feature = "jit")]
+    Jit(ProfileKind),
+}
+
+impl Default for Backend {
+    fn default() -> Self {
+        Backend::SharedLibrary
+    }
+}
+
+/// A function that receives the diagnostics of a compilation, when the [`Runtime`] compiles Mun
+/// source code itself.
+pub type DiagnosticsFn = Box<dyn FnMut(&str) + Send>;
+
+/// A function that is called with a description of the changes, whenever the [`Runtime`] reloads
+/// assemblies. It is called once the update that reloaded the assemblies has completed.
+pub type ReloadFn = Box<dyn FnMut(&ReloadEvent) + Send>;
+
+/// The maximum number of [`ReloadEvent`]s that the [`Runtime`] retains until they are polled using
+/// [`Runtime::poll_reload_event`]. When exceeded, the oldest event is discarded.
+pub const MAX_QUEUED_RELOAD_EVENTS: usize = 16;
 
 /// Options for the construction of a [`Runtime`].
 pub struct RuntimeOptions {
-    /// Path to the entry point library
+    /// Path to the entry point library, or - when using the [`Backend::Jit`] - the package manifest
     pub library_path: PathBuf,
+    /// The backend used to execute Mun code
+    pub backend: Backend,
+    /// Function that receives compiler diagnostics. If it is not specified, diagnostics are
+    /// logged. Only the JIT backend compiles Mun code, so it cannot be specified for other
+    /// backends.
+    pub diagnostics_fn: Option<DiagnosticsFn>,
+    /// Function that is called whenever assemblies are reloaded.
+    pub reload_fn: Option<ReloadFn>,
+    /// Contents of libraries that are loaded from memory instead of from disk, by library path.
+    /// Libraries that are loaded from memory are not hot reloaded.
+    pub library_bytes: Vec<(PathBuf, Vec<u8>)>,
+    /// Libraries that are statically linked into the executable, by library path. Statically
+    /// linked libraries are not hot reloaded.
+    pub static_libraries: Vec<(PathBuf, StaticLibrary)>,
     /// Custom user injected functions
     pub user_functions: Vec<(abi::FunctionDefinition, abi::FunctionDefinitionStorage)>,
+    /// Custom user defined types, that can be used as `extern struct`s
+    pub user_types: Vec<ExternTypeInfo>,
+    /// Whether or not to profile instrumented functions
+    pub enable_profiler: bool,
+    /// Whether or not to count the block executions of instrumented functions
+    pub enable_coverage: bool,
+    /// Whether or not to watch loaded libraries - or, when using the [`Backend::Jit`], source
+    /// files - for changes. Without the `hot_reloading` feature, this option has no effect.
+    pub hot_reloading: bool,
 }
 
 /// Retrieve the allocator using the provided handle.
@@ -80,6 +162,14 @@ extern "C" fn new(
     handle.into()
 }
 
+/// A library that is not loaded from disk, and can therefore not be hot reloaded.
+enum EmbeddedLibrary {
+    /// The contents of a shared library, which is loaded from memory
+    Bytes(Vec<u8>),
+    /// A library that is statically linked into the executable
+    Static(StaticLibrary),
+}
+
 /// A builder for the [`Runtime`].
 pub struct RuntimeBuilder {
     options: RuntimeOptions,
@@ -91,11 +181,63 @@ impl RuntimeBuilder {
         Self {
             options: RuntimeOptions {
                 library_path: library_path.into(),
+                backend: Backend::default(),
+                diagnostics_fn: None,
+                reload_fn: None,
+                library_bytes: Default::default(),
+                static_libraries: Default::default(),
                 user_functions: Default::default(),
+                user_types: Default::default(),
+                enable_profiler: false,
+                enable_coverage: false,
+                hot_reloading: true,
             },
         }
     }
 
+    /// Constructs a new `RuntimeBuilder` for a shared library that is loaded from its contents,
+    /// `bytes`, instead of from disk. The `library_path` identifies the library, and is used to
+    /// resolve the paths of its dependencies.
+    pub fn from_bytes<P: Into<PathBuf>>(library_path: P, bytes: Vec<u8>) -> Self {
+        let library_path = library_path.into();
+        Self::new(library_path.clone()).insert_library_bytes(library_path, bytes)
+    }
+
+    /// Adds the contents of the shared library at `library_path`, such that it is loaded from
+    /// memory instead of from disk. This allows loading the dependencies of a library from memory.
+    pub fn insert_library_bytes<P: Into<PathBuf>>(
+        mut self,
+        library_path: P,
+        bytes: Vec<u8>,
+    ) -> Self {
+        self.options
+            .library_bytes
+            .push((library_path.into(), bytes));
+        self
+    }
+
+    /// Constructs a new `RuntimeBuilder` for an assembly that is statically linked into the
+    /// executable. The `library_path` identifies the library, and is used to resolve the paths of
+    /// its dependencies.
+    pub fn from_static<P: Into<PathBuf>>(library_path: P, library: StaticLibrary) -> Self {
+        let library_path = library_path.into();
+        Self::new(library_path.clone()).insert_static_library(library_path, library)
+    }
+
+    /// Adds an assembly that is statically linked into the executable, such that it is used
+    /// instead of loading the shared library at `library_path`. This allows statically linking
+    /// the dependencies of a library.
+    pub fn insert_static_library<P: Into<PathBuf>>(
+        mut self,
+        library_path: P,
+        library: StaticLibrary,
+    ) -> Self {
+        self.options
+            .static_libraries
+            .push((library_path.into(), library));
+        self
+    }
+
     /// Adds a custom user function to the dispatch table.
     pub fn insert_fn<S: AsRef<str>, F: abi::IntoFunctionDefinition>(
         mut self,
@@ -106,6 +248,75 @@ impl RuntimeBuilder {
         self
     }
 
+    /// Adds a custom user type, that can be used in Mun code by declaring an `extern struct` with
+    /// the same name.
+    ///
+    /// To pass objects of the type to and from `extern fn`s, the functions' parameter and return
+    /// types must implement [`abi::HasStaticTypeInfo`], returning a `TypeInfo` with the same `Guid`
+    /// as `type_info` - e.g. by using [`abi::TypeInfo::new_extern_struct`].
+    pub fn insert_type(mut self, type_info: ExternTypeInfo) -> Self {
+        self.options.user_types.push(type_info);
+        self
+    }
+
+    /// Constructs a new `RuntimeBuilder` for the Mun package at `manifest_path`. The [`Runtime`]
+    /// compiles the package itself - with the settings of the package's `profile` - watches its
+    /// source files, and hot reloads them when [`Runtime::update`] is called.
+    ///
+    /// Compiler diagnostics are passed to the function specified with
+    /// [`RuntimeBuilder::on_diagnostics`].
+    #[cfg(feature = "jit")]
+    pub fn from_package<P: Into<PathBuf>>(manifest_path: P, profile: ProfileKind) -> Self {
+        Self::new(manifest_path).backend(Backend::Jit(profile))
+    }
+
+    /// Sets the function that receives the diagnostics - both errors and warnings - of compilations
+    /// performed by the [`Runtime`]. By default, diagnostics are logged.
+    ///
+    /// Only the JIT backend compiles Mun code, so spawning a [`Runtime`] that uses another backend
+    /// fails if this function is set.
+    pub fn on_diagnostics<F: FnMut(&str) + Send + 'static>(mut self, diagnostics_fn: F) -> Self {
+        self.options.diagnostics_fn = Some(Box::new(diagnostics_fn));
+        self
+    }
+
+    /// Sets the function that is called with a description of the changes, whenever the
+    /// [`Runtime`] reloads assemblies. The events can also be polled using
+    /// [`Runtime::poll_reload_event`].
+    pub fn on_reload<F: FnMut(&ReloadEvent) + Send + 'static>(mut self, reload_fn: F) -> Self {
+        self.options.reload_fn = Some(Box::new(reload_fn));
+        self
+    }
+
+    /// Sets the backend that the [`Runtime`] uses to execute Mun code. By default, shared libraries
+    /// are loaded.
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.options.backend = backend;
+        self
+    }
+
+    /// Enables the profiler of the [`Runtime`]. Only assemblies that were compiled with function
+    /// instrumentation are profiled.
+    pub fn enable_profiler(mut self) -> Self {
+        self.options.enable_profiler = true;
+        self
+    }
+
+    /// Enables code coverage of the [`Runtime`]. Only assemblies that were compiled with coverage
+    /// instrumentation are counted.
+    pub fn enable_coverage(mut self) -> Self {
+        self.options.enable_coverage = true;
+        self
+    }
+
+    /// Disables hot reloading. The [`Runtime`] does not watch for changes, and
+    /// [`Runtime::update`] never reloads assemblies. This avoids the overhead of file watching in
+    /// shipped applications.
+    pub fn disable_hot_reloading(mut self) -> Self {
+        self.options.hot_reloading = false;
+        self
+    }
+
     /// Spawns a [`Runtime`] with the builder's options.
     pub fn spawn(self) -> anyhow::Result<Rc<RefCell<Runtime>>> {
         Runtime::new(self.options).map(|runtime| Rc::new(RefCell::new(runtime)))
@@ -141,6 +352,14 @@ impl DispatchTable {
         self.functions.insert(fn_path.to_string(), fn_info)
     }
 
+    /// Returns an iterator over the paths and [`abi::FunctionDefinition`]s of all functions in the
+    /// dispatch table.
+    pub fn iter_fns(&self) -> impl Iterator<Item = (&str, &abi::FunctionDefinition)> {
+        self.functions
+            .iter()
+            .map(|(fn_path, fn_info)| (fn_path.as_str(), fn_info))
+    }
+
     /// Removes and returns the `fn_info` corresponding to `fn_path`, if it exists.
     pub fn remove_fn<S: AsRef<str>>(&mut self, fn_path: S) -> Option<abi::FunctionDefinition> {
         self.functions.remove(fn_path.as_ref())
@@ -193,22 +412,42 @@ impl DispatchTable {
 pub struct Runtime {
     assemblies: HashMap<PathBuf, Assembly>,
     /// Assemblies that have changed and thus need to be relinked. Maps the old to the (potentially) new path.
+    #[cfg(feature = "hot_reloading")]
     assemblies_to_relink: VecDeque<(PathBuf, PathBuf)>,
     dispatch_table: DispatchTable,
-    watcher: RecommendedWatcher,
+    /// Watches the directories of loaded libraries, unless hot reloading is disabled
+    #[cfg(feature = "hot_reloading")]
+    watcher: Option<RecommendedWatcher>,
+    #[cfg(feature = "hot_reloading")]
     watcher_rx: Receiver<RawEvent>,
+    #[cfg(feature = "hot_reloading")]
     renamed_files: HashMap<u32, PathBuf>,
     gc: Arc<GarbageCollector>,
+    // Refers to the `extern_types`, which are never modified after construction
+    host_gc: HostGc,
+    reload_fn: Option<ReloadFn>,
+    /// Reload events that the `reload_fn` has not been called with yet
+    unnotified_reload_events: Vec<ReloadEvent>,
+    /// Reload events that have not been polled yet
+    reload_events: VecDeque<ReloadEvent>,
+    profiler: Option<Arc<Profiler>>,
+    coverage: Option<Arc<Coverage>>,
     _user_functions: Vec<abi::FunctionDefinitionStorage>,
+    #[cfg(feature = "jit")]
+    jit: Option<jit::JitCompiler>,
+    // Objects of extern types are allocated by the garbage collector, so the types must outlive
+    // it. Hence, this must be the last field.
+    extern_types: Vec<ExternTypeInfo>,
 }
 
+// A `Runtime` can be moved to another thread, e.g. to be driven by a dedicated update thread
+static_assertions::assert_impl_all!(Runtime: Send);
+
 impl Runtime {
     /// Constructs a new `Runtime` that loads the library at `library_path` and its
     /// dependencies. The `Runtime` contains a file watcher that is triggered with an interval
     /// of `dur`.
     pub fn new(mut options: RuntimeOptions) -> anyhow::Result<Runtime> {
-        let (tx, rx) = channel();
-
         let mut dispatch_table = DispatchTable::default();
 
         // Add internal functions
@@ -216,6 +455,18 @@ impl Runtime {
             new as extern "C" fn(*const abi::TypeInfo, *mut ffi::c_void) -> *const *mut ffi::c_void,
             "new",
         ));
+        options.user_functions.push(IntoFunctionDefinition::into(
+            profiler_enter as extern "C" fn(*const u8, *mut ffi::c_void),
+            "profiler_enter",
+        ));
+        options.user_functions.push(IntoFunctionDefinition::into(
+            profiler_exit as extern "C" fn(*const u8, *mut ffi::c_void),
+            "profiler_exit",
+        ));
+        options.user_functions.push(IntoFunctionDefinition::into(
+            coverage_hit as extern "C" fn(*const u8, u32, *mut ffi::c_void),
+            "coverage_hit",
+        ));
 
         let mut storages = Vec::with_capacity(options.user_functions.len());
         for (info, storage) in options.user_functions.into_iter() {
@@ -223,25 +474,98 @@ impl Runtime {
             storages.push(storage)
         }
 
-        let watcher: RecommendedWatcher = Watcher::new_raw(tx)?;
+        #[cfg(feature = "hot_reloading")]
+        let (tx, rx) = channel();
+        #[cfg(feature = "hot_reloading")]
+        let watcher: Option<RecommendedWatcher> = if options.hot_reloading {
+            Some(Watcher::new_raw(tx)?)
+        } else {
+            None
+        };
+
+        let gc = Arc::new(self::garbage_collector::GarbageCollector::default());
+        let extern_types = options.user_types;
+        // Safety: the `extern_types` are stored in the runtime after the `host_gc`, so they outlive
+        // it. Moving the `Vec` does not move its elements.
+        let host_gc = unsafe { HostGc::new(gc.clone(), &extern_types) };
+
         let mut runtime = Runtime {
             assemblies: HashMap::new(),
+            #[cfg(feature = "hot_reloading")]
             assemblies_to_relink: VecDeque::new(),
             dispatch_table,
+            #[cfg(feature = "hot_reloading")]
             watcher,
+            #[cfg(feature = "hot_reloading")]
             watcher_rx: rx,
+            #[cfg(feature = "hot_reloading")]
             renamed_files: HashMap::new(),
-            gc: Arc::new(self::garbage_collector::GarbageCollector::default()),
+            gc,
+            host_gc,
+            reload_fn: options.reload_fn,
+            unnotified_reload_events: Vec::new(),
+            reload_events: VecDeque::new(),
+            profiler: if options.enable_profiler {
+                Some(Arc::new(Profiler::default()))
+            } else {
+                None
+            },
+            coverage: if options.enable_coverage {
+                Some(Arc::new(Coverage::default()))
+            } else {
+                None
+            },
             _user_functions: storages,
+            #[cfg(feature = "jit")]
+            jit: None,
+            extern_types,
         };
 
-        runtime.add_assembly(&options.library_path)?;
+        match options.backend {
+            Backend::SharedLibrary => {
+                if options.diagnostics_fn.is_some() {
+                    return Err(anyhow::anyhow!(
+                        "compiler diagnostics are only available when using the JIT backend"
+                    ));
+                }
+
+                let embedded_libraries = options
+                    .library_bytes
+                    .into_iter()
+                    .map(|(path, bytes)| (path, EmbeddedLibrary::Bytes(bytes)))
+                    .chain(
+                        options
+                            .static_libraries
+                            .into_iter()
+                            .map(|(path, library)| (path, EmbeddedLibrary::Static(library))),
+                    )
+                    .collect();
+                runtime.add_assembly(&options.library_path, &embedded_libraries)?
+            }
+            #[cfg(feature = "jit")]
+            Backend::Jit(profile) => runtime.add_jit_package(
+                &options.library_path,
+                profile,
+                options.hot_reloading,
+                options.diagnostics_fn,
+            )?,
+        }
         Ok(runtime)
     }
 
-    /// Adds an assembly corresponding to the library at `library_path`.
-    fn add_assembly(&mut self, library_path: &Path) -> anyhow::Result<()> {
-        let library_path = library_path.canonicalize()?;
+    /// Adds an assembly corresponding to the library at `library_path`. Libraries that are
+    /// contained in `embedded_libraries` are not loaded from disk.
+    fn add_assembly(
+        &mut self,
+        library_path: &Path,
+        embedded_libraries: &HashMap<PathBuf, EmbeddedLibrary>,
+    ) -> anyhow::Result<()> {
+        // Embedded libraries do not necessarily exist on disk
+        let library_path = if embedded_libraries.contains_key(library_path) {
+            library_path.to_path_buf()
+        } else {
+            library_path.canonicalize()?
+        };
         if self.assemblies.contains_key(&library_path) {
             return Err(io::Error::new(
                 io::ErrorKind::AlreadyExists,
@@ -261,7 +585,28 @@ impl Runtime {
                 continue;
             }
 
-            let assembly = Assembly::load(&library_path, self.gc.clone())?;
+            let assembly = match embedded_libraries.get(&library_path) {
+                Some(EmbeddedLibrary::Bytes(bytes)) => Assembly::load_from_bytes(
+                    &library_path,
+                    bytes,
+                    self.gc.clone(),
+                    self.profiler.clone(),
+                    self.coverage.clone(),
+                )?,
+                Some(EmbeddedLibrary::Static(library)) => Assembly::load_static(
+                    &library_path,
+                    *library,
+                    self.gc.clone(),
+                    self.profiler.clone(),
+                    self.coverage.clone(),
+                )?,
+                None => Assembly::load(
+                    &library_path,
+                    self.gc.clone(),
+                    self.profiler.clone(),
+                    self.coverage.clone(),
+                )?,
+            };
 
             let parent = library_path.parent().expect("Invalid library path");
             let extension = library_path.extension();
@@ -282,11 +627,23 @@ impl Runtime {
             }
         }
 
-        self.dispatch_table = Assembly::link_all(loaded.values_mut(), &self.dispatch_table)?;
+        self.dispatch_table = Assembly::link_all(
+            loaded.values_mut(),
+            &self.dispatch_table,
+            &self.extern_types,
+        )?;
 
         for (library_path, assembly) in loaded.into_iter() {
-            self.watcher
-                .watch(library_path.parent().unwrap(), RecursiveMode::NonRecursive)?;
+            // Embedded libraries cannot be hot reloaded
+            #[cfg(feature = "hot_reloading")]
+            {
+                if let Some(watcher) = self.watcher.as_mut() {
+                    if !embedded_libraries.contains_key(&library_path) {
+                        watcher
+                            .watch(library_path.parent().unwrap(), RecursiveMode::NonRecursive)?;
+                    }
+                }
+            }
 
             self.assemblies.insert(library_path, assembly);
         }
@@ -302,6 +659,15 @@ impl Runtime {
 
     /// Retrieves the type definition corresponding to `type_name`, if available.
     pub fn get_type_info(&self, type_name: &str) -> Option<&abi::TypeInfo> {
+        // Extern structs are declared in assemblies, but their definitions are provided by the host
+        if let Some(extern_type) = self
+            .extern_types
+            .iter()
+            .find(|ty| ty.type_info().name() == type_name)
+        {
+            return Some(extern_type.type_info());
+        }
+
         for assembly in self.assemblies.values() {
             for type_info in assembly.info().symbols.types().iter() {
                 if type_info.name() == type_name {
@@ -313,16 +679,86 @@ impl Runtime {
         None
     }
 
+    /// Returns an iterator over the definitions of all functions in the runtime, including
+    /// functions that were inserted by the host.
+    pub fn function_definitions(&self) -> impl Iterator<Item = &abi::FunctionDefinition> {
+        self.dispatch_table.iter_fns().map(|(_, fn_info)| fn_info)
+    }
+
+    /// Returns an iterator over all types in the runtime, including extern types that were
+    /// inserted by the host. Types that are used by multiple assemblies are only returned once.
+    pub fn type_infos(&self) -> impl Iterator<Item = &abi::TypeInfo> {
+        let mut visited = HashSet::new();
+        self.extern_types
+            .iter()
+            .map(ExternTypeInfo::type_info)
+            .chain(
+                self.assemblies
+                    .values()
+                    .flat_map(|assembly| assembly.info().symbols.types().iter().copied()),
+            )
+            .filter(move |type_info| visited.insert(type_info.guid))
+    }
+
     /// Updates the state of the runtime. This includes checking for file changes, and reloading
     /// compiled assemblies.
+    ///
+    /// Returns `true` if the assemblies were reloaded. Failures to reload are logged; use
+    /// [`Runtime::try_update`] to handle them.
     pub fn update(&mut self) -> bool {
+        match self.try_update() {
+            Ok(updated) => updated,
+            Err(e) => {
+                error!("Failed to relink assemblies, due to {}.", e);
+                false
+            }
+        }
+    }
+
+    /// Updates the state of the runtime. This includes checking for file changes, and reloading
+    /// compiled assemblies.
+    ///
+    /// Returns `Ok(true)` if the assemblies were reloaded. Reloading is atomic: if an error is
+    /// returned, the previously linked assemblies, their functions, and the heap are left intact.
+    /// The failed assemblies remain queued, so they are retried on the next reload.
+    pub fn try_update(&mut self) -> Result<bool, ReloadError> {
+        let updated = self.update_assemblies();
+        self.notify_reloaded();
+        updated
+    }
+
+    /// Reloads the assemblies that changed, using the runtime's backend.
+    fn update_assemblies(&mut self) -> Result<bool, ReloadError> {
+        #[cfg(feature = "jit")]
+        {
+            if self.jit.is_some() {
+                return self.jit_update();
+            }
+        }
+
+        #[cfg(feature = "hot_reloading")]
+        {
+            if self.watcher.is_some() {
+                return self.reload_libraries();
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Relinks the libraries that changed on disk, once the compiler removes its lockfile.
+    #[cfg(feature = "hot_reloading")]
+    fn reload_libraries(&mut self) -> Result<bool, ReloadError> {
         fn is_lockfile(path: &Path) -> bool {
             path.file_name().expect("Invalid file path.") == OsString::from(LOCKFILE_NAME)
         }
 
-        fn relink_assemblies(runtime: &mut Runtime) -> anyhow::Result<DispatchTable> {
+        fn relink_assemblies(
+            runtime: &mut Runtime,
+        ) -> Result<(DispatchTable, ReloadEvent), ReloadError> {
             let mut loaded = HashMap::new();
-            let to_load = &mut runtime.assemblies_to_relink;
+            // Work on a copy of the queue, such that it is retained if relinking fails
+            let mut to_load = runtime.assemblies_to_relink.clone();
 
             info!("Relinking assemblies:");
             for (old_path, new_path) in to_load.iter() {
@@ -340,7 +776,16 @@ impl Runtime {
                     continue;
                 }
 
-                let assembly = Assembly::load(&new_path, runtime.gc.clone())?;
+                let assembly = Assembly::load(
+                    &new_path,
+                    runtime.gc.clone(),
+                    runtime.profiler.clone(),
+                    runtime.coverage.clone(),
+                )
+                .map_err(|source| ReloadError::Load {
+                    path: new_path.clone(),
+                    source,
+                })?;
 
                 let parent = new_path.parent().expect("Invalid library path");
                 let extension = new_path.extension();
@@ -367,6 +812,7 @@ impl Runtime {
                 &mut loaded,
                 &mut runtime.assemblies,
                 &runtime.dispatch_table,
+                &runtime.extern_types,
             )
         }
 
@@ -381,17 +827,14 @@ impl Runtime {
                     if op.contains(notify::op::REMOVE) {
                         debug!("Lockfile deleted");
 
-                        match relink_assemblies(self) {
-                            Ok(table) => {
-                                info!("Succesfully reloaded assemblies.");
+                        let (table, event) = relink_assemblies(self)?;
+                        info!("Succesfully reloaded assemblies.");
 
-                                self.dispatch_table = table;
-                                self.assemblies_to_relink.clear();
+                        self.dispatch_table = table;
+                        self.assemblies_to_relink.clear();
+                        self.on_reloaded(event);
 
-                                return true;
-                            }
-                            Err(e) => error!("Failed to relink assemblies, due to {}.", e),
-                        }
+                        return Ok(true);
                     }
                 } else {
                     let path = path.canonicalize().unwrap_or_else(|_| {
@@ -414,15 +857,49 @@ impl Runtime {
             }
         }
 
-        false
+        Ok(false)
+    }
+
+    /// Queues the `event` of a successful reload to be passed to the reload function, once the
+    /// update has completed, and to be polled.
+    #[cfg_attr(not(feature = "hot_reloading"), allow(dead_code))]
+    fn on_reloaded(&mut self, event: ReloadEvent) {
+        if self.reload_fn.is_some() {
+            self.unnotified_reload_events.push(event.clone());
+        }
+
+        if self.reload_events.len() == MAX_QUEUED_RELOAD_EVENTS {
+            self.reload_events.pop_front();
+        }
+        self.reload_events.push_back(event);
+    }
+
+    /// Calls the reload function with the events of the reloads that completed since it was last
+    /// called.
+    fn notify_reloaded(&mut self) {
+        if let Some(reload_fn) = self.reload_fn.as_mut() {
+            for event in self.unnotified_reload_events.drain(..) {
+                reload_fn(&event);
+            }
+        }
+    }
+
+    /// Retrieves the oldest description of a reload that has not been polled yet, if any. The
+    /// runtime retains up to [`MAX_QUEUED_RELOAD_EVENTS`] events.
+    pub fn poll_reload_event(&mut self) -> Option<ReloadEvent> {
+        self.reload_events.pop_front()
     }
 
     /// Returns a shared reference to the runtime's garbage collector.
     ///
     /// We cannot return an `Arc` here, because the lifetime of data contained in `GarbageCollector`
     /// is dependent on the `Runtime`.
-    pub fn gc(&self) -> &dyn GcRuntime<UnsafeTypeInfo> {
-        self.gc.as_ref()
+    ///
+    /// Objects of extern types are always allocated with the type information that was registered
+    /// using [`RuntimeBuilder::insert_type`]. Allocating an object of an extern type that was not
+    /// registered fails.
+    pub fn gc(&self) -> &HostGc {
+        &self.host_gc
     }
 
     /// Collects all memory that is no longer referenced by rooted objects. Returns `true` if memory
@@ -435,6 +912,16 @@ impl Runtime {
     pub fn gc_stats(&self) -> gc::Stats {
         self.gc.stats()
     }
+
+    /// Returns the runtime's profiler, if it was enabled.
+    pub fn profiler(&self) -> Option<&Profiler> {
+        self.profiler.as_deref()
+    }
+
+    /// Returns the runtime's code coverage counters, if they were enabled.
+    pub fn coverage(&self) -> Option<&Coverage> {
+        self.coverage.as_deref()
+    }
 }
 
 invoke_fn_impl! {
@@ -177,7 +177,7 @@ macro_rules! invoke_fn_impl {
                             let function: fn($($T::MunType),*) -> Output::MunType = unsafe {
                                 core::mem::transmute(function_info.fn_ptr)
                             };
-                            let result = function($($Arg.marshal_into()),*);
+                            let result = function($($Arg.marshal_into_runtime(runtime)),*);
 
                             // Marshall the result
                             return Ok(Marshal::marshal_from(result, runtime))
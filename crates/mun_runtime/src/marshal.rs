@@ -18,6 +18,15 @@ pub trait Marshal<'t>: Sized {
     /// Marshals itself into a `Marshalled` value (i.e. Rust -> Mun).
     fn marshal_into(self) -> Self::MunType;
 
+    /// Marshals itself into a `Marshalled` value (i.e. Rust -> Mun), allocating memory in the
+    /// `runtime` if required.
+    ///
+    /// By default, this calls [`Marshal::marshal_into`]. Types that are copied into memory managed
+    /// by the `runtime` - i.e. types that derive [`MunStruct`](crate::MunStruct) - override it.
+    fn marshal_into_runtime(self, _runtime: &Runtime) -> Self::MunType {
+        self.marshal_into()
+    }
+
     /// Marshals the value at memory location `ptr` into a `Marshalled` value (i.e. Mun -> Rust).
     fn marshal_from_ptr<'r>(
         ptr: NonNull<Self::MunType>,
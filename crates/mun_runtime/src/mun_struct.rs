@@ -0,0 +1,132 @@
+use crate::{
+    adt::RawStruct, garbage_collector::UnsafeTypeInfo, reflection::equals_return_type,
+    ReturnTypeReflection, Runtime,
+};
+use memory::gc::HasIndirectionPtr;
+use std::ptr::NonNull;
+
+/// A Rust struct that mirrors the memory layout of a Mun `value` struct, which allows it to be
+/// marshalled by copy.
+///
+/// This trait should not be implemented manually. Instead, use `#[derive(MunStruct)]`, which also
+/// implements [`Marshal`](crate::Marshal), [`ArgumentReflection`](crate::ArgumentReflection), and
+/// [`ReturnTypeReflection`] for the struct.
+///
+/// # Safety
+///
+/// The struct must be `#[repr(C)]` and its fields must be listed in [`MunStruct::FIELD_NAMES`] in
+/// declaration order.
+pub unsafe trait MunStruct: Sized {
+    /// The name of the corresponding Mun struct.
+    const NAME: &'static str;
+
+    /// The names of the struct's fields, in declaration order.
+    const FIELD_NAMES: &'static [&'static str];
+
+    /// Validates the types of the struct's fields against the Mun struct's `field_types`, which
+    /// are in declaration order.
+    fn validate_field_types(field_types: &[&abi::TypeInfo]) -> Result<(), String>;
+
+    /// Validates that the memory layout of the struct matches that of the Mun struct `type_info`;
+    /// i.e. that it is a `value` struct with the same size, alignment, field names, and field
+    /// types.
+    fn validate(type_info: &abi::TypeInfo) -> Result<(), String> {
+        let struct_info = type_info
+            .as_struct()
+            .ok_or_else(|| format!("`{}` is not a struct.", type_info.name()))?;
+
+        if struct_info.memory_kind != abi::StructMemoryKind::Value {
+            return Err(format!(
+                "`{}` must be a value struct to be marshalled by copy.",
+                type_info.name()
+            ));
+        }
+
+        if type_info.size_in_bytes() != std::mem::size_of::<Self>() {
+            return Err(format!(
+                "Mismatched size for `{}`. Expected: {}. Found: {}.",
+                type_info.name(),
+                type_info.size_in_bytes(),
+                std::mem::size_of::<Self>(),
+            ));
+        }
+
+        if type_info.alignment() != std::mem::align_of::<Self>() {
+            return Err(format!(
+                "Mismatched alignment for `{}`. Expected: {}. Found: {}.",
+                type_info.name(),
+                type_info.alignment(),
+                std::mem::align_of::<Self>(),
+            ));
+        }
+
+        if !struct_info
+            .field_names()
+            .eq(Self::FIELD_NAMES.iter().cloned())
+        {
+            return Err(format!(
+                "Mismatched fields for `{}`. Expected: `{}`. Found: `{}`.",
+                type_info.name(),
+                struct_info.field_names().collect::<Vec<_>>().join(", "),
+                Self::FIELD_NAMES.join(", "),
+            ));
+        }
+
+        Self::validate_field_types(struct_info.field_types())
+    }
+
+    /// Validates that the field `field_name` of type `F` matches the Mun `field_type`.
+    fn validate_field_type<F: ReturnTypeReflection>(
+        field_name: &str,
+        field_type: &abi::TypeInfo,
+    ) -> Result<(), String> {
+        equals_return_type::<F>(field_type).map_err(|(expected, found)| {
+            format!(
+                "Mismatched types for `{}::{}`. Expected: `{}`. Found: `{}`.",
+                Self::NAME,
+                field_name,
+                expected,
+                found,
+            )
+        })
+    }
+
+    /// Retrieves the `Guid` of the Mun struct in `runtime`, if it matches the memory layout of the
+    /// struct. Otherwise, the `Guid` is derived from the struct's name.
+    fn runtime_type_guid(runtime: &Runtime) -> abi::Guid {
+        runtime
+            .get_type_info(Self::NAME)
+            .filter(|type_info| Self::validate(type_info).is_ok())
+            .map_or_else(Self::static_type_guid, |type_info| type_info.guid)
+    }
+
+    /// Retrieves the `Guid` derived from the struct's name.
+    fn static_type_guid() -> abi::Guid {
+        abi::Guid(md5::compute(Self::NAME).0)
+    }
+
+    /// Copies the struct into a newly allocated Mun struct. Returns an error if the Mun struct does
+    /// not exist in the `runtime`.
+    fn into_raw(self, runtime: &Runtime) -> Result<RawStruct, String> {
+        let type_info = runtime
+            .get_type_info(Self::NAME)
+            .ok_or_else(|| format!("Failed to obtain type `{}`.", Self::NAME))?;
+
+        let mut handle = runtime
+            .gc()
+            .alloc(UnsafeTypeInfo::new(NonNull::from(type_info)))?;
+
+        // Safety: the object was allocated with the memory layout of `Self`
+        unsafe { handle.deref_mut::<Self>().write(self) };
+        Ok(RawStruct::from(handle))
+    }
+
+    /// Copies the struct out of a Mun struct.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must have the memory layout of `Self`.
+    unsafe fn from_raw(raw: &RawStruct) -> Self {
+        raw.get_ptr().cast::<Self>().read()
+    }
+}
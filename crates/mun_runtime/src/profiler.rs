@@ -0,0 +1,333 @@
+use parking_lot::Mutex;
+use rustc_hash::FxHashMap;
+use std::{
+    collections::VecDeque,
+    ffi::{self, CStr},
+    io::{self, Write},
+    os::raw::c_char,
+    time::{Duration, Instant},
+};
+
+/// Identifies a function within the profiler.
+type FunctionId = usize;
+
+/// The maximum number of function entries and exits that the [`Profiler`] retains for
+/// [`Profiler::write_chrome_trace`]. When exceeded, the oldest events are discarded.
+pub const MAX_TRACE_EVENTS: usize = 1 << 20;
+
+/// Aggregated profiling information of a single function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionProfile {
+    /// The full path of the function
+    pub path: String,
+    /// The number of times the function was called
+    pub call_count: u64,
+    /// The total time spent in the function, including the time spent in the functions it called.
+    ///
+    /// Recursive calls are only counted once.
+    pub inclusive_time: Duration,
+    /// The total time spent in the function, excluding the time spent in the functions it called.
+    pub exclusive_time: Duration,
+}
+
+/// A function that is currently executing.
+struct Frame {
+    function: FunctionId,
+    start: Instant,
+    child_time: Duration,
+}
+
+/// Statistics of a single function.
+#[derive(Default)]
+struct FunctionStats {
+    call_count: u64,
+    inclusive_time: Duration,
+    exclusive_time: Duration,
+    /// The number of activations of the function that are currently on the call stack.
+    active: usize,
+}
+
+#[derive(Clone, Copy)]
+enum TracePhase {
+    Begin,
+    End,
+}
+
+/// An entry or exit of a function, relative to the start of the profiler.
+struct TraceEvent {
+    function: FunctionId,
+    phase: TracePhase,
+    timestamp: Duration,
+}
+
+#[derive(Default)]
+struct ProfilerState {
+    /// Functions are identified by their path rather than by the address of their name, because
+    /// munlibs are unloaded when they are reloaded, after which the address can be reused.
+    path_to_id: FxHashMap<String, FunctionId>,
+    paths: Vec<String>,
+    stats: Vec<FunctionStats>,
+    stack: Vec<Frame>,
+    /// The most recent function entries and exits, up to `MAX_TRACE_EVENTS`
+    events: VecDeque<TraceEvent>,
+    folded_stacks: FxHashMap<Vec<FunctionId>, Duration>,
+}
+
+impl ProfilerState {
+    /// Returns the `FunctionId` of the function with the null-terminated name, `fn_name`.
+    ///
+    /// # Safety
+    ///
+    /// `fn_name` must point to a valid null-terminated string.
+    unsafe fn function_id(&mut self, fn_name: *const u8) -> FunctionId {
+        let path = CStr::from_ptr(fn_name as *const c_char).to_string_lossy();
+        if let Some(id) = self.path_to_id.get(path.as_ref()) {
+            return *id;
+        }
+
+        let path = path.into_owned();
+        let id = self.paths.len();
+        self.paths.push(path.clone());
+        self.stats.push(FunctionStats::default());
+        self.path_to_id.insert(path, id);
+        id
+    }
+
+    /// Records a trace event, discarding the oldest event if `MAX_TRACE_EVENTS` is exceeded.
+    fn push_event(&mut self, event: TraceEvent) {
+        if self.events.len() == MAX_TRACE_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+
+    fn enter(&mut self, function: FunctionId, now: Instant, timestamp: Duration) {
+        let stats = &mut self.stats[function];
+        stats.call_count += 1;
+        stats.active += 1;
+
+        self.stack.push(Frame {
+            function,
+            start: now,
+            child_time: Duration::default(),
+        });
+        self.push_event(TraceEvent {
+            function,
+            phase: TracePhase::Begin,
+            timestamp,
+        });
+    }
+
+    fn exit(&mut self, function: FunctionId, now: Instant, timestamp: Duration) {
+        // Frames can be out of sync if a function was swapped out while it was executing, so pop
+        // until the frame of the exited function is found.
+        if !self.stack.iter().any(|frame| frame.function == function) {
+            return;
+        }
+
+        while let Some(frame) = self.stack.pop() {
+            let elapsed = now.saturating_duration_since(frame.start);
+            let exclusive_time = elapsed.checked_sub(frame.child_time).unwrap_or_default();
+
+            let mut call_path: Vec<FunctionId> =
+                self.stack.iter().map(|frame| frame.function).collect();
+            call_path.push(frame.function);
+            *self.folded_stacks.entry(call_path).or_default() += exclusive_time;
+
+            let stats = &mut self.stats[frame.function];
+            stats.exclusive_time += exclusive_time;
+            stats.active -= 1;
+            if stats.active == 0 {
+                stats.inclusive_time += elapsed;
+            }
+
+            if let Some(parent) = self.stack.last_mut() {
+                parent.child_time += elapsed;
+            }
+
+            self.push_event(TraceEvent {
+                function: frame.function,
+                phase: TracePhase::End,
+                timestamp,
+            });
+
+            if frame.function == function {
+                break;
+            }
+        }
+    }
+}
+
+/// A function-level profiler that aggregates the calls of instrumented Mun functions.
+///
+/// Mun functions are only instrumented if their assembly was compiled with function
+/// instrumentation enabled.
+///
+/// Every entry and exit of an instrumented function locks the profiler, looks up the function by
+/// its name, and records a trace event. The profiler retains up to [`MAX_TRACE_EVENTS`] trace
+/// events, each of which takes 32 bytes, in addition to the aggregated statistics of every
+/// function and call stack.
+pub struct Profiler {
+    epoch: Instant,
+    state: Mutex<ProfilerState>,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self {
+            epoch: Instant::now(),
+            state: Mutex::new(ProfilerState::default()),
+        }
+    }
+}
+
+impl Profiler {
+    /// Returns the profiling information of all functions that were called since the profiler
+    /// was created or last reset, ordered by descending exclusive time.
+    pub fn snapshot(&self) -> Vec<FunctionProfile> {
+        let state = self.state.lock();
+        let mut profiles: Vec<FunctionProfile> = state
+            .paths
+            .iter()
+            .zip(state.stats.iter())
+            .filter(|(_, stats)| stats.call_count > 0)
+            .map(|(path, stats)| FunctionProfile {
+                path: path.clone(),
+                call_count: stats.call_count,
+                inclusive_time: stats.inclusive_time,
+                exclusive_time: stats.exclusive_time,
+            })
+            .collect();
+
+        profiles.sort_by(|a, b| {
+            b.exclusive_time
+                .cmp(&a.exclusive_time)
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        profiles
+    }
+
+    /// Clears all recorded profiling information.
+    pub fn reset(&self) {
+        let mut state = self.state.lock();
+        for stats in state.stats.iter_mut() {
+            stats.call_count = 0;
+            stats.inclusive_time = Duration::default();
+            stats.exclusive_time = Duration::default();
+        }
+        state.events.clear();
+        state.folded_stacks.clear();
+    }
+
+    /// Writes the most recent - up to [`MAX_TRACE_EVENTS`] - function entries and exits in the
+    /// Chrome Trace Event format, which can be viewed in `chrome://tracing` or
+    /// [Perfetto](https://ui.perfetto.dev).
+    pub fn write_chrome_trace<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let state = self.state.lock();
+
+        write!(writer, "{{\"traceEvents\":[")?;
+        for (idx, event) in state.events.iter().enumerate() {
+            if idx > 0 {
+                write!(writer, ",")?;
+            }
+
+            let phase = match event.phase {
+                TracePhase::Begin => "B",
+                TracePhase::End => "E",
+            };
+            write!(
+                writer,
+                "\n{{\"name\":\"{}\",\"cat\":\"mun\",\"ph\":\"{}\",\"ts\":{}.{:03},\"pid\":0,\"tid\":0}}",
+                escape_json(&state.paths[event.function]),
+                phase,
+                event.timestamp.as_micros(),
+                event.timestamp.subsec_nanos() % 1000,
+            )?;
+        }
+        writeln!(writer, "\n],\"displayTimeUnit\":\"ns\"}}")
+    }
+
+    /// Writes the exclusive time, in microseconds, of every recorded call stack in the folded
+    /// stack format used by [flamegraph](https://github.com/brendangregg/FlameGraph) and
+    /// [inferno](https://github.com/jonhoo/inferno).
+    pub fn write_folded_stacks<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let state = self.state.lock();
+
+        let mut stacks: Vec<(String, Duration)> = state
+            .folded_stacks
+            .iter()
+            .map(|(call_path, duration)| {
+                let call_path: Vec<&str> = call_path
+                    .iter()
+                    .map(|function| state.paths[*function].as_str())
+                    .collect();
+                (call_path.join(";"), *duration)
+            })
+            .collect();
+        stacks.sort();
+
+        for (call_path, duration) in stacks {
+            writeln!(writer, "{} {}", call_path, duration.as_micros())?;
+        }
+        Ok(())
+    }
+
+    /// Records that the function with the specified name was entered.
+    ///
+    /// # Safety
+    ///
+    /// `fn_name` must point to a valid null-terminated string.
+    unsafe fn enter(&self, fn_name: *const u8) {
+        let now = Instant::now();
+        let mut state = self.state.lock();
+        let function = state.function_id(fn_name);
+        state.enter(function, now, now - self.epoch);
+    }
+
+    /// Records that the function with the specified name is about to return.
+    ///
+    /// # Safety
+    ///
+    /// `fn_name` must point to a valid null-terminated string.
+    unsafe fn exit(&self, fn_name: *const u8) {
+        let now = Instant::now();
+        let mut state = self.state.lock();
+        let function = state.function_id(fn_name);
+        state.exit(function, now, now - self.epoch);
+    }
+}
+
+/// Escapes the characters in `value` that are not allowed in a JSON string.
+fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Called by instrumented Mun functions when they are entered.
+pub(crate) extern "C" fn profiler_enter(fn_name: *const u8, profiler_handle: *mut ffi::c_void) {
+    // Safety: the profiler handle is either null or set by the `Runtime` using
+    // `set_profiler_handle`, in which case the `Runtime` keeps the profiler alive for as long as the
+    // munlib is loaded.
+    if let Some(profiler) = unsafe { (profiler_handle as *const Profiler).as_ref() } {
+        // Safety: the Mun Compiler guarantees that `fn_name` is a null-terminated string, embedded
+        // in the munlib.
+        unsafe { profiler.enter(fn_name) };
+    }
+}
+
+/// Called by instrumented Mun functions right before they return.
+pub(crate) extern "C" fn profiler_exit(fn_name: *const u8, profiler_handle: *mut ffi::c_void) {
+    // Safety: see `profiler_enter`
+    if let Some(profiler) = unsafe { (profiler_handle as *const Profiler).as_ref() } {
+        // Safety: see `profiler_enter`
+        unsafe { profiler.exit(fn_name) };
+    }
+}
@@ -26,7 +26,9 @@ pub fn equals_return_type<T: ReturnTypeReflection>(
             }
         }
         abi::TypeInfoData::Struct(_) => {
-            if <StructRef as ReturnTypeReflection>::type_guid() != T::type_guid() {
+            if <StructRef as ReturnTypeReflection>::type_guid() != T::type_guid()
+                && !T::accepts_struct(type_info)
+            {
                 return Err(("struct", T::type_name()));
             }
         }
@@ -41,6 +43,12 @@ pub trait ReturnTypeReflection: Sized {
 
     /// Retrieves the type's name.
     fn type_name() -> &'static str;
+
+    /// Returns whether the type can be marshalled from the struct `type_info`. Apart from
+    /// [`StructRef`], only types that implement [`MunStruct`](crate::MunStruct) accept structs.
+    fn accepts_struct(_type_info: &abi::TypeInfo) -> bool {
+        false
+    }
 }
 
 /// A type to emulate dynamic typing across compilation units for statically typed values.
@@ -86,7 +94,7 @@ macro_rules! impl_primitive_type {
                     value
                 }
 
-                fn marshal_into<'r>(self) -> Self::MunType {
+                fn marshal_into(self) -> Self::MunType {
                     self
                 }
 
@@ -142,7 +150,7 @@ impl<'t> Marshal<'t> for () {
         value
     }
 
-    fn marshal_into<'r>(self) -> Self::MunType {
+    fn marshal_into(self) -> Self::MunType {
         self
     }
 
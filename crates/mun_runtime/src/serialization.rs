@@ -0,0 +1,492 @@
+//! Serialization of Mun structs using [serde](https://serde.rs).
+//!
+//! A struct is serialized as a sequence of all objects that are reachable from it, starting with
+//! the struct itself. Each object is a map of its fields. `value` structs are stored inline,
+//! whereas `gc` structs are stored as the index of the referenced object in the sequence. This
+//! allows shared and cyclic references to be restored upon deserialization.
+
+use crate::{
+    adt::RawStruct,
+    garbage_collector::{GcPtr, UnsafeTypeInfo},
+    marshal::Marshal,
+    RootedStruct, Runtime, StructRef,
+};
+use abi::HasStaticTypeInfo;
+use memory::gc::HasIndirectionPtr;
+use serde::{
+    de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor},
+    ser::{self, SerializeMap, SerializeSeq},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fmt,
+    ptr::{self, NonNull},
+};
+
+macro_rules! impl_primitive_serialization {
+    ($($ty:ty),+) => {
+        /// Serializes the primitive at `ptr` of type `type_info`.
+        ///
+        /// # Safety
+        ///
+        /// `ptr` must point to initialized memory of type `type_info`.
+        unsafe fn serialize_primitive<S: Serializer>(
+            ptr: *const u8,
+            type_info: &abi::TypeInfo,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            $(
+                if type_info.guid == <$ty>::type_info().guid {
+                    return (*ptr.cast::<$ty>()).serialize(serializer);
+                }
+            )+
+
+            Err(ser::Error::custom(format!(
+                "Unsupported type `{}`.",
+                type_info.name()
+            )))
+        }
+
+        /// Deserializes a primitive of type `type_info` and writes it to `ptr`.
+        ///
+        /// # Safety
+        ///
+        /// `ptr` must point to memory of type `type_info`.
+        unsafe fn deserialize_primitive<'de, D: Deserializer<'de>>(
+            ptr: *mut u8,
+            type_info: &abi::TypeInfo,
+            deserializer: D,
+        ) -> Result<(), D::Error> {
+            $(
+                if type_info.guid == <$ty>::type_info().guid {
+                    ptr.cast::<$ty>().write(<$ty>::deserialize(deserializer)?);
+                    return Ok(());
+                }
+            )+
+
+            Err(de::Error::custom(format!(
+                "Unsupported type `{}`.",
+                type_info.name()
+            )))
+        }
+    };
+}
+
+impl_primitive_serialization!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64, bool);
+
+/// The objects that are reachable from a serialized struct, in order of discovery.
+#[derive(Default)]
+struct Objects<'t> {
+    indices: HashMap<GcPtr, u64>,
+    objects: Vec<(GcPtr, &'t abi::TypeInfo)>,
+}
+
+impl<'t> Objects<'t> {
+    /// Collects all objects that are reachable from `root`.
+    fn collect(root: GcPtr, type_info: &'t abi::TypeInfo) -> Self {
+        let mut objects = Objects::default();
+        objects.insert(root, type_info);
+
+        let mut idx = 0;
+        while let Some((handle, type_info)) = objects.objects.get(idx).cloned() {
+            // Safety: `handle` points to a struct of type `type_info`
+            unsafe { objects.insert_fields(handle.deref::<u8>(), type_info) };
+            idx += 1;
+        }
+
+        objects
+    }
+
+    fn insert(&mut self, handle: GcPtr, type_info: &'t abi::TypeInfo) {
+        let objects = &mut self.objects;
+        self.indices.entry(handle).or_insert_with(|| {
+            objects.push((handle, type_info));
+            objects.len() as u64 - 1
+        });
+    }
+
+    /// Inserts all `gc` structs that are referenced by the fields of the struct at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to initialized memory of type `type_info`.
+    unsafe fn insert_fields(&mut self, ptr: *const u8, type_info: &'t abi::TypeInfo) {
+        // Safety: only structs are collected
+        let struct_info = type_info.as_struct().unwrap();
+        for (field_type, offset) in struct_info
+            .field_types()
+            .iter()
+            .zip(struct_info.field_offsets().iter())
+        {
+            let field_ptr = ptr.add(*offset as usize);
+            if let Some(field_struct) = field_type.as_struct() {
+                match field_struct.memory_kind {
+                    abi::StructMemoryKind::Value => self.insert_fields(field_ptr, field_type),
+                    abi::StructMemoryKind::GC => {
+                        self.insert(*field_ptr.cast::<GcPtr>(), field_type)
+                    }
+                    // Extern structs are opaque, so they cannot be serialized
+                    abi::StructMemoryKind::Extern => {}
+                }
+            }
+        }
+    }
+}
+
+/// Serializes the fields of a struct as a map.
+struct SerializeFields<'a, 't> {
+    ptr: *const u8,
+    type_info: &'t abi::TypeInfo,
+    objects: &'a Objects<'t>,
+}
+
+impl<'a, 't> Serialize for SerializeFields<'a, 't> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Safety: only structs are serialized as a map
+        let struct_info = self.type_info.as_struct().unwrap();
+        let mut map = serializer.serialize_map(Some(struct_info.num_fields()))?;
+        for ((name, field_type), offset) in struct_info
+            .field_names()
+            .zip(struct_info.field_types().iter())
+            .zip(struct_info.field_offsets().iter())
+        {
+            map.serialize_entry(
+                name,
+                &SerializeField {
+                    // Safety: the field offset lies within the struct's memory
+                    ptr: unsafe { self.ptr.add(*offset as usize) },
+                    type_info: field_type,
+                    objects: self.objects,
+                },
+            )?;
+        }
+        map.end()
+    }
+}
+
+/// Serializes a single field of a struct.
+struct SerializeField<'a, 't> {
+    ptr: *const u8,
+    type_info: &'t abi::TypeInfo,
+    objects: &'a Objects<'t>,
+}
+
+impl<'a, 't> Serialize for SerializeField<'a, 't> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.type_info.as_struct().map(|s| &s.memory_kind) {
+            Some(abi::StructMemoryKind::Value) => SerializeFields {
+                ptr: self.ptr,
+                type_info: self.type_info,
+                objects: self.objects,
+            }
+            .serialize(serializer),
+            Some(abi::StructMemoryKind::GC) => {
+                // Safety: a `gc` struct field contains a `GcPtr`, which was collected before
+                // serialization.
+                let handle = unsafe { *self.ptr.cast::<GcPtr>() };
+                serializer.serialize_u64(self.objects.indices[&handle])
+            }
+            Some(abi::StructMemoryKind::Extern) => Err(ser::Error::custom(format!(
+                "Extern struct `{}` cannot be serialized.",
+                self.type_info.name()
+            ))),
+            // Safety: the field contains a value of type `type_info`
+            None => unsafe { serialize_primitive(self.ptr, self.type_info, serializer) },
+        }
+    }
+}
+
+impl<'s> Serialize for StructRef<'s> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let type_info = self.type_info();
+        if type_info
+            .as_struct()
+            .map_or(false, |s| s.memory_kind == abi::StructMemoryKind::Extern)
+        {
+            return Err(ser::Error::custom(format!(
+                "Extern struct `{}` cannot be serialized.",
+                type_info.name()
+            )));
+        }
+
+        let objects = Objects::collect(self.clone().into_raw().handle(), self.type_info());
+
+        let mut seq = serializer.serialize_seq(Some(objects.objects.len()))?;
+        for (handle, type_info) in objects.objects.iter() {
+            seq.serialize_element(&SerializeFields {
+                // Safety: `handle` points to a struct of type `type_info`
+                ptr: unsafe { handle.deref::<u8>() },
+                type_info,
+                objects: &objects,
+            })?;
+        }
+        seq.end()
+    }
+}
+
+impl Serialize for RootedStruct {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.by_ref().serialize(serializer)
+    }
+}
+
+/// Deserializes a struct of a specific type, allocating it and all objects it references in the
+/// runtime's garbage collector.
+///
+/// The deserialized struct is not rooted, and must be rooted using [`StructRef::root`] to outlive
+/// the next garbage collection. Primitive fields that are missing from the serialized data are
+/// zero initialized, whereas missing fields that are - or contain - `gc` structs result in an
+/// error, as they cannot be left null. Unknown fields are ignored.
+pub struct StructSeed<'r> {
+    runtime: &'r Runtime,
+    type_info: &'r abi::TypeInfo,
+}
+
+impl<'r> StructSeed<'r> {
+    /// Constructs a `StructSeed` that deserializes a struct of type `type_info` in the `runtime`.
+    pub fn new(runtime: &'r Runtime, type_info: &'r abi::TypeInfo) -> Self {
+        Self { runtime, type_info }
+    }
+}
+
+impl<'de, 'r> DeserializeSeed<'de> for StructSeed<'r> {
+    type Value = StructRef<'r>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, 'r> Visitor<'de> for StructSeed<'r> {
+    type Value = StructRef<'r>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a sequence of Mun structs")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        match self.type_info.as_struct().map(|s| &s.memory_kind) {
+            None => {
+                return Err(de::Error::custom(format!(
+                    "`{}` is not a struct.",
+                    self.type_info.name()
+                )))
+            }
+            Some(abi::StructMemoryKind::Extern) => {
+                return Err(de::Error::custom(format!(
+                    "Extern struct `{}` cannot be deserialized.",
+                    self.type_info.name()
+                )))
+            }
+            _ => (),
+        }
+
+        let mut allocator = Allocator {
+            runtime: self.runtime,
+            objects: HashMap::new(),
+        };
+        let root = allocator
+            .reference(0, self.type_info)
+            .map_err(de::Error::custom)?;
+
+        let mut idx = 0;
+        while let Some(()) = seq.next_element_seed(DeserializeObject {
+            allocator: &mut allocator,
+            idx,
+        })? {
+            idx += 1;
+        }
+
+        // Objects are allocated upon their first reference, so every index that is referenced but
+        // lies beyond the end of the sequence refers to an object that was never defined
+        if let Some(undefined) = allocator
+            .objects
+            .keys()
+            .filter(|referenced| **referenced >= idx)
+            .min()
+        {
+            return Err(de::Error::custom(format!(
+                "Undefined object with index {}.",
+                undefined
+            )));
+        }
+
+        Ok(StructRef::marshal_from(RawStruct::from(root), self.runtime))
+    }
+}
+
+/// Allocates the objects of a deserialized struct upon their first reference.
+struct Allocator<'r> {
+    runtime: &'r Runtime,
+    objects: HashMap<u64, (GcPtr, &'r abi::TypeInfo)>,
+}
+
+impl<'r> Allocator<'r> {
+    /// Returns the object with index `idx`, allocating a zero initialized object of type
+    /// `type_info` if it did not exist yet.
+    fn reference(&mut self, idx: u64, type_info: &'r abi::TypeInfo) -> Result<GcPtr, String> {
+        match self.objects.entry(idx) {
+            Entry::Occupied(entry) => {
+                let (handle, object_type) = *entry.get();
+                if object_type.guid != type_info.guid {
+                    return Err(format!(
+                        "Mismatched types for object with index {}. Expected: `{}`. Found: `{}`.",
+                        idx,
+                        object_type.name(),
+                        type_info.name(),
+                    ));
+                }
+                Ok(handle)
+            }
+            Entry::Vacant(entry) => {
+                let mut handle = self
+                    .runtime
+                    .gc()
+                    .alloc(UnsafeTypeInfo::new(NonNull::from(type_info)))?;
+
+                // Safety: the object was allocated with the size of `type_info`
+                unsafe { ptr::write_bytes(handle.deref_mut::<u8>(), 0, type_info.size_in_bytes()) };
+
+                entry.insert((handle, type_info));
+                Ok(handle)
+            }
+        }
+    }
+}
+
+/// Deserializes the object with index `idx`.
+struct DeserializeObject<'a, 'r> {
+    allocator: &'a mut Allocator<'r>,
+    idx: u64,
+}
+
+impl<'a, 'de, 'r> DeserializeSeed<'de> for DeserializeObject<'a, 'r> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        let (mut handle, type_info) = *self.allocator.objects.get(&self.idx).ok_or_else(|| {
+            de::Error::custom(format!("Object with index {} is not referenced.", self.idx))
+        })?;
+
+        deserializer.deserialize_map(DeserializeFields {
+            allocator: self.allocator,
+            // Safety: `handle` points to a struct of type `type_info`
+            ptr: unsafe { handle.deref_mut::<u8>() },
+            type_info,
+        })
+    }
+}
+
+/// Deserializes the fields of a struct from a map.
+struct DeserializeFields<'a, 'r> {
+    allocator: &'a mut Allocator<'r>,
+    ptr: *mut u8,
+    type_info: &'r abi::TypeInfo,
+}
+
+impl<'a, 'de, 'r> Visitor<'de> for DeserializeFields<'a, 'r> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "the fields of `{}`", self.type_info.name())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        // Safety: only structs are deserialized from a map
+        let struct_info = self.type_info.as_struct().unwrap();
+        let mut seen = vec![false; struct_info.num_fields()];
+        while let Some(field_name) = map.next_key::<String>()? {
+            match struct_info
+                .field_names()
+                .position(|name| name == field_name)
+            {
+                Some(field_idx) => {
+                    seen[field_idx] = true;
+                    map.next_value_seed(DeserializeField {
+                        allocator: &mut *self.allocator,
+                        // Safety: the field offset lies within the struct's memory
+                        ptr: unsafe {
+                            self.ptr
+                                .add(struct_info.field_offsets()[field_idx] as usize)
+                        },
+                        type_info: struct_info.field_types()[field_idx],
+                    })?
+                }
+                None => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        // Zero initialized primitives are valid, but a zero initialized `gc` struct field would
+        // be a null pointer
+        if let Some((name, _)) = struct_info
+            .field_names()
+            .zip(struct_info.field_types().iter())
+            .zip(seen)
+            .find(|((_, field_type), seen)| !seen && contains_gc_struct(field_type))
+            .map(|(field, _)| field)
+        {
+            return Err(de::Error::custom(format!(
+                "Missing field `{}` of `{}`.",
+                name,
+                self.type_info.name()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Returns whether a value of type `type_info` is - or inlines a field that is - a `gc` struct.
+fn contains_gc_struct(type_info: &abi::TypeInfo) -> bool {
+    match type_info.as_struct() {
+        Some(struct_info) => match struct_info.memory_kind {
+            abi::StructMemoryKind::GC => true,
+            abi::StructMemoryKind::Value => struct_info
+                .field_types()
+                .iter()
+                .any(|field_type| contains_gc_struct(field_type)),
+            abi::StructMemoryKind::Extern => false,
+        },
+        None => false,
+    }
+}
+
+/// Deserializes a single field of a struct.
+struct DeserializeField<'a, 'r> {
+    allocator: &'a mut Allocator<'r>,
+    ptr: *mut u8,
+    type_info: &'r abi::TypeInfo,
+}
+
+impl<'a, 'de, 'r> DeserializeSeed<'de> for DeserializeField<'a, 'r> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        match self.type_info.as_struct().map(|s| &s.memory_kind) {
+            Some(abi::StructMemoryKind::Value) => deserializer.deserialize_map(DeserializeFields {
+                allocator: self.allocator,
+                ptr: self.ptr,
+                type_info: self.type_info,
+            }),
+            Some(abi::StructMemoryKind::GC) => {
+                let idx = u64::deserialize(deserializer)?;
+                let handle = self
+                    .allocator
+                    .reference(idx, self.type_info)
+                    .map_err(de::Error::custom)?;
+
+                // Safety: a `gc` struct field contains a `GcPtr`
+                unsafe { self.ptr.cast::<GcPtr>().write(handle) };
+                Ok(())
+            }
+            Some(abi::StructMemoryKind::Extern) => Err(de::Error::custom(format!(
+                "Extern struct `{}` cannot be deserialized.",
+                self.type_info.name()
+            ))),
+            // Safety: the field contains a value of type `type_info`
+            None => unsafe { deserialize_primitive(self.ptr, self.type_info, deserializer) },
+        }
+    }
+}
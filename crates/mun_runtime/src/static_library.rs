@@ -0,0 +1,82 @@
+use crate::assembly::{AssemblyLibrary, LibrarySymbols};
+use abi::AssemblyInfo;
+use std::ffi::c_void;
+
+/// The functions of an assembly that is statically linked into the executable - i.e. an object
+/// file that was emitted by `mun build --emit-static` - through which the runtime accesses it.
+///
+/// The functions are prefixed with the name of the package. For a package called `game`, the
+/// host declares and collects them as follows:
+///
+/// ```ignore
+/// extern "C" {
+///     fn game_get_version() -> u32;
+///     fn game_get_info() -> abi::AssemblyInfo;
+///     fn game_set_allocator_handle(allocator_ptr: *mut c_void);
+/// }
+///
+/// let library = StaticLibrary {
+///     get_abi_version: game_get_version,
+///     get_info: game_get_info,
+///     set_allocator_handle: game_set_allocator_handle,
+///     set_profiler_handle: None,
+///     set_coverage_handle: None,
+/// };
+/// ```
+#[derive(Clone, Copy)]
+pub struct StaticLibrary {
+    /// Returns the ABI version that the assembly was compiled with: `{prefix}_get_version`
+    pub get_abi_version: unsafe extern "C" fn() -> u32,
+    /// Returns the assembly's information: `{prefix}_get_info`
+    pub get_info: unsafe extern "C" fn() -> AssemblyInfo,
+    /// Sets the allocator handle of the assembly: `{prefix}_set_allocator_handle`
+    pub set_allocator_handle: unsafe extern "C" fn(*mut c_void),
+    /// Sets the profiler handle of the assembly: `{prefix}_set_profiler_handle`. It only exists if
+    /// the assembly was compiled with function instrumentation.
+    pub set_profiler_handle: Option<unsafe extern "C" fn(*mut c_void)>,
+    /// Sets the coverage handle of the assembly: `{prefix}_set_coverage_handle`. It only exists if
+    /// the assembly was compiled with coverage instrumentation.
+    pub set_coverage_handle: Option<unsafe extern "C" fn(*mut c_void)>,
+}
+
+impl From<StaticLibrary> for AssemblyLibrary {
+    fn from(library: StaticLibrary) -> Self {
+        AssemblyLibrary::Static(library)
+    }
+}
+
+impl LibrarySymbols for StaticLibrary {
+    fn get_abi_version(&self) -> u32 {
+        unsafe { (self.get_abi_version)() }
+    }
+
+    fn get_info(&self) -> AssemblyInfo {
+        unsafe { (self.get_info)() }
+    }
+
+    fn set_allocator_handle(&mut self, allocator_ptr: *mut c_void) {
+        unsafe { (self.set_allocator_handle)(allocator_ptr) }
+    }
+
+    fn set_profiler_handle(&mut self, profiler_ptr: *mut c_void) -> bool {
+        set_instrumentation_handle(self.set_profiler_handle, profiler_ptr)
+    }
+
+    fn set_coverage_handle(&mut self, coverage_ptr: *mut c_void) -> bool {
+        set_instrumentation_handle(self.set_coverage_handle, coverage_ptr)
+    }
+}
+
+/// Sets an instrumentation handle using `set_handle_fn`, if it exists. Returns whether or not the
+/// assembly is instrumented.
+fn set_instrumentation_handle(
+    set_handle_fn: Option<unsafe extern "C" fn(*mut c_void)>,
+    handle: *mut c_void,
+) -> bool {
+    if let Some(set_handle_fn) = set_handle_fn {
+        unsafe { set_handle_fn(handle) };
+        true
+    } else {
+        false
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{adt::RawStruct, marshal::Marshal, reflection::ArgumentReflection, Runtime, StructRef};
+use abi::HasStaticTypeInfo;
+use std::ptr::NonNull;
+
+/// A dynamically typed Mun value. Contrary to [`StructRef::get`] and [`StructRef::set`], this
+/// allows fields to be read and written without knowing their Rust type at compile time.
+///
+/// A `value` struct is copied when it is read, whereas a `gc` struct is referenced. Modifying the
+/// fields of a `value` struct therefore requires the struct to be written back to its parent.
+#[derive(Clone)]
+pub enum Value<'s> {
+    /// An `i8` value
+    I8(i8),
+    /// An `i16` value
+    I16(i16),
+    /// An `i32` value
+    I32(i32),
+    /// An `i64` value
+    I64(i64),
+    /// An `i128` value
+    I128(i128),
+    /// A `u8` value
+    U8(u8),
+    /// A `u16` value
+    U16(u16),
+    /// A `u32` value
+    U32(u32),
+    /// A `u64` value
+    U64(u64),
+    /// A `u128` value
+    U128(u128),
+    /// An `f32` value
+    F32(f32),
+    /// An `f64` value
+    F64(f64),
+    /// A `bool` value
+    Bool(bool),
+    /// A `value` or `gc` struct
+    Struct(StructRef<'s>),
+}
+
+macro_rules! impl_primitive_values {
+    ($($variant:ident($ty:ty)),+) => {
+        impl<'s> Value<'s> {
+            /// Reads a value of type `type_info` from `ptr`. Returns an error if the type is not
+            /// supported.
+            ///
+            /// # Safety
+            ///
+            /// `ptr` must point to initialized memory of type `type_info`.
+            pub(crate) unsafe fn read<'r>(
+                ptr: NonNull<u8>,
+                type_info: &abi::TypeInfo,
+                runtime: &'r Runtime,
+            ) -> Result<Self, String>
+            where
+                'r: 's,
+            {
+                if type_info.data.is_struct() {
+                    return Ok(Value::Struct(StructRef::marshal_from_ptr(
+                        ptr.cast::<RawStruct>(),
+                        runtime,
+                        Some(type_info),
+                    )));
+                }
+
+                $(
+                    if type_info.guid == <$ty>::type_info().guid {
+                        return Ok(Value::$variant(ptr.cast::<$ty>().as_ptr().read()));
+                    }
+                )+
+
+                Err(format!("Unsupported type `{}`.", type_info.name()))
+            }
+
+            /// Writes the value to `ptr`, which is of type `type_info`.
+            ///
+            /// # Safety
+            ///
+            /// The type of the value must match `type_info` and `ptr` must point to memory of type
+            /// `type_info`.
+            pub(crate) unsafe fn write(self, ptr: NonNull<u8>, type_info: &abi::TypeInfo) {
+                match self {
+                    $(Value::$variant(value) => ptr.cast::<$ty>().as_ptr().write(value),)+
+                    Value::Struct(value) => {
+                        StructRef::marshal_to_ptr(value, ptr.cast::<RawStruct>(), Some(type_info))
+                    }
+                }
+            }
+        }
+
+        impl<'s> ArgumentReflection for Value<'s> {
+            fn type_guid(&self, runtime: &Runtime) -> abi::Guid {
+                match self {
+                    $(Value::$variant(_) => <$ty>::type_info().guid,)+
+                    Value::Struct(value) => value.type_guid(runtime),
+                }
+            }
+
+            fn type_name<'r>(&'r self, runtime: &'r Runtime) -> &'r str {
+                match self {
+                    $(Value::$variant(_) => <$ty>::type_info().name(),)+
+                    Value::Struct(value) => value.type_name(runtime),
+                }
+            }
+        }
+
+        $(
+            impl<'s> From<$ty> for Value<'s> {
+                fn from(value: $ty) -> Self {
+                    Value::$variant(value)
+                }
+            }
+        )+
+    };
+}
+
+impl_primitive_values!(
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    F32(f32),
+    F64(f64),
+    Bool(bool)
+);
+
+impl<'s> From<StructRef<'s>> for Value<'s> {
+    fn from(value: StructRef<'s>) -> Self {
+        Value::Struct(value)
+    }
+}
@@ -0,0 +1,102 @@
+use compiler::Config;
+use mun_test::CompileAndRunTestDriver;
+
+#[macro_use]
+mod util;
+
+fn instrument_coverage(config: Config) -> Config {
+    Config {
+        instrument_coverage: true,
+        ..config
+    }
+}
+
+#[test]
+fn coverage_counts_branches() {
+    let driver = CompileAndRunTestDriver::with_compiler_config(
+        r"
+    pub fn main(a: i32) -> i32 {
+        if a > 5 {
+            1
+        } else {
+            2
+        }
+    }
+
+    pub fn unused() {}
+    ",
+        instrument_coverage,
+        |builder| builder.enable_coverage(),
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 1, driver, "main", 6i32);
+    assert_invoke_eq!(i32, 2, driver, "main", 3i32);
+    assert_invoke_eq!(i32, 2, driver, "main", 4i32);
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+    let coverage = runtime_ref.coverage().expect("coverage is not enabled");
+
+    let counters = coverage.counters();
+    assert_eq!(counters.len(), 1);
+    assert_eq!(counters[0].path, "main");
+    assert_eq!(counters[0].counters, vec![3, 1, 2]);
+
+    let mut written = Vec::new();
+    coverage.write_counters(&mut written).unwrap();
+    assert_eq!(
+        String::from_utf8(written).unwrap(),
+        "main 0 3\nmain 1 1\nmain 2 2\n"
+    );
+
+    coverage.reset();
+    assert!(coverage.counters().is_empty());
+}
+
+#[test]
+fn coverage_counts_else_if_branches() {
+    let driver = CompileAndRunTestDriver::with_compiler_config(
+        r"
+    pub fn main(a: i32) -> i32 {
+        if a > 5 {
+            1
+        } else if a > 2 {
+            2
+        } else {
+            3
+        }
+    }
+    ",
+        instrument_coverage,
+        |builder| builder.enable_coverage(),
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 1, driver, "main", 6i32);
+    assert_invoke_eq!(i32, 2, driver, "main", 3i32);
+    assert_invoke_eq!(i32, 3, driver, "main", 0i32);
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+    let coverage = runtime_ref.coverage().expect("coverage is not enabled");
+
+    let counters = coverage.counters();
+    assert_eq!(counters.len(), 1);
+    assert_eq!(counters[0].counters, vec![3, 1, 2, 1, 1]);
+}
+
+#[test]
+fn coverage_disabled() {
+    let driver = CompileAndRunTestDriver::with_compiler_config(
+        r"
+    pub fn main() -> i32 { 5 }
+    ",
+        instrument_coverage,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 5, driver, "main");
+    assert!(driver.runtime().borrow().coverage().is_none());
+}
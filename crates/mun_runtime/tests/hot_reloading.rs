@@ -1,7 +1,10 @@
 #[macro_use]
 mod util;
 
+use mun_runtime::{invoke_fn, LinkError, ReloadError, ReloadEvent, StructRef};
 use mun_test::CompileAndRunTestDriver;
+use parking_lot::Mutex;
+use std::sync::Arc;
 
 #[test]
 fn hotreloadable() {
@@ -66,3 +69,199 @@ fn hotreload_struct_decl() {
     "#,
     );
 }
+
+#[test]
+fn reload_rollback_signature_mismatch() {
+    extern "C" fn add(a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+    extern fn add(a: i32, b: i32) -> i32;
+
+    struct Foo {
+        a: i32,
+    }
+
+    pub fn foo_new(a: i32) -> Foo { Foo { a } }
+    pub fn main() -> i32 { add(2, 3) }
+    "#,
+        |builder| builder.insert_fn("add", add as extern "C" fn(i32, i32) -> i32),
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+    let foo: StructRef = invoke_fn!(runtime_ref, "foo_new", 5i32).unwrap();
+    let foo = foo.root(driver.runtime());
+
+    let result = driver.try_update(
+        runtime_ref,
+        "mod.mun",
+        r#"
+    extern fn add(a: f32, b: f32) -> f32;
+
+    struct Foo {
+        a: f64,
+    }
+
+    pub fn foo_new(a: f64) -> Foo { Foo { a } }
+    pub fn main() -> f32 { add(2.0, 3.0) }
+    "#,
+    );
+    assert!(matches!(
+        result,
+        Err(ReloadError::Link(LinkError::SignatureMismatch { ref name, .. })) if name == "add"
+    ));
+
+    // The previous assembly and its memory should be intact
+    assert_eq!(foo.by_ref().get::<i32>("a").unwrap(), 5);
+    assert_invoke_eq!(i32, 5, driver, "main");
+
+    // A subsequent valid reload should succeed
+    let runtime = driver.runtime();
+    driver.update(
+        runtime.borrow(),
+        "mod.mun",
+        r#"
+    extern fn add(a: i32, b: i32) -> i32;
+
+    struct Foo {
+        a: i64,
+    }
+
+    pub fn foo_new(a: i64) -> Foo { Foo { a } }
+    pub fn main() -> i32 { add(3, 4) }
+    "#,
+    );
+    assert_eq!(foo.by_ref().get::<i64>("a").unwrap(), 5);
+    assert_invoke_eq!(i32, 7, driver, "main");
+}
+
+#[test]
+fn reload_rollback_missing_function() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+    pub fn main() -> i32 { 5 }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let result = driver.try_update(
+        runtime.borrow(),
+        "mod.mun",
+        r#"
+    extern fn missing() -> i32;
+
+    pub fn main() -> i32 { missing() }
+    "#,
+    );
+    assert!(matches!(
+        result,
+        Err(ReloadError::Link(LinkError::MissingFunctions(ref names))) if names == &["missing"]
+    ));
+
+    assert_invoke_eq!(i32, 5, driver, "main");
+}
+
+#[test]
+fn reload_rollback_incompatible_migration() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+    struct Foo {
+        a: i32,
+    }
+
+    pub fn foo_new(a: i32) -> Foo { Foo { a } }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+    let foo: StructRef = invoke_fn!(runtime_ref, "foo_new", 5i32).unwrap();
+    let foo = foo.root(driver.runtime());
+
+    let result = driver.try_update(
+        runtime_ref,
+        "mod.mun",
+        r#"
+    struct Bar {
+        value: i32,
+    }
+
+    struct Foo {
+        a: Bar,
+    }
+
+    struct OldFoo {
+        b: i32,
+    }
+
+    #[migrate]
+    fn migrate_foo(old: OldFoo) -> Foo {
+        Foo { a: Bar { value: old.b } }
+    }
+    "#,
+    );
+    assert!(matches!(
+        result,
+        Err(ReloadError::IncompatibleMigration { ref name, ref ty }) if name == "migrate_foo" && ty == "Foo"
+    ));
+
+    // The heap should not have been mapped
+    assert_eq!(foo.by_ref().get::<i32>("a").unwrap(), 5);
+}
+
+#[test]
+fn reload_event() {
+    let reloads = Arc::new(Mutex::new(Vec::new()));
+    let reloads_clone = reloads.clone();
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct(gc) Foo {
+        a: i32,
+    }
+
+    pub fn foo_new(a: i32) -> Foo { Foo { a } }
+    pub fn main() -> i32 { 5 }
+    "#,
+        move |builder| {
+            builder.on_reload(move |event: &ReloadEvent| reloads_clone.lock().push(event.clone()))
+        },
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    assert_eq!(runtime.borrow_mut().poll_reload_event(), None);
+
+    driver.update(
+        runtime.borrow(),
+        "mod.mun",
+        r#"
+    pub struct(gc) Foo {
+        a: i64,
+    }
+
+    pub fn foo_new(a: i64) -> Foo { Foo { a } }
+    pub fn bar() -> i32 { 5 }
+    "#,
+    );
+
+    let reloads = reloads.lock();
+    assert_eq!(reloads.len(), 1);
+
+    let event = &reloads[0];
+    assert_eq!(event.assemblies.len(), 1);
+    assert!(event.assemblies[0].ends_with("mod.munlib"));
+    assert_eq!(event.changed_functions, vec!["bar", "foo_new", "main"]);
+    assert_eq!(event.migrated_types, vec!["Foo"]);
+
+    let mut runtime = runtime.borrow_mut();
+    assert_eq!(runtime.poll_reload_event().as_ref(), Some(event));
+    assert_eq!(runtime.poll_reload_event(), None);
+}
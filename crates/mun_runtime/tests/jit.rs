@@ -0,0 +1,153 @@
+#![cfg(feature = "jit")]
+
+use mun_runtime::{invoke_fn, Backend, ProfileKind, ReloadError, RuntimeBuilder};
+use parking_lot::Mutex;
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+/// Writes a package with a single `mod.mun` source file to a temporary directory. Returns the
+/// directory and the path of the package's manifest.
+fn write_package(source: &str) -> (tempfile::TempDir, PathBuf) {
+    let package_dir = tempfile::TempDir::new().unwrap();
+    let manifest_path = package_dir.path().join("mun.toml");
+    std::fs::write(
+        &manifest_path,
+        r#"
+[package]
+name = "jit"
+authors = []
+version = "0.1.0"
+"#,
+    )
+    .unwrap();
+
+    let source_dir = package_dir.path().join("src");
+    std::fs::create_dir(&source_dir).unwrap();
+    std::fs::write(source_dir.join("mod.mun"), source).unwrap();
+
+    (package_dir, manifest_path)
+}
+
+#[test]
+fn jit_hotreloadable() {
+    let (package_dir, manifest_path) = write_package("pub fn main() -> i32 { 5 }");
+
+    let runtime = RuntimeBuilder::new(&manifest_path)
+        .backend(Backend::Jit(ProfileKind::Dev))
+        .spawn()
+        .expect("Failed to spawn runtime");
+
+    let result: i32 = invoke_fn!(runtime.borrow(), "main").unwrap();
+    assert_eq!(result, 5);
+
+    std::fs::write(
+        package_dir.path().join("src/mod.mun"),
+        "pub fn main() -> i32 { 10 }",
+    )
+    .unwrap();
+
+    let start_time = Instant::now();
+    while !runtime.borrow_mut().try_update().unwrap() {
+        if Instant::now() - start_time > Duration::from_secs(10) {
+            panic!("runtime did not update after recompilation within 10 seconds");
+        }
+        sleep(Duration::from_millis(1));
+    }
+
+    let result: i32 = invoke_fn!(runtime.borrow(), "main").unwrap();
+    assert_eq!(result, 10);
+}
+
+#[test]
+fn jit_compile_error() {
+    let (_package_dir, manifest_path) = write_package("pub fn main() -> i32 { true }");
+
+    let runtime = RuntimeBuilder::new(&manifest_path)
+        .backend(Backend::Jit(ProfileKind::Dev))
+        .spawn();
+    assert!(runtime.is_err());
+}
+
+#[test]
+fn from_package_diagnostics() {
+    let (package_dir, manifest_path) = write_package("pub fn main() -> i32 { 5 }");
+
+    let diagnostics = Arc::new(Mutex::new(Vec::new()));
+    let diagnostics_clone = diagnostics.clone();
+    let runtime = RuntimeBuilder::from_package(&manifest_path, ProfileKind::Dev)
+        .on_diagnostics(move |d| diagnostics_clone.lock().push(d.to_string()))
+        .spawn()
+        .expect("Failed to spawn runtime");
+    assert!(diagnostics.lock().is_empty());
+
+    let result: i32 = invoke_fn!(runtime.borrow(), "main").unwrap();
+    assert_eq!(result, 5);
+
+    std::fs::write(
+        package_dir.path().join("src/mod.mun"),
+        "pub fn main() -> i32 { true }",
+    )
+    .unwrap();
+
+    let start_time = Instant::now();
+    loop {
+        match runtime.borrow_mut().try_update() {
+            Err(ReloadError::Compile(_)) => break,
+            Ok(false) => (),
+            result => panic!("unexpected result: {:?}", result),
+        }
+        if Instant::now() - start_time > Duration::from_secs(10) {
+            panic!("runtime did not try to recompile within 10 seconds");
+        }
+        sleep(Duration::from_millis(1));
+    }
+    assert_eq!(diagnostics.lock().len(), 1);
+
+    // The previously compiled code is still linked
+    let result: i32 = invoke_fn!(runtime.borrow(), "main").unwrap();
+    assert_eq!(result, 5);
+}
+
+#[test]
+fn jit_remove_source_file() {
+    let (package_dir, manifest_path) = write_package("pub fn main() -> i32 { 5 }");
+    std::fs::write(
+        package_dir.path().join("src/foo.mun"),
+        "pub fn foo() -> i32 { 1 }",
+    )
+    .unwrap();
+
+    let runtime = RuntimeBuilder::new(&manifest_path)
+        .backend(Backend::Jit(ProfileKind::Dev))
+        .spawn()
+        .expect("Failed to spawn runtime");
+
+    let result: i32 = invoke_fn!(runtime.borrow(), "foo::foo").unwrap();
+    assert_eq!(result, 1);
+
+    std::fs::remove_file(package_dir.path().join("src/foo.mun")).unwrap();
+
+    let start_time = Instant::now();
+    while !runtime.borrow_mut().try_update().unwrap() {
+        if Instant::now() - start_time > Duration::from_secs(10) {
+            panic!("runtime did not update after removing a source file within 10 seconds");
+        }
+        sleep(Duration::from_millis(1));
+    }
+
+    // The assembly of the removed source file is unlinked
+    assert!(runtime
+        .borrow()
+        .get_function_definition("foo::foo")
+        .is_none());
+    let event = runtime.borrow_mut().poll_reload_event().unwrap();
+    assert_eq!(event.assemblies.len(), 1);
+    assert_eq!(event.changed_functions, vec!["foo::foo".to_owned()]);
+
+    let result: i32 = invoke_fn!(runtime.borrow(), "main").unwrap();
+    assert_eq!(result, 5);
+}
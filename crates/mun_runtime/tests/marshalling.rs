@@ -1,4 +1,9 @@
-use mun_runtime::{invoke_fn, ArgumentReflection, Marshal, ReturnTypeReflection, StructRef};
+use mun_runtime::{
+    abi, invoke_fn, ArgumentReflection, ExternTypeInfo, Marshal, MunStruct, RawStruct,
+    ReturnTypeReflection, StructRef, Value,
+};
+use once_cell::sync::OnceCell;
+use std::{alloc::Layout, ffi::CStr};
 
 use mun_test::CompileAndRunTestDriver;
 
@@ -532,6 +537,247 @@ fn marshal_struct() {
     assert!(bar_err.is_err());
 }
 
+#[test]
+fn marshal_struct_dynamic() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    struct(value) Foo { a: i32, b: bool };
+    struct(gc) Bar { c: f64 };
+    struct Baz { foo: Foo, bar: Bar };
+
+    pub fn baz_new(a: i32, b: bool, c: f64) -> Baz {
+        Baz { foo: Foo { a, b }, bar: Bar { c } }
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+    let mut baz: StructRef = invoke_fn!(runtime_ref, "baz_new", 3i32, true, 1.5f64).unwrap();
+
+    // Enumerate the fields
+    let values = baz.values().unwrap();
+    assert_eq!(
+        values.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+        vec!["foo", "bar"]
+    );
+
+    // Read and write a nested value struct, which is copied
+    let mut foo = match baz.get_value("foo").unwrap() {
+        Value::Struct(foo) => foo,
+        _ => panic!("expected a struct"),
+    };
+    assert!(matches!(foo.get_value("a"), Ok(Value::I32(3))));
+    assert!(matches!(foo.get_value("b"), Ok(Value::Bool(true))));
+    foo.set_value("a", Value::I32(4)).unwrap();
+    assert!(matches!(
+        baz.get_value("foo"),
+        Ok(Value::Struct(ref foo)) if foo.get::<i32>("a") == Ok(3)
+    ));
+    baz.set_value("foo", Value::Struct(foo)).unwrap();
+    assert!(matches!(
+        baz.get_value("foo"),
+        Ok(Value::Struct(ref foo)) if foo.get::<i32>("a") == Ok(4)
+    ));
+
+    // Read and write a nested gc struct, which is referenced
+    let mut bar = match baz.get_value("bar").unwrap() {
+        Value::Struct(bar) => bar,
+        _ => panic!("expected a struct"),
+    };
+    assert!(matches!(bar.get_value("c"), Ok(Value::F64(_))));
+    assert_eq!(bar.get::<f64>("c"), Ok(1.5));
+    bar.set_value("c", 2.5f64.into()).unwrap();
+    assert_eq!(
+        baz.get::<StructRef>("bar").unwrap().get::<f64>("c"),
+        Ok(2.5)
+    );
+
+    // Mismatched types are rejected
+    assert!(bar.set_value("c", Value::F32(1.0)).is_err());
+    assert!(baz.set_value("foo", Value::Struct(bar)).is_err());
+    assert!(baz.get_value("missing").is_err());
+}
+
+#[test]
+fn marshal_struct_derive() {
+    #[derive(Clone, Copy, Debug, PartialEq, MunStruct)]
+    #[repr(C)]
+    struct Vec2 {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, MunStruct)]
+    #[repr(C)]
+    struct Transform {
+        position: Vec2,
+        scale: f32,
+        visible: bool,
+    }
+
+    mod reexport {
+        pub use ::mun_runtime;
+    }
+
+    #[derive(Clone, Copy, MunStruct)]
+    #[mun(crate = "reexport::mun_runtime")]
+    #[repr(C)]
+    struct Foo {
+        a: i64,
+    }
+
+    #[derive(Clone, Copy, MunStruct)]
+    #[repr(C, align(8))]
+    struct Pair {
+        a: u32,
+        b: u32,
+    }
+
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct(value) Vec2 { x: f32, y: f32 };
+    pub struct(value) Transform { position: Vec2, scale: f32, visible: bool };
+    pub struct(value) Foo { a: i32 };
+    pub struct(value) Pair { a: u32, b: u32 };
+    pub struct(gc) Entity { transform: Transform };
+
+    pub fn vec2_add(a: Vec2, b: Vec2) -> Vec2 { Vec2 { x: a.x + b.x, y: a.y + b.y } }
+    pub fn transform_new(position: Vec2) -> Transform {
+        Transform { position, scale: 1.0, visible: true }
+    }
+    pub fn entity_new(transform: Transform) -> Entity { Entity { transform } }
+    pub fn foo_new() -> Foo { Foo { a: 1 } }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+
+    // Value structs are passed and returned by copy
+    let sum: Vec2 = invoke_fn!(
+        runtime_ref,
+        "vec2_add",
+        Vec2 { x: 1.0, y: 2.0 },
+        Vec2 { x: 3.0, y: 4.0 }
+    )
+    .unwrap();
+    assert_eq!(sum, Vec2 { x: 4.0, y: 6.0 });
+
+    let transform: Transform = invoke_fn!(runtime_ref, "transform_new", sum).unwrap();
+    assert_eq!(
+        transform,
+        Transform {
+            position: sum,
+            scale: 1.0,
+            visible: true,
+        }
+    );
+
+    // Fields of a Mun struct can be read and written by copy
+    let mut entity: StructRef = invoke_fn!(runtime_ref, "entity_new", transform).unwrap();
+    assert_eq!(entity.get::<Transform>("transform"), Ok(transform));
+    let moved = Transform {
+        position: Vec2 { x: 0.0, y: 0.0 },
+        ..transform
+    };
+    entity.set("transform", moved).unwrap();
+    assert_eq!(entity.get::<Transform>("transform"), Ok(moved));
+    assert!(entity.get::<Vec2>("transform").is_err());
+
+    // Mismatched memory layouts are rejected
+    let foo_type = runtime_ref.get_type_info("Foo").unwrap();
+    assert!(Foo::validate(foo_type).is_err());
+    let result: Result<Foo, _> = invoke_fn!(runtime_ref, "foo_new");
+    assert!(result.is_err());
+    let vec2_type = runtime_ref.get_type_info("Vec2").unwrap();
+    assert!(Vec2::validate(vec2_type).is_ok());
+    assert!(Transform::validate(vec2_type).is_err());
+
+    // Structs of the same size with a stricter alignment are rejected
+    let pair_type = runtime_ref.get_type_info("Pair").unwrap();
+    assert_eq!(
+        Pair::validate(pair_type),
+        Err("Mismatched alignment for `Pair`. Expected: 4. Found: 8.".to_owned())
+    );
+}
+
+#[test]
+fn invoke_dynamic() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    struct(value) Foo { a: i32, b: bool };
+    struct(gc) Bar { c: f64 };
+
+    pub fn add(a: i64, b: i64) -> i64 { a + b }
+    pub fn scale(a: f32, b: f32) -> f32 { a * b }
+    pub fn is_max(a: u8) -> bool { a == 255 }
+    pub fn foo_new(a: i32, b: bool) -> Foo { Foo { a, b } }
+    pub fn foo_a(foo: Foo) -> i32 { foo.a }
+    pub fn bar_new(c: f64) -> Bar { Bar { c } }
+    pub fn bar_set(bar: Bar, c: f64) { bar.c = c; }
+    pub fn add_wide(a: i128, b: i128) -> i128 { a + b }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+
+    // Primitive arguments and return values
+    assert!(matches!(
+        runtime_ref.invoke_dynamic("add", &[Value::I64(3), Value::I64(4)]),
+        Ok(Some(Value::I64(7)))
+    ));
+    match runtime_ref.invoke_dynamic("scale", &[Value::F32(1.5), Value::F32(2.0)]) {
+        Ok(Some(Value::F32(result))) => assert_eq!(result, 3.0),
+        _ => panic!("expected an f32"),
+    }
+    assert!(matches!(
+        runtime_ref.invoke_dynamic("is_max", &[Value::U8(255)]),
+        Ok(Some(Value::Bool(true)))
+    ));
+
+    // Value structs are passed and returned by handle
+    let foo = match runtime_ref.invoke_dynamic("foo_new", &[Value::I32(5), Value::Bool(true)]) {
+        Ok(Some(Value::Struct(foo))) => foo,
+        _ => panic!("expected a struct"),
+    };
+    assert_eq!(foo.get::<i32>("a"), Ok(5));
+    assert!(matches!(
+        runtime_ref.invoke_dynamic("foo_a", &[Value::Struct(foo.clone())]),
+        Ok(Some(Value::I32(5)))
+    ));
+
+    // Gc structs are passed by reference and functions without a return type return `None`
+    let bar: StructRef = invoke_fn!(runtime_ref, "bar_new", 1.5f64).unwrap();
+    assert!(matches!(
+        runtime_ref.invoke_dynamic("bar_set", &[Value::Struct(bar.clone()), Value::F64(2.5)]),
+        Ok(None)
+    ));
+    assert_eq!(bar.get::<f64>("c"), Ok(2.5));
+
+    // Mismatched signatures are rejected
+    assert!(runtime_ref.invoke_dynamic("missing", &[]).is_err());
+    assert!(runtime_ref.invoke_dynamic("add", &[Value::I64(3)]).is_err());
+    assert!(runtime_ref
+        .invoke_dynamic("add", &[Value::I64(3), Value::I32(4)])
+        .is_err());
+    assert!(runtime_ref
+        .invoke_dynamic("foo_a", &[Value::Struct(bar)])
+        .is_err());
+
+    // 128-bit integers cannot be passed dynamically
+    assert!(runtime_ref
+        .invoke_dynamic("add_wide", &[Value::I128(3), Value::I128(4)])
+        .is_err());
+}
+
 #[test]
 fn extern_fn() {
     extern "C" fn add_int(a: i32, b: i32) -> i32 {
@@ -709,6 +955,97 @@ fn can_add_external_without_return() {
     let _: () = invoke_fn!(runtime_ref, "main").unwrap();
 }
 
+#[test]
+fn extern_struct() {
+    #[repr(C)]
+    struct Entity {
+        id: u32,
+    }
+
+    /// A handle to an `Entity`, as passed to and from `extern fn`s
+    #[repr(transparent)]
+    struct EntityRef(RawStruct);
+
+    impl abi::HasStaticTypeInfo for EntityRef {
+        fn type_info() -> &'static abi::TypeInfo {
+            static TYPE_INFO: OnceCell<abi::TypeInfo> = OnceCell::new();
+            TYPE_INFO.get_or_init(|| {
+                abi::TypeInfo::new_extern_struct(
+                    CStr::from_bytes_with_nul(b"Entity\0").unwrap(),
+                    (std::mem::size_of::<EntityRef>() * 8) as u32,
+                    std::mem::align_of::<EntityRef>() as u8,
+                )
+            })
+        }
+    }
+
+    extern "C" fn entity_id(entity: EntityRef) -> u32 {
+        unsafe { (*entity.0.get_ptr().cast::<Entity>()).id }
+    }
+
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    extern struct Entity;
+    extern fn entity_id(entity: Entity) -> u32;
+
+    pub struct Player {
+        entity: Entity,
+        score: u32,
+    }
+
+    pub fn new_player(entity: Entity) -> Player {
+        Player { entity, score: 0 }
+    }
+
+    pub fn player_id(player: Player) -> u32 {
+        entity_id(player.entity)
+    }
+    "#,
+        |builder| {
+            builder
+                .insert_type(ExternTypeInfo::new("Entity", Layout::new::<Entity>()))
+                .insert_fn("entity_id", entity_id as extern "C" fn(EntityRef) -> u32)
+        },
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+
+    let entity = runtime_ref.new_extern("Entity", Entity { id: 42 }).unwrap();
+    let entity = StructRef::marshal_from(entity, &runtime_ref);
+    assert_eq!(entity.type_info().name(), "Entity");
+
+    let player: StructRef = invoke_fn!(runtime_ref, "new_player", entity).unwrap();
+    let player = player.root(driver.runtime());
+
+    // The entity is referenced by the rooted player, so it is not collected
+    runtime_ref.gc_collect();
+
+    let id: u32 = invoke_fn!(runtime_ref, "player_id", unsafe {
+        player.as_ref(&runtime_ref)
+    })
+    .unwrap();
+    assert_eq!(id, 42);
+
+    // The layout of the extern type must match
+    assert!(runtime_ref.new_extern("Entity", 42u64).is_err());
+    assert!(runtime_ref.new_extern("Unknown", 42u32).is_err());
+}
+
+#[test]
+fn extern_struct_missing() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    extern struct Entity;
+    pub fn main(entity: Entity) -> Entity { entity }
+    "#,
+        |builder| builder,
+    );
+
+    assert!(driver.is_err());
+}
+
 #[test]
 fn signed_and_unsigned_rem() {
     let driver = CompileAndRunTestDriver::new(
@@ -1,5 +1,7 @@
-use mun_runtime::{invoke_fn, StructRef};
+use memory::gc::HasIndirectionPtr;
+use mun_runtime::{abi, invoke_fn, ExternTypeInfo, Marshal, RawStruct, StructRef, UnsafeTypeInfo};
 use mun_test::CompileAndRunTestDriver;
+use std::{alloc::Layout, ffi::CString, ptr::NonNull, sync::Arc};
 
 #[macro_use]
 mod util;
@@ -45,6 +47,232 @@ fn gc_trace() {
     assert_eq!(runtime_ref.gc_stats().allocated_memory, 0);
 }
 
+#[test]
+fn gc_trace_extern() {
+    #[repr(C)]
+    struct Inventory {
+        item: RawStruct,
+    }
+
+    fn trace_inventory(object: NonNull<u8>, visit: &mut dyn FnMut(RawStruct)) {
+        let inventory = unsafe { &*object.cast::<Inventory>().as_ptr() };
+        visit(inventory.item.clone());
+    }
+
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Item {
+        value: i64,
+    }
+
+    pub fn new_item(value: i64) -> Item {
+        Item { value }
+    }
+
+    pub fn item_value(item: Item) -> i64 {
+        item.value
+    }
+    "#,
+        |builder| {
+            builder.insert_type(
+                ExternTypeInfo::new("Inventory", Layout::new::<Inventory>())
+                    .with_trace(trace_inventory),
+            )
+        },
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+
+    let item: StructRef = invoke_fn!(runtime_ref, "new_item", 5i64).unwrap();
+    let inventory = runtime_ref
+        .new_extern(
+            "Inventory",
+            Inventory {
+                item: item.into_raw(),
+            },
+        )
+        .unwrap();
+    let inventory = StructRef::marshal_from(inventory, &runtime_ref).root(driver.runtime());
+
+    // The item is traced through the rooted inventory, so it is not collected
+    assert_eq!(runtime_ref.gc_collect(), false);
+
+    let item = unsafe {
+        (*inventory
+            .by_ref()
+            .clone()
+            .into_raw()
+            .get_ptr()
+            .cast::<Inventory>())
+        .item
+        .clone()
+    };
+    let item = StructRef::marshal_from(item, &runtime_ref);
+    let value: i64 = invoke_fn!(runtime_ref, "item_value", item).unwrap();
+    assert_eq!(value, 5);
+
+    drop(inventory);
+
+    assert_eq!(runtime_ref.gc_collect(), true);
+    assert_eq!(runtime_ref.gc_stats().allocated_memory, 0);
+}
+
+#[test]
+fn gc_alloc_extern_assembly_type() {
+    #[repr(C)]
+    struct Inventory {
+        item: RawStruct,
+    }
+
+    fn trace_inventory(object: NonNull<u8>, visit: &mut dyn FnMut(RawStruct)) {
+        let inventory = unsafe { &*object.cast::<Inventory>().as_ptr() };
+        visit(inventory.item.clone());
+    }
+
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Item {
+        value: i64,
+    }
+
+    extern struct Inventory;
+
+    pub fn new_item(value: i64) -> Item {
+        Item { value }
+    }
+
+    pub fn inventory_id(inventory: Inventory) -> Inventory {
+        inventory
+    }
+    "#,
+        |builder| {
+            builder.insert_type(
+                ExternTypeInfo::new("Inventory", Layout::new::<Inventory>())
+                    .with_trace(trace_inventory),
+            )
+        },
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+
+    // The assembly contains its own type information for the extern struct
+    let assembly_type = runtime_ref
+        .get_function_definition("inventory_id")
+        .unwrap()
+        .prototype
+        .signature
+        .return_type()
+        .unwrap();
+    let extern_type = runtime_ref.get_type_info("Inventory").unwrap();
+    assert!(!std::ptr::eq(assembly_type, extern_type));
+
+    // Allocating with the assembly's type information resolves to the registered extern type
+    let mut handle = runtime_ref
+        .gc()
+        .alloc(UnsafeTypeInfo::new(NonNull::from(assembly_type)))
+        .unwrap();
+    assert!(std::ptr::eq(
+        runtime_ref.gc().ptr_type(handle).into_inner().as_ptr(),
+        extern_type
+    ));
+
+    let item: StructRef = invoke_fn!(runtime_ref, "new_item", 5i64).unwrap();
+    unsafe {
+        handle.deref_mut::<Inventory>().write(Inventory {
+            item: item.into_raw(),
+        })
+    };
+    let inventory =
+        StructRef::marshal_from(RawStruct::from(handle), &runtime_ref).root(driver.runtime());
+
+    // The item is traced through the rooted inventory, so it is not collected
+    assert_eq!(runtime_ref.gc_collect(), false);
+
+    drop(inventory);
+
+    assert_eq!(runtime_ref.gc_collect(), true);
+    assert_eq!(runtime_ref.gc_stats().allocated_memory, 0);
+}
+
+#[test]
+fn gc_alloc_unregistered_extern_type() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub fn main() {}
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+
+    let name = CString::new("Inventory").unwrap();
+    let type_info = abi::TypeInfo::new_extern_struct(&name, 64, 8);
+    assert!(runtime_ref
+        .gc()
+        .alloc(UnsafeTypeInfo::new(NonNull::from(&type_info)))
+        .is_err());
+}
+
+#[test]
+fn gc_drop_extern() {
+    struct Resource {
+        counter: Arc<()>,
+    }
+
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub fn main() {}
+    "#,
+        |builder| {
+            builder
+                .insert_type(ExternTypeInfo::of::<Resource>("Resource"))
+                .insert_type(ExternTypeInfo::new("Opaque", Layout::new::<Resource>()))
+        },
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+
+    let counter = Arc::new(());
+    let resource = runtime_ref
+        .new_extern(
+            "Resource",
+            Resource {
+                counter: counter.clone(),
+            },
+        )
+        .unwrap();
+    let resource = StructRef::marshal_from(resource, &runtime_ref).root(driver.runtime());
+
+    // The resource is only dropped once it is collected
+    assert_eq!(runtime_ref.gc_collect(), false);
+    assert_eq!(Arc::strong_count(&counter), 2);
+
+    drop(resource);
+
+    assert_eq!(runtime_ref.gc_collect(), true);
+    assert_eq!(Arc::strong_count(&counter), 1);
+
+    // A value of a different type, or that cannot be dropped, is rejected
+    assert!(runtime_ref.new_extern("Resource", counter.clone()).is_err());
+    assert!(runtime_ref
+        .new_extern(
+            "Opaque",
+            Resource {
+                counter: counter.clone(),
+            },
+        )
+        .is_err());
+    assert_eq!(Arc::strong_count(&counter), 1);
+}
+
 #[test]
 fn map_struct_insert_field1() {
     let mut driver = CompileAndRunTestDriver::new(
@@ -548,6 +776,151 @@ fn map_struct_rename_field2() {
     assert_eq!(foo.by_ref().get::<f64>("f").unwrap(), c);
 }
 
+#[test]
+fn map_struct_renamed_from_field() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+        struct Foo {
+            pos: f32,
+            b: i64,
+        }
+
+        pub fn foo_new(pos: f32, b: i64) -> Foo {
+            Foo { pos, b }
+        }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+
+    let pos = 2.0f32;
+    let b = 5i64;
+    let foo: StructRef = invoke_fn!(runtime_ref, "foo_new", pos, b).unwrap();
+    let foo = foo.root(driver.runtime());
+
+    driver.update(
+        runtime_ref,
+        "mod.mun",
+        r#"
+        struct Foo {
+            b: i64,
+            #[renamed_from(pos)]
+            position: f64,
+        }
+    "#,
+    );
+    assert_eq!(foo.by_ref().get::<i64>("b").unwrap(), b);
+    assert_eq!(foo.by_ref().get::<f64>("position").unwrap(), pos.into());
+}
+
+#[test]
+fn map_struct_migration() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+        struct Player {
+            health: f32,
+        }
+
+        pub fn player_new(health: f32) -> Player {
+            Player { health }
+        }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+
+    let health = 75.0f32;
+    let player: StructRef = invoke_fn!(runtime_ref, "player_new", health).unwrap();
+    let player = player.root(driver.runtime());
+
+    driver.update(
+        runtime_ref,
+        "mod.mun",
+        r#"
+        struct Health {
+            value: f32,
+        }
+
+        struct Player {
+            health: Health,
+        }
+
+        struct OldPlayer {
+            health: f32,
+        }
+
+        #[migrate]
+        fn migrate_player(old: OldPlayer) -> Player {
+            Player { health: Health { value: old.health } }
+        }
+    "#,
+    );
+    let health_ref = player.by_ref().get::<StructRef>("health").unwrap();
+    assert_eq!(health_ref.get::<f32>("value").unwrap(), health);
+}
+
+#[test]
+fn map_struct_migration_collect() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+        struct Player {
+            health: f32,
+        }
+
+        pub fn player_new(health: f32) -> Player {
+            Player { health }
+        }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+
+    let health = 75.0f32;
+    let player: StructRef = invoke_fn!(runtime_ref, "player_new", health).unwrap();
+    let player = player.root(driver.runtime());
+
+    driver.update(
+        runtime_ref,
+        "mod.mun",
+        r#"
+        struct Health {
+            value: f32,
+        }
+
+        struct Player {
+            health: Health,
+        }
+
+        struct OldPlayer {
+            health: f32,
+        }
+
+        #[migrate]
+        fn migrate_player(old: OldPlayer) -> Player {
+            Player { health: Health { value: old.health } }
+        }
+    "#,
+    );
+
+    // The argument and result of the migration are no longer referenced, but the `Health` object
+    // is referenced by the migrated player.
+    let runtime_ref = runtime.borrow();
+    assert_eq!(runtime_ref.gc_collect(), true);
+    drop(runtime_ref);
+
+    let health_ref = player.by_ref().get::<StructRef>("health").unwrap();
+    assert_eq!(health_ref.get::<f32>("value").unwrap(), health);
+}
+
 #[test]
 fn map_struct_all() {
     let mut driver = CompileAndRunTestDriver::new(
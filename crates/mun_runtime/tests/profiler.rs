@@ -0,0 +1,153 @@
+use compiler::Config;
+use mun_test::CompileAndRunTestDriver;
+
+#[macro_use]
+mod util;
+
+fn instrument_functions(config: Config) -> Config {
+    Config {
+        instrument_functions: true,
+        ..config
+    }
+}
+
+#[test]
+fn profiler_counts_calls() {
+    let driver = CompileAndRunTestDriver::with_compiler_config(
+        r"
+    fn fibonacci(n: i32) -> i32 {
+        if n <= 1 {
+            return n;
+        }
+        fibonacci(n - 1) + fibonacci(n - 2)
+    }
+
+    pub fn main() -> i32 {
+        fibonacci(5)
+    }
+    ",
+        instrument_functions,
+        |builder| builder.enable_profiler(),
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 5, driver, "main");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+    let profiler = runtime_ref.profiler().expect("profiler is not enabled");
+    let snapshot = profiler.snapshot();
+
+    let main = snapshot.iter().find(|f| f.path == "main").unwrap();
+    let fibonacci = snapshot.iter().find(|f| f.path == "fibonacci").unwrap();
+    assert_eq!(main.call_count, 1);
+    assert_eq!(fibonacci.call_count, 15);
+    assert!(main.inclusive_time >= fibonacci.inclusive_time);
+    assert!(fibonacci.inclusive_time >= fibonacci.exclusive_time);
+
+    profiler.reset();
+    assert!(profiler.snapshot().is_empty());
+}
+
+#[test]
+fn profiler_counts_calls_across_reloads() {
+    let mut driver = CompileAndRunTestDriver::with_compiler_config(
+        r"
+    pub fn main() -> i32 { 5 }
+    ",
+        instrument_functions,
+        |builder| builder.enable_profiler(),
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 5, driver, "main");
+
+    // The reloaded library may reuse the addresses of the unloaded one, but its calls are still
+    // attributed to the functions by their path
+    let runtime = driver.runtime();
+    driver.update(
+        runtime.borrow(),
+        "mod.mun",
+        r"
+    pub fn main() -> i32 { 6 }
+    ",
+    );
+    assert_invoke_eq!(i32, 6, driver, "main");
+
+    let runtime_ref = runtime.borrow();
+    let snapshot = runtime_ref.profiler().unwrap().snapshot();
+    assert_eq!(snapshot.len(), 1);
+    assert_eq!(snapshot[0].path, "main");
+    assert_eq!(snapshot[0].call_count, 2);
+}
+
+#[test]
+fn profiler_exports() {
+    let driver = CompileAndRunTestDriver::with_compiler_config(
+        r"
+    fn bar() {}
+
+    pub fn foo() {
+        bar();
+        bar()
+    }
+    ",
+        instrument_functions,
+        |builder| builder.enable_profiler(),
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+    let _: () = mun_runtime::invoke_fn!(runtime_ref, "foo").unwrap();
+
+    let profiler = runtime_ref.profiler().expect("profiler is not enabled");
+
+    let mut folded_stacks = Vec::new();
+    profiler.write_folded_stacks(&mut folded_stacks).unwrap();
+    let folded_stacks = String::from_utf8(folded_stacks).unwrap();
+    let call_paths: Vec<&str> = folded_stacks
+        .lines()
+        .map(|line| line.rsplitn(2, ' ').nth(1).unwrap())
+        .collect();
+    assert_eq!(call_paths, vec!["foo", "foo;bar"]);
+
+    let mut chrome_trace = Vec::new();
+    profiler.write_chrome_trace(&mut chrome_trace).unwrap();
+    let chrome_trace = String::from_utf8(chrome_trace).unwrap();
+    assert_eq!(chrome_trace.matches("\"ph\":\"B\"").count(), 3);
+    assert_eq!(chrome_trace.matches("\"ph\":\"E\"").count(), 3);
+}
+
+#[test]
+fn profiler_without_instrumentation() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn main() -> i32 { 5 }
+    ",
+        |builder| builder.enable_profiler(),
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 5, driver, "main");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+    let profiler = runtime_ref.profiler().expect("profiler is not enabled");
+    assert!(profiler.snapshot().is_empty());
+}
+
+#[test]
+fn profiler_disabled() {
+    let driver = CompileAndRunTestDriver::with_compiler_config(
+        r"
+    pub fn main() -> i32 { 5 }
+    ",
+        instrument_functions,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 5, driver, "main");
+    assert!(driver.runtime().borrow().profiler().is_none());
+}
@@ -1,4 +1,6 @@
-use mun_test::CompileAndRunTestDriver;
+use compiler::Config;
+use mun_runtime::{abi, invoke_fn, RuntimeBuilder, StaticLibrary};
+use mun_test::{CompileAndRunTestDriver, CompileTestDriver};
 use std::io;
 
 #[macro_use]
@@ -114,3 +116,218 @@ fn arg_missing_bug() {
     );
     driver.unwrap();
 }
+
+#[test]
+fn diagnostics_require_jit() {
+    let driver = CompileTestDriver::from_file("pub fn main() {}");
+
+    // Shared libraries are compiled beforehand, so there are no diagnostics to receive
+    let error = RuntimeBuilder::new(driver.lib_path())
+        .on_diagnostics(|_| ())
+        .spawn()
+        .err()
+        .expect("diagnostics cannot be received for shared libraries");
+    assert_eq!(
+        error.to_string(),
+        "compiler diagnostics are only available when using the JIT backend"
+    );
+}
+
+#[test]
+fn load_from_bytes() {
+    let driver = CompileTestDriver::from_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name="foo"
+    version="0.0.0"
+
+    //- /src/mod.mun
+    pub fn main() -> i32 { foo::foo() }
+
+    //- /src/foo.mun
+    pub fn foo() -> i32 { 5 }
+    "#,
+    );
+
+    // The libraries do not exist on disk at the specified paths
+    let main_bytes = std::fs::read(driver.lib_path()).unwrap();
+    let foo_bytes = std::fs::read(driver.lib_path().with_file_name("foo.munlib")).unwrap();
+    let runtime = RuntimeBuilder::from_bytes("archive/mod.munlib", main_bytes)
+        .insert_library_bytes("archive/foo.munlib", foo_bytes)
+        .spawn()
+        .expect("Failed to spawn runtime");
+
+    let result: i32 = invoke_fn!(runtime.borrow(), "main").unwrap();
+    assert_eq!(result, 5);
+}
+
+#[test]
+fn direct_calls() {
+    let driver = CompileTestDriver::from_fixture_with_config(
+        r#"
+    //- /mun.toml
+    [package]
+    name="foo"
+    version="0.0.0"
+
+    //- /src/mod.mun
+    pub fn main() -> i32 { foo::foo() }
+
+    //- /src/foo.mun
+    pub fn foo() -> i32 { 5 }
+    "#,
+        |config| Config {
+            direct_calls: true,
+            ..config
+        },
+    );
+
+    // All modules are compiled into a single assembly
+    assert!(!driver.lib_path().with_file_name("foo.munlib").exists());
+
+    let runtime = RuntimeBuilder::new(driver.lib_path())
+        .disable_hot_reloading()
+        .spawn()
+        .expect("Failed to spawn runtime");
+
+    let result: i32 = invoke_fn!(runtime.borrow(), "main").unwrap();
+    assert_eq!(result, 5);
+    assert!(!runtime.borrow_mut().try_update().unwrap());
+}
+
+#[test]
+fn path_dependency() {
+    // The fixture is compiled using `Driver::with_package_path`, which also loads the dependency
+    let driver = CompileTestDriver::from_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name="foo"
+    version="0.0.0"
+
+    [dependencies]
+    math = { path = "math" }
+
+    //- /src/mod.mun
+    pub fn main() -> i32 { math::add(40, 2) }
+
+    //- /math/mun.toml
+    [package]
+    name="math"
+    version="0.0.0"
+
+    //- /math/src/mod.mun
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    "#,
+    );
+
+    // The dependency is compiled into an assembly named after its package
+    assert!(driver.lib_path().with_file_name("math.munlib").exists());
+    let library = libloader::MunLibrary::new(driver.lib_path()).unwrap();
+    assert_eq!(
+        library.get_info().dependencies().collect::<Vec<_>>(),
+        ["math"]
+    );
+
+    let runtime = RuntimeBuilder::new(driver.lib_path())
+        .spawn()
+        .expect("Failed to spawn runtime");
+
+    let result: i32 = invoke_fn!(runtime.borrow(), "main").unwrap();
+    assert_eq!(result, 42);
+    let result: i32 = invoke_fn!(runtime.borrow(), "math::add", 1, 2).unwrap();
+    assert_eq!(result, 3);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn from_static() {
+    let driver =
+        CompileTestDriver::from_file_with_config("pub fn main() -> i32 { 5 }", |config| Config {
+            emit_static: true,
+            ..config
+        });
+    let object_path = driver.lib_path().with_extension("o");
+    assert!(!driver.lib_path().exists());
+
+    // Emulate static linking, by linking the object file into a shared library that exports its
+    // functions. The functions of a package without a name are prefixed with `mun`.
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let library_path = temp_dir.path().join("libstatic.so");
+    let status = std::process::Command::new(std::env::var("CC").unwrap_or_else(|_| "cc".into()))
+        .arg("-shared")
+        .arg(&object_path)
+        .arg("-o")
+        .arg(&library_path)
+        .status()
+        .expect("Failed to invoke linker");
+    assert!(status.success(), "Failed to link object file");
+
+    let library = libloader::TempLibrary::new(&library_path).unwrap();
+    let static_library = unsafe {
+        StaticLibrary {
+            get_abi_version: *library.library().get(b"mun_get_version").unwrap(),
+            get_info: *library.library().get(b"mun_get_info").unwrap(),
+            set_allocator_handle: *library.library().get(b"mun_set_allocator_handle").unwrap(),
+            set_profiler_handle: None,
+            set_coverage_handle: None,
+        }
+    };
+
+    // The unprefixed functions of a shared library are not exported
+    assert!(unsafe {
+        library
+            .library()
+            .get::<unsafe extern "C" fn() -> u32>(abi::GET_VERSION_FN_NAME.as_bytes())
+    }
+    .is_err());
+
+    let runtime = RuntimeBuilder::from_static("static/mod.munlib", static_library)
+        .spawn()
+        .expect("Failed to spawn runtime");
+
+    let result: i32 = invoke_fn!(runtime.borrow(), "main").unwrap();
+    assert_eq!(result, 5);
+}
+
+#[test]
+fn type_infos_and_function_definitions() {
+    let driver = CompileAndRunTestDriver::from_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name="foo"
+    version="0.0.0"
+
+    //- /src/mod.mun
+    pub struct Foo { a: i32 }
+
+    pub fn main() -> Foo { Foo { a: foo::bar() } }
+
+    //- /src/foo.mun
+    pub fn bar() -> i32 { 5 }
+
+    pub fn baz(foo: super::Foo) -> i32 { foo.a }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime = runtime.borrow();
+
+    let mut fn_names: Vec<&str> = runtime
+        .function_definitions()
+        .map(|fn_info| fn_info.prototype.name())
+        .collect();
+    fn_names.sort_unstable();
+    assert!(["foo::bar", "foo::baz", "main"]
+        .iter()
+        .all(|name| fn_names.contains(name)));
+
+    // `Foo` is used by both assemblies, but is only returned once
+    let type_names: Vec<&str> = runtime.type_infos().map(abi::TypeInfo::name).collect();
+    assert_eq!(type_names.iter().filter(|name| **name == "Foo").count(), 1);
+    assert!(type_names.contains(&"core::i32"));
+}
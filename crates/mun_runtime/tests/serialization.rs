@@ -0,0 +1,154 @@
+#![cfg(feature = "serde")]
+
+use mun_runtime::{invoke_fn, StructRef, StructSeed};
+use mun_test::CompileAndRunTestDriver;
+use serde::de::DeserializeSeed;
+
+#[test]
+fn serialize_struct() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    struct(gc) Bar { c: f64 };
+    struct(value) Foo { a: i32, bar: Bar };
+    struct Baz { foo: Foo, bar: Bar, flag: bool };
+
+    pub fn baz_new(c: f64) -> Baz {
+        let bar = Bar { c };
+        Baz { foo: Foo { a: 3, bar }, bar, flag: true }
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+    let baz: StructRef = invoke_fn!(runtime_ref, "baz_new", 1.5f64).unwrap();
+
+    // The shared `gc` struct is only serialized once
+    let json = serde_json::to_string(&baz).unwrap();
+    assert_eq!(
+        json,
+        r#"[{"foo":{"a":3,"bar":1},"bar":1,"flag":true},{"c":1.5}]"#
+    );
+
+    let baz_type = runtime_ref.get_type_info("Baz").unwrap();
+    let mut deserializer = serde_json::Deserializer::from_str(&json);
+    let baz = StructSeed::new(&runtime_ref, baz_type)
+        .deserialize(&mut deserializer)
+        .unwrap();
+    assert_eq!(baz.get::<bool>("flag"), Ok(true));
+
+    let foo = baz.get::<StructRef>("foo").unwrap();
+    assert_eq!(foo.get::<i32>("a"), Ok(3));
+
+    // The deserialized `gc` struct is still shared
+    let mut bar = baz.get::<StructRef>("bar").unwrap();
+    assert_eq!(bar.get::<f64>("c"), Ok(1.5));
+    bar.set("c", 2.5f64).unwrap();
+    assert_eq!(
+        foo.get::<StructRef>("bar").unwrap().get::<f64>("c"),
+        Ok(2.5)
+    );
+
+    assert_eq!(
+        serde_json::to_string(&baz).unwrap(),
+        r#"[{"foo":{"a":3,"bar":1},"bar":1,"flag":true},{"c":2.5}]"#
+    );
+}
+
+#[test]
+fn deserialize_struct_invalid() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    struct(gc) Bar { c: f64 };
+    struct Baz { bar: Bar, flag: bool };
+
+    pub fn main() -> Baz { Baz { bar: Bar { c: 1.0 }, flag: false } }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+    let baz_type = runtime_ref.get_type_info("Baz").unwrap();
+
+    let deserialize = |json: &str| {
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        StructSeed::new(&runtime_ref, baz_type)
+            .deserialize(&mut deserializer)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    };
+
+    // Unknown fields are ignored and missing primitive fields are zero initialized
+    assert!(deserialize(r#"[{"bar":1,"unknown":3},{"c":1.0}]"#).is_ok());
+
+    // Referenced objects must be defined
+    let error = deserialize(r#"[{"bar":1,"flag":true}]"#).unwrap_err();
+    assert!(
+        error.contains("Undefined object with index 1."),
+        "unexpected error: {}",
+        error
+    );
+
+    // `gc` struct fields cannot be left out, as they would be null
+    let error = deserialize(r#"[{"flag":true}]"#).unwrap_err();
+    assert!(
+        error.contains("Missing field `bar` of `Baz`."),
+        "unexpected error: {}",
+        error
+    );
+
+    // Objects must be referenced with a consistent type
+    assert!(deserialize(r#"[{"bar":0,"flag":true}]"#).is_err());
+
+    // Primitive fields must have the correct type
+    assert!(deserialize(r#"[{"bar":1,"flag":3},{"c":1.0}]"#).is_err());
+}
+
+#[test]
+fn deserialize_struct_missing_nested_gc_field() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    struct(gc) Bar { c: f64 };
+    struct(value) Foo { a: i32, bar: Bar };
+    struct Baz { foo: Foo };
+
+    pub fn main() -> Baz { Baz { foo: Foo { a: 1, bar: Bar { c: 1.0 } } } }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = driver.runtime();
+    let runtime_ref = runtime.borrow();
+    let baz_type = runtime_ref.get_type_info("Baz").unwrap();
+
+    let deserialize = |json: &str| {
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        StructSeed::new(&runtime_ref, baz_type)
+            .deserialize(&mut deserializer)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    };
+
+    // A `value` struct that inlines a `gc` struct cannot be left out
+    let error = deserialize(r#"[{}]"#).unwrap_err();
+    assert!(
+        error.contains("Missing field `foo` of `Baz`."),
+        "unexpected error: {}",
+        error
+    );
+
+    // Nor can the `gc` struct field of an inlined `value` struct
+    let error = deserialize(r#"[{"foo":{"a":1}}]"#).unwrap_err();
+    assert!(
+        error.contains("Missing field `bar` of `Foo`."),
+        "unexpected error: {}",
+        error
+    );
+
+    assert!(deserialize(r#"[{"foo":{"bar":1}},{"c":1.0}]"#).is_ok());
+}
@@ -0,0 +1,703 @@
+#ifndef MUN_RUNTIME_H_
+#define MUN_RUNTIME_H_
+
+/*
+ * A header-only C++17 wrapper around the Mun Runtime C API.
+ *
+ * The wrapper mirrors the Rust API of the Mun Runtime: a `Runtime` owns a `MunRuntimeHandle`,
+ * functions are invoked through `invoke_fn`, and structs are accessed through `StructRef` and the
+ * rooted `RootedStruct`.
+ */
+
+#include <cstdint>
+#include <functional>
+#include <iostream>
+#include <memory>
+#include <optional>
+#include <stdexcept>
+#include <string>
+#include <string_view>
+#include <tuple>
+#include <type_traits>
+#include <utility>
+#include <variant>
+#include <vector>
+
+#include "mun/runtime_capi.h"
+
+namespace mun {
+
+/** An error that was reported by the Mun Runtime. */
+class Error {
+   public:
+    /** Constructs an empty error. */
+    Error() = default;
+
+    /** Constructs an error from a message. */
+    explicit Error(std::string message) : m_message(std::move(message)) {}
+
+    /**
+     * Takes ownership of the error corresponding to `handle` and destroys it, after retrieving
+     * its message.
+     */
+    explicit Error(MunErrorHandle handle) {
+        if (handle._0 != 0) {
+            if (const char* message = mun_error_message(handle)) {
+                m_message = message;
+                mun_destroy_string(message);
+            }
+            mun_error_destroy(handle);
+        }
+    }
+
+    /** Returns whether this represents an error. */
+    bool is_error() const noexcept { return m_message.has_value(); }
+
+    explicit operator bool() const noexcept { return is_error(); }
+
+    /** Returns the message of the error, if any. */
+    const std::optional<std::string>& message() const noexcept { return m_message; }
+
+   private:
+    std::optional<std::string> m_message;
+};
+
+namespace details {
+/** Stores `error` in `out_error`, if both exist, and returns whether `error` is an error. */
+inline bool report(Error error, Error* out_error) {
+    const bool is_error = error.is_error();
+    if (is_error && out_error) {
+        *out_error = std::move(error);
+    }
+    return is_error;
+}
+
+/** Returns the name of the Mun type that corresponds to a value kind. */
+inline std::string_view kind_name(uint8_t kind) noexcept {
+    switch (kind) {
+        case MunValueKind_I8:
+            return "core::i8";
+        case MunValueKind_I16:
+            return "core::i16";
+        case MunValueKind_I32:
+            return "core::i32";
+        case MunValueKind_I64:
+            return "core::i64";
+        case MunValueKind_U8:
+            return "core::u8";
+        case MunValueKind_U16:
+            return "core::u16";
+        case MunValueKind_U32:
+            return "core::u32";
+        case MunValueKind_U64:
+            return "core::u64";
+        case MunValueKind_F32:
+            return "core::f32";
+        case MunValueKind_F64:
+            return "core::f64";
+        case MunValueKind_Bool:
+            return "core::bool";
+        case MunValueKind_Struct:
+            return "struct";
+    }
+    return "unknown";
+}
+}  // namespace details
+
+/**
+ * Describes the changes made when a runtime reloaded assemblies, such that hosts can invalidate
+ * what they cached of the reloaded assemblies; e.g. function definitions and type information.
+ */
+struct ReloadEvent {
+    /** The library paths of the reloaded assemblies */
+    std::vector<std::string> assemblies;
+    /** The sorted names of all functions that were added, removed, or replaced */
+    std::vector<std::string> changed_functions;
+    /**
+     * The sorted names of all struct types of which the memory layout changed. Existing objects of
+     * these types were mapped - or migrated - to their new layout.
+     */
+    std::vector<std::string> migrated_types;
+};
+
+/** A function that is called with a description of the changes whenever assemblies are reloaded. */
+using ReloadFn = std::function<void(const ReloadEvent&)>;
+
+namespace details {
+/** Copies a `MunReloadEvent`. */
+inline ReloadEvent to_reload_event(const MunReloadEvent& event) {
+    const auto strings = [](const char* const* data, size_t len) {
+        return std::vector<std::string>(data, data + len);
+    };
+
+    ReloadEvent result;
+    result.assemblies = strings(event.assemblies, event.num_assemblies);
+    result.changed_functions = strings(event.changed_functions, event.num_changed_functions);
+    result.migrated_types = strings(event.migrated_types, event.num_migrated_types);
+    return result;
+}
+
+/** Forwards a `MunReloadEvent` to the `ReloadFn` that is passed as `user_data`. */
+inline void on_reload(const MunReloadEvent* event, void* user_data) noexcept {
+    (*static_cast<ReloadFn*>(user_data))(to_reload_event(*event));
+}
+}  // namespace details
+
+/** Options required to construct a `Runtime` through `make_runtime`. */
+struct RuntimeOptions {
+    /**
+     * Function definitions that should be inserted in the runtime before a mun library is loaded.
+     * This is useful to initialize `extern` functions used in a mun library.
+     */
+    std::vector<MunFunctionDefinition> functions;
+
+    /**
+     * Function that is called whenever the runtime reloads assemblies, from within
+     * `Runtime::update`. It must not throw, nor access the runtime. Descriptions of reloads can
+     * also be polled using `Runtime::poll_reload_event`.
+     */
+    ReloadFn on_reload;
+};
+
+/** A wrapper around a `MunRuntimeHandle` that destroys the runtime when it goes out of scope. */
+class Runtime {
+   public:
+    /**
+     * Takes ownership of the runtime corresponding to `handle`, and of the reload function that
+     * was passed to the runtime, if any.
+     */
+    explicit Runtime(MunRuntimeHandle handle,
+                     std::unique_ptr<ReloadFn> on_reload = nullptr) noexcept
+        : m_handle(handle), m_on_reload(std::move(on_reload)) {}
+
+    Runtime(const Runtime&) = delete;
+    Runtime& operator=(const Runtime&) = delete;
+
+    Runtime(Runtime&& other) noexcept
+        : m_handle(other.m_handle), m_on_reload(std::move(other.m_on_reload)) {
+        other.m_handle._0 = nullptr;
+    }
+
+    Runtime& operator=(Runtime&& other) noexcept {
+        std::swap(m_handle, other.m_handle);
+        std::swap(m_on_reload, other.m_on_reload);
+        return *this;
+    }
+
+    ~Runtime() noexcept { mun_runtime_destroy(m_handle); }
+
+    /** Returns the handle of the runtime. */
+    MunRuntimeHandle handle() const noexcept { return m_handle; }
+
+    /**
+     * Retrieves the definition of the function with the specified name, if it exists.
+     *
+     * The definition remains valid until the runtime is updated.
+     */
+    std::optional<MunFunctionDefinition> get_function_definition(std::string_view fn_name,
+                                                                 Error* out_error = nullptr) const {
+        const std::string name(fn_name);
+        bool has_fn_info = false;
+        MunFunctionDefinition definition;
+        if (details::report(Error(mun_runtime_get_function_definition(m_handle, name.c_str(),
+                                                                      &has_fn_info, &definition)),
+                            out_error) ||
+            !has_fn_info) {
+            return std::nullopt;
+        }
+        return definition;
+    }
+
+    /**
+     * Retrieves the type information of the type with the specified name, if it exists.
+     *
+     * The type information remains valid until the runtime is updated.
+     */
+    const MunTypeInfo* get_type_info(std::string_view type_name, Error* out_error = nullptr) const {
+        const std::string name(type_name);
+        bool has_type_info = false;
+        const MunTypeInfo* type_info = nullptr;
+        if (details::report(Error(mun_runtime_get_type_info_by_name(m_handle, name.c_str(),
+                                                                    &has_type_info, &type_info)),
+                            out_error) ||
+            !has_type_info) {
+            return nullptr;
+        }
+        return type_info;
+    }
+
+    /**
+     * Invokes the function `fn_name` with the dynamically typed `args`. The arguments are
+     * validated against the function's signature. Returns the result of the function, if it
+     * returns a value.
+     */
+    std::optional<MunValue> invoke_dynamic(std::string_view fn_name,
+                                           const std::vector<MunValue>& args,
+                                           Error* out_error = nullptr) const {
+        const std::string name(fn_name);
+        bool has_result = false;
+        MunValue result;
+        if (details::report(Error(mun_runtime_invoke_dynamic(
+                                m_handle, name.c_str(), args.data(),
+                                static_cast<uint32_t>(args.size()), &has_result, &result)),
+                            out_error) ||
+            !has_result) {
+            return std::nullopt;
+        }
+        return result;
+    }
+
+    /**
+     * Collects all memory that is no longer referenced by rooted objects. Returns whether memory
+     * was reclaimed.
+     */
+    bool gc_collect(Error* out_error = nullptr) const {
+        bool reclaimed = false;
+        details::report(Error(mun_gc_collect(m_handle, &reclaimed)), out_error);
+        return reclaimed;
+    }
+
+    /** Checks for updates to hot reloadable assemblies. Returns whether the runtime was updated. */
+    bool update(Error* out_error = nullptr) {
+        bool updated = false;
+        details::report(Error(mun_runtime_update(m_handle, &updated)), out_error);
+        return updated;
+    }
+
+    /**
+     * Retrieves the oldest description of a reload that has not been polled yet, if any. The
+     * runtime retains the events of a limited number of reloads, so events should be polled after
+     * every update.
+     */
+    std::optional<ReloadEvent> poll_reload_event(Error* out_error = nullptr) {
+        bool has_event = false;
+        MunReloadEvent event;
+        if (details::report(Error(mun_runtime_poll_reload_event(m_handle, &has_event, &event)),
+                            out_error) ||
+            !has_event) {
+            return std::nullopt;
+        }
+
+        ReloadEvent result = details::to_reload_event(event);
+        mun_reload_event_destroy(event);
+        return result;
+    }
+
+   private:
+    MunRuntimeHandle m_handle;
+    // The runtime refers to the reload function, so it must outlive the runtime
+    std::unique_ptr<ReloadFn> m_on_reload;
+};
+
+/**
+ * Constructs a runtime that loads the library at `library_path` and its dependencies. If an error
+ * occurs, `std::nullopt` is returned and `out_error` is set.
+ */
+inline std::optional<Runtime> make_runtime(std::string_view library_path,
+                                           const RuntimeOptions& options = {},
+                                           Error* out_error = nullptr) {
+    const std::string path(library_path);
+    MunRuntimeOptions runtime_options;
+    runtime_options.functions = options.functions.data();
+    runtime_options.num_functions = static_cast<uint32_t>(options.functions.size());
+
+    std::unique_ptr<ReloadFn> on_reload;
+    if (options.on_reload) {
+        on_reload = std::make_unique<ReloadFn>(options.on_reload);
+        runtime_options.reload_fn = details::on_reload;
+        runtime_options.reload_user_data = on_reload.get();
+    } else {
+        runtime_options.reload_fn = nullptr;
+        runtime_options.reload_user_data = nullptr;
+    }
+
+    MunRuntimeHandle handle;
+    if (details::report(Error(mun_runtime_create(path.c_str(), runtime_options, &handle)),
+                        out_error)) {
+        return std::nullopt;
+    }
+    return Runtime(handle, std::move(on_reload));
+}
+
+class RootedStruct;
+
+/**
+ * A reference to a struct that is allocated by the runtime.
+ *
+ * The struct is not rooted, so it is only guaranteed to outlive the next garbage collection when
+ * it is rooted using `StructRef::root`.
+ */
+class StructRef {
+   public:
+    StructRef(MunRuntimeHandle runtime, MunGcPtr handle) noexcept
+        : m_runtime(runtime), m_handle(handle) {}
+
+    /** Returns the garbage collector handle of the struct. */
+    MunGcPtr handle() const noexcept { return m_handle; }
+
+    /** Returns the type information of the struct. */
+    const MunTypeInfo* type_info() const noexcept {
+        MunUnsafeTypeInfo type_info = nullptr;
+        Error(mun_gc_ptr_type(m_runtime, m_handle, &type_info));
+        return type_info;
+    }
+
+    /** Returns the number of fields of the struct. */
+    uint16_t num_fields() const noexcept {
+        uint16_t num_fields = 0;
+        Error(mun_struct_num_fields(m_runtime, m_handle, &num_fields));
+        return num_fields;
+    }
+
+    /** Returns information about the field at `field_idx`, if it exists. */
+    std::optional<MunFieldInfo> field_info(uint16_t field_idx, Error* out_error = nullptr) const {
+        MunFieldInfo field_info;
+        if (details::report(Error(mun_struct_field_info(m_runtime, m_handle, field_idx,
+                                                        &field_info)),
+                            out_error)) {
+            return std::nullopt;
+        }
+        return field_info;
+    }
+
+    /**
+     * Retrieves the value of the field corresponding to `field_name`, if its type matches `T`.
+     */
+    template <typename T>
+    std::optional<T> get(std::string_view field_name, Error* out_error = nullptr) const;
+
+    /**
+     * Sets the value of the field corresponding to `field_name`, if its type matches `T`. Returns
+     * whether the value was set.
+     */
+    template <typename T>
+    bool set(std::string_view field_name, const T& value, Error* out_error = nullptr);
+
+    /** Roots the struct, preventing it from being garbage collected. */
+    RootedStruct root() const noexcept;
+
+   private:
+    MunRuntimeHandle m_runtime;
+    MunGcPtr m_handle;
+};
+
+/** A struct that is rooted while this object is alive. */
+class RootedStruct {
+   public:
+    /** Roots the struct referenced by `handle`. */
+    RootedStruct(MunRuntimeHandle runtime, MunGcPtr handle) noexcept
+        : m_runtime(runtime), m_handle(handle) {
+        Error(mun_gc_root(m_runtime, m_handle));
+    }
+
+    RootedStruct(const RootedStruct& other) noexcept
+        : RootedStruct(other.m_runtime, other.m_handle) {}
+
+    RootedStruct(RootedStruct&& other) noexcept
+        : m_runtime(other.m_runtime), m_handle(other.m_handle) {
+        other.m_handle = nullptr;
+    }
+
+    RootedStruct& operator=(RootedStruct other) noexcept {
+        std::swap(m_runtime, other.m_runtime);
+        std::swap(m_handle, other.m_handle);
+        return *this;
+    }
+
+    ~RootedStruct() noexcept {
+        if (m_handle) {
+            Error(mun_gc_unroot(m_runtime, m_handle));
+        }
+    }
+
+    /** Returns a reference to the struct. */
+    StructRef by_ref() const noexcept { return StructRef(m_runtime, m_handle); }
+
+   private:
+    MunRuntimeHandle m_runtime;
+    MunGcPtr m_handle;
+};
+
+inline RootedStruct StructRef::root() const noexcept { return RootedStruct(m_runtime, m_handle); }
+
+/**
+ * Converts between C++ types and dynamically typed `MunValue`s. `Marshal` is specialized for all
+ * types that can be passed to and returned from Mun functions.
+ */
+template <typename T>
+struct Marshal;
+
+#define MUN_MARSHAL_PRIMITIVE(T, KIND, FIELD)                                     \
+    template <>                                                                   \
+    struct Marshal<T> {                                                           \
+        static constexpr MunValueKind kind = KIND;                                \
+                                                                                  \
+        static MunValue to_value(MunRuntimeHandle, T value) noexcept {            \
+            MunValue result;                                                      \
+            result.kind = kind;                                                   \
+            result.data.FIELD = value;                                            \
+            return result;                                                        \
+        }                                                                         \
+                                                                                  \
+        static T from_value(MunRuntimeHandle, const MunValue& value) noexcept {   \
+            return value.data.FIELD;                                              \
+        }                                                                         \
+    };
+
+MUN_MARSHAL_PRIMITIVE(int8_t, MunValueKind_I8, i8)
+MUN_MARSHAL_PRIMITIVE(int16_t, MunValueKind_I16, i16)
+MUN_MARSHAL_PRIMITIVE(int32_t, MunValueKind_I32, i32)
+MUN_MARSHAL_PRIMITIVE(int64_t, MunValueKind_I64, i64)
+MUN_MARSHAL_PRIMITIVE(uint8_t, MunValueKind_U8, u8)
+MUN_MARSHAL_PRIMITIVE(uint16_t, MunValueKind_U16, u16)
+MUN_MARSHAL_PRIMITIVE(uint32_t, MunValueKind_U32, u32)
+MUN_MARSHAL_PRIMITIVE(uint64_t, MunValueKind_U64, u64)
+MUN_MARSHAL_PRIMITIVE(float, MunValueKind_F32, f32)
+MUN_MARSHAL_PRIMITIVE(double, MunValueKind_F64, f64)
+MUN_MARSHAL_PRIMITIVE(bool, MunValueKind_Bool, boolean)
+
+#undef MUN_MARSHAL_PRIMITIVE
+
+template <>
+struct Marshal<StructRef> {
+    static constexpr MunValueKind kind = MunValueKind_Struct;
+
+    static MunValue to_value(MunRuntimeHandle, const StructRef& value) noexcept {
+        MunValue result;
+        result.kind = kind;
+        result.data.gc_ptr = value.handle();
+        return result;
+    }
+
+    static StructRef from_value(MunRuntimeHandle runtime, const MunValue& value) noexcept {
+        return StructRef(runtime, value.data.gc_ptr);
+    }
+};
+
+template <>
+struct Marshal<RootedStruct> {
+    static constexpr MunValueKind kind = MunValueKind_Struct;
+
+    static MunValue to_value(MunRuntimeHandle runtime, const RootedStruct& value) noexcept {
+        return Marshal<StructRef>::to_value(runtime, value.by_ref());
+    }
+
+    static RootedStruct from_value(MunRuntimeHandle runtime, const MunValue& value) noexcept {
+        return RootedStruct(runtime, value.data.gc_ptr);
+    }
+};
+
+namespace details {
+/** Converts `value` to `T`, if its kind matches. `context` describes the value in errors. */
+template <typename T>
+std::optional<T> from_value(MunRuntimeHandle runtime, const MunValue& value,
+                            std::string_view context, Error* out_error) {
+    if (value.kind != Marshal<T>::kind) {
+        details::report(Error("Mismatched types for " + std::string(context) + ". Expected: `" +
+                              std::string(kind_name(Marshal<T>::kind)) + "`. Found: `" +
+                              std::string(kind_name(value.kind)) + "`."),
+                        out_error);
+        return std::nullopt;
+    }
+    return Marshal<T>::from_value(runtime, value);
+}
+}  // namespace details
+
+template <typename T>
+std::optional<T> StructRef::get(std::string_view field_name, Error* out_error) const {
+    const std::string name(field_name);
+    MunValue value;
+    if (details::report(Error(mun_struct_get_field(m_runtime, m_handle, name.c_str(), &value)),
+                        out_error)) {
+        return std::nullopt;
+    }
+    return details::from_value<T>(m_runtime, value, "`" + name + "`", out_error);
+}
+
+template <typename T>
+bool StructRef::set(std::string_view field_name, const T& value, Error* out_error) {
+    const std::string name(field_name);
+    return !details::report(
+        Error(mun_struct_set_field(m_runtime, m_handle, name.c_str(),
+                                   Marshal<T>::to_value(m_runtime, value))),
+        out_error);
+}
+
+namespace details {
+/**
+ * Helpers for `Marshal` specializations, such as those generated by `mun bindgen`. As marshalling
+ * cannot report errors, these throw a `std::runtime_error` on failure.
+ */
+
+/** Allocates a struct of the type `type_name`. Its fields are uninitialized. */
+inline StructRef alloc_struct(MunRuntimeHandle runtime, std::string_view type_name) {
+    const std::string name(type_name);
+    bool has_type_info = false;
+    const MunTypeInfo* type_info = nullptr;
+    Error error(mun_runtime_get_type_info_by_name(runtime, name.c_str(), &has_type_info,
+                                                  &type_info));
+    if (error) {
+        throw std::runtime_error(*error.message());
+    } else if (!has_type_info) {
+        throw std::runtime_error("Unknown type: `" + name + "`.");
+    }
+
+    MunGcPtr handle = nullptr;
+    error = Error(mun_gc_alloc(runtime, const_cast<MunUnsafeTypeInfo>(type_info), &handle));
+    if (error) {
+        throw std::runtime_error(*error.message());
+    }
+    return StructRef(runtime, handle);
+}
+
+/** Converts `value` to a reference to a struct of the type `type_name`. */
+inline StructRef expect_struct(MunRuntimeHandle runtime, const MunValue& value,
+                               std::string_view type_name) {
+    if (value.kind != MunValueKind_Struct) {
+        throw std::runtime_error("Mismatched types. Expected: `" + std::string(type_name) +
+                                 "`. Found: `" + std::string(kind_name(value.kind)) + "`.");
+    }
+
+    const StructRef result = Marshal<StructRef>::from_value(runtime, value);
+    const std::string_view found = result.type_info()->name;
+    if (found != type_name) {
+        throw std::runtime_error("Mismatched types. Expected: `" + std::string(type_name) +
+                                 "`. Found: `" + std::string(found) + "`.");
+    }
+    return result;
+}
+
+/** Retrieves the value of the field corresponding to `field_name`. */
+template <typename T>
+T get_field(const StructRef& s, std::string_view field_name) {
+    Error error;
+    if (auto value = s.get<T>(field_name, &error)) {
+        return std::move(*value);
+    }
+    throw std::runtime_error(*error.message());
+}
+
+/** Sets the value of the field corresponding to `field_name`. */
+template <typename T>
+void set_field(StructRef& s, std::string_view field_name, const T& value) {
+    Error error;
+    if (!s.set(field_name, value, &error)) {
+        throw std::runtime_error(*error.message());
+    }
+}
+}  // namespace details
+
+/**
+ * The result of a function invocation through `invoke_fn`. On failure, it contains the error
+ * message and the arguments of the invocation, allowing the caller to retry the invocation.
+ */
+template <typename Output, typename... Args>
+class InvokeResult {
+    using Value = std::conditional_t<std::is_void_v<Output>, std::monostate, Output>;
+
+   public:
+    InvokeResult(Runtime& runtime, std::string_view fn_name, std::tuple<Args...> args)
+        : m_runtime(&runtime), m_fn_name(fn_name), m_args(std::move(args)) {
+        invoke();
+    }
+
+    /** Returns whether the invocation succeeded. */
+    bool is_ok() const noexcept { return std::holds_alternative<Value>(m_result); }
+
+    /** Returns the error message of a failed invocation. */
+    std::optional<std::string> error_message() const {
+        if (auto message = std::get_if<std::string>(&m_result)) {
+            return *message;
+        }
+        return std::nullopt;
+    }
+
+    /** Returns the output of a successful invocation. */
+    Output unwrap() {
+        if constexpr (!std::is_void_v<Output>) {
+            return std::move(std::get<Value>(m_result));
+        }
+    }
+
+    /**
+     * Waits for the runtime to be updated and retries a failed invocation once. Returns whether
+     * the invocation succeeded.
+     */
+    bool retry() {
+        if (is_ok()) {
+            return true;
+        }
+
+        std::cerr << *error_message() << std::endl;
+        while (!m_runtime->update()) {
+            // Wait until there has been an update that might fix the error
+        }
+        invoke();
+        return is_ok();
+    }
+
+    /** Retries the invocation until it succeeds, resulting in an output. */
+    Output wait() {
+        while (!retry()) {
+        }
+        return unwrap();
+    }
+
+   private:
+    void invoke() {
+        const std::vector<MunValue> args = std::apply(
+            [this](const Args&... values) {
+                return std::vector<MunValue>{
+                    Marshal<Args>::to_value(m_runtime->handle(), values)...};
+            },
+            m_args);
+
+        Error error;
+        const auto result = m_runtime->invoke_dynamic(m_fn_name, args, &error);
+        if (error) {
+            m_result = *error.message();
+            return;
+        }
+
+        if constexpr (std::is_void_v<Output>) {
+            if (result) {
+                m_result = "Mismatched return type for `" + m_fn_name +
+                           "`. Expected: `()`. Found: `" +
+                           std::string(details::kind_name(result->kind)) + "`.";
+            } else {
+                m_result = std::monostate{};
+            }
+        } else if (!result) {
+            m_result = "Mismatched return type for `" + m_fn_name + "`. Expected: `" +
+                       std::string(details::kind_name(Marshal<Output>::kind)) +
+                       "`. Found: `()`.";
+        } else if (auto output = details::from_value<Output>(
+                       m_runtime->handle(), *result, "the return type of `" + m_fn_name + "`",
+                       &error)) {
+            m_result = std::move(*output);
+        } else {
+            m_result = *error.message();
+        }
+    }
+
+    Runtime* m_runtime;
+    std::string m_fn_name;
+    std::tuple<Args...> m_args;
+    std::variant<std::string, Value> m_result;
+};
+
+/**
+ * Invokes the function `fn_name` with arguments `args`. The arguments and return type are
+ * validated against the function's signature.
+ *
+ * Returned structs are not rooted; use `Output = RootedStruct` to root them immediately.
+ */
+template <typename Output, typename... Args>
+InvokeResult<Output, Args...> invoke_fn(Runtime& runtime, std::string_view fn_name,
+                                        Args... args) {
+    return InvokeResult<Output, Args...>(runtime, fn_name, std::make_tuple(std::move(args)...));
+}
+
+}  // namespace mun
+
+#endif  // MUN_RUNTIME_H_
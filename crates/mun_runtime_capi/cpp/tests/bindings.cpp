@@ -0,0 +1,44 @@
+// Tests the C++ bindings generated by `mun_bindgen` for the assembly compiled from the `bindings`
+// package, of which the path is passed as the first argument.
+
+#include <cstdlib>
+#include <iostream>
+
+#include "bindings.h"
+
+#define CHECK(condition)                                                              \
+    do {                                                                              \
+        if (!(condition)) {                                                           \
+            std::cerr << __FILE__ << ":" << __LINE__ << ": check failed: " #condition \
+                      << std::endl;                                                   \
+            std::exit(EXIT_FAILURE);                                                  \
+        }                                                                             \
+    } while (false)
+
+int main(int argc, char* argv[]) {
+    if (argc < 2) {
+        std::cerr << "Usage: " << argv[0] << " <library_path>" << std::endl;
+        return EXIT_FAILURE;
+    }
+
+    auto runtime = mun::make_runtime(argv[1]);
+    CHECK(runtime.has_value());
+
+    auto sum = bindings::add(*runtime, bindings::Vec2{1.0f, 2.0f}, bindings::Vec2{3.0f, 4.0f});
+    CHECK(sum.is_ok());
+    CHECK(sum.unwrap().x == 4.0f);
+
+    auto circle = bindings::circle::new_(*runtime, 2.0f).wait();
+    CHECK(circle.radius() == 2.0f);
+    CHECK(bindings::circle::area(*runtime, circle).wait() == 12.0f);
+
+    auto origin = bindings::circle::origin(*runtime).wait();
+    CHECK(origin.x == 0.0f && origin.y == 0.0f);
+
+    auto square = bindings::square::new_(*runtime, 2.0f).wait();
+    CHECK(square.set_side(3.0f));
+    CHECK(bindings::square::area(*runtime, square).wait() == 9.0f);
+
+    CHECK(bindings::impl::match(*runtime, int32_t{1}, int32_t{1}).wait());
+    return EXIT_SUCCESS;
+}
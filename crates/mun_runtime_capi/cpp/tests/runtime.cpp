@@ -0,0 +1,115 @@
+// Tests the C++ wrapper of the Mun Runtime C API against the assembly compiled from `runtime.mun`,
+// of which the path is passed as the first argument.
+
+#include <cstdlib>
+#include <iostream>
+
+#include "mun/runtime.h"
+
+#define CHECK(condition)                                                              \
+    do {                                                                              \
+        if (!(condition)) {                                                           \
+            std::cerr << __FILE__ << ":" << __LINE__ << ": check failed: " #condition \
+                      << std::endl;                                                   \
+            std::exit(EXIT_FAILURE);                                                  \
+        }                                                                             \
+    } while (false)
+
+static void test_invoke_fn(mun::Runtime& runtime) {
+    auto result = mun::invoke_fn<int32_t>(runtime, "add", int32_t{3}, int32_t{4});
+    CHECK(result.is_ok());
+    CHECK(result.unwrap() == 7);
+
+    auto missing = mun::invoke_fn<int32_t>(runtime, "missing");
+    CHECK(!missing.is_ok());
+    CHECK(missing.error_message() == "Failed to obtain function 'missing'");
+
+    auto mismatched_arg = mun::invoke_fn<int32_t>(runtime, "add", int32_t{3}, 4.0);
+    CHECK(!mismatched_arg.is_ok());
+
+    auto mismatched_output = mun::invoke_fn<int64_t>(runtime, "add", int32_t{3}, int32_t{4});
+    CHECK(!mismatched_output.is_ok());
+    CHECK(mismatched_output.error_message() ==
+          "Mismatched types for the return type of `add`. Expected: `core::i64`. Found: "
+          "`core::i32`.");
+}
+
+static void test_struct_ref(mun::Runtime& runtime) {
+    auto foo = mun::invoke_fn<mun::StructRef>(runtime, "foo_new", int32_t{2}, 3.0).wait();
+    CHECK(foo.type_info() == runtime.get_type_info("Foo"));
+    CHECK(foo.num_fields() == 3);
+
+    auto field_info = foo.field_info(1);
+    CHECK(field_info.has_value());
+    CHECK(std::string_view(field_info->name) == "b");
+    CHECK(std::string_view(field_info->type_info->name) == "core::f64");
+    CHECK(!foo.field_info(3).has_value());
+
+    CHECK(foo.get<int32_t>("a") == 2);
+    CHECK(foo.get<double>("b") == 3.0);
+    CHECK(foo.get<bool>("visible") == true);
+
+    CHECK(foo.set("a", int32_t{5}));
+    CHECK(foo.get<int32_t>("a") == 5);
+
+    mun::Error error;
+    CHECK(!foo.get<float>("b", &error).has_value());
+    CHECK(error.message() ==
+          "Mismatched types for `b`. Expected: `core::f32`. Found: `core::f64`.");
+    CHECK(!foo.set("a", 1.0f, &error));
+    CHECK(error.message() == "Mismatched types for `Foo::a`. Expected: `core::i32`. Found: "
+                             "`core::f32`.");
+    CHECK(!foo.get<int32_t>("c", &error).has_value());
+    CHECK(error.is_error());
+
+    CHECK(mun::invoke_fn<void>(runtime, "foo_scale", foo, 2.0).is_ok());
+    CHECK(mun::invoke_fn<double>(runtime, "foo_b", foo).wait() == 6.0);
+}
+
+static void test_rooted_struct(mun::Runtime& runtime) {
+    // Reclaim unrooted structs of previous tests
+    runtime.gc_collect();
+
+    auto foo = mun::invoke_fn<mun::RootedStruct>(runtime, "foo_new", int32_t{1}, 2.0).wait();
+    CHECK(!runtime.gc_collect());
+
+    {
+        // Copies root the struct again
+        auto copy = foo;
+        CHECK(copy.by_ref().get<double>("b") == 2.0);
+    }
+    CHECK(!runtime.gc_collect());
+    CHECK(foo.by_ref().get<double>("b") == 2.0);
+
+    foo = mun::invoke_fn<mun::RootedStruct>(runtime, "foo_new", int32_t{4}, 5.0).wait();
+    CHECK(runtime.gc_collect());
+    CHECK(mun::invoke_fn<double>(runtime, "foo_b", foo).wait() == 5.0);
+}
+
+int main(int argc, char* argv[]) {
+    if (argc < 2) {
+        std::cerr << "Usage: " << argv[0] << " <library_path>" << std::endl;
+        return EXIT_FAILURE;
+    }
+
+    mun::Error error;
+    CHECK(!mun::make_runtime("missing.munlib", {}, &error).has_value());
+    CHECK(error.is_error());
+
+    bool reloaded = false;
+    mun::RuntimeOptions options;
+    options.on_reload = [&reloaded](const mun::ReloadEvent&) { reloaded = true; };
+    auto runtime = mun::make_runtime(argv[1], options);
+    CHECK(runtime.has_value());
+    CHECK(!runtime->update());
+    CHECK(!runtime->poll_reload_event().has_value());
+    CHECK(!reloaded);
+    CHECK(runtime->get_function_definition("add").has_value());
+    CHECK(!runtime->get_function_definition("missing").has_value());
+    CHECK(runtime->get_type_info("Bar") == nullptr);
+
+    test_invoke_fn(*runtime);
+    test_struct_ref(*runtime);
+    test_rooted_struct(*runtime);
+    return EXIT_SUCCESS;
+}
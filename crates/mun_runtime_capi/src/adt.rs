@@ -0,0 +1,266 @@
+//! Exposes access to the fields of Mun structs.
+
+use crate::{
+    type_info::{mun_type_info_field_info, mun_type_info_num_fields},
+    value::Value,
+    ErrorHandle, RuntimeHandle, HUB,
+};
+use anyhow::anyhow;
+use memory::gc::GcPtr;
+use runtime::{Marshal, RawStruct, Runtime, StructRef};
+use std::{ffi::CStr, os::raw::c_char};
+
+/// Information about a field of a struct.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct FieldInfo {
+    /// The name of the field
+    pub name: *const c_char,
+    /// The type of the field
+    pub type_info: *const abi::TypeInfo,
+    /// The offset of the field in bytes from the start of the struct
+    pub offset: u16,
+}
+
+impl FieldInfo {
+    /// Constructs the information of the field at `field_idx` of the struct `type_info`.
+    pub(crate) fn new(
+        type_info: &abi::TypeInfo,
+        struct_info: &abi::StructInfo,
+        field_idx: u16,
+    ) -> Result<Self, anyhow::Error> {
+        let field_idx = usize::from(field_idx);
+        if field_idx >= struct_info.num_fields() {
+            return Err(anyhow!(
+                "Invalid argument: 'field_idx' is out of bounds. `{}` has {} fields.",
+                type_info.name(),
+                struct_info.num_fields()
+            ));
+        }
+
+        Ok(FieldInfo {
+            // Safety: `field_idx` is within the bounds of the `field_names` array.
+            name: unsafe { *struct_info.field_names.add(field_idx) },
+            type_info: struct_info.field_types()[field_idx],
+            offset: struct_info.field_offsets()[field_idx],
+        })
+    }
+}
+
+/// Wraps `obj` in a [`StructRef`], after verifying that it is a struct.
+///
+/// # Safety
+///
+/// `obj` must be a valid object that was allocated by the runtime.
+unsafe fn struct_ref(runtime: &Runtime, obj: GcPtr) -> Result<StructRef<'_>, anyhow::Error> {
+    let type_info = &*runtime.gc().ptr_type(obj).into_inner().as_ptr();
+    if type_info.as_struct().is_none() {
+        return Err(anyhow!(
+            "Invalid argument: 'obj' is not a struct, but a `{}`.",
+            type_info.name()
+        ));
+    }
+
+    Ok(StructRef::marshal_from(RawStruct::from(obj), runtime))
+}
+
+/// Retrieves the value of the field corresponding to `field_name` of the struct `obj`. If
+/// successful, `value` is set, otherwise a non-zero error handle is returned.
+///
+/// Struct fields are returned as an unrooted garbage collector handle, and must be rooted using
+/// [`mun_gc_root`] to outlive the next garbage collection.
+///
+/// If a non-zero error handle is returned, it must be manually destructed using
+/// [`mun_error_destroy`].
+///
+/// # Safety
+///
+/// This function receives raw pointers as parameters. If any of the arguments is a null pointer,
+/// an error will be returned. Passing pointers to invalid data, will lead to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_struct_get_field(
+    handle: RuntimeHandle,
+    obj: GcPtr,
+    field_name: *const c_char,
+    value: *mut Value,
+) -> ErrorHandle {
+    let runtime = match handle.runtime() {
+        Some(runtime) => runtime,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'runtime' is null pointer."))
+        }
+    };
+
+    if field_name.is_null() {
+        return HUB
+            .errors
+            .register(anyhow!("Invalid argument: 'field_name' is null pointer."));
+    }
+
+    let field_name = match CStr::from_ptr(field_name).to_str() {
+        Ok(name) => name,
+        Err(_) => {
+            return HUB.errors.register(anyhow!(
+                "Invalid argument: 'field_name' is not UTF-8 encoded."
+            ))
+        }
+    };
+
+    let value = match value.as_mut() {
+        Some(value) => value,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'value' is null pointer."))
+        }
+    };
+
+    let struct_ref = match struct_ref(runtime, obj) {
+        Ok(struct_ref) => struct_ref,
+        Err(e) => return HUB.errors.register(e),
+    };
+
+    match struct_ref
+        .get_value(field_name)
+        .map_err(|e| anyhow!(e))
+        .and_then(Value::from_runtime)
+    {
+        Ok(field_value) => *value = field_value,
+        Err(e) => return HUB.errors.register(e),
+    }
+
+    ErrorHandle::default()
+}
+
+/// Sets the field corresponding to `field_name` of the struct `obj` to `value`. The type of
+/// `value` is checked against the type of the field. If successful, the field has been set,
+/// otherwise a non-zero error handle is returned.
+///
+/// If a non-zero error handle is returned, it must be manually destructed using
+/// [`mun_error_destroy`].
+///
+/// # Safety
+///
+/// This function receives raw pointers as parameters. If any of the arguments is a null pointer,
+/// an error will be returned. Passing pointers to invalid data, will lead to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_struct_set_field(
+    handle: RuntimeHandle,
+    obj: GcPtr,
+    field_name: *const c_char,
+    value: Value,
+) -> ErrorHandle {
+    let runtime = match handle.runtime() {
+        Some(runtime) => runtime,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'runtime' is null pointer."))
+        }
+    };
+
+    if field_name.is_null() {
+        return HUB
+            .errors
+            .register(anyhow!("Invalid argument: 'field_name' is null pointer."));
+    }
+
+    let field_name = match CStr::from_ptr(field_name).to_str() {
+        Ok(name) => name,
+        Err(_) => {
+            return HUB.errors.register(anyhow!(
+                "Invalid argument: 'field_name' is not UTF-8 encoded."
+            ))
+        }
+    };
+
+    let mut struct_ref = match struct_ref(runtime, obj) {
+        Ok(struct_ref) => struct_ref,
+        Err(e) => return HUB.errors.register(e),
+    };
+
+    let value = match value.into_runtime(runtime) {
+        Ok(value) => value,
+        Err(e) => return HUB.errors.register(e),
+    };
+
+    if let Err(e) = struct_ref.set_value(field_name, value) {
+        return HUB.errors.register(anyhow!(e));
+    }
+
+    ErrorHandle::default()
+}
+
+/// Retrieves the number of fields of the struct `obj`. If successful, `num_fields` is set,
+/// otherwise a non-zero error handle is returned.
+///
+/// Together with [`mun_struct_field_info`] this can be used to iterate over the fields of a
+/// struct.
+///
+/// If a non-zero error handle is returned, it must be manually destructed using
+/// [`mun_error_destroy`].
+///
+/// # Safety
+///
+/// This function receives raw pointers as parameters. If any of the arguments is a null pointer,
+/// an error will be returned. Passing pointers to invalid data, will lead to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_struct_num_fields(
+    handle: RuntimeHandle,
+    obj: GcPtr,
+    num_fields: *mut u16,
+) -> ErrorHandle {
+    let runtime = match handle.runtime() {
+        Some(runtime) => runtime,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'runtime' is null pointer."))
+        }
+    };
+
+    let struct_ref = match struct_ref(runtime, obj) {
+        Ok(struct_ref) => struct_ref,
+        Err(e) => return HUB.errors.register(e),
+    };
+
+    mun_type_info_num_fields(struct_ref.type_info(), num_fields)
+}
+
+/// Retrieves information about the field at `field_idx` of the struct `obj`. If successful,
+/// `field_info` is set, otherwise a non-zero error handle is returned.
+///
+/// The name and type of the field remain valid until the runtime is updated.
+///
+/// If a non-zero error handle is returned, it must be manually destructed using
+/// [`mun_error_destroy`].
+///
+/// # Safety
+///
+/// This function receives raw pointers as parameters. If any of the arguments is a null pointer,
+/// an error will be returned. Passing pointers to invalid data, will lead to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_struct_field_info(
+    handle: RuntimeHandle,
+    obj: GcPtr,
+    field_idx: u16,
+    field_info: *mut FieldInfo,
+) -> ErrorHandle {
+    let runtime = match handle.runtime() {
+        Some(runtime) => runtime,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'runtime' is null pointer."))
+        }
+    };
+
+    let struct_ref = match struct_ref(runtime, obj) {
+        Ok(struct_ref) => struct_ref,
+        Err(e) => return HUB.errors.register(e),
+    };
+
+    mun_type_info_field_info(struct_ref.type_info(), field_idx, field_info)
+}
@@ -2,7 +2,6 @@
 
 use crate::{ErrorHandle, RuntimeHandle, HUB};
 use anyhow::anyhow;
-use runtime::Runtime;
 
 pub use memory::gc::GcPtr;
 pub use runtime::UnsafeTypeInfo;
@@ -23,7 +22,7 @@ pub unsafe extern "C" fn mun_gc_alloc(
     type_info: UnsafeTypeInfo,
     obj: *mut GcPtr,
 ) -> ErrorHandle {
-    let runtime = match (handle.0 as *mut Runtime).as_ref() {
+    let runtime = match handle.runtime() {
         Some(runtime) => runtime,
         None => {
             return HUB
@@ -41,7 +40,10 @@ pub unsafe extern "C" fn mun_gc_alloc(
         }
     };
 
-    *obj = runtime.gc().alloc(type_info);
+    match runtime.gc().alloc(type_info) {
+        Ok(handle) => *obj = handle,
+        Err(e) => return HUB.errors.register(anyhow!(e)),
+    }
     ErrorHandle::default()
 }
 
@@ -61,7 +63,7 @@ pub unsafe extern "C" fn mun_gc_ptr_type(
     obj: GcPtr,
     type_info: *mut UnsafeTypeInfo,
 ) -> ErrorHandle {
-    let runtime = match (handle.0 as *mut Runtime).as_ref() {
+    let runtime = match handle.runtime() {
         Some(runtime) => runtime,
         None => {
             return HUB
@@ -98,7 +100,7 @@ pub unsafe extern "C" fn mun_gc_ptr_type(
 /// an error will be returned. Passing pointers to invalid data, will lead to undefined behavior.
 #[no_mangle]
 pub unsafe extern "C" fn mun_gc_root(handle: RuntimeHandle, obj: GcPtr) -> ErrorHandle {
-    let runtime = match (handle.0 as *mut Runtime).as_ref() {
+    let runtime = match handle.runtime() {
         Some(runtime) => runtime,
         None => {
             return HUB
@@ -125,7 +127,7 @@ pub unsafe extern "C" fn mun_gc_root(handle: RuntimeHandle, obj: GcPtr) -> Error
 /// an error will be returned. Passing pointers to invalid data, will lead to undefined behavior.
 #[no_mangle]
 pub unsafe extern "C" fn mun_gc_unroot(handle: RuntimeHandle, obj: GcPtr) -> ErrorHandle {
-    let runtime = match (handle.0 as *mut Runtime).as_ref() {
+    let runtime = match handle.runtime() {
         Some(runtime) => runtime,
         None => {
             return HUB
@@ -154,7 +156,7 @@ pub unsafe extern "C" fn mun_gc_collect(
     handle: RuntimeHandle,
     reclaimed: *mut bool,
 ) -> ErrorHandle {
-    let runtime = match (handle.0 as *mut Runtime).as_ref() {
+    let runtime = match handle.runtime() {
         Some(runtime) => runtime,
         None => {
             return HUB
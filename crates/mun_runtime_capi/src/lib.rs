@@ -4,20 +4,27 @@
 //! integrate the Mun Runtime into other languages that allow interoperability with C.
 #![warn(missing_docs)]
 
+pub mod adt;
 pub mod error;
 pub mod gc;
 pub mod hub;
+pub mod reload;
+pub mod type_info;
+pub mod value;
 
 #[cfg(test)]
 mod tests;
 
 use std::{
+    collections::VecDeque,
     ffi::{c_void, CStr, CString},
     os::raw::c_char,
+    sync::Arc,
 };
 
-use crate::{error::ErrorHandle, hub::HUB};
+use crate::{error::ErrorHandle, hub::HUB, reload::ReloadCallback, value::Value};
 use anyhow::anyhow;
+use parking_lot::Mutex;
 use runtime::Runtime;
 
 pub(crate) type Token = usize;
@@ -37,6 +44,41 @@ pub trait TypedHandle {
 #[derive(Clone, Copy)]
 pub struct RuntimeHandle(*mut c_void);
 
+impl RuntimeHandle {
+    /// Returns a reference to the runtime corresponding to the handle, or `None` if the handle is
+    /// null.
+    ///
+    /// # Safety
+    ///
+    /// The handle must be null, or constructed by [`mun_runtime_create`] and not yet destructed.
+    pub(crate) unsafe fn runtime<'a>(self) -> Option<&'a Runtime> {
+        (self.0 as *const CapiRuntime)
+            .as_ref()
+            .map(|capi_runtime| &capi_runtime.runtime)
+    }
+
+    /// Returns a mutable reference to the runtime corresponding to the handle, or `None` if the
+    /// handle is null.
+    ///
+    /// # Safety
+    ///
+    /// The handle must be null, or constructed by [`mun_runtime_create`] and not yet destructed.
+    pub(crate) unsafe fn runtime_mut<'a>(self) -> Option<&'a mut Runtime> {
+        (self.0 as *mut CapiRuntime)
+            .as_mut()
+            .map(|capi_runtime| &mut capi_runtime.runtime)
+    }
+}
+
+/// A runtime that was constructed through the C API, together with its reload callback.
+struct CapiRuntime {
+    runtime: Runtime,
+    reload_fn: ReloadCallback,
+    reload_user_data: *mut c_void,
+    /// Reload events that the `reload_fn` has not been called with yet
+    reload_events: Arc<Mutex<VecDeque<runtime::ReloadEvent>>>,
+}
+
 /// Options required to construct a [`RuntimeHandle`] through [`mun_runtime_create`]
 ///
 /// # Safety
@@ -55,6 +97,14 @@ pub struct RuntimeOptions {
 
     /// The number of functions in the [`functions`] array.
     pub num_functions: u32,
+
+    /// Function that is called whenever the runtime reloads assemblies, with a description of
+    /// the changes and [`reload_user_data`]. If it is a null pointer, the descriptions can only be
+    /// polled using [`mun_runtime_poll_reload_event`].
+    pub reload_fn: ReloadCallback,
+
+    /// User data that is passed to the [`reload_fn`].
+    pub reload_user_data: *mut c_void,
 }
 
 impl Default for RuntimeOptions {
@@ -62,6 +112,8 @@ impl Default for RuntimeOptions {
         RuntimeOptions {
             functions: std::ptr::null(),
             num_functions: 0,
+            reload_fn: None,
+            reload_user_data: std::ptr::null_mut(),
         }
     }
 }
@@ -83,6 +135,56 @@ pub unsafe extern "C" fn mun_runtime_create(
     library_path: *const c_char,
     options: RuntimeOptions,
     handle: *mut RuntimeHandle,
+) -> ErrorHandle {
+    create_runtime(library_path, None, options, handle)
+}
+
+/// Constructs a new runtime that loads the library identified by `library_path` from the
+/// `num_bytes` bytes at `bytes` - i.e. the contents of a `*.munlib` file - instead of from disk.
+/// Its dependencies are loaded from disk, relative to `library_path`. If successful, the runtime
+/// `handle` is set, otherwise a non-zero error handle is returned.
+///
+/// The runtime copies the bytes, so they can be released after this function returns. Libraries
+/// that are loaded from memory are not hot reloaded.
+///
+/// If a non-zero error handle is returned, it must be manually destructed using
+/// [`mun_error_destroy`].
+///
+/// The runtime must be manually destructed using [`mun_runtime_destroy`].
+///
+/// # Safety
+///
+/// This function receives raw pointers as parameters. If any of the arguments is a null pointer,
+/// an error will be returned. Passing pointers to invalid data, will lead to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_runtime_create_from_bytes(
+    library_path: *const c_char,
+    bytes: *const u8,
+    num_bytes: usize,
+    options: RuntimeOptions,
+    handle: *mut RuntimeHandle,
+) -> ErrorHandle {
+    if bytes.is_null() {
+        return HUB
+            .errors
+            .register(anyhow!("Invalid argument: 'bytes' is null pointer."));
+    }
+
+    let bytes = std::slice::from_raw_parts(bytes, num_bytes);
+    create_runtime(library_path, Some(bytes), options, handle)
+}
+
+/// Constructs a new runtime that loads the library at `library_path` - or from `bytes`, if
+/// specified - and its dependencies.
+///
+/// # Safety
+///
+/// See [`mun_runtime_create`].
+unsafe fn create_runtime(
+    library_path: *const c_char,
+    bytes: Option<&[u8]>,
+    options: RuntimeOptions,
+    handle: *mut RuntimeHandle,
 ) -> ErrorHandle {
     if library_path.is_null() {
         return HUB
@@ -114,7 +216,7 @@ pub unsafe extern "C" fn mun_runtime_create(
         }
     };
 
-    let user_functions =
+    let user_functions = if options.num_functions > 0 {
         std::slice::from_raw_parts(options.functions, options.num_functions as usize)
             .iter()
             .map(|def| {
@@ -125,11 +227,32 @@ pub unsafe extern "C" fn mun_runtime_create(
                     def.fn_ptr,
                 )
             })
-            .collect();
+            .collect()
+    } else {
+        Vec::new()
+    };
 
+    // The C callback is called by `mun_runtime_update`, once the runtime is no longer borrowed
+    let reload_events = Arc::new(Mutex::new(VecDeque::new()));
     let runtime_options = runtime::RuntimeOptions {
         library_path: library_path.into(),
+        backend: runtime::Backend::SharedLibrary,
+        diagnostics_fn: None,
+        reload_fn: options.reload_fn.map(|_| {
+            let reload_events = reload_events.clone();
+            Box::new(move |event: &runtime::ReloadEvent| {
+                reload_events.lock().push_back(event.clone());
+            }) as runtime::ReloadFn
+        }),
+        library_bytes: bytes
+            .map(|bytes| vec![(library_path.into(), bytes.to_vec())])
+            .unwrap_or_default(),
+        static_libraries: Vec::new(),
         user_functions,
+        user_types: Vec::new(),
+        enable_profiler: false,
+        enable_coverage: false,
+        hot_reloading: true,
     };
 
     let runtime = match Runtime::new(runtime_options) {
@@ -137,7 +260,13 @@ pub unsafe extern "C" fn mun_runtime_create(
         Err(e) => return HUB.errors.register(e),
     };
 
-    handle.0 = Box::into_raw(Box::new(runtime)) as *mut _;
+    let capi_runtime = CapiRuntime {
+        runtime,
+        reload_fn: options.reload_fn,
+        reload_user_data: options.reload_user_data,
+        reload_events,
+    };
+    handle.0 = Box::into_raw(Box::new(capi_runtime)) as *mut _;
     ErrorHandle::default()
 }
 
@@ -145,7 +274,7 @@ pub unsafe extern "C" fn mun_runtime_create(
 #[no_mangle]
 pub extern "C" fn mun_runtime_destroy(handle: RuntimeHandle) {
     if !handle.0.is_null() {
-        let _runtime = unsafe { Box::from_raw(handle.0) };
+        let _runtime = unsafe { Box::from_raw(handle.0 as *mut CapiRuntime) };
     }
 }
 
@@ -167,7 +296,7 @@ pub unsafe extern "C" fn mun_runtime_get_function_definition(
     has_fn_info: *mut bool,
     fn_definition: *mut abi::FunctionDefinition,
 ) -> ErrorHandle {
-    let runtime = match (handle.0 as *mut Runtime).as_ref() {
+    let runtime = match handle.runtime() {
         Some(runtime) => runtime,
         None => {
             return HUB
@@ -220,6 +349,106 @@ pub unsafe extern "C" fn mun_runtime_get_function_definition(
     ErrorHandle::default()
 }
 
+/// Invokes the function `fn_name` with `num_args` arguments `args` in the runtime corresponding
+/// to `handle`. The arguments are validated against the function's signature. If successful,
+/// `has_result` is set and - if the function returns a value - `result`, otherwise a non-zero
+/// error handle is returned.
+///
+/// Returned structs are not rooted, and must be rooted using [`mun_gc_root`] to outlive the next
+/// garbage collection.
+///
+/// If a non-zero error handle is returned, it must be manually destructed using
+/// [`mun_error_destroy`].
+///
+/// # Safety
+///
+/// This function receives raw pointers as parameters. If any of the arguments is a null pointer,
+/// an error will be returned. Passing pointers to invalid data, will lead to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_runtime_invoke_dynamic(
+    handle: RuntimeHandle,
+    fn_name: *const c_char,
+    args: *const Value,
+    num_args: u32,
+    has_result: *mut bool,
+    result: *mut Value,
+) -> ErrorHandle {
+    let runtime = match handle.runtime() {
+        Some(runtime) => runtime,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'runtime' is null pointer."))
+        }
+    };
+
+    if fn_name.is_null() {
+        return HUB
+            .errors
+            .register(anyhow!("Invalid argument: 'fn_name' is null pointer."));
+    }
+
+    let fn_name = match CStr::from_ptr(fn_name).to_str() {
+        Ok(name) => name,
+        Err(_) => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'fn_name' is not UTF-8 encoded."))
+        }
+    };
+
+    if num_args > 0 && args.is_null() {
+        return HUB
+            .errors
+            .register(anyhow!("Invalid argument: 'args' is null pointer."));
+    }
+
+    let has_result = match has_result.as_mut() {
+        Some(has_result) => has_result,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'has_result' is null pointer."))
+        }
+    };
+
+    let result = match result.as_mut() {
+        Some(result) => result,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'result' is null pointer."))
+        }
+    };
+
+    let args: Result<Vec<runtime::Value>, _> = if num_args > 0 {
+        std::slice::from_raw_parts(args, num_args as usize)
+            .iter()
+            .map(|arg| arg.into_runtime(runtime))
+            .collect()
+    } else {
+        Ok(Vec::new())
+    };
+    let args = match args {
+        Ok(args) => args,
+        Err(e) => return HUB.errors.register(e),
+    };
+
+    match runtime.invoke_dynamic(fn_name, &args) {
+        Ok(Some(value)) => match Value::from_runtime(value) {
+            Ok(value) => {
+                *has_result = true;
+                *result = value;
+            }
+            Err(e) => return HUB.errors.register(e),
+        },
+        Ok(None) => *has_result = false,
+        Err(e) => return HUB.errors.register(anyhow!(e)),
+    }
+
+    ErrorHandle::default()
+}
+
 /// Updates the runtime corresponding to `handle`. If successful, `updated` is set, otherwise a
 /// non-zero error handle is returned.
 ///
@@ -235,8 +464,8 @@ pub unsafe extern "C" fn mun_runtime_update(
     handle: RuntimeHandle,
     updated: *mut bool,
 ) -> ErrorHandle {
-    let runtime = match (handle.0 as *mut Runtime).as_mut() {
-        Some(runtime) => runtime,
+    let capi_runtime = match (handle.0 as *mut CapiRuntime).as_mut() {
+        Some(capi_runtime) => capi_runtime,
         None => {
             return HUB
                 .errors
@@ -253,7 +482,19 @@ pub unsafe extern "C" fn mun_runtime_update(
         }
     };
 
-    *updated = runtime.update();
+    *updated = capi_runtime.runtime.update();
+
+    // Call the reload callback once the runtime is no longer mutably borrowed
+    let reload_fn = capi_runtime.reload_fn;
+    let reload_user_data = capi_runtime.reload_user_data;
+    let reload_events = std::mem::take(&mut *capi_runtime.reload_events.lock());
+    if let Some(reload_fn) = reload_fn {
+        for event in reload_events {
+            let event = reload::ReloadEvent::new(&event);
+            reload_fn(&event, reload_user_data);
+            event.destroy();
+        }
+    }
     ErrorHandle::default()
 }
 
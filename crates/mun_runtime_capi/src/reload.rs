@@ -0,0 +1,172 @@
+//! Exposes descriptions of the changes made when a runtime reloads assemblies.
+
+use crate::{
+    type_info::{destroy_array, into_raw_array},
+    ErrorHandle, RuntimeHandle, HUB,
+};
+use anyhow::anyhow;
+use std::{
+    ffi::{c_void, CString},
+    os::raw::c_char,
+};
+
+/// A function that is called whenever a runtime reloads assemblies, with a description of the
+/// changes and the user data that was specified together with the function.
+///
+/// The function is called by [`crate::mun_runtime_update`], after the runtime finished reloading.
+/// The `event` is only valid for the duration of the call. The runtime must not be accessed from
+/// the function.
+pub type ReloadCallback =
+    Option<unsafe extern "C" fn(event: *const ReloadEvent, user_data: *mut c_void)>;
+
+/// Describes the changes made when a runtime reloaded assemblies, such that hosts can invalidate
+/// what they cached of the reloaded assemblies; e.g. function definitions and type information.
+///
+/// Events that were retrieved using [`mun_runtime_poll_reload_event`] must be manually destructed
+/// using [`mun_reload_event_destroy`].
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct ReloadEvent {
+    /// The library paths of the reloaded assemblies
+    pub assemblies: *const *const c_char,
+    /// The number of elements in the [`assemblies`] array
+    pub num_assemblies: usize,
+    /// The sorted names of all functions that were added, removed, or replaced
+    pub changed_functions: *const *const c_char,
+    /// The number of elements in the [`changed_functions`] array
+    pub num_changed_functions: usize,
+    /// The sorted names of all struct types of which the memory layout changed. Existing objects
+    /// of these types were mapped - or migrated - to their new layout.
+    pub migrated_types: *const *const c_char,
+    /// The number of elements in the [`migrated_types`] array
+    pub num_migrated_types: usize,
+}
+
+impl ReloadEvent {
+    /// Copies `event` to the heap. The memory must be released using [`ReloadEvent::destroy`].
+    pub(crate) fn new(event: &runtime::ReloadEvent) -> Self {
+        fn into_raw_strings<'s>(
+            strings: impl Iterator<Item = &'s str>,
+        ) -> (*const *const c_char, usize) {
+            into_raw_array(
+                strings
+                    .map(|string| {
+                        CString::new(string)
+                            .expect("names and paths do not contain null characters")
+                            .into_raw() as *const c_char
+                    })
+                    .collect(),
+            )
+        }
+
+        let assemblies = event
+            .assemblies
+            .iter()
+            .map(|path| path.to_string_lossy())
+            .collect::<Vec<_>>();
+        let (assemblies, num_assemblies) =
+            into_raw_strings(assemblies.iter().map(|path| path.as_ref()));
+        let (changed_functions, num_changed_functions) =
+            into_raw_strings(event.changed_functions.iter().map(String::as_str));
+        let (migrated_types, num_migrated_types) =
+            into_raw_strings(event.migrated_types.iter().map(String::as_str));
+
+        ReloadEvent {
+            assemblies,
+            num_assemblies,
+            changed_functions,
+            num_changed_functions,
+            migrated_types,
+            num_migrated_types,
+        }
+    }
+
+    /// Releases an event that was created using [`ReloadEvent::new`].
+    ///
+    /// # Safety
+    ///
+    /// The event must have been created using [`ReloadEvent::new`], and must not be used
+    /// afterwards.
+    pub(crate) unsafe fn destroy(self) {
+        unsafe fn destroy_strings(strings: *const *const c_char, len: usize) {
+            if !strings.is_null() {
+                for string in std::slice::from_raw_parts(strings, len) {
+                    let _string = CString::from_raw(*string as *mut c_char);
+                }
+            }
+            destroy_array(strings, len);
+        }
+
+        destroy_strings(self.assemblies, self.num_assemblies);
+        destroy_strings(self.changed_functions, self.num_changed_functions);
+        destroy_strings(self.migrated_types, self.num_migrated_types);
+    }
+}
+
+/// Retrieves the oldest description of a reload by the runtime corresponding to `handle` that has
+/// not been polled yet. If successful, `has_event` is set and - if there is an event - `event` is
+/// set, otherwise a non-zero error handle is returned.
+///
+/// The runtime retains the events of a limited number of reloads, so events should be polled after
+/// every update. The event must be manually destructed using [`mun_reload_event_destroy`].
+///
+/// If a non-zero error handle is returned, it must be manually destructed using
+/// [`mun_error_destroy`].
+///
+/// # Safety
+///
+/// This function receives raw pointers as parameters. If any of the arguments is a null pointer,
+/// an error will be returned. Passing pointers to invalid data, will lead to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_runtime_poll_reload_event(
+    handle: RuntimeHandle,
+    has_event: *mut bool,
+    event: *mut ReloadEvent,
+) -> ErrorHandle {
+    let runtime = match handle.runtime_mut() {
+        Some(runtime) => runtime,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'runtime' is null pointer."))
+        }
+    };
+
+    let has_event = match has_event.as_mut() {
+        Some(has_event) => has_event,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'has_event' is null pointer."))
+        }
+    };
+
+    let event = match event.as_mut() {
+        Some(event) => event,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'event' is null pointer."))
+        }
+    };
+
+    match runtime.poll_reload_event() {
+        Some(reload_event) => {
+            *has_event = true;
+            *event = ReloadEvent::new(&reload_event);
+        }
+        None => *has_event = false,
+    }
+    ErrorHandle::default()
+}
+
+/// Deallocates a reload event that was allocated by the runtime.
+///
+/// # Safety
+///
+/// Passing events that were not returned by [`mun_runtime_poll_reload_event`], will lead to
+/// undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_reload_event_destroy(event: ReloadEvent) {
+    event.destroy();
+}
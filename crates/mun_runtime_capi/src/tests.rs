@@ -1,4 +1,4 @@
-use crate::{error::*, gc::*, *};
+use crate::{adt::*, error::*, gc::*, reload::*, type_info::*, value::*, *};
 use compiler::{Config, Driver, PathOrInline, RelativePathBuf};
 use memory::gc::{GcPtr, HasIndirectionPtr, RawGcPtr};
 use runtime::UnsafeTypeInfo;
@@ -8,18 +8,27 @@ use std::{
     mem::{self, MaybeUninit},
     path::Path,
     ptr::{self, NonNull},
+    thread::sleep,
+    time::{Duration, Instant},
 };
 
 /// Combines a compiler and runtime in one. Use of the TestDriver allows for quick testing of Mun
 /// constructs in the runtime with hot-reloading support.
 struct TestDriver {
     _temp_dir: tempfile::TempDir,
+    driver: Driver,
     runtime: RuntimeHandle,
 }
 
 impl TestDriver {
     /// Constructs a new `TestDriver` from Mun source
     fn new(text: &str) -> Self {
+        Self::with_options(text, RuntimeOptions::default())
+    }
+
+    /// Constructs a new `TestDriver` from Mun source, of which the runtime is created using
+    /// `options`
+    fn with_options(text: &str, options: RuntimeOptions) -> Self {
         let temp_dir = tempfile::TempDir::new().unwrap();
         let config = Config {
             out_dir: Some(temp_dir.path().to_path_buf()),
@@ -35,12 +44,35 @@ impl TestDriver {
         }
         let out_path = driver.assembly_output_path_from_file(file_id);
         driver.write_all_assemblies(false).unwrap();
-        let runtime = make_runtime(&out_path);
+        let runtime = make_runtime(&out_path, options);
         TestDriver {
             _temp_dir: temp_dir,
+            driver,
             runtime,
         }
     }
+
+    /// Updates the Mun source, recompiles it, and updates the runtime until it reloaded the
+    /// assembly.
+    fn update(&mut self, text: &str) {
+        self.driver.set_file_text("mod.mun", text).unwrap();
+        if self.driver.emit_diagnostics(&mut stderr()).unwrap() {
+            panic!("compiler errors..")
+        }
+        self.driver.write_all_assemblies(true).unwrap();
+
+        let start_time = Instant::now();
+        let mut updated = false;
+        while !updated {
+            if start_time.elapsed() > Duration::from_secs(10) {
+                panic!("runtime did not update after recompilation within 10 seconds");
+            }
+            sleep(Duration::from_millis(1));
+
+            let handle = unsafe { mun_runtime_update(self.runtime, &mut updated as *mut _) };
+            assert_eq!(handle.token(), 0);
+        }
+    }
 }
 
 impl Drop for TestDriver {
@@ -49,18 +81,12 @@ impl Drop for TestDriver {
     }
 }
 
-fn make_runtime(lib_path: &Path) -> RuntimeHandle {
+fn make_runtime(lib_path: &Path, options: RuntimeOptions) -> RuntimeHandle {
     let lib_path = lib_path.to_str().expect("Invalid lib path");
     let lib_path = CString::new(lib_path).unwrap();
 
     let mut handle = RuntimeHandle(ptr::null_mut());
-    let error = unsafe {
-        mun_runtime_create(
-            lib_path.as_ptr(),
-            RuntimeOptions::default(),
-            &mut handle as *mut _,
-        )
-    };
+    let error = unsafe { mun_runtime_create(lib_path.as_ptr(), options, &mut handle as *mut _) };
     assert_eq!(error.token(), 0, "Failed to create runtime");
     handle
 }
@@ -92,12 +118,34 @@ macro_rules! test_invalid_runtime {
 
 test_invalid_runtime!(
     runtime_get_function_definition(ptr::null(), ptr::null_mut(), ptr::null_mut()),
+    runtime_invoke_dynamic(
+        ptr::null(),
+        ptr::null(),
+        0,
+        ptr::null_mut(),
+        ptr::null_mut()
+    ),
     runtime_update(ptr::null_mut()),
     gc_alloc(UnsafeTypeInfo::new(NonNull::dangling()), ptr::null_mut()),
     gc_ptr_type(mem::zeroed::<GcPtr>(), ptr::null_mut()),
     gc_root(mem::zeroed::<GcPtr>()),
     gc_unroot(mem::zeroed::<GcPtr>()),
-    gc_collect(ptr::null_mut())
+    gc_collect(ptr::null_mut()),
+    struct_get_field(mem::zeroed::<GcPtr>(), ptr::null(), ptr::null_mut()),
+    struct_set_field(
+        mem::zeroed::<GcPtr>(),
+        ptr::null(),
+        Value {
+            kind: ValueKind::Bool as u8,
+            data: ValueData { boolean: false },
+        }
+    ),
+    struct_num_fields(mem::zeroed::<GcPtr>(), ptr::null_mut()),
+    struct_field_info(mem::zeroed::<GcPtr>(), 0, ptr::null_mut()),
+    runtime_get_type_info_by_name(ptr::null(), ptr::null_mut(), ptr::null_mut()),
+    runtime_get_type_infos(ptr::null_mut()),
+    runtime_get_function_definitions(ptr::null_mut()),
+    runtime_poll_reload_event(ptr::null_mut(), ptr::null_mut())
 );
 
 #[test]
@@ -159,6 +207,100 @@ fn test_runtime_create_invalid_handle() {
     unsafe { mun_destroy_string(message.as_ptr()) };
 }
 
+#[test]
+fn test_runtime_create_invalid_functions() {
+    let lib_path = CString::new("some/path").expect("Invalid library path");
+    let options = RuntimeOptions {
+        functions: ptr::null(),
+        num_functions: 1,
+        ..RuntimeOptions::default()
+    };
+
+    let mut runtime = RuntimeHandle(ptr::null_mut());
+    let handle = unsafe { mun_runtime_create(lib_path.as_ptr(), options, &mut runtime as *mut _) };
+    assert_ne!(handle.token(), 0);
+
+    let message = unsafe { CStr::from_ptr(mun_error_message(handle)) };
+    assert_eq!(
+        message.to_str().unwrap(),
+        "Invalid argument: 'functions' is null pointer."
+    );
+
+    unsafe { mun_destroy_string(message.as_ptr()) };
+}
+
+#[test]
+fn test_runtime_create_from_bytes_invalid_bytes() {
+    let lib_path = CString::new("some/path").expect("Invalid library path");
+
+    let handle = unsafe {
+        mun_runtime_create_from_bytes(
+            lib_path.as_ptr(),
+            ptr::null(),
+            0,
+            RuntimeOptions::default(),
+            ptr::null_mut(),
+        )
+    };
+    assert_ne!(handle.token(), 0);
+
+    let message = unsafe { CStr::from_ptr(mun_error_message(handle)) };
+    assert_eq!(
+        message.to_str().unwrap(),
+        "Invalid argument: 'bytes' is null pointer."
+    );
+
+    unsafe { mun_destroy_string(message.as_ptr()) };
+}
+
+#[test]
+fn test_runtime_create_from_bytes() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = Config {
+        out_dir: Some(temp_dir.path().to_path_buf()),
+        ..Config::default()
+    };
+    let input = PathOrInline::Inline {
+        rel_path: RelativePathBuf::from("mod.mun"),
+        contents: "pub fn main() -> i32 { 3 }".to_owned(),
+    };
+    let (mut driver, file_id) = Driver::with_file(config, input).unwrap();
+    if driver.emit_diagnostics(&mut stderr()).unwrap() {
+        panic!("compiler errors..")
+    }
+    driver.write_all_assemblies(false).unwrap();
+    let bytes = std::fs::read(driver.assembly_output_path_from_file(file_id)).unwrap();
+
+    let lib_path = CString::new("archive/mod.munlib").expect("Invalid library path");
+    let mut runtime = RuntimeHandle(ptr::null_mut());
+    let handle = unsafe {
+        mun_runtime_create_from_bytes(
+            lib_path.as_ptr(),
+            bytes.as_ptr(),
+            bytes.len(),
+            RuntimeOptions::default(),
+            &mut runtime as *mut _,
+        )
+    };
+    assert_eq!(handle.token(), 0, "Failed to create runtime");
+
+    let fn_name = CString::new("main").expect("Invalid function name");
+    let mut has_fn_info = false;
+    let mut fn_definition = MaybeUninit::uninit();
+    let handle = unsafe {
+        mun_runtime_get_function_definition(
+            runtime,
+            fn_name.as_ptr(),
+            &mut has_fn_info as *mut _,
+            fn_definition.as_mut_ptr(),
+        )
+    };
+    assert_eq!(handle.token(), 0);
+    assert!(has_fn_info);
+
+    mun_runtime_destroy(runtime);
+}
+
 #[test]
 fn test_runtime_get_function_info_invalid_fn_name() {
     let driver = TestDriver::new(
@@ -291,6 +433,181 @@ fn test_runtime_get_function_info() {
     let _fn_definition = unsafe { fn_definition.assume_init() };
 }
 
+#[test]
+fn test_runtime_invoke_dynamic_invalid_args() {
+    let driver = TestDriver::new(
+        r#"
+        pub fn add(a: i32, b: i32) -> i32 { a + b }
+    "#,
+    );
+
+    let fn_name = CString::new("add").expect("Invalid function name");
+    let mut has_result = false;
+    let mut result = MaybeUninit::uninit();
+    let handle = unsafe {
+        mun_runtime_invoke_dynamic(
+            driver.runtime,
+            fn_name.as_ptr(),
+            ptr::null(),
+            2,
+            &mut has_result as *mut _,
+            result.as_mut_ptr(),
+        )
+    };
+
+    let message = unsafe { CStr::from_ptr(mun_error_message(handle)) };
+    assert_eq!(
+        message.to_str().unwrap(),
+        "Invalid argument: 'args' is null pointer."
+    );
+
+    unsafe { mun_destroy_string(message.as_ptr()) };
+}
+
+#[test]
+fn test_runtime_invoke_dynamic_invalid_signature() {
+    let driver = TestDriver::new(
+        r#"
+        pub fn add(a: i32, b: i32) -> i32 { a + b }
+    "#,
+    );
+
+    let fn_name = CString::new("add").expect("Invalid function name");
+    let args = [Value {
+        kind: ValueKind::I64 as u8,
+        data: ValueData { i64: 3 },
+    }];
+    let mut has_result = false;
+    let mut result = MaybeUninit::uninit();
+    let handle = unsafe {
+        mun_runtime_invoke_dynamic(
+            driver.runtime,
+            fn_name.as_ptr(),
+            args.as_ptr(),
+            args.len() as u32,
+            &mut has_result as *mut _,
+            result.as_mut_ptr(),
+        )
+    };
+
+    let message = unsafe { CStr::from_ptr(mun_error_message(handle)) };
+    assert_eq!(
+        message.to_str().unwrap(),
+        "Invalid number of arguments. Expected: 2. Found: 1."
+    );
+
+    unsafe { mun_destroy_string(message.as_ptr()) };
+}
+
+#[test]
+fn test_runtime_invoke_dynamic_invalid_kind() {
+    let driver = TestDriver::new(
+        r#"
+        pub fn add(a: i32, b: i32) -> i32 { a + b }
+    "#,
+    );
+
+    let fn_name = CString::new("add").expect("Invalid function name");
+    let args = [
+        Value {
+            kind: ValueKind::I32 as u8,
+            data: ValueData { i32: 3 },
+        },
+        Value {
+            kind: 12,
+            data: ValueData { i32: 4 },
+        },
+    ];
+    let mut has_result = false;
+    let mut result = MaybeUninit::uninit();
+    let handle = unsafe {
+        mun_runtime_invoke_dynamic(
+            driver.runtime,
+            fn_name.as_ptr(),
+            args.as_ptr(),
+            args.len() as u32,
+            &mut has_result as *mut _,
+            result.as_mut_ptr(),
+        )
+    };
+
+    let message = unsafe { CStr::from_ptr(mun_error_message(handle)) };
+    assert_eq!(
+        message.to_str().unwrap(),
+        "Invalid argument: '12' is not a valid value kind."
+    );
+
+    unsafe { mun_destroy_string(message.as_ptr()) };
+}
+
+#[test]
+fn test_runtime_invoke_dynamic() {
+    let driver = TestDriver::new(
+        r#"
+        struct(gc) Foo { a: i32 };
+
+        pub fn foo_new(a: i32, b: i32) -> Foo { Foo { a: a + b } }
+        pub fn foo_set(foo: Foo, a: i32) { foo.a = a; }
+    "#,
+    );
+
+    let fn_name = CString::new("foo_new").expect("Invalid function name");
+    let args = [
+        Value {
+            kind: ValueKind::I32 as u8,
+            data: ValueData { i32: 3 },
+        },
+        Value {
+            kind: ValueKind::I32 as u8,
+            data: ValueData { i32: 4 },
+        },
+    ];
+    let mut has_result = false;
+    let mut result = MaybeUninit::uninit();
+    let handle = unsafe {
+        mun_runtime_invoke_dynamic(
+            driver.runtime,
+            fn_name.as_ptr(),
+            args.as_ptr(),
+            args.len() as u32,
+            &mut has_result as *mut _,
+            result.as_mut_ptr(),
+        )
+    };
+    assert_eq!(handle.token(), 0);
+    assert!(has_result);
+
+    let foo = unsafe { result.assume_init() };
+    assert_eq!(foo.kind, ValueKind::Struct as u8);
+    let foo = unsafe { foo.data.gc_ptr };
+    assert_eq!(unsafe { *foo.deref::<i32>() }, 7);
+
+    let fn_name = CString::new("foo_set").expect("Invalid function name");
+    let args = [
+        Value {
+            kind: ValueKind::Struct as u8,
+            data: ValueData { gc_ptr: foo },
+        },
+        Value {
+            kind: ValueKind::I32 as u8,
+            data: ValueData { i32: 5 },
+        },
+    ];
+    let handle = unsafe {
+        mun_runtime_invoke_dynamic(
+            driver.runtime,
+            fn_name.as_ptr(),
+            args.as_ptr(),
+            args.len() as u32,
+            &mut has_result as *mut _,
+            result.as_mut_ptr(),
+        )
+    };
+    assert_eq!(handle.token(), 0);
+    assert!(!has_result);
+    assert_eq!(unsafe { *foo.deref::<i32>() }, 5);
+}
+
 #[test]
 fn test_runtime_update_invalid_updated() {
     let driver = TestDriver::new(
@@ -544,3 +861,560 @@ fn test_gc_ptr_collect_invalid_reclaimed() {
 
     unsafe { mun_destroy_string(message.as_ptr()) };
 }
+
+/// Invokes `fn_name` without arguments and returns the struct it returns.
+fn invoke_struct_fn(driver: &TestDriver, fn_name: &str) -> GcPtr {
+    let fn_name = CString::new(fn_name).expect("Invalid function name");
+    let mut has_result = false;
+    let mut result = MaybeUninit::uninit();
+    let handle = unsafe {
+        mun_runtime_invoke_dynamic(
+            driver.runtime,
+            fn_name.as_ptr(),
+            ptr::null(),
+            0,
+            &mut has_result as *mut _,
+            result.as_mut_ptr(),
+        )
+    };
+    assert_eq!(handle.token(), 0);
+    assert!(has_result);
+
+    let result = unsafe { result.assume_init() };
+    assert_eq!(result.kind, ValueKind::Struct as u8);
+    unsafe { result.data.gc_ptr }
+}
+
+#[test]
+fn test_struct_get_field_invalid_field_name() {
+    let driver = TestDriver::new(
+        r#"
+        pub struct Foo { a: i32 }
+
+        pub fn foo_new() -> Foo { Foo { a: 3 } }
+    "#,
+    );
+    let foo = invoke_struct_fn(&driver, "foo_new");
+
+    let mut value = MaybeUninit::uninit();
+    let handle =
+        unsafe { mun_struct_get_field(driver.runtime, foo, ptr::null(), value.as_mut_ptr()) };
+
+    let message = unsafe { CStr::from_ptr(mun_error_message(handle)) };
+    assert_eq!(
+        message.to_str().unwrap(),
+        "Invalid argument: 'field_name' is null pointer."
+    );
+
+    unsafe { mun_destroy_string(message.as_ptr()) };
+}
+
+#[test]
+fn test_struct_get_field_invalid_value() {
+    let driver = TestDriver::new(
+        r#"
+        pub struct Foo { a: i32 }
+
+        pub fn foo_new() -> Foo { Foo { a: 3 } }
+    "#,
+    );
+    let foo = invoke_struct_fn(&driver, "foo_new");
+
+    let field_name = CString::new("a").unwrap();
+    let handle =
+        unsafe { mun_struct_get_field(driver.runtime, foo, field_name.as_ptr(), ptr::null_mut()) };
+
+    let message = unsafe { CStr::from_ptr(mun_error_message(handle)) };
+    assert_eq!(
+        message.to_str().unwrap(),
+        "Invalid argument: 'value' is null pointer."
+    );
+
+    unsafe { mun_destroy_string(message.as_ptr()) };
+}
+
+#[test]
+fn test_struct_get_field_missing() {
+    let driver = TestDriver::new(
+        r#"
+        pub struct Foo { a: i32 }
+
+        pub fn foo_new() -> Foo { Foo { a: 3 } }
+    "#,
+    );
+    let foo = invoke_struct_fn(&driver, "foo_new");
+
+    let field_name = CString::new("b").unwrap();
+    let mut value = MaybeUninit::uninit();
+    let handle = unsafe {
+        mun_struct_get_field(driver.runtime, foo, field_name.as_ptr(), value.as_mut_ptr())
+    };
+    assert_ne!(handle.token(), 0);
+
+    mun_error_destroy(handle);
+}
+
+#[test]
+fn test_struct_get_set_field() {
+    let driver = TestDriver::new(
+        r#"
+        pub struct Bar { b: i64 }
+        pub struct Foo { a: i32, bar: Bar }
+
+        pub fn foo_new() -> Foo { Foo { a: 3, bar: Bar { b: 7 } } }
+    "#,
+    );
+    let foo = invoke_struct_fn(&driver, "foo_new");
+
+    let field_name = CString::new("a").unwrap();
+    let mut value = MaybeUninit::uninit();
+    let handle = unsafe {
+        mun_struct_get_field(driver.runtime, foo, field_name.as_ptr(), value.as_mut_ptr())
+    };
+    assert_eq!(handle.token(), 0);
+
+    let value = unsafe { value.assume_init() };
+    assert_eq!(value.kind, ValueKind::I32 as u8);
+    assert_eq!(unsafe { value.data.i32 }, 3);
+
+    let new_value = Value {
+        kind: ValueKind::I32 as u8,
+        data: ValueData { i32: 5 },
+    };
+    let handle =
+        unsafe { mun_struct_set_field(driver.runtime, foo, field_name.as_ptr(), new_value) };
+    assert_eq!(handle.token(), 0);
+    assert_eq!(unsafe { *foo.deref::<i32>() }, 5);
+
+    // Nested structs are returned as garbage collector handles
+    let field_name = CString::new("bar").unwrap();
+    let mut bar = MaybeUninit::uninit();
+    let handle =
+        unsafe { mun_struct_get_field(driver.runtime, foo, field_name.as_ptr(), bar.as_mut_ptr()) };
+    assert_eq!(handle.token(), 0);
+
+    let bar = unsafe { bar.assume_init() };
+    assert_eq!(bar.kind, ValueKind::Struct as u8);
+
+    let field_name = CString::new("b").unwrap();
+    let mut value = MaybeUninit::uninit();
+    let handle = unsafe {
+        mun_struct_get_field(
+            driver.runtime,
+            bar.data.gc_ptr,
+            field_name.as_ptr(),
+            value.as_mut_ptr(),
+        )
+    };
+    assert_eq!(handle.token(), 0);
+
+    let value = unsafe { value.assume_init() };
+    assert_eq!(value.kind, ValueKind::I64 as u8);
+    assert_eq!(unsafe { value.data.i64 }, 7);
+}
+
+#[test]
+fn test_struct_set_field_mismatched_type() {
+    let driver = TestDriver::new(
+        r#"
+        pub struct Foo { a: i32 }
+
+        pub fn foo_new() -> Foo { Foo { a: 3 } }
+    "#,
+    );
+    let foo = invoke_struct_fn(&driver, "foo_new");
+
+    let field_name = CString::new("a").unwrap();
+    let value = Value {
+        kind: ValueKind::F32 as u8,
+        data: ValueData { f32: 5.0 },
+    };
+    let handle = unsafe { mun_struct_set_field(driver.runtime, foo, field_name.as_ptr(), value) };
+
+    let message = unsafe { CStr::from_ptr(mun_error_message(handle)) };
+    assert_eq!(
+        message.to_str().unwrap(),
+        "Mismatched types for `Foo::a`. Expected: `core::i32`. Found: `core::f32`."
+    );
+    assert_eq!(unsafe { *foo.deref::<i32>() }, 3);
+
+    unsafe { mun_destroy_string(message.as_ptr()) };
+}
+
+#[test]
+fn test_struct_field_iteration() {
+    let driver = TestDriver::new(
+        r#"
+        pub struct Foo { a: i32, b: bool }
+
+        pub fn foo_new() -> Foo { Foo { a: 3, b: true } }
+    "#,
+    );
+    let foo = invoke_struct_fn(&driver, "foo_new");
+
+    let mut num_fields = 0;
+    let handle = unsafe { mun_struct_num_fields(driver.runtime, foo, &mut num_fields as *mut _) };
+    assert_eq!(handle.token(), 0);
+    assert_eq!(num_fields, 2);
+
+    let fields: Vec<(String, String)> = (0..num_fields)
+        .map(|field_idx| {
+            let mut field_info = MaybeUninit::uninit();
+            let handle = unsafe {
+                mun_struct_field_info(driver.runtime, foo, field_idx, field_info.as_mut_ptr())
+            };
+            assert_eq!(handle.token(), 0);
+
+            let field_info = unsafe { field_info.assume_init() };
+            let name = unsafe { CStr::from_ptr(field_info.name) };
+            let type_info = unsafe { &*field_info.type_info };
+            (
+                name.to_str().unwrap().to_owned(),
+                type_info.name().to_owned(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            (String::from("a"), String::from("core::i32")),
+            (String::from("b"), String::from("core::bool")),
+        ]
+    );
+
+    let mut field_info = MaybeUninit::uninit();
+    let handle =
+        unsafe { mun_struct_field_info(driver.runtime, foo, num_fields, field_info.as_mut_ptr()) };
+
+    let message = unsafe { CStr::from_ptr(mun_error_message(handle)) };
+    assert_eq!(
+        message.to_str().unwrap(),
+        "Invalid argument: 'field_idx' is out of bounds. `Foo` has 2 fields."
+    );
+
+    unsafe { mun_destroy_string(message.as_ptr()) };
+}
+
+#[test]
+fn test_runtime_get_type_info_by_name_invalid_type_name() {
+    let driver = TestDriver::new(
+        r#"
+        pub struct Foo { a: i32 }
+    "#,
+    );
+
+    let mut has_type_info = false;
+    let mut type_info = ptr::null();
+    let handle = unsafe {
+        mun_runtime_get_type_info_by_name(
+            driver.runtime,
+            ptr::null(),
+            &mut has_type_info as *mut _,
+            &mut type_info as *mut _,
+        )
+    };
+
+    let message = unsafe { CStr::from_ptr(mun_error_message(handle)) };
+    assert_eq!(
+        message.to_str().unwrap(),
+        "Invalid argument: 'type_name' is null pointer."
+    );
+
+    unsafe { mun_destroy_string(message.as_ptr()) };
+}
+
+#[test]
+fn test_runtime_get_type_info_by_name() {
+    let driver = TestDriver::new(
+        r#"
+        pub struct(value) Foo { a: i32, b: f64 }
+
+        pub fn foo_new() -> Foo { Foo { a: 3, b: 1.0 } }
+    "#,
+    );
+
+    let type_name = CString::new("Foo").unwrap();
+    let mut has_type_info = false;
+    let mut type_info = ptr::null();
+    let handle = unsafe {
+        mun_runtime_get_type_info_by_name(
+            driver.runtime,
+            type_name.as_ptr(),
+            &mut has_type_info as *mut _,
+            &mut type_info as *mut _,
+        )
+    };
+    assert_eq!(handle.token(), 0);
+    assert!(has_type_info);
+
+    let mut size = 0usize;
+    let handle = unsafe { mun_type_info_size(type_info, &mut size as *mut _) };
+    assert_eq!(handle.token(), 0);
+    assert_eq!(size, 16);
+
+    let mut alignment = 0usize;
+    let handle = unsafe { mun_type_info_alignment(type_info, &mut alignment as *mut _) };
+    assert_eq!(handle.token(), 0);
+    assert_eq!(alignment, 8);
+
+    let mut has_memory_kind = false;
+    let mut memory_kind = abi::StructMemoryKind::GC;
+    let handle = unsafe {
+        mun_type_info_memory_kind(
+            type_info,
+            &mut has_memory_kind as *mut _,
+            &mut memory_kind as *mut _,
+        )
+    };
+    assert_eq!(handle.token(), 0);
+    assert!(has_memory_kind);
+    assert_eq!(memory_kind, abi::StructMemoryKind::Value);
+
+    let mut num_fields = 0u16;
+    let handle = unsafe { mun_type_info_num_fields(type_info, &mut num_fields as *mut _) };
+    assert_eq!(handle.token(), 0);
+    assert_eq!(num_fields, 2);
+
+    let mut field_info = MaybeUninit::uninit();
+    let handle = unsafe { mun_type_info_field_info(type_info, 1, field_info.as_mut_ptr()) };
+    assert_eq!(handle.token(), 0);
+
+    let field_info = unsafe { field_info.assume_init() };
+    assert_eq!(unsafe { CStr::from_ptr(field_info.name) }.to_str(), Ok("b"));
+    assert_eq!(unsafe { &*field_info.type_info }.name(), "core::f64");
+    assert_eq!(field_info.offset, 8);
+
+    // Primitive types have no memory kind and no fields
+    let mut has_memory_kind = true;
+    let handle = unsafe {
+        mun_type_info_memory_kind(
+            field_info.type_info,
+            &mut has_memory_kind as *mut _,
+            &mut memory_kind as *mut _,
+        )
+    };
+    assert_eq!(handle.token(), 0);
+    assert!(!has_memory_kind);
+
+    let handle =
+        unsafe { mun_type_info_num_fields(field_info.type_info, &mut num_fields as *mut _) };
+    assert_eq!(handle.token(), 0);
+    assert_eq!(num_fields, 0);
+
+    // Unknown types are not found
+    let type_name = CString::new("Bar").unwrap();
+    let handle = unsafe {
+        mun_runtime_get_type_info_by_name(
+            driver.runtime,
+            type_name.as_ptr(),
+            &mut has_type_info as *mut _,
+            &mut type_info as *mut _,
+        )
+    };
+    assert_eq!(handle.token(), 0);
+    assert!(!has_type_info);
+}
+
+#[test]
+fn test_type_info_field_info_invalid_type_info() {
+    let mut field_info = MaybeUninit::uninit();
+    let handle = unsafe { mun_type_info_field_info(ptr::null(), 0, field_info.as_mut_ptr()) };
+
+    let message = unsafe { CStr::from_ptr(mun_error_message(handle)) };
+    assert_eq!(
+        message.to_str().unwrap(),
+        "Invalid argument: 'type_info' is null pointer."
+    );
+
+    unsafe { mun_destroy_string(message.as_ptr()) };
+}
+
+#[test]
+fn test_runtime_get_type_infos_and_function_definitions() {
+    let driver = TestDriver::new(
+        r#"
+        pub struct Foo { a: i32 }
+
+        pub fn foo_new() -> Foo { Foo { a: 3 } }
+        pub fn foo_a(foo: Foo) -> i32 { foo.a }
+    "#,
+    );
+
+    let mut type_infos = MaybeUninit::uninit();
+    let handle = unsafe { mun_runtime_get_type_infos(driver.runtime, type_infos.as_mut_ptr()) };
+    assert_eq!(handle.token(), 0);
+
+    let type_infos = unsafe { type_infos.assume_init() };
+    let type_names: Vec<&str> = unsafe {
+        std::slice::from_raw_parts(type_infos.type_infos, type_infos.num_type_infos)
+            .iter()
+            .map(|type_info| (**type_info).name())
+            .collect()
+    };
+    assert!(type_names.contains(&"Foo"));
+    assert!(type_names.contains(&"core::i32"));
+    unsafe { mun_type_info_array_destroy(type_infos) };
+
+    let mut definitions = MaybeUninit::uninit();
+    let handle =
+        unsafe { mun_runtime_get_function_definitions(driver.runtime, definitions.as_mut_ptr()) };
+    assert_eq!(handle.token(), 0);
+
+    let definitions = unsafe { definitions.assume_init() };
+    let mut fn_names: Vec<&str> = unsafe {
+        std::slice::from_raw_parts(definitions.definitions, definitions.num_definitions)
+            .iter()
+            .map(|definition| definition.prototype.name())
+            .collect()
+    };
+    fn_names.sort_unstable();
+    assert_eq!(fn_names, ["foo_a", "foo_new"]);
+    unsafe { mun_function_definition_array_destroy(definitions) };
+}
+
+#[test]
+fn test_runtime_poll_reload_event_invalid_has_event() {
+    let driver = TestDriver::new(
+        r#"
+        pub fn main() -> i32 { 3 }
+    "#,
+    );
+
+    let mut event = MaybeUninit::uninit();
+    let handle = unsafe {
+        mun_runtime_poll_reload_event(driver.runtime, ptr::null_mut(), event.as_mut_ptr())
+    };
+
+    let message = unsafe { CStr::from_ptr(mun_error_message(handle)) };
+    assert_eq!(
+        message.to_str().unwrap(),
+        "Invalid argument: 'has_event' is null pointer."
+    );
+
+    unsafe { mun_destroy_string(message.as_ptr()) };
+}
+
+#[test]
+fn test_runtime_poll_reload_event_invalid_event() {
+    let driver = TestDriver::new(
+        r#"
+        pub fn main() -> i32 { 3 }
+    "#,
+    );
+
+    let mut has_event = false;
+    let handle = unsafe {
+        mun_runtime_poll_reload_event(driver.runtime, &mut has_event as *mut _, ptr::null_mut())
+    };
+
+    let message = unsafe { CStr::from_ptr(mun_error_message(handle)) };
+    assert_eq!(
+        message.to_str().unwrap(),
+        "Invalid argument: 'event' is null pointer."
+    );
+
+    unsafe { mun_destroy_string(message.as_ptr()) };
+}
+
+#[test]
+fn test_runtime_poll_reload_event() {
+    let driver = TestDriver::new(
+        r#"
+        pub fn main() -> i32 { 3 }
+    "#,
+    );
+
+    let mut has_event = true;
+    let mut event = MaybeUninit::uninit();
+    let handle = unsafe {
+        mun_runtime_poll_reload_event(driver.runtime, &mut has_event as *mut _, event.as_mut_ptr())
+    };
+    assert_eq!(handle.token(), 0);
+    assert!(!has_event);
+}
+
+/// Collects the names of the changed functions of a reload `event`, into the `Vec<Vec<String>>`
+/// that `user_data` points to.
+unsafe extern "C" fn collect_changed_functions(event: *const ReloadEvent, user_data: *mut c_void) {
+    let event = &*event;
+    let changed_functions = &mut *(user_data as *mut Vec<Vec<String>>);
+    changed_functions.push(
+        std::slice::from_raw_parts(event.changed_functions, event.num_changed_functions)
+            .iter()
+            .map(|name| CStr::from_ptr(*name).to_str().unwrap().to_owned())
+            .collect(),
+    );
+}
+
+#[test]
+fn test_runtime_update_reload() {
+    let mut changed_functions: Vec<Vec<String>> = Vec::new();
+    let mut driver = TestDriver::with_options(
+        r#"
+        pub fn main() -> i32 { 3 }
+    "#,
+        RuntimeOptions {
+            reload_fn: Some(collect_changed_functions),
+            reload_user_data: &mut changed_functions as *mut _ as *mut c_void,
+            ..RuntimeOptions::default()
+        },
+    );
+
+    driver.update(
+        r#"
+        pub fn main() -> i32 { 5 }
+        pub fn add(a: i32, b: i32) -> i32 { a + b }
+    "#,
+    );
+    assert_eq!(changed_functions, [["add", "main"]]);
+
+    let mut has_event = false;
+    let mut event = MaybeUninit::uninit();
+    let handle = unsafe {
+        mun_runtime_poll_reload_event(driver.runtime, &mut has_event as *mut _, event.as_mut_ptr())
+    };
+    assert_eq!(handle.token(), 0);
+    assert!(has_event);
+
+    let event = unsafe { event.assume_init() };
+    assert_eq!(event.num_assemblies, 1);
+    assert_eq!(event.num_changed_functions, 2);
+    unsafe { mun_reload_event_destroy(event) };
+
+    let mut event = MaybeUninit::uninit();
+    let handle = unsafe {
+        mun_runtime_poll_reload_event(driver.runtime, &mut has_event as *mut _, event.as_mut_ptr())
+    };
+    assert_eq!(handle.token(), 0);
+    assert!(!has_event);
+}
+
+#[test]
+fn test_reload_event() {
+    let event = ReloadEvent::new(&runtime::ReloadEvent {
+        assemblies: vec!["/target/mod.munlib".into()],
+        changed_functions: vec![String::from("foo_new"), String::from("main")],
+        migrated_types: vec![String::from("Foo")],
+    });
+
+    let strings = |strings: *const *const c_char, len: usize| -> Vec<&str> {
+        unsafe { std::slice::from_raw_parts(strings, len) }
+            .iter()
+            .map(|string| unsafe { CStr::from_ptr(*string) }.to_str().unwrap())
+            .collect()
+    };
+    assert_eq!(
+        strings(event.assemblies, event.num_assemblies),
+        ["/target/mod.munlib"]
+    );
+    assert_eq!(
+        strings(event.changed_functions, event.num_changed_functions),
+        ["foo_new", "main"]
+    );
+    assert_eq!(
+        strings(event.migrated_types, event.num_migrated_types),
+        ["Foo"]
+    );
+
+    unsafe { mun_reload_event_destroy(event) };
+}
@@ -0,0 +1,462 @@
+//! Exposes type lookup and type metadata queries.
+
+use crate::{adt::FieldInfo, ErrorHandle, RuntimeHandle, HUB};
+use anyhow::anyhow;
+use std::{ffi::CStr, os::raw::c_char, ptr};
+
+/// An array of the type information of all types in a runtime.
+///
+/// The array must be manually destructed using [`mun_type_info_array_destroy`].
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct TypeInfoArray {
+    /// Pointer to the type information
+    pub type_infos: *const *const abi::TypeInfo,
+    /// The number of elements in the [`type_infos`] array
+    pub num_type_infos: usize,
+}
+
+/// An array of the definitions of all functions in a runtime.
+///
+/// The array must be manually destructed using [`mun_function_definition_array_destroy`].
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct FunctionDefinitionArray {
+    /// Pointer to the function definitions
+    pub definitions: *const abi::FunctionDefinition,
+    /// The number of elements in the [`definitions`] array
+    pub num_definitions: usize,
+}
+
+/// Moves `values` to the heap, returning a pointer to the first element and the number of
+/// elements. The memory must be released using [`destroy_array`].
+pub(crate) fn into_raw_array<T>(values: Vec<T>) -> (*const T, usize) {
+    let values = values.into_boxed_slice();
+    let len = values.len();
+    (Box::into_raw(values) as *const T, len)
+}
+
+/// Releases an array that was created using [`into_raw_array`].
+///
+/// # Safety
+///
+/// `data` and `len` must have been returned by [`into_raw_array`].
+pub(crate) unsafe fn destroy_array<T>(data: *const T, len: usize) {
+    if !data.is_null() {
+        let _values = Box::from_raw(ptr::slice_from_raw_parts_mut(data as *mut T, len));
+    }
+}
+
+/// Retrieves the type information corresponding to `type_name` from the runtime corresponding to
+/// `handle`. If successful, `has_type_info` and `type_info` are set, otherwise a non-zero error
+/// handle is returned.
+///
+/// The type information remains valid until the runtime is updated.
+///
+/// If a non-zero error handle is returned, it must be manually destructed using
+/// [`mun_error_destroy`].
+///
+/// # Safety
+///
+/// This function receives raw pointers as parameters. If any of the arguments is a null pointer,
+/// an error will be returned. Passing pointers to invalid data, will lead to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_runtime_get_type_info_by_name(
+    handle: RuntimeHandle,
+    type_name: *const c_char,
+    has_type_info: *mut bool,
+    type_info: *mut *const abi::TypeInfo,
+) -> ErrorHandle {
+    let runtime = match handle.runtime() {
+        Some(runtime) => runtime,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'runtime' is null pointer."))
+        }
+    };
+
+    if type_name.is_null() {
+        return HUB
+            .errors
+            .register(anyhow!("Invalid argument: 'type_name' is null pointer."));
+    }
+
+    let type_name = match CStr::from_ptr(type_name).to_str() {
+        Ok(name) => name,
+        Err(_) => {
+            return HUB.errors.register(anyhow!(
+                "Invalid argument: 'type_name' is not UTF-8 encoded."
+            ))
+        }
+    };
+
+    let has_type_info = match has_type_info.as_mut() {
+        Some(has_type_info) => has_type_info,
+        None => {
+            return HUB.errors.register(anyhow!(
+                "Invalid argument: 'has_type_info' is null pointer."
+            ))
+        }
+    };
+
+    let type_info = match type_info.as_mut() {
+        Some(type_info) => type_info,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'type_info' is null pointer."))
+        }
+    };
+
+    match runtime.get_type_info(type_name) {
+        Some(info) => {
+            *has_type_info = true;
+            *type_info = info;
+        }
+        None => *has_type_info = false,
+    }
+
+    ErrorHandle::default()
+}
+
+/// Retrieves the type information of all types in the runtime corresponding to `handle`. If
+/// successful, `type_infos` is set, otherwise a non-zero error handle is returned.
+///
+/// The type information remains valid until the runtime is updated. The array must be manually
+/// destructed using [`mun_type_info_array_destroy`].
+///
+/// If a non-zero error handle is returned, it must be manually destructed using
+/// [`mun_error_destroy`].
+///
+/// # Safety
+///
+/// This function receives raw pointers as parameters. If any of the arguments is a null pointer,
+/// an error will be returned. Passing pointers to invalid data, will lead to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_runtime_get_type_infos(
+    handle: RuntimeHandle,
+    type_infos: *mut TypeInfoArray,
+) -> ErrorHandle {
+    let runtime = match handle.runtime() {
+        Some(runtime) => runtime,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'runtime' is null pointer."))
+        }
+    };
+
+    let type_infos = match type_infos.as_mut() {
+        Some(type_infos) => type_infos,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'type_infos' is null pointer."))
+        }
+    };
+
+    let (data, len) = into_raw_array(
+        runtime
+            .type_infos()
+            .map(|type_info| type_info as *const abi::TypeInfo)
+            .collect(),
+    );
+    *type_infos = TypeInfoArray {
+        type_infos: data,
+        num_type_infos: len,
+    };
+    ErrorHandle::default()
+}
+
+/// Deallocates an array of type information that was allocated by the runtime.
+///
+/// # Safety
+///
+/// Only when the `type_infos` pointer of the array is not a null pointer, its content will be
+/// deallocated. Passing arrays that were not returned by [`mun_runtime_get_type_infos`], will lead
+/// to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_type_info_array_destroy(array: TypeInfoArray) {
+    destroy_array(array.type_infos, array.num_type_infos);
+}
+
+/// Retrieves the definitions of all functions in the runtime corresponding to `handle`. If
+/// successful, `definitions` is set, otherwise a non-zero error handle is returned.
+///
+/// The names and signatures of the functions remain valid until the runtime is updated. The array
+/// must be manually destructed using [`mun_function_definition_array_destroy`].
+///
+/// If a non-zero error handle is returned, it must be manually destructed using
+/// [`mun_error_destroy`].
+///
+/// # Safety
+///
+/// This function receives raw pointers as parameters. If any of the arguments is a null pointer,
+/// an error will be returned. Passing pointers to invalid data, will lead to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_runtime_get_function_definitions(
+    handle: RuntimeHandle,
+    definitions: *mut FunctionDefinitionArray,
+) -> ErrorHandle {
+    let runtime = match handle.runtime() {
+        Some(runtime) => runtime,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'runtime' is null pointer."))
+        }
+    };
+
+    let definitions = match definitions.as_mut() {
+        Some(definitions) => definitions,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'definitions' is null pointer."))
+        }
+    };
+
+    let (data, len) = into_raw_array(runtime.function_definitions().cloned().collect());
+    *definitions = FunctionDefinitionArray {
+        definitions: data,
+        num_definitions: len,
+    };
+    ErrorHandle::default()
+}
+
+/// Deallocates an array of function definitions that was allocated by the runtime.
+///
+/// # Safety
+///
+/// Only when the `definitions` pointer of the array is not a null pointer, its content will be
+/// deallocated. Passing arrays that were not returned by [`mun_runtime_get_function_definitions`],
+/// will lead to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_function_definition_array_destroy(array: FunctionDefinitionArray) {
+    destroy_array(array.definitions, array.num_definitions);
+}
+
+/// Retrieves the size in bytes of the type corresponding to `type_info`. If successful, `size` is
+/// set, otherwise a non-zero error handle is returned.
+///
+/// If a non-zero error handle is returned, it must be manually destructed using
+/// [`mun_error_destroy`].
+///
+/// # Safety
+///
+/// This function receives raw pointers as parameters. If any of the arguments is a null pointer,
+/// an error will be returned. Passing pointers to invalid data, will lead to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_type_info_size(
+    type_info: *const abi::TypeInfo,
+    size: *mut usize,
+) -> ErrorHandle {
+    let type_info = match type_info.as_ref() {
+        Some(type_info) => type_info,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'type_info' is null pointer."))
+        }
+    };
+
+    let size = match size.as_mut() {
+        Some(size) => size,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'size' is null pointer."))
+        }
+    };
+
+    *size = type_info.size_in_bytes();
+    ErrorHandle::default()
+}
+
+/// Retrieves the alignment in bytes of the type corresponding to `type_info`. If successful,
+/// `alignment` is set, otherwise a non-zero error handle is returned.
+///
+/// If a non-zero error handle is returned, it must be manually destructed using
+/// [`mun_error_destroy`].
+///
+/// # Safety
+///
+/// This function receives raw pointers as parameters. If any of the arguments is a null pointer,
+/// an error will be returned. Passing pointers to invalid data, will lead to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_type_info_alignment(
+    type_info: *const abi::TypeInfo,
+    alignment: *mut usize,
+) -> ErrorHandle {
+    let type_info = match type_info.as_ref() {
+        Some(type_info) => type_info,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'type_info' is null pointer."))
+        }
+    };
+
+    let alignment = match alignment.as_mut() {
+        Some(alignment) => alignment,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'alignment' is null pointer."))
+        }
+    };
+
+    *alignment = type_info.alignment();
+    ErrorHandle::default()
+}
+
+/// Retrieves the memory kind of the struct corresponding to `type_info`. If successful,
+/// `has_memory_kind` and - if the type is a struct - `memory_kind` are set, otherwise a non-zero
+/// error handle is returned.
+///
+/// If a non-zero error handle is returned, it must be manually destructed using
+/// [`mun_error_destroy`].
+///
+/// # Safety
+///
+/// This function receives raw pointers as parameters. If any of the arguments is a null pointer,
+/// an error will be returned. Passing pointers to invalid data, will lead to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_type_info_memory_kind(
+    type_info: *const abi::TypeInfo,
+    has_memory_kind: *mut bool,
+    memory_kind: *mut abi::StructMemoryKind,
+) -> ErrorHandle {
+    let type_info = match type_info.as_ref() {
+        Some(type_info) => type_info,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'type_info' is null pointer."))
+        }
+    };
+
+    let has_memory_kind = match has_memory_kind.as_mut() {
+        Some(has_memory_kind) => has_memory_kind,
+        None => {
+            return HUB.errors.register(anyhow!(
+                "Invalid argument: 'has_memory_kind' is null pointer."
+            ))
+        }
+    };
+
+    let memory_kind = match memory_kind.as_mut() {
+        Some(memory_kind) => memory_kind,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'memory_kind' is null pointer."))
+        }
+    };
+
+    match type_info.as_struct() {
+        Some(struct_info) => {
+            *has_memory_kind = true;
+            *memory_kind = struct_info.memory_kind.clone();
+        }
+        None => *has_memory_kind = false,
+    }
+
+    ErrorHandle::default()
+}
+
+/// Retrieves the number of fields of the type corresponding to `type_info`. If successful,
+/// `num_fields` is set, otherwise a non-zero error handle is returned. Types that are not structs
+/// have no fields.
+///
+/// Together with [`mun_type_info_field_info`] this can be used to iterate over the fields of a
+/// type.
+///
+/// If a non-zero error handle is returned, it must be manually destructed using
+/// [`mun_error_destroy`].
+///
+/// # Safety
+///
+/// This function receives raw pointers as parameters. If any of the arguments is a null pointer,
+/// an error will be returned. Passing pointers to invalid data, will lead to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_type_info_num_fields(
+    type_info: *const abi::TypeInfo,
+    num_fields: *mut u16,
+) -> ErrorHandle {
+    let type_info = match type_info.as_ref() {
+        Some(type_info) => type_info,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'type_info' is null pointer."))
+        }
+    };
+
+    let num_fields = match num_fields.as_mut() {
+        Some(num_fields) => num_fields,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'num_fields' is null pointer."))
+        }
+    };
+
+    // The ABI stores the number of fields as a `u16`
+    *num_fields = type_info
+        .as_struct()
+        .map_or(0, |struct_info| struct_info.num_fields() as u16);
+    ErrorHandle::default()
+}
+
+/// Retrieves information about the field at `field_idx` of the struct corresponding to
+/// `type_info`. If successful, `field_info` is set, otherwise a non-zero error handle is returned.
+///
+/// If a non-zero error handle is returned, it must be manually destructed using
+/// [`mun_error_destroy`].
+///
+/// # Safety
+///
+/// This function receives raw pointers as parameters. If any of the arguments is a null pointer,
+/// an error will be returned. Passing pointers to invalid data, will lead to undefined behavior.
+#[no_mangle]
+pub unsafe extern "C" fn mun_type_info_field_info(
+    type_info: *const abi::TypeInfo,
+    field_idx: u16,
+    field_info: *mut FieldInfo,
+) -> ErrorHandle {
+    let type_info = match type_info.as_ref() {
+        Some(type_info) => type_info,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'type_info' is null pointer."))
+        }
+    };
+
+    let field_info = match field_info.as_mut() {
+        Some(field_info) => field_info,
+        None => {
+            return HUB
+                .errors
+                .register(anyhow!("Invalid argument: 'field_info' is null pointer."))
+        }
+    };
+
+    let struct_info = match type_info.as_struct() {
+        Some(struct_info) => struct_info,
+        None => {
+            return HUB.errors.register(anyhow!(
+                "Invalid argument: 'type_info' is not a struct, but a `{}`.",
+                type_info.name()
+            ))
+        }
+    };
+
+    *field_info = match FieldInfo::new(type_info, struct_info, field_idx) {
+        Ok(info) => info,
+        Err(e) => return HUB.errors.register(e),
+    };
+    ErrorHandle::default()
+}
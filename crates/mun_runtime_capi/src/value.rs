@@ -0,0 +1,163 @@
+//! Exposes dynamically typed Mun values.
+
+use anyhow::anyhow;
+use memory::gc::GcPtr;
+use runtime::{Marshal, RawStruct, Runtime, StructRef};
+use std::convert::TryFrom;
+
+/// The type of a [`Value`].
+///
+/// cbindgen:prefix-with-name
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValueKind {
+    /// An `i8` value
+    I8,
+    /// An `i16` value
+    I16,
+    /// An `i32` value
+    I32,
+    /// An `i64` value
+    I64,
+    /// A `u8` value
+    U8,
+    /// A `u16` value
+    U16,
+    /// A `u32` value
+    U32,
+    /// A `u64` value
+    U64,
+    /// An `f32` value
+    F32,
+    /// An `f64` value
+    F64,
+    /// A `bool` value
+    Bool,
+    /// A handle to a `value` or `gc` struct
+    Struct,
+}
+
+impl TryFrom<u8> for ValueKind {
+    type Error = anyhow::Error;
+
+    fn try_from(kind: u8) -> Result<Self, Self::Error> {
+        let kind = match kind {
+            0 => ValueKind::I8,
+            1 => ValueKind::I16,
+            2 => ValueKind::I32,
+            3 => ValueKind::I64,
+            4 => ValueKind::U8,
+            5 => ValueKind::U16,
+            6 => ValueKind::U32,
+            7 => ValueKind::U64,
+            8 => ValueKind::F32,
+            9 => ValueKind::F64,
+            10 => ValueKind::Bool,
+            11 => ValueKind::Struct,
+            _ => {
+                return Err(anyhow!(
+                    "Invalid argument: '{}' is not a valid value kind.",
+                    kind
+                ))
+            }
+        };
+        Ok(kind)
+    }
+}
+
+/// The data of a [`Value`]. Which field is valid depends on the [`ValueKind`] of the value.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(missing_docs)]
+pub union ValueData {
+    pub i8: i8,
+    pub i16: i16,
+    pub i32: i32,
+    pub i64: i64,
+    pub u8: u8,
+    pub u16: u16,
+    pub u32: u32,
+    pub u64: u64,
+    pub f32: f32,
+    pub f64: f64,
+    pub boolean: bool,
+    pub gc_ptr: GcPtr,
+}
+
+/// A dynamically typed Mun value. Structs are represented by their garbage collector handle, which
+/// is not rooted.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct Value {
+    /// The type of the value; one of the [`ValueKind`] variants. Values are received from hosts as
+    /// integers, such that invalid kinds can be rejected.
+    pub kind: u8,
+    /// The data of the value
+    pub data: ValueData,
+}
+
+impl Value {
+    /// Constructs a value of the specified kind.
+    pub(crate) fn new(kind: ValueKind, data: ValueData) -> Self {
+        Value {
+            kind: kind as u8,
+            data,
+        }
+    }
+
+    /// Converts the value into a runtime value. Returns an error if the value's kind is invalid.
+    ///
+    /// # Safety
+    ///
+    /// The `data` field that corresponds to the value's `kind` must be initialized.
+    pub(crate) unsafe fn into_runtime(
+        self,
+        runtime: &Runtime,
+    ) -> Result<runtime::Value, anyhow::Error> {
+        Ok(match ValueKind::try_from(self.kind)? {
+            ValueKind::I8 => runtime::Value::I8(self.data.i8),
+            ValueKind::I16 => runtime::Value::I16(self.data.i16),
+            ValueKind::I32 => runtime::Value::I32(self.data.i32),
+            ValueKind::I64 => runtime::Value::I64(self.data.i64),
+            ValueKind::U8 => runtime::Value::U8(self.data.u8),
+            ValueKind::U16 => runtime::Value::U16(self.data.u16),
+            ValueKind::U32 => runtime::Value::U32(self.data.u32),
+            ValueKind::U64 => runtime::Value::U64(self.data.u64),
+            ValueKind::F32 => runtime::Value::F32(self.data.f32),
+            ValueKind::F64 => runtime::Value::F64(self.data.f64),
+            ValueKind::Bool => runtime::Value::Bool(self.data.boolean),
+            ValueKind::Struct => runtime::Value::Struct(StructRef::marshal_from(
+                RawStruct::from(self.data.gc_ptr),
+                runtime,
+            )),
+        })
+    }
+
+    /// Converts a runtime value into a value. Returns an error if the value's type cannot be
+    /// represented.
+    pub(crate) fn from_runtime(value: runtime::Value) -> Result<Self, anyhow::Error> {
+        let (kind, data) = match value {
+            runtime::Value::I8(i8) => (ValueKind::I8, ValueData { i8 }),
+            runtime::Value::I16(i16) => (ValueKind::I16, ValueData { i16 }),
+            runtime::Value::I32(i32) => (ValueKind::I32, ValueData { i32 }),
+            runtime::Value::I64(i64) => (ValueKind::I64, ValueData { i64 }),
+            runtime::Value::U8(u8) => (ValueKind::U8, ValueData { u8 }),
+            runtime::Value::U16(u16) => (ValueKind::U16, ValueData { u16 }),
+            runtime::Value::U32(u32) => (ValueKind::U32, ValueData { u32 }),
+            runtime::Value::U64(u64) => (ValueKind::U64, ValueData { u64 }),
+            runtime::Value::F32(f32) => (ValueKind::F32, ValueData { f32 }),
+            runtime::Value::F64(f64) => (ValueKind::F64, ValueData { f64 }),
+            runtime::Value::Bool(boolean) => (ValueKind::Bool, ValueData { boolean }),
+            runtime::Value::Struct(s) => (
+                ValueKind::Struct,
+                ValueData {
+                    gc_ptr: s.into_raw().handle(),
+                },
+            ),
+            runtime::Value::I128(_) | runtime::Value::U128(_) => {
+                return Err(anyhow!("128-bit integers are not supported by the C API."))
+            }
+        };
+        Ok(Value::new(kind, data))
+    }
+}
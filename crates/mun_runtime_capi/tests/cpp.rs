@@ -0,0 +1,157 @@
+//! Compiles and runs the tests of the C++ wrapper in `cpp/tests` against the runtime C API.
+#![cfg(target_os = "linux")]
+
+use compiler::{Config, Driver, PathOrInline};
+use std::{
+    io::stderr,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Returns the directory that contains the `mun_runtime` shared library. Cargo does not build
+/// `cdylib` targets for integration tests, and building it from within a test would wait on the
+/// lock that the outer build holds, so the library has to be built beforehand using
+/// `cargo build`.
+fn runtime_capi_dir() -> PathBuf {
+    // The test executable is located in `target/<profile>/deps`
+    let target_dir = std::env::current_exe()
+        .unwrap()
+        .parent()
+        .and_then(Path::parent)
+        .unwrap()
+        .to_path_buf();
+
+    let lib_path = target_dir.join("libmun_runtime.so");
+    let lib_modified = std::fs::metadata(&lib_path)
+        .and_then(|metadata| metadata.modified())
+        .unwrap_or_else(|_| {
+            panic!(
+                "`{}` does not exist. Run `cargo build -p mun_runtime_capi` first.",
+                lib_path.display()
+            )
+        });
+
+    // Testing the bindings of the current sources against an outdated library is meaningless
+    let src_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    for entry in std::fs::read_dir(src_dir).unwrap() {
+        let src_modified = entry.unwrap().metadata().unwrap().modified().unwrap();
+        assert!(
+            src_modified <= lib_modified,
+            "`{}` is outdated. Run `cargo build -p mun_runtime_capi` first.",
+            lib_path.display()
+        );
+    }
+
+    target_dir
+}
+
+/// Generates the C bindings of the runtime into `include_dir`, such that the tests are compiled
+/// against the current API rather than the - possibly outdated - bindings in `ffi/include`.
+fn generate_runtime_capi_header(include_dir: &Path) {
+    let bindings = cbindgen::generate(env!("CARGO_MANIFEST_DIR"))
+        .expect("Failed to generate C bindings of the runtime");
+    bindings.write_to_file(include_dir.join("mun/runtime_capi.h"));
+}
+
+/// Compiles the Mun source at `path` into `out_dir`, returning the path of the assembly.
+fn build_assembly(path: &Path, out_dir: &Path) -> PathBuf {
+    let config = Config {
+        out_dir: Some(out_dir.to_path_buf()),
+        ..Config::default()
+    };
+    let (mut driver, file_id) =
+        Driver::with_file(config, PathOrInline::Path(path.to_path_buf())).unwrap();
+    if driver.emit_diagnostics(&mut stderr()).unwrap() {
+        panic!("compiler errors..")
+    }
+    let out_path = driver.assembly_output_path_from_file(file_id);
+    driver.write_all_assemblies(false).unwrap();
+    out_path
+}
+
+/// Compiles all modules of the Mun package described by the manifest at `manifest_path` into a
+/// single assembly in `out_dir`, returning the path of the assembly.
+fn build_package(manifest_path: &Path, out_dir: &Path) -> PathBuf {
+    let config = Config {
+        out_dir: Some(out_dir.to_path_buf()),
+        direct_calls: true,
+        ..Config::default()
+    };
+    let (_, mut driver) = Driver::with_package_path(manifest_path, config).unwrap();
+    if driver.emit_diagnostics(&mut stderr()).unwrap() {
+        panic!("compiler errors..")
+    }
+    driver.write_all_assemblies(false).unwrap();
+    out_dir.join("mod.munlib")
+}
+
+/// Compiles the C++ test at `path` against the runtime C API, with the headers in `include_dir`,
+/// and runs it with the assembly at `assembly_path`.
+fn run_cpp_test(path: &Path, include_dir: &Path, assembly_path: &Path) {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let lib_dir = runtime_capi_dir();
+    generate_runtime_capi_header(include_dir);
+
+    let test_path = include_dir.with_file_name(path.file_stem().unwrap());
+    let status = Command::new(std::env::var("CXX").unwrap_or_else(|_| String::from("c++")))
+        .args(&["-std=c++17", "-Wall", "-Wextra", "-Werror"])
+        .arg("-I")
+        .arg(crate_dir.join("cpp/include"))
+        .arg("-I")
+        .arg(include_dir)
+        .arg(path)
+        .arg("-o")
+        .arg(&test_path)
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-lmun_runtime")
+        .status()
+        .expect("Failed to invoke C++ compiler");
+    assert!(status.success(), "Failed to compile C++ tests");
+
+    let status = Command::new(&test_path)
+        .arg(assembly_path)
+        .status()
+        .unwrap();
+    assert!(status.success(), "C++ tests failed");
+}
+
+#[test]
+fn cpp_runtime() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let assembly_path = build_assembly(&crate_dir.join("cpp/tests/runtime.mun"), temp_dir.path());
+
+    run_cpp_test(
+        &crate_dir.join("cpp/tests/runtime.cpp"),
+        &temp_dir.path().join("include"),
+        &assembly_path,
+    );
+}
+
+#[test]
+fn cpp_bindings() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let assembly_path = build_package(
+        &crate_dir.join("cpp/tests/bindings/mun.toml"),
+        temp_dir.path(),
+    );
+
+    // Generate the bindings of the assembly, which are included as `bindings.h`
+    let include_dir = temp_dir.path().join("include");
+    let bindings = mun_bindgen::Bindings::from_library(&assembly_path).unwrap();
+    std::fs::create_dir_all(&include_dir).unwrap();
+    std::fs::write(
+        include_dir.join("bindings.h"),
+        mun_bindgen::cpp::generate(&bindings),
+    )
+    .unwrap();
+
+    run_cpp_test(
+        &crate_dir.join("cpp/tests/bindings.cpp"),
+        &include_dir,
+        &assembly_path,
+    );
+}
@@ -0,0 +1,264 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    parse_macro_input, punctuated::Punctuated, Data, DeriveInput, Fields, Lit, Meta, NestedMeta,
+    Path, Token,
+};
+
+/// This procedural macro implements the `MunStruct` trait as well as the `Marshal`,
+/// `ArgumentReflection`, and `ReturnTypeReflection` traits for a `#[repr(C)]` struct. This allows
+/// the struct to be marshalled by copy to and from a Mun `value` struct with the same name, field
+/// names, and field types. The memory layout is validated against the Mun struct at runtime.
+///
+/// The generated code refers to the `mun_runtime` crate as `::mun_runtime`. If the crate is
+/// re-exported under a different path, specify it using `#[mun(crate = "path::to::mun_runtime")]`.
+/// A Mun struct that is defined in a submodule is specified by its fully qualified name, using
+/// `#[mun(name = "module::Struct")]`.
+#[proc_macro_derive(MunStruct, attributes(mun))]
+pub fn mun_struct_derive(input: TokenStream) -> TokenStream {
+    // Parse Phase
+    let derive_input = parse_macro_input!(input as DeriveInput);
+
+    match mun_struct_impl(&derive_input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn mun_struct_impl(derive_input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &derive_input.ident;
+
+    if !derive_input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &derive_input.generics,
+            "`MunStruct` cannot be derived for generic structs",
+        ));
+    }
+
+    if !is_repr_c(derive_input) {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "`MunStruct` can only be derived for `#[repr(C)]` structs",
+        ));
+    }
+
+    let attributes = MunAttributes::parse(derive_input)?;
+    let krate = attributes.krate;
+
+    let fields = match &derive_input.data {
+        Data::Struct(struct_data) => match &struct_data.fields {
+            Fields::Named(fields) => fields.named.iter().collect::<Vec<_>>(),
+            Fields::Unit => Vec::new(),
+            Fields::Unnamed(fields) => {
+                return Err(syn::Error::new_spanned(
+                    fields,
+                    "`MunStruct` can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "`MunStruct` can only be derived for structs",
+            ))
+        }
+    };
+
+    let name = attributes.name.unwrap_or_else(|| ident.to_string());
+    let field_names = fields
+        .iter()
+        .map(|f| f.ident.as_ref().unwrap().to_string())
+        .collect::<Vec<_>>();
+
+    // Generate a list of statements that validate each field's type against the Mun struct.
+    //
+    // Expects:
+    // - field_types: &[&abi::TypeInfo]
+    let validate_field_types =
+        fields
+            .iter()
+            .zip(field_names.iter())
+            .enumerate()
+            .map(|(idx, (f, field_name))| {
+                let ty = &f.ty;
+                quote! {
+                    <Self as #krate::MunStruct>::validate_field_type::<#ty>(
+                        #field_name,
+                        field_types[#idx],
+                    )?;
+                }
+            });
+
+    // Generate Phase
+    Ok(quote! {
+        unsafe impl #krate::MunStruct for #ident {
+            const NAME: &'static str = #name;
+            const FIELD_NAMES: &'static [&'static str] = &[ #(#field_names),* ];
+
+            fn validate_field_types(
+                field_types: &[&#krate::abi::TypeInfo],
+            ) -> ::core::result::Result<(), ::std::string::String> {
+                #(#validate_field_types)*
+                Ok(())
+            }
+        }
+
+        impl #krate::ArgumentReflection for #ident {
+            fn type_guid(&self, runtime: &#krate::Runtime) -> #krate::abi::Guid {
+                <Self as #krate::MunStruct>::runtime_type_guid(runtime)
+            }
+
+            fn type_name<'r>(&'r self, _runtime: &'r #krate::Runtime) -> &'r str {
+                <Self as #krate::MunStruct>::NAME
+            }
+        }
+
+        impl #krate::ReturnTypeReflection for #ident {
+            fn type_guid() -> #krate::abi::Guid {
+                <Self as #krate::MunStruct>::static_type_guid()
+            }
+
+            fn type_name() -> &'static str {
+                <Self as #krate::MunStruct>::NAME
+            }
+
+            fn accepts_struct(type_info: &#krate::abi::TypeInfo) -> bool {
+                <Self as #krate::MunStruct>::validate(type_info).is_ok()
+            }
+        }
+
+        impl<'t> #krate::Marshal<'t> for #ident {
+            type MunType = #krate::RawStruct;
+
+            fn marshal_from<'r>(value: Self::MunType, _runtime: &'r #krate::Runtime) -> Self
+            where
+                Self: 't,
+                'r: 't,
+            {
+                // Safety: the struct's memory layout was validated against the Mun struct.
+                unsafe { <Self as #krate::MunStruct>::from_raw(&value) }
+            }
+
+            fn marshal_into(self) -> Self::MunType {
+                panic!(
+                    "`{}` is copied into memory that is managed by the runtime, so it must be marshalled using `Marshal::marshal_into_runtime`.",
+                    <Self as #krate::MunStruct>::NAME
+                )
+            }
+
+            fn marshal_into_runtime(self, runtime: &#krate::Runtime) -> Self::MunType {
+                // An argument is only marshalled after it was validated against the function's
+                // signature, which guarantees that the Mun struct exists.
+                <Self as #krate::MunStruct>::into_raw(self, runtime)
+                    .expect("the Mun struct of a validated argument must exist")
+            }
+
+            fn marshal_from_ptr<'r>(
+                ptr: ::core::ptr::NonNull<Self::MunType>,
+                _runtime: &'r #krate::Runtime,
+                _type_info: Option<&#krate::abi::TypeInfo>,
+            ) -> Self
+            where
+                Self: 't,
+                'r: 't,
+            {
+                // Safety: for a value struct, `ptr` points to a struct value that was validated
+                // against the Mun struct.
+                unsafe { ptr.cast::<Self>().as_ptr().read() }
+            }
+
+            fn marshal_to_ptr(
+                value: Self,
+                ptr: ::core::ptr::NonNull<Self::MunType>,
+                _type_info: Option<&#krate::abi::TypeInfo>,
+            ) {
+                // Safety: for a value struct, `ptr` points to a struct value that was validated
+                // against the Mun struct.
+                unsafe { ptr.cast::<Self>().as_ptr().write(value) };
+            }
+        }
+    })
+}
+
+/// Returns whether the struct has a `#[repr(C)]` attribute, which can be combined with other
+/// representation hints; e.g. `#[repr(C, align(8))]`.
+fn is_repr_c(derive_input: &DeriveInput) -> bool {
+    derive_input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .any(|repr| repr.path().is_ident("C"))
+}
+
+/// The options that are specified using `#[mun(...)]` attributes.
+struct MunAttributes {
+    /// The path to the `mun_runtime` crate, which defaults to `::mun_runtime`
+    krate: Path,
+    /// The fully qualified name of the Mun struct, which defaults to the name of the Rust struct
+    name: Option<String>,
+}
+
+impl MunAttributes {
+    fn parse(derive_input: &DeriveInput) -> syn::Result<Self> {
+        let mut krate = None;
+        let mut name = None;
+        for attr in derive_input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("mun"))
+        {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                meta => return Err(syn::Error::new_spanned(meta, "expected `#[mun(...)]`")),
+            };
+
+            for nested in list.nested.iter() {
+                let name_value = match nested {
+                    NestedMeta::Meta(Meta::NameValue(name_value)) => name_value,
+                    _ => return Err(syn::Error::new_spanned(
+                        nested,
+                        "unknown `mun` attribute, expected `crate = \"...\"` or `name = \"...\"`",
+                    )),
+                };
+
+                let value = match &name_value.lit {
+                    Lit::Str(value) => value,
+                    lit => return Err(syn::Error::new_spanned(lit, "expected a string")),
+                };
+
+                if name_value.path.is_ident("crate") {
+                    if krate.is_some() {
+                        return Err(syn::Error::new_spanned(
+                            name_value,
+                            "duplicate `crate` attribute",
+                        ));
+                    }
+                    krate = Some(value.parse::<Path>()?);
+                } else if name_value.path.is_ident("name") {
+                    if name.is_some() {
+                        return Err(syn::Error::new_spanned(
+                            name_value,
+                            "duplicate `name` attribute",
+                        ));
+                    }
+                    name = Some(value.value());
+                } else {
+                    return Err(syn::Error::new_spanned(
+                        &name_value.path,
+                        "unknown `mun` attribute, expected `crate = \"...\"` or `name = \"...\"`",
+                    ));
+                }
+            }
+        }
+
+        Ok(MunAttributes {
+            krate: krate.unwrap_or_else(|| syn::parse_quote!(::mun_runtime)),
+            name,
+        })
+    }
+}
@@ -55,6 +55,37 @@ impl ast::FunctionDef {
     }
 }
 
+impl ast::Attr {
+    /// Returns the name of the attribute if its path consists of a single identifier, e.g. `foo`
+    /// for `#[foo(bar)]`.
+    pub fn simple_name(&self) -> Option<SmolStr> {
+        let path = self.path()?;
+        if path.qualifier().is_some() {
+            return None;
+        }
+        match path.segment()?.kind()? {
+            PathSegmentKind::Name(name_ref) => Some(name_ref.text().clone()),
+            _ => None,
+        }
+    }
+}
+
+impl ast::TokenTree {
+    /// Returns the identifier if the token tree consists of a single identifier, e.g. `bar` for
+    /// `#[foo(bar)]`.
+    pub fn single_ident(&self) -> Option<SmolStr> {
+        let mut tokens = self
+            .syntax()
+            .children_with_tokens()
+            .filter(|c| !c.kind().is_trivia() && !matches!(c.kind(), T!['('] | T![')']));
+        let ident = tokens.next()?.into_token()?;
+        if ident.kind() != SyntaxKind::IDENT || tokens.next().is_some() {
+            return None;
+        }
+        Some(ident.text().clone())
+    }
+}
+
 fn text_of_first_token(node: &SyntaxNode) -> &SmolStr {
     node.green()
         .children()
@@ -43,6 +43,37 @@ impl ArgList {
     }
 }
 
+// Attr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Attr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Attr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, ATTR)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Attr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl Attr {
+    pub fn path(&self) -> Option<Path> {
+        super::child_opt(self)
+    }
+    pub fn token_tree(&self) -> Option<TokenTree> {
+        super::child_opt(self)
+    }
+}
+
 // BinExpr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -452,6 +483,7 @@ impl AstNode for FunctionDef {
 impl ast::NameOwner for FunctionDef {}
 impl ast::VisibilityOwner for FunctionDef {}
 impl ast::DocCommentsOwner for FunctionDef {}
+impl ast::AttrsOwner for FunctionDef {}
 impl ast::ExternOwner for FunctionDef {}
 impl FunctionDef {
     pub fn param_list(&self) -> Option<ParamList> {
@@ -1103,6 +1135,7 @@ impl AstNode for RecordFieldDef {
 impl ast::NameOwner for RecordFieldDef {}
 impl ast::VisibilityOwner for RecordFieldDef {}
 impl ast::DocCommentsOwner for RecordFieldDef {}
+impl ast::AttrsOwner for RecordFieldDef {}
 impl ast::TypeAscriptionOwner for RecordFieldDef {}
 impl RecordFieldDef {}
 
@@ -1380,12 +1413,38 @@ impl AstNode for StructDef {
 impl ast::NameOwner for StructDef {}
 impl ast::VisibilityOwner for StructDef {}
 impl ast::DocCommentsOwner for StructDef {}
+impl ast::AttrsOwner for StructDef {}
+impl ast::ExternOwner for StructDef {}
 impl StructDef {
     pub fn memory_type_specifier(&self) -> Option<MemoryTypeSpecifier> {
         super::child_opt(self)
     }
 }
 
+// TokenTree
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TokenTree {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TokenTree {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, TOKEN_TREE)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(TokenTree { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl TokenTree {}
+
 // TupleFieldDef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
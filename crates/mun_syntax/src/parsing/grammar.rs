@@ -1,4 +1,5 @@
 mod adt;
+mod attributes;
 mod declarations;
 mod expressions;
 mod params;
@@ -93,6 +93,7 @@ pub(super) fn tuple_field_def_list(p: &mut Parser) {
 
 fn record_field_def(p: &mut Parser) {
     let m = p.start();
+    attributes::outer_attributes(p);
     opt_visibility(p);
     if p.at(IDENT) {
         name(p);
@@ -0,0 +1,40 @@
+use super::*;
+
+pub(super) fn outer_attributes(p: &mut Parser) {
+    while p.at(T![#]) {
+        attribute(p);
+    }
+}
+
+fn attribute(p: &mut Parser) {
+    assert!(p.at(T![#]));
+    let m = p.start();
+    p.bump(T![#]);
+    if p.expect(T!['[']) {
+        if paths::is_path_start(p) {
+            paths::expr_path(p);
+        } else {
+            p.error("expected an attribute name");
+        }
+        if p.at(T!['(']) {
+            token_tree(p);
+        }
+        p.expect(T![']']);
+    }
+    m.complete(p, ATTR);
+}
+
+fn token_tree(p: &mut Parser) {
+    assert!(p.at(T!['(']));
+    let m = p.start();
+    p.bump(T!['(']);
+    while !p.at(EOF) && !p.at(T![')']) && !p.at(T![']']) {
+        if p.at(T!['(']) {
+            token_tree(p);
+        } else {
+            p.bump_any();
+        }
+    }
+    p.expect(T![')']);
+    m.complete(p, TOKEN_TREE);
+}
@@ -2,7 +2,7 @@ use super::*;
 use crate::{parsing::grammar::paths::is_use_path_start, T};
 
 pub(super) const DECLARATION_RECOVERY_SET: TokenSet =
-    TokenSet::new(&[T![fn], T![pub], T![struct], T![use]]);
+    TokenSet::new(&[T![fn], T![pub], T![struct], T![use], T![#]]);
 
 pub(super) fn mod_contents(p: &mut Parser) {
     while !p.at(EOF) {
@@ -33,6 +33,7 @@ pub(super) fn declaration(p: &mut Parser) {
 }
 
 pub(super) fn maybe_declaration(p: &mut Parser, m: Marker) -> Result<(), Marker> {
+    attributes::outer_attributes(p);
     opt_visibility(p);
 
     let m = match declarations_without_modifiers(p, m) {
@@ -49,6 +50,9 @@ pub(super) fn maybe_declaration(p: &mut Parser, m: Marker) -> Result<(), Marker>
             fn_def(p);
             m.complete(p, FUNCTION_DEF);
         }
+        T![struct] => {
+            adt::struct_def(p, m);
+        }
         _ => return Err(m),
     }
     Ok(())
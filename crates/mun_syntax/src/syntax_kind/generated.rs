@@ -147,6 +147,8 @@ pub enum SyntaxKind {
     USE_TREE,
     USE_TREE_LIST,
     RENAME,
+    ATTR,
+    TOKEN_TREE,
     // Technical kind so that we can cast from u16 safely
     #[doc(hidden)]
     __LAST,
@@ -616,6 +618,8 @@ impl SyntaxKind {
             USE_TREE => &SyntaxInfo { name: "USE_TREE" },
             USE_TREE_LIST => &SyntaxInfo { name: "USE_TREE_LIST" },
             RENAME => &SyntaxInfo { name: "RENAME" },
+            ATTR => &SyntaxInfo { name: "ATTR" },
+            TOKEN_TREE => &SyntaxInfo { name: "TOKEN_TREE" },
             TOMBSTONE => &SyntaxInfo { name: "TOMBSTONE" },
             EOF => &SyntaxInfo { name: "EOF" },
             __LAST => &SyntaxInfo { name: "__LAST" },
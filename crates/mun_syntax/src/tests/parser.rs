@@ -356,6 +356,15 @@ fn extern_fn() {
     )
 }
 
+#[test]
+fn extern_struct() {
+    snapshot_test(
+        r#"
+    pub extern struct Foo;
+    "#,
+    )
+}
+
 #[test]
 fn type_alias_def() {
     snapshot_test(
@@ -411,3 +420,26 @@ fn use_() {
         "#,
     )
 }
+
+#[test]
+fn attributes() {
+    snapshot_test(
+        r#"
+    struct Foo {
+        #[renamed_from(pos)]
+        position: f32,
+        #[renamed_from(a)] pub b: i32,
+    }
+    "#,
+    )
+}
+
+#[test]
+fn function_attributes() {
+    snapshot_test(
+        r#"
+    #[migrate]
+    pub fn foo(old: Bar) -> Foo {}
+    "#,
+    )
+}
@@ -8,7 +8,7 @@ pub fn opts() -> TargetOptions {
     }
 }
 
-fn macos_deployment_target() -> (u32, u32) {
+fn macos_deployment_target(arch: &str) -> (u32, u32) {
     let deployment_target = env::var("MACOSX_DEPLOYMENT_TARGET").ok();
     let version = deployment_target
         .as_ref()
@@ -22,10 +22,12 @@ fn macos_deployment_target() -> (u32, u32) {
                 .ok()
         });
 
-    version.unwrap_or((10, 7))
+    // Apple Silicon is only supported as of macOS 11
+    let default_version = if arch == "arm64" { (11, 0) } else { (10, 7) };
+    version.unwrap_or(default_version)
 }
 
 pub fn macos_llvm_target(arch: &str) -> String {
-    let (major, minor) = macos_deployment_target();
+    let (major, minor) = macos_deployment_target(arch);
     format!("{}-apple-macosx{}.{}.0", arch, major, minor)
 }
@@ -23,3 +23,26 @@ fn data_layout_linux() {
 
     insta::assert_debug_snapshot!(layout);
 }
+
+#[test]
+fn data_layout_aarch64_darwin() {
+    let layout = TargetDataLayout::parse(&Target::search("aarch64-apple-darwin").unwrap()).unwrap();
+
+    insta::assert_debug_snapshot!(layout);
+}
+
+#[test]
+fn data_layout_aarch64_linux() {
+    let layout =
+        TargetDataLayout::parse(&Target::search("aarch64-unknown-linux-gnu").unwrap()).unwrap();
+
+    insta::assert_debug_snapshot!(layout);
+}
+
+#[test]
+fn data_layout_wasm32() {
+    let layout =
+        TargetDataLayout::parse(&Target::search("wasm32-unknown-unknown").unwrap()).unwrap();
+
+    insta::assert_debug_snapshot!(layout);
+}
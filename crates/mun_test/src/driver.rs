@@ -1,5 +1,5 @@
 use compiler::{Config, DisplayColor, Driver, PathOrInline, RelativePathBuf};
-use runtime::{Runtime, RuntimeBuilder};
+use runtime::{ReloadError, Runtime, RuntimeBuilder};
 use std::{
     cell::{Ref, RefCell},
     io::Cursor,
@@ -27,12 +27,18 @@ impl CompileTestDriver {
     ///    mod.mun
     /// ```
     pub fn from_fixture(text: &str) -> Self {
+        Self::from_fixture_with_config(text, |config| config)
+    }
+
+    /// Constructs a new `CompilerTestDriver` from a fixture that describes an entire mun project
+    /// and a `config_fn` that allows modification of the compiler's [`Config`].
+    pub fn from_fixture_with_config(text: &str, config_fn: impl FnOnce(Config) -> Config) -> Self {
         let temp_output_dir = tempfile::TempDir::new().unwrap();
-        let config = Config {
+        let config = config_fn(Config {
             out_dir: Some(temp_output_dir.path().to_path_buf()),
             display_color: DisplayColor::Disable,
             ..Config::default()
-        };
+        });
 
         // Write the contents of the fixture to a temporary directory
         let temp_source_dir = tempfile::TempDir::new().unwrap();
@@ -70,12 +76,18 @@ impl CompileTestDriver {
 
     /// Constructs a new `CompileTestDriver` from a single Mun source.
     pub fn from_file(text: &str) -> Self {
+        Self::from_file_with_config(text, |config| config)
+    }
+
+    /// Constructs a new `CompileTestDriver` from a single Mun source and a `config_fn` that allows
+    /// modification of the compiler's [`Config`].
+    pub fn from_file_with_config(text: &str, config_fn: impl FnOnce(Config) -> Config) -> Self {
         let temp_dir = tempfile::TempDir::new().unwrap();
-        let config = Config {
+        let config = config_fn(Config {
             out_dir: Some(temp_dir.path().to_path_buf()),
             display_color: DisplayColor::Disable,
             ..Config::default()
-        };
+        });
         let input = PathOrInline::Inline {
             rel_path: RelativePathBuf::from("mod.mun"),
             contents: text.to_owned(),
@@ -182,6 +194,21 @@ impl CompileAndRunTestDriver {
         Ok(Self { driver, runtime })
     }
 
+    /// Constructs a `CompileAndRunTestDriver` from a single Mun source file, a `compiler_config_fn`
+    /// that allows modification of the compiler's [`Config`], and a `config_fn` that allows
+    /// modification of a [`RuntimeBuilder`].
+    pub fn with_compiler_config(
+        text: &str,
+        compiler_config_fn: impl FnOnce(Config) -> Config,
+        config_fn: impl FnOnce(RuntimeBuilder) -> RuntimeBuilder,
+    ) -> Result<Self, anyhow::Error> {
+        let driver = CompileTestDriver::from_file_with_config(text, compiler_config_fn);
+        let builder = RuntimeBuilder::new(driver.lib_path());
+        let runtime = config_fn(builder).spawn()?;
+
+        Ok(Self { driver, runtime })
+    }
+
     /// Updates the text of the Mun source and ensures that the generated assembly has been
     /// reloaded.
     ///
@@ -208,6 +235,29 @@ impl CompileAndRunTestDriver {
         }
     }
 
+    /// Updates the text of the Mun source and waits for the runtime to try to reload the generated
+    /// assembly, returning the result of the attempt.
+    pub fn try_update(
+        &mut self,
+        runtime: Ref<'_, Runtime>,
+        path: impl AsRef<paths::RelativePath>,
+        text: &str,
+    ) -> Result<(), ReloadError> {
+        self.driver.update(path, text);
+
+        let start_time = Instant::now();
+        drop(runtime);
+        while !self.runtime().borrow_mut().try_update()? {
+            let now = Instant::now();
+            if now - start_time > Duration::from_secs(10) {
+                panic!("runtime did not try to update after recompilation within 10 seconds");
+            } else {
+                sleep(Duration::from_millis(1));
+            }
+        }
+        Ok(())
+    }
+
     /// Returns the `Runtime` used by the driver.
     pub fn runtime(&self) -> Rc<RefCell<Runtime>> {
         self.runtime.clone()
@@ -0,0 +1,182 @@
+//! Functionality for loading Mun assemblies that were compiled for the `wasm32-unknown-unknown`
+//! target.
+//!
+//! A WebAssembly munlib cannot be loaded by the native runtime. Instead, a host instantiates the
+//! module with a WebAssembly engine of its choice (e.g. `wasmtime`) and exposes the instance
+//! through the [`WasmInstance`] trait. [`WasmAssembly::load`] then calls the module's `get_info`
+//! function and reads the resulting ABI structs from the module's linear memory, in which all
+//! pointers are 32-bit offsets.
+//!
+//! Function pointers in a WebAssembly module are indices into its exported
+//! `__indirect_function_table`. These are the values stored in [`FunctionDefinition::fn_index`]
+//! and in the slots of the dispatch table.
+mod memory;
+
+use memory::MemoryReader;
+use std::collections::HashMap;
+
+/// The size of a WebAssembly page in bytes
+pub const WASM_PAGE_SIZE: u32 = 65536;
+
+/// An instantiated WebAssembly module that contains a Mun assembly.
+pub trait WasmInstance {
+    /// Reads `buf.len()` bytes from linear memory, starting at `offset`.
+    fn read_memory(&self, offset: u32, buf: &mut [u8]) -> Result<(), anyhow::Error>;
+
+    /// Writes `buf` to linear memory, starting at `offset`.
+    fn write_memory(&mut self, offset: u32, buf: &[u8]) -> Result<(), anyhow::Error>;
+
+    /// Grows linear memory by the specified number of pages and returns the previous size in
+    /// pages.
+    fn grow_memory(&mut self, pages: u32) -> Result<u32, anyhow::Error>;
+
+    /// Calls the exported function with the specified name and `i32` arguments, returning its
+    /// `i32` result, if any.
+    fn call_export(&mut self, name: &str, args: &[u32]) -> Result<Option<u32>, anyhow::Error>;
+}
+
+/// A reference to a type defined in an assembly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeRef {
+    /// Type GUID
+    pub guid: abi::Guid,
+    /// Type name
+    pub name: String,
+}
+
+/// A function prototype: the name and type signature of a function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionPrototype {
+    /// Function name
+    pub name: String,
+    /// Argument types
+    pub arg_types: Vec<TypeRef>,
+    /// Optional return type
+    pub return_type: Option<TypeRef>,
+}
+
+/// A function that is defined in an assembly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionDefinition {
+    /// Function prototype
+    pub prototype: FunctionPrototype,
+    /// Index of the function in the module's function table
+    pub fn_index: u32,
+}
+
+/// An entry of an assembly's dispatch table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DispatchTableEntry {
+    /// Function prototype
+    pub prototype: FunctionPrototype,
+    /// Address in linear memory that holds the function table index of the linked function
+    pub slot: u32,
+}
+
+impl DispatchTableEntry {
+    /// Returns the function table index that is currently stored in this entry's slot.
+    pub fn fn_index(&self, instance: &impl WasmInstance) -> Result<u32, anyhow::Error> {
+        MemoryReader::new(instance).read_u32(self.slot)
+    }
+}
+
+/// The symbols of a Mun assembly that was compiled to WebAssembly.
+#[derive(Clone, Debug)]
+pub struct WasmAssembly {
+    functions: Vec<FunctionDefinition>,
+    types: Vec<TypeRef>,
+    dispatch_table: Vec<DispatchTableEntry>,
+    dependencies: Vec<String>,
+}
+
+impl WasmAssembly {
+    /// Verifies the ABI version of the module in `instance` and reads its assembly info.
+    ///
+    /// Every call grows the linear memory of `instance` by one page, which is never released.
+    /// Linear memory is capped at 65536 pages - or the maximum that the module declares - so once
+    /// the cap is reached, loading fails. Hot reloading an assembly instantiates its new module,
+    /// so loading once per instance stays far below the cap.
+    pub fn load(instance: &mut impl WasmInstance) -> Result<Self, anyhow::Error> {
+        let version = instance
+            .call_export(abi::GET_VERSION_FN_NAME, &[])?
+            .ok_or_else(|| {
+                anyhow::anyhow!("`{}` did not return a value", abi::GET_VERSION_FN_NAME)
+            })?;
+        if abi::ABI_VERSION != version {
+            return Err(anyhow::anyhow!(
+                "ABI version mismatch. munlib is `{}` but runtime is `{}`",
+                version,
+                abi::ABI_VERSION
+            ));
+        }
+
+        // `get_info` returns the `AssemblyInfo` through a pointer. A newly allocated page is
+        // guaranteed not to overlap with the module's data or stack.
+        let info_ptr = instance
+            .grow_memory(1)?
+            .checked_mul(WASM_PAGE_SIZE)
+            .ok_or_else(|| anyhow::anyhow!("linear memory is exhausted"))?;
+        instance.call_export(abi::GET_INFO_FN_NAME, &[info_ptr])?;
+
+        MemoryReader::new(instance).read_assembly_info(info_ptr)
+    }
+
+    /// Returns the functions that are defined in the assembly.
+    pub fn functions(&self) -> &[FunctionDefinition] {
+        &self.functions
+    }
+
+    /// Returns the function with the specified name, if it exists.
+    pub fn get_function(&self, name: &str) -> Option<&FunctionDefinition> {
+        self.functions.iter().find(|f| f.prototype.name == name)
+    }
+
+    /// Returns the types that are defined in the assembly.
+    pub fn types(&self) -> &[TypeRef] {
+        &self.types
+    }
+
+    /// Returns the entries of the assembly's dispatch table.
+    pub fn dispatch_table(&self) -> &[DispatchTableEntry] {
+        &self.dispatch_table
+    }
+
+    /// Returns the paths of the assemblies this assembly depends on.
+    pub fn dependencies(&self) -> impl Iterator<Item = &str> {
+        self.dependencies.iter().map(String::as_str)
+    }
+
+    /// Links the dispatch table by storing the function table index of every entry in its slot.
+    /// Entries are resolved from the assembly's own functions first and from `host_functions`
+    /// second; the latter maps function names to indices of functions that the host added to the
+    /// module's function table.
+    pub fn link(
+        &self,
+        instance: &mut impl WasmInstance,
+        host_functions: &HashMap<String, u32>,
+    ) -> Result<(), anyhow::Error> {
+        let mut missing = Vec::new();
+        for entry in self.dispatch_table.iter() {
+            let name = entry.prototype.name.as_str();
+            let fn_index = self
+                .get_function(name)
+                .map(|f| f.fn_index)
+                .or_else(|| host_functions.get(name).copied());
+
+            match fn_index {
+                Some(fn_index) => instance.write_memory(entry.slot, &fn_index.to_le_bytes())?,
+                None if entry.fn_index(instance)? == 0 => missing.push(name),
+                None => (),
+            }
+        }
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "Failed to link: functions are missing: {}",
+                missing.join(", ")
+            ))
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{
+    DispatchTableEntry, FunctionDefinition, FunctionPrototype, TypeRef, WasmAssembly, WasmInstance,
+};
+
+/// The size of a pointer on `wasm32`
+const POINTER_SIZE: u32 = 4;
+
+/// The size of `abi::FunctionPrototype` on `wasm32`: a name pointer, followed by a signature that
+/// consists of two pointers and a (padded) `u16`.
+const FUNCTION_PROTOTYPE_SIZE: u32 = 16;
+
+/// The size of `abi::FunctionDefinition` on `wasm32`: a prototype followed by a function pointer.
+const FUNCTION_DEFINITION_SIZE: u32 = FUNCTION_PROTOTYPE_SIZE + POINTER_SIZE;
+
+/// The maximum length of a string that is read from linear memory
+const MAX_STRING_LENGTH: usize = 4096;
+
+/// Reads the `wasm32` layout of the Mun ABI from the linear memory of a [`WasmInstance`].
+pub(crate) struct MemoryReader<'a, I: WasmInstance + ?Sized> {
+    instance: &'a I,
+}
+
+impl<'a, I: WasmInstance + ?Sized> MemoryReader<'a, I> {
+    pub fn new(instance: &'a I) -> Self {
+        Self { instance }
+    }
+
+    pub fn read_u32(&self, offset: u32) -> Result<u32, anyhow::Error> {
+        let mut buf = [0u8; 4];
+        self.instance.read_memory(offset, &mut buf)?;
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn read_u16(&self, offset: u32) -> Result<u16, anyhow::Error> {
+        let mut buf = [0u8; 2];
+        self.instance.read_memory(offset, &mut buf)?;
+        Ok(u16::from_le_bytes(buf))
+    }
+
+    fn read_guid(&self, offset: u32) -> Result<abi::Guid, anyhow::Error> {
+        let mut buf = [0u8; 16];
+        self.instance.read_memory(offset, &mut buf)?;
+        Ok(abi::Guid(buf))
+    }
+
+    /// Reads a nul-terminated UTF-8 string.
+    fn read_str(&self, offset: u32) -> Result<String, anyhow::Error> {
+        if offset == 0 {
+            return Err(anyhow::anyhow!("encountered a null string pointer"));
+        }
+
+        let mut bytes = Vec::new();
+        let mut byte = [0u8; 1];
+        loop {
+            let address = offset
+                .checked_add(bytes.len() as u32)
+                .ok_or_else(|| anyhow::anyhow!("string at {:#x} is not terminated", offset))?;
+            self.instance.read_memory(address, &mut byte)?;
+            if byte[0] == 0 {
+                break;
+            }
+            if bytes.len() == MAX_STRING_LENGTH {
+                return Err(anyhow::anyhow!("string at {:#x} is too long", offset));
+            }
+            bytes.push(byte[0]);
+        }
+
+        String::from_utf8(bytes).map_err(Into::into)
+    }
+
+    /// Reads an array of `len` pointers that starts at `offset`.
+    fn read_pointers(&self, offset: u32, len: u32) -> Result<Vec<u32>, anyhow::Error> {
+        (0..len)
+            .map(|idx| self.read_u32(offset + idx * POINTER_SIZE))
+            .collect()
+    }
+
+    /// Reads the GUID and name of an `abi::TypeInfo`.
+    fn read_type_ref(&self, offset: u32) -> Result<TypeRef, anyhow::Error> {
+        Ok(TypeRef {
+            guid: self.read_guid(offset)?,
+            name: self.read_str(self.read_u32(offset + 16)?)?,
+        })
+    }
+
+    fn read_function_prototype(&self, offset: u32) -> Result<FunctionPrototype, anyhow::Error> {
+        let name = self.read_str(self.read_u32(offset)?)?;
+        let arg_types_ptr = self.read_u32(offset + 4)?;
+        let return_type_ptr = self.read_u32(offset + 8)?;
+        let num_arg_types = self.read_u16(offset + 12)?;
+
+        let arg_types = self
+            .read_pointers(arg_types_ptr, num_arg_types.into())?
+            .into_iter()
+            .map(|ptr| self.read_type_ref(ptr))
+            .collect::<Result<_, _>>()?;
+        let return_type = if return_type_ptr == 0 {
+            None
+        } else {
+            Some(self.read_type_ref(return_type_ptr)?)
+        };
+
+        Ok(FunctionPrototype {
+            name,
+            arg_types,
+            return_type,
+        })
+    }
+
+    fn read_function_definitions(
+        &self,
+        offset: u32,
+        len: u32,
+    ) -> Result<Vec<FunctionDefinition>, anyhow::Error> {
+        (0..len)
+            .map(|idx| {
+                let offset = offset + idx * FUNCTION_DEFINITION_SIZE;
+                Ok(FunctionDefinition {
+                    prototype: self.read_function_prototype(offset)?,
+                    fn_index: self.read_u32(offset + FUNCTION_PROTOTYPE_SIZE)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Reads an `abi::AssemblyInfo`, which on `wasm32` is laid out as:
+    ///
+    /// | offset | field                                         |
+    /// |--------|-----------------------------------------------|
+    /// | 0      | `symbols.path`                                |
+    /// | 4      | `symbols.functions`                           |
+    /// | 8      | `symbols.types`                               |
+    /// | 12     | `symbols.migrations`                          |
+    /// | 16     | `symbols.num_functions`                       |
+    /// | 20     | `symbols.num_types`                           |
+    /// | 24     | `symbols.num_migrations`                      |
+    /// | 28     | `dispatch_table.prototypes`                   |
+    /// | 32     | `dispatch_table.fn_ptrs`                      |
+    /// | 36     | `dispatch_table.num_entries`                  |
+    /// | 40     | `dependencies`                                |
+    /// | 44     | `num_dependencies`                            |
+    pub fn read_assembly_info(&self, offset: u32) -> Result<WasmAssembly, anyhow::Error> {
+        let functions_ptr = self.read_u32(offset + 4)?;
+        let types_ptr = self.read_u32(offset + 8)?;
+        let num_functions = self.read_u32(offset + 16)?;
+        let num_types = self.read_u32(offset + 20)?;
+        let prototypes_ptr = self.read_u32(offset + 28)?;
+        let fn_ptrs_ptr = self.read_u32(offset + 32)?;
+        let num_entries = self.read_u32(offset + 36)?;
+        let dependencies_ptr = self.read_u32(offset + 40)?;
+        let num_dependencies = self.read_u32(offset + 44)?;
+
+        let functions = self.read_function_definitions(functions_ptr, num_functions)?;
+        let types = self
+            .read_pointers(types_ptr, num_types)?
+            .into_iter()
+            .map(|ptr| self.read_type_ref(ptr))
+            .collect::<Result<_, _>>()?;
+        let dispatch_table = (0..num_entries)
+            .map(|idx| {
+                Ok(DispatchTableEntry {
+                    prototype: self
+                        .read_function_prototype(prototypes_ptr + idx * FUNCTION_PROTOTYPE_SIZE)?,
+                    slot: fn_ptrs_ptr + idx * POINTER_SIZE,
+                })
+            })
+            .collect::<Result<_, anyhow::Error>>()?;
+        let dependencies = self
+            .read_pointers(dependencies_ptr, num_dependencies)?
+            .into_iter()
+            .map(|ptr| self.read_str(ptr))
+            .collect::<Result<_, _>>()?;
+
+        Ok(WasmAssembly {
+            functions,
+            types,
+            dispatch_table,
+            dependencies,
+        })
+    }
+}
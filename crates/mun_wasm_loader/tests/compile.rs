@@ -0,0 +1,141 @@
+use compiler::{Config, DisplayColor, Driver, PathOrInline, RelativePathBuf, Target};
+use mun_wasm_loader::{WasmAssembly, WasmInstance};
+use std::collections::HashMap;
+use wasmtime::{Extern, ExternType, Func, Instance, Memory, Module, Store, Trap, Val};
+
+/// A WebAssembly module that was instantiated by `wasmtime`.
+struct WasmtimeInstance {
+    instance: Instance,
+    memory: Memory,
+}
+
+impl WasmtimeInstance {
+    /// Instantiates the module. Functions that the linker left undefined trap when called.
+    fn new(bytes: &[u8]) -> Self {
+        let store = Store::default();
+        let module = Module::new(store.engine(), bytes).unwrap();
+        let imports = module
+            .imports()
+            .map(|import| match import.ty() {
+                ExternType::Func(ty) => Func::new(&store, ty, |_, _, _| {
+                    Err(Trap::new("called an undefined import"))
+                })
+                .into(),
+                ty => panic!("unexpected import: {:?}", ty),
+            })
+            .collect::<Vec<Extern>>();
+
+        let instance = Instance::new(&store, &module, &imports).unwrap();
+        let memory = instance.get_memory("memory").unwrap();
+        WasmtimeInstance { instance, memory }
+    }
+
+    /// Calls the function with the specified index in the module's function table.
+    fn call_indirect(&self, fn_index: u32, args: &[Val]) -> Box<[Val]> {
+        let table = self
+            .instance
+            .get_table("__indirect_function_table")
+            .unwrap();
+        match table.get(fn_index) {
+            Some(Val::FuncRef(Some(func))) => func.call(args).unwrap(),
+            _ => panic!("function table index {} is not a function", fn_index),
+        }
+    }
+}
+
+impl WasmInstance for WasmtimeInstance {
+    fn read_memory(&self, offset: u32, buf: &mut [u8]) -> Result<(), anyhow::Error> {
+        self.memory.read(offset as usize, buf)?;
+        Ok(())
+    }
+
+    fn write_memory(&mut self, offset: u32, buf: &[u8]) -> Result<(), anyhow::Error> {
+        self.memory.write(offset as usize, buf)?;
+        Ok(())
+    }
+
+    fn grow_memory(&mut self, pages: u32) -> Result<u32, anyhow::Error> {
+        self.memory.grow(pages)
+    }
+
+    fn call_export(&mut self, name: &str, args: &[u32]) -> Result<Option<u32>, anyhow::Error> {
+        let func = self
+            .instance
+            .get_func(name)
+            .ok_or_else(|| anyhow::anyhow!("unknown export `{}`", name))?;
+        let args = args
+            .iter()
+            .map(|arg| Val::I32(*arg as i32))
+            .collect::<Vec<_>>();
+        let results = func.call(&args)?;
+        Ok(results
+            .first()
+            .and_then(Val::i32)
+            .map(|result| result as u32))
+    }
+}
+
+/// Compiles `text` for the `wasm32-unknown-unknown` target, links it with the WebAssembly linker
+/// and returns the resulting module.
+fn compile_wasm(text: &str) -> Vec<u8> {
+    let out_dir = tempfile::tempdir().unwrap();
+    let config = Config {
+        target: Target::search("wasm32-unknown-unknown").unwrap(),
+        out_dir: Some(out_dir.path().to_path_buf()),
+        display_color: DisplayColor::Disable,
+        ..Config::default()
+    };
+    let input = PathOrInline::Inline {
+        rel_path: RelativePathBuf::from("mod.mun"),
+        contents: text.to_owned(),
+    };
+    let (mut driver, file_id) = Driver::with_file(config, input).unwrap();
+    driver.write_all_assemblies(false).unwrap();
+
+    std::fs::read(driver.assembly_output_path_from_file(file_id)).unwrap()
+}
+
+#[test]
+fn compile_link_and_load() {
+    let bytes = compile_wasm(
+        r#"
+    pub fn add(a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    pub fn main() -> i32 {
+        add(40, 2)
+    }
+    "#,
+    );
+    assert_eq!(&bytes[0..4], b"\0asm");
+
+    let mut instance = WasmtimeInstance::new(&bytes);
+    let assembly = WasmAssembly::load(&mut instance).unwrap();
+
+    let mut names = assembly
+        .functions()
+        .iter()
+        .map(|f| f.prototype.name.as_str())
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    assert_eq!(names, ["add", "main"]);
+    assert_eq!(
+        assembly.get_function("add").unwrap().prototype.arg_types[0].name,
+        "core::i32"
+    );
+
+    assembly.link(&mut instance, &HashMap::new()).unwrap();
+    for entry in assembly.dispatch_table() {
+        let function = assembly.get_function(&entry.prototype.name).unwrap();
+        assert_eq!(entry.fn_index(&instance).unwrap(), function.fn_index);
+    }
+
+    let add = assembly.get_function("add").unwrap();
+    let result = instance.call_indirect(add.fn_index, &[Val::I32(3), Val::I32(4)]);
+    assert_eq!(result[0].unwrap_i32(), 7);
+
+    let main = assembly.get_function("main").unwrap();
+    let result = instance.call_indirect(main.fn_index, &[]);
+    assert_eq!(result[0].unwrap_i32(), 42);
+}
@@ -0,0 +1,188 @@
+use mun_wasm_loader::{WasmAssembly, WasmInstance, WASM_PAGE_SIZE};
+use std::collections::HashMap;
+
+/// A fake WebAssembly instance whose `get_info` writes an `AssemblyInfo` that references data in
+/// its first page of linear memory.
+struct FakeInstance {
+    memory: Vec<u8>,
+    max_pages: u32,
+    version: u32,
+    assembly_info: [u32; 12],
+}
+
+impl WasmInstance for FakeInstance {
+    fn read_memory(&self, offset: u32, buf: &mut [u8]) -> Result<(), anyhow::Error> {
+        let offset = offset as usize;
+        let bytes = self
+            .memory
+            .get(offset..offset + buf.len())
+            .ok_or_else(|| anyhow::anyhow!("out of bounds memory access"))?;
+        buf.copy_from_slice(bytes);
+        Ok(())
+    }
+
+    fn write_memory(&mut self, offset: u32, buf: &[u8]) -> Result<(), anyhow::Error> {
+        let offset = offset as usize;
+        self.memory
+            .get_mut(offset..offset + buf.len())
+            .ok_or_else(|| anyhow::anyhow!("out of bounds memory access"))?
+            .copy_from_slice(buf);
+        Ok(())
+    }
+
+    fn grow_memory(&mut self, pages: u32) -> Result<u32, anyhow::Error> {
+        let previous = self.memory.len() as u32 / WASM_PAGE_SIZE;
+        if previous + pages > self.max_pages {
+            return Err(anyhow::anyhow!("failed to grow memory"));
+        }
+        self.memory
+            .resize(self.memory.len() + (pages * WASM_PAGE_SIZE) as usize, 0);
+        Ok(previous)
+    }
+
+    fn call_export(&mut self, name: &str, args: &[u32]) -> Result<Option<u32>, anyhow::Error> {
+        match name {
+            "get_version" => Ok(Some(self.version)),
+            "get_info" => {
+                let bytes = self
+                    .assembly_info
+                    .iter()
+                    .flat_map(|value| value.to_le_bytes().to_vec())
+                    .collect::<Vec<_>>();
+                self.write_memory(args[0], &bytes)?;
+                Ok(None)
+            }
+            _ => Err(anyhow::anyhow!("unknown export `{}`", name)),
+        }
+    }
+}
+
+/// Builds the linear memory of a module that defines `fn main() -> i32` and `fn add(i32, i32) ->
+/// i32`, and calls `add` and the extern function `host` through its dispatch table.
+fn fake_instance() -> FakeInstance {
+    let mut memory = vec![0u8; WASM_PAGE_SIZE as usize];
+    let mut write = |offset: usize, bytes: &[u8]| {
+        memory[offset..offset + bytes.len()].copy_from_slice(bytes);
+    };
+
+    // Strings
+    write(0x100, b"core::i32\0");
+    write(0x110, b"main\0");
+    write(0x118, b"add\0");
+    write(0x120, b"host\0");
+    write(0x128, b"dependency.munlib\0");
+
+    // `TypeInfo` of `i32`: a GUID followed by a name pointer
+    write(0x200, &[42u8; 16]);
+    write(0x210, &0x100u32.to_le_bytes());
+
+    // Argument types of `add`
+    write(0x300, &0x200u32.to_le_bytes());
+    write(0x304, &0x200u32.to_le_bytes());
+
+    // Function definitions: name, arg types, return type, number of args (padded) and table index
+    let main = [0x110u32, 0, 0x200, 0, 1];
+    let add = [0x118u32, 0x300, 0x200, 2, 2];
+    for (idx, value) in main.iter().chain(add.iter()).enumerate() {
+        write(0x400 + idx * 4, &value.to_le_bytes());
+    }
+
+    // Dispatch table prototypes, followed by its unlinked function pointers
+    let add_prototype = [0x118u32, 0x300, 0x200, 2];
+    let host_prototype = [0x120u32, 0, 0, 0];
+    for (idx, value) in add_prototype
+        .iter()
+        .chain(host_prototype.iter())
+        .enumerate()
+    {
+        write(0x500 + idx * 4, &value.to_le_bytes());
+    }
+
+    // Module types and dependencies
+    write(0x600, &0x200u32.to_le_bytes());
+    write(0x700, &0x128u32.to_le_bytes());
+
+    FakeInstance {
+        memory,
+        max_pages: 65536,
+        version: abi::ABI_VERSION,
+        assembly_info: [0x110, 0x400, 0x600, 0, 2, 1, 0, 0x500, 0x580, 2, 0x700, 1],
+    }
+}
+
+#[test]
+fn load() {
+    let mut instance = fake_instance();
+    let assembly = WasmAssembly::load(&mut instance).unwrap();
+
+    let names = assembly
+        .functions()
+        .iter()
+        .map(|f| f.prototype.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["main", "add"]);
+
+    let add = assembly.get_function("add").unwrap();
+    assert_eq!(add.fn_index, 2);
+    assert_eq!(add.prototype.arg_types.len(), 2);
+    assert_eq!(add.prototype.arg_types[0].name, "core::i32");
+    assert_eq!(add.prototype.arg_types[0].guid, abi::Guid([42u8; 16]));
+    assert_eq!(
+        add.prototype.return_type.as_ref().unwrap().name,
+        "core::i32"
+    );
+
+    assert_eq!(assembly.types().len(), 1);
+    assert_eq!(
+        assembly.dependencies().collect::<Vec<_>>(),
+        ["dependency.munlib"]
+    );
+
+    let dispatch_table = assembly.dispatch_table();
+    assert_eq!(dispatch_table.len(), 2);
+    assert_eq!(dispatch_table[0].prototype, add.prototype);
+    assert_eq!(dispatch_table[1].prototype.name, "host");
+    assert_eq!(dispatch_table[1].prototype.return_type, None);
+}
+
+#[test]
+fn link() {
+    let mut instance = fake_instance();
+    let assembly = WasmAssembly::load(&mut instance).unwrap();
+
+    let err = assembly.link(&mut instance, &HashMap::new()).unwrap_err();
+    assert!(err.to_string().contains("host"));
+
+    let mut host_functions = HashMap::new();
+    host_functions.insert(String::from("host"), 7);
+    assembly.link(&mut instance, &host_functions).unwrap();
+
+    let dispatch_table = assembly.dispatch_table();
+    assert_eq!(dispatch_table[0].fn_index(&instance).unwrap(), 2);
+    assert_eq!(dispatch_table[1].fn_index(&instance).unwrap(), 7);
+}
+
+#[test]
+fn abi_version_mismatch() {
+    let mut instance = fake_instance();
+    instance.version = abi::ABI_VERSION + 1;
+
+    assert!(WasmAssembly::load(&mut instance).is_err());
+}
+
+#[test]
+fn load_repeatedly() {
+    let mut instance = fake_instance();
+    for loaded in 1..=3 {
+        let assembly = WasmAssembly::load(&mut instance).unwrap();
+        assert_eq!(assembly.functions().len(), 2);
+        assert_eq!(
+            instance.memory.len(),
+            (1 + loaded) * WASM_PAGE_SIZE as usize
+        );
+    }
+
+    instance.max_pages = 5;
+    WasmAssembly::load(&mut instance).unwrap();
+    assert!(WasmAssembly::load(&mut instance).is_err());
+}
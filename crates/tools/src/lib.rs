@@ -14,7 +14,11 @@ pub mod syntax;
 /// A helper to update file on disk if it has changed.
 /// With verify = false,
 fn update(path: &Path, contents: &str, mode: Mode) -> Result<()> {
-    let old_contents = fs::read_to_string(path)?;
+    let old_contents = if path.exists() {
+        fs::read_to_string(path)?
+    } else {
+        String::new()
+    };
     let old_contents = old_contents.replace("\r\n", "\n");
     let contents = contents.replace("\r\n", "\n");
     if old_contents == contents {
@@ -1,16 +1,25 @@
 use crate::{project_root, update, Result};
+use std::fs;
 use teraron::Mode;
 
 pub const RUNTIME_CAPI_DIR: &str = "crates/mun_runtime_capi";
 
-/// Generates the FFI bindings for the Mun runtime
+/// Generates the FFI bindings for the Mun runtime, and copies the header-only C++ wrapper around
+/// them to the bindings.
 pub fn generate(mode: Mode) -> Result<()> {
     let crate_dir = project_root().join(RUNTIME_CAPI_DIR);
     let file_path = crate_dir.join("ffi/include/mun/runtime_capi.h");
 
     let mut file_contents = Vec::<u8>::new();
-    cbindgen::generate(crate_dir)?.write(&mut file_contents);
+    cbindgen::generate(&crate_dir)?.write(&mut file_contents);
 
     let file_contents = String::from_utf8(file_contents)?;
-    update(&file_path, &file_contents, mode)
+    update(&file_path, &file_contents, mode)?;
+
+    let cpp_contents = fs::read_to_string(crate_dir.join("cpp/include/mun/runtime.h"))?;
+    update(
+        &crate_dir.join("ffi/include/mun/runtime.h"),
+        &cpp_contents,
+        mode,
+    )
 }